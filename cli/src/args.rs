@@ -1,9 +1,14 @@
 use clap::{Parser, Subcommand, ValueEnum};
+use std::net::SocketAddr;
 use std::path::PathBuf;
 
-use fuelcheck_core::model::OutputFormat;
+use fuelcheck_core::budget::{Budget, BudgetPeriod};
+use fuelcheck_core::config::Defaults;
+use fuelcheck_core::model::{OutputFormat, OutputSchema};
 use fuelcheck_core::providers::{ProviderSelector, SourcePreference};
-use fuelcheck_core::reports::CostReportKind;
+use fuelcheck_core::reports::{CostReportGroupBy, CostReportKind, ReportSort};
+use fuelcheck_core::schema::SchemaTarget;
+use fuelcheck_ui::i18n::Locale;
 
 use crate::logger::LogLevel;
 
@@ -28,6 +33,40 @@ pub struct GlobalArgs {
     pub json_only: bool,
     #[arg(short = 'v', long, global = true)]
     pub verbose: bool,
+    #[arg(long, global = true, value_name = "SECONDS")]
+    pub timeout: Option<u64>,
+    #[arg(long, global = true, value_name = "N", value_parser = parse_concurrency)]
+    pub concurrency: Option<usize>,
+    #[arg(long, global = true, value_name = "URL")]
+    pub proxy: Option<String>,
+    #[arg(long, global = true)]
+    pub no_network: bool,
+    #[arg(long, global = true)]
+    pub trace_http: bool,
+    #[arg(long, global = true)]
+    pub trace_http_bodies: bool,
+    #[arg(long, global = true, value_name = "LANG", value_parser = parse_locale)]
+    pub lang: Option<Locale>,
+    #[arg(long, global = true, value_name = "VERSION", default_value = "v2")]
+    pub output_schema: OutputSchemaArg,
+}
+
+fn parse_concurrency(raw: &str) -> Result<usize, String> {
+    match raw.parse::<usize>() {
+        Ok(limit) if limit > 0 => Ok(limit),
+        _ => Err(format!("invalid concurrency \"{}\" (use 1 or more)", raw)),
+    }
+}
+
+fn parse_forecast_days(raw: &str) -> Result<u32, String> {
+    match raw.parse::<u32>() {
+        Ok(days) if days > 0 => Ok(days),
+        _ => Err(format!("invalid days \"{}\" (use 1 or more)", raw)),
+    }
+}
+
+fn parse_locale(raw: &str) -> Result<Locale, String> {
+    Locale::parse(raw).ok_or_else(|| format!("unsupported language \"{}\" (use en or zh)", raw))
 }
 
 #[derive(Subcommand, Debug)]
@@ -36,24 +75,53 @@ pub enum Command {
     Cost(CostArgs),
     Config(ConfigCommandArgs),
     Setup(SetupArgs),
+    Login(LoginArgs),
+    Logout(LogoutArgs),
+    Whoami(WhoamiArgs),
+    Paths(PathsArgs),
+    Providers(ProvidersArgs),
+    Status(StatusArgs),
+    Version(VersionArgs),
+    Hook(HookArgs),
+    Guard(GuardArgs),
+    CiGate(CiGateArgs),
+    Budget(BudgetArgs),
+    Forecast(ForecastArgs),
+    Compare(CompareArgs),
+    Reconcile(ReconcileArgs),
+    Sessions(SessionsCommandArgs),
+    History(HistoryArgs),
+    #[command(alias = "serve")]
+    Daemon(DaemonArgs),
+    Completions(CompletionsArgs),
+    Schema(SchemaArgs),
 }
 
 #[derive(Parser, Debug, Clone)]
 pub struct UsageArgs {
-    #[arg(short, long = "provider")]
-    pub providers: Vec<ProviderSelectorArg>,
+    #[arg(short, long = "provider", value_parser = parse_provider_arg)]
+    pub providers: Vec<ProviderArg>,
     #[arg(long, default_value = "auto")]
     pub source: SourcePreferenceArg,
-    #[arg(long, default_value = "text")]
-    pub format: OutputFormatArg,
     #[arg(long)]
-    pub json: bool,
+    pub format: Option<OutputFormatArg>,
     #[arg(long)]
+    pub json: bool,
+    #[arg(long, overrides_with = "no_pretty")]
     pub pretty: bool,
-    #[arg(long)]
+    // Turns off a `defaults.pretty` (likewise `--no-status` and `--credits`).
+    #[arg(long, overrides_with = "pretty")]
+    pub no_pretty: bool,
+    #[arg(long, conflicts_with = "watch")]
+    pub stream: bool,
+    #[arg(long, overrides_with = "no_status")]
     pub status: bool,
-    #[arg(long)]
+    #[arg(long, overrides_with = "status")]
+    pub no_status: bool,
+    #[arg(long, overrides_with = "credits")]
     pub no_credits: bool,
+    #[arg(long, overrides_with = "no_credits")]
+    pub credits: bool,
     #[arg(long)]
     pub refresh: bool,
     #[arg(long)]
@@ -72,36 +140,127 @@ pub struct UsageArgs {
     pub antigravity_plan_debug: bool,
     #[arg(long)]
     pub watch: bool,
-    #[arg(long, default_value = "10")]
-    pub interval: u64,
+    #[arg(long)]
+    pub interval: Option<u64>,
+    // Watch alerts: a window's remaining percent dropping below this.
+    #[arg(long, value_name = "PERCENT", requires = "watch")]
+    pub alert_below: Option<f64>,
+    #[arg(long, requires = "watch")]
+    pub bell: bool,
+    #[arg(long, requires = "watch")]
+    pub notify: bool,
+    #[arg(long)]
+    pub timezone: Option<String>,
+    // Writes the rendered output here instead of stdout; `-` is stdout.
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["watch", "stream"])]
+    pub output: Option<PathBuf>,
+    #[arg(long, alias = "fail-on-error")]
+    pub strict: bool,
+    // Exit with code 11 when a window reaches its `alert_at_percent`.
+    #[arg(long)]
+    pub fail_on_alert: bool,
+}
+
+impl UsageArgs {
+    pub fn apply_defaults(&mut self, defaults: &Defaults) {
+        self.format = default_format(self.format, self.json, defaults);
+        // `--format ndjson` is `--stream` spelled as a format.
+        self.stream |= self.format == Some(OutputFormatArg::Ndjson) && !self.watch;
+        self.pretty = default_flag(self.pretty, self.no_pretty, defaults.pretty);
+        self.status = default_flag(self.status, self.no_status, defaults.status);
+        self.no_credits = default_flag(self.no_credits, self.credits, defaults.no_credits);
+        self.interval = self.interval.or(defaults.interval);
+        self.alert_below = self.alert_below.or(defaults.alert_below);
+        if self.timezone.is_none() {
+            self.timezone = defaults.timezone.clone();
+        }
+    }
+}
+
+// `--json` picks the format as much as `--format` does, so the default format
+// only applies when neither was given.
+fn default_format(
+    format: Option<OutputFormatArg>,
+    json: bool,
+    defaults: &Defaults,
+) -> Option<OutputFormatArg> {
+    match format {
+        None if !json => defaults.format.map(Into::into),
+        format => format,
+    }
+}
+
+// A flag set from the CLI, either way, wins over its default.
+fn default_flag(on: bool, off: bool, default: Option<bool>) -> bool {
+    on || (!off && default.unwrap_or(false))
 }
 
 #[derive(Parser, Debug, Clone)]
 pub struct CostArgs {
-    #[arg(short, long = "provider")]
-    pub providers: Vec<ProviderSelectorArg>,
-    #[arg(long, default_value = "text")]
-    pub format: OutputFormatArg,
+    #[arg(short, long = "provider", value_parser = parse_provider_arg)]
+    pub providers: Vec<ProviderArg>,
     #[arg(long)]
-    pub json: bool,
+    pub format: Option<OutputFormatArg>,
     #[arg(long)]
+    pub json: bool,
+    #[arg(long, overrides_with = "no_pretty")]
     pub pretty: bool,
+    #[arg(long, overrides_with = "pretty")]
+    pub no_pretty: bool,
+    #[arg(long, conflicts_with = "report")]
+    pub stream: bool,
     #[arg(long)]
     pub report: Option<CostReportKindArg>,
-    #[arg(long)]
+    #[arg(long, requires = "report")]
     pub since: Option<String>,
-    #[arg(long)]
+    #[arg(long, requires = "report")]
     pub until: Option<String>,
-    #[arg(long)]
+    #[arg(long, visible_alias = "tz")]
     pub timezone: Option<String>,
     #[arg(long)]
     pub compact: bool,
+    #[arg(long, requires = "report")]
+    pub show_efficiency: bool,
+    #[arg(long, requires = "report")]
+    pub group_by: Option<CostReportGroupByArg>,
+    #[arg(long, requires = "report")]
+    pub sort: Option<ReportSortArg>,
+    #[arg(long, requires = "sort")]
+    pub desc: bool,
+    #[arg(long, requires = "report")]
+    pub follow: bool,
+    // Today's cost per provider and model in the TUI, re-read every interval.
+    #[arg(long, conflicts_with_all = ["report", "stream", "output"])]
+    pub watch: bool,
+    #[arg(long, requires = "watch")]
+    pub interval: Option<u64>,
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["follow", "stream"])]
+    pub output: Option<PathBuf>,
     #[arg(long)]
     pub config: Option<PathBuf>,
+    #[arg(long, alias = "fail-on-error")]
+    pub strict: bool,
+}
+
+impl CostArgs {
+    pub fn apply_defaults(&mut self, defaults: &Defaults) {
+        // Reports have no NDJSON form, so an ndjson default leaves them as text.
+        if self.report.is_none() || defaults.format != Some(OutputFormat::Ndjson) {
+            self.format = default_format(self.format, self.json, defaults);
+        }
+        self.stream |= self.format == Some(OutputFormatArg::Ndjson) && self.report.is_none();
+        self.pretty = default_flag(self.pretty, self.no_pretty, defaults.pretty);
+        self.interval = self.interval.or(defaults.interval);
+        if self.timezone.is_none() {
+            self.timezone = defaults.timezone.clone();
+        }
+    }
 }
 
 #[derive(Parser, Debug, Clone)]
 pub struct SetupArgs {
+    #[arg(short, long = "provider", value_parser = parse_provider_arg)]
+    pub providers: Vec<ProviderArg>,
     #[arg(long)]
     pub force: bool,
     #[arg(long)]
@@ -112,6 +271,386 @@ pub struct SetupArgs {
     pub cursor_cookie: Option<String>,
     #[arg(long, alias = "droid-cookie")]
     pub factory_cookie: Option<String>,
+    #[arg(long, requires = "providers")]
+    pub api_key: Option<String>,
+    #[arg(long, value_name = "PATH", num_args = 0..=1)]
+    pub import_codexbar: Option<Option<PathBuf>>,
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct WhoamiArgs {
+    #[arg(short, long = "provider", value_parser = parse_provider_arg)]
+    pub providers: Vec<ProviderArg>,
+    #[arg(long, default_value = "auto")]
+    pub source: SourcePreferenceArg,
+    #[arg(long)]
+    pub format: Option<OutputFormatArg>,
+    #[arg(long)]
+    pub json: bool,
+    #[arg(long, overrides_with = "no_pretty")]
+    pub pretty: bool,
+    #[arg(long, overrides_with = "pretty")]
+    pub no_pretty: bool,
+    #[arg(long)]
+    pub stream: bool,
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+    #[arg(long, alias = "fail-on-error")]
+    pub strict: bool,
+}
+
+impl WhoamiArgs {
+    pub fn apply_defaults(&mut self, defaults: &Defaults) {
+        self.format = default_format(self.format, self.json, defaults);
+        self.stream |= self.format == Some(OutputFormatArg::Ndjson);
+        self.pretty = default_flag(self.pretty, self.no_pretty, defaults.pretty);
+    }
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct StatusArgs {
+    #[arg(short, long = "provider", value_parser = parse_provider_arg)]
+    pub providers: Vec<ProviderArg>,
+    #[arg(long)]
+    pub format: Option<OutputFormatArg>,
+    #[arg(long)]
+    pub json: bool,
+    #[arg(long, overrides_with = "no_pretty")]
+    pub pretty: bool,
+    #[arg(long, overrides_with = "pretty")]
+    pub no_pretty: bool,
+    #[arg(long)]
+    pub stream: bool,
+    #[arg(long, default_value = "20")]
+    pub web_timeout: u64,
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+    #[arg(long, alias = "fail-on-error")]
+    pub strict: bool,
+}
+
+impl StatusArgs {
+    pub fn apply_defaults(&mut self, defaults: &Defaults) {
+        self.format = default_format(self.format, self.json, defaults);
+        self.stream |= self.format == Some(OutputFormatArg::Ndjson);
+        self.pretty = default_flag(self.pretty, self.no_pretty, defaults.pretty);
+    }
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct PathsArgs {
+    #[arg(long)]
+    pub json: bool,
+    #[arg(long)]
+    pub pretty: bool,
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct VersionArgs {
+    #[arg(long)]
+    pub json: bool,
+    #[arg(long)]
+    pub pretty: bool,
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct CompletionsArgs {
+    pub shell: clap_complete::Shell,
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct SchemaArgs {
+    #[arg(value_enum)]
+    pub target: SchemaTargetArg,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct HookArgs {
+    #[arg(value_enum)]
+    pub agent: HookAgentArg,
+    // Event JSON as the agent passes it; read from stdin when omitted or `-`.
+    pub payload: Option<String>,
+    #[arg(long, default_value = "20", value_name = "PERCENT")]
+    pub warn_below: f64,
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct GuardArgs {
+    #[arg(short, long = "provider", value_parser = parse_provider_arg)]
+    pub providers: Vec<ProviderArg>,
+    #[arg(long, default_value = "15", value_name = "PERCENT")]
+    pub min_remaining: f64,
+    #[arg(long)]
+    pub block: bool,
+    // Usage samples in the history newer than this are used instead of fetching.
+    #[arg(long, default_value = "30", value_name = "MINUTES")]
+    pub max_age: u64,
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct CiGateArgs {
+    #[arg(short, long = "provider", value_parser = parse_provider_arg)]
+    pub providers: Vec<ProviderArg>,
+    #[arg(long, value_parser = parse_budget)]
+    pub budget: Budget,
+    #[arg(long, default_value = "month")]
+    pub period: BudgetPeriodArg,
+    #[arg(long)]
+    pub timezone: Option<String>,
+    #[arg(long)]
+    pub json: bool,
+    #[arg(long)]
+    pub pretty: bool,
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+}
+
+impl CiGateArgs {
+    pub fn apply_defaults(&mut self, defaults: &Defaults) {
+        if self.timezone.is_none() {
+            self.timezone = defaults.timezone.clone();
+        }
+    }
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct BudgetArgs {
+    // Providers with a `monthly_budget` when none are given.
+    #[arg(short, long = "provider", value_parser = parse_provider_arg)]
+    pub providers: Vec<ProviderArg>,
+    #[arg(long)]
+    pub timezone: Option<String>,
+    #[arg(long)]
+    pub json: bool,
+    #[arg(long)]
+    pub pretty: bool,
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+}
+
+impl BudgetArgs {
+    pub fn apply_defaults(&mut self, defaults: &Defaults) {
+        if self.timezone.is_none() {
+            self.timezone = defaults.timezone.clone();
+        }
+    }
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct ForecastArgs {
+    #[arg(short, long = "provider", value_parser = parse_provider_arg)]
+    pub providers: Vec<ProviderArg>,
+    // Days, ending today, the burn rate is averaged over.
+    #[arg(long, default_value = "7", value_parser = parse_forecast_days)]
+    pub days: u32,
+    #[arg(long)]
+    pub timezone: Option<String>,
+    #[arg(long)]
+    pub json: bool,
+    #[arg(long)]
+    pub pretty: bool,
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+}
+
+impl ForecastArgs {
+    pub fn apply_defaults(&mut self, defaults: &Defaults) {
+        if self.timezone.is_none() {
+            self.timezone = defaults.timezone.clone();
+        }
+    }
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct CompareArgs {
+    #[arg(short, long = "provider", value_parser = parse_provider_arg)]
+    pub providers: Vec<ProviderArg>,
+    #[arg(long)]
+    pub since: String,
+    #[arg(long)]
+    pub until: String,
+    #[arg(long)]
+    pub baseline_since: String,
+    #[arg(long)]
+    pub baseline_until: String,
+    #[arg(long)]
+    pub timezone: Option<String>,
+    #[arg(long)]
+    pub json: bool,
+    #[arg(long)]
+    pub pretty: bool,
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+}
+
+impl CompareArgs {
+    pub fn apply_defaults(&mut self, defaults: &Defaults) {
+        if self.timezone.is_none() {
+            self.timezone = defaults.timezone.clone();
+        }
+    }
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct ReconcileArgs {
+    #[arg(short, long, value_parser = parse_provider_arg, default_value = "codex")]
+    pub provider: ProviderArg,
+    #[arg(long, value_name = "YYYY-MM")]
+    pub month: Option<String>,
+    #[arg(long)]
+    pub timezone: Option<String>,
+    // USD per credit; implied from the month's totals when unset.
+    #[arg(long, value_name = "USD")]
+    pub credit_usd: Option<f64>,
+    #[arg(long, default_value = "15", value_name = "PERCENT")]
+    pub tolerance: f64,
+    #[arg(long)]
+    pub json: bool,
+    #[arg(long)]
+    pub pretty: bool,
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+}
+
+impl ReconcileArgs {
+    pub fn apply_defaults(&mut self, defaults: &Defaults) {
+        if self.timezone.is_none() {
+            self.timezone = defaults.timezone.clone();
+        }
+    }
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct HistoryArgs {
+    #[arg(short, long = "provider", value_parser = parse_provider_arg)]
+    pub providers: Vec<ProviderArg>,
+    // YYYY-MM-DD (UTC) or an RFC 3339 timestamp.
+    #[arg(long)]
+    pub since: Option<String>,
+    // Inclusive for dates, exclusive for timestamps.
+    #[arg(long)]
+    pub until: Option<String>,
+    #[arg(long, default_value = "50")]
+    pub limit: usize,
+    #[arg(long)]
+    pub json: bool,
+    #[arg(long)]
+    pub pretty: bool,
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug)]
+pub struct SessionsCommandArgs {
+    #[command(subcommand)]
+    pub command: SessionsCommand,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SessionsCommand {
+    Tag(SessionTagArgs),
+    Untag(SessionUntagArgs),
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct SessionTagArgs {
+    // Session UUID or rollout file name, as shown by `cost --report session`.
+    #[arg(value_name = "SESSION_ID")]
+    pub session: String,
+    pub name: String,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct SessionUntagArgs {
+    #[arg(value_name = "SESSION_ID")]
+    pub session: String,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct DaemonArgs {
+    #[arg(short, long = "provider", value_parser = parse_provider_arg)]
+    pub providers: Vec<ProviderArg>,
+    #[arg(long, default_value = "auto")]
+    pub source: SourcePreferenceArg,
+    #[arg(long)]
+    pub dbus: bool,
+    // Prometheus `/metrics` endpoint, e.g. 0.0.0.0:9184.
+    #[arg(long, value_name = "ADDR", conflicts_with = "dbus")]
+    pub listen: Option<SocketAddr>,
+    // JSON file rewritten after every refresh, for tools that poll a file.
+    #[arg(long, value_name = "PATH", conflicts_with_all = ["dbus", "listen"])]
+    pub state_file: Option<PathBuf>,
+    #[arg(long, default_value = "300", value_name = "SECONDS")]
+    pub interval: u64,
+    #[arg(long)]
+    pub no_credits: bool,
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+}
+
+fn parse_budget(raw: &str) -> Result<Budget, String> {
+    raw.parse().map_err(|err: anyhow::Error| err.to_string())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum HookAgentArg {
+    Codex,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct ProvidersArgs {
+    // `providers` on its own also lists.
+    #[command(subcommand)]
+    pub command: Option<ProvidersCommand>,
+    #[arg(long, global = true)]
+    pub json: bool,
+    #[arg(long, global = true)]
+    pub pretty: bool,
+    #[arg(long, global = true)]
+    pub config: Option<PathBuf>,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ProvidersCommand {
+    List,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct LoginArgs {
+    pub provider: LoginProviderArg,
+    // Print the sign-in URL without trying to open a browser.
+    #[arg(long)]
+    pub no_browser: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LoginProviderArg {
+    Codex,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct LogoutArgs {
+    #[arg(short, long = "provider", value_parser = parse_provider_arg)]
+    pub providers: Vec<ProviderArg>,
+    #[arg(long)]
+    pub account: Option<String>,
+    #[arg(long)]
+    pub all: bool,
+    // Also delete the Codex, Claude and Gemini CLIs' own credentials.
+    #[arg(long)]
+    pub cli_credentials: bool,
     #[arg(long)]
     pub config: Option<PathBuf>,
 }
@@ -124,8 +663,25 @@ pub struct ConfigCommandArgs {
 
 #[derive(Subcommand, Debug)]
 pub enum ConfigCommand {
-    Validate(ConfigArgs),
+    Validate(ConfigValidateArgs),
     Dump(ConfigArgs),
+    Migrate(ConfigMigrateArgs),
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct ConfigValidateArgs {
+    #[command(flatten)]
+    pub common: ConfigArgs,
+    #[arg(long)]
+    pub strict: bool,
+}
+
+#[derive(Parser, Debug, Clone)]
+pub struct ConfigMigrateArgs {
+    #[command(flatten)]
+    pub common: ConfigArgs,
+    #[arg(long)]
+    pub dry_run: bool,
 }
 
 #[derive(Parser, Debug, Clone)]
@@ -141,14 +697,40 @@ pub struct ConfigArgs {
 impl ConfigCommand {
     pub fn format(&self) -> OutputFormat {
         match self {
-            Self::Validate(args) => args.format.map(Into::into).unwrap_or(OutputFormat::Text),
+            Self::Validate(args) => args
+                .common
+                .format
+                .map(Into::into)
+                .unwrap_or(OutputFormat::Text),
             Self::Dump(args) => args.format.map(Into::into).unwrap_or(OutputFormat::Json),
+            Self::Migrate(args) => args
+                .common
+                .format
+                .map(Into::into)
+                .unwrap_or(OutputFormat::Text),
         }
     }
 
     pub fn pretty(&self) -> bool {
         match self {
-            Self::Validate(args) | Self::Dump(args) => args.pretty,
+            Self::Validate(args) => args.common.pretty,
+            Self::Dump(args) => args.pretty,
+            Self::Migrate(args) => args.common.pretty,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputSchemaArg {
+    V1,
+    V2,
+}
+
+impl From<OutputSchemaArg> for OutputSchema {
+    fn from(value: OutputSchemaArg) -> Self {
+        match value {
+            OutputSchemaArg::V1 => OutputSchema::V1,
+            OutputSchemaArg::V2 => OutputSchema::V2,
         }
     }
 }
@@ -157,6 +739,29 @@ impl ConfigCommand {
 pub enum OutputFormatArg {
     Text,
     Json,
+    Raycast,
+    Alfred,
+    Share,
+    Markdown,
+    Statusbar,
+    Waybar,
+    Ndjson,
+}
+
+impl From<OutputFormat> for OutputFormatArg {
+    fn from(value: OutputFormat) -> Self {
+        match value {
+            OutputFormat::Text => OutputFormatArg::Text,
+            OutputFormat::Json => OutputFormatArg::Json,
+            OutputFormat::Raycast => OutputFormatArg::Raycast,
+            OutputFormat::Alfred => OutputFormatArg::Alfred,
+            OutputFormat::Share => OutputFormatArg::Share,
+            OutputFormat::Markdown => OutputFormatArg::Markdown,
+            OutputFormat::Statusbar => OutputFormatArg::Statusbar,
+            OutputFormat::Waybar => OutputFormatArg::Waybar,
+            OutputFormat::Ndjson => OutputFormatArg::Ndjson,
+        }
+    }
 }
 
 impl From<OutputFormatArg> for OutputFormat {
@@ -164,6 +769,13 @@ impl From<OutputFormatArg> for OutputFormat {
         match value {
             OutputFormatArg::Text => OutputFormat::Text,
             OutputFormatArg::Json => OutputFormat::Json,
+            OutputFormatArg::Raycast => OutputFormat::Raycast,
+            OutputFormatArg::Alfred => OutputFormat::Alfred,
+            OutputFormatArg::Share => OutputFormat::Share,
+            OutputFormatArg::Markdown => OutputFormat::Markdown,
+            OutputFormatArg::Statusbar => OutputFormat::Statusbar,
+            OutputFormatArg::Waybar => OutputFormat::Waybar,
+            OutputFormatArg::Ndjson => OutputFormat::Ndjson,
         }
     }
 }
@@ -211,10 +823,36 @@ pub enum ProviderSelectorArg {
     Amp,
     Warp,
     OpenCode,
+    AnthropicApi,
+    OpenAIPlatform,
+    #[value(alias = "lechat")]
+    Mistral,
+    Perplexity,
+    Aider,
+    #[value(alias = "roo", alias = "roo-code")]
+    Cline,
+    Ollama,
     All,
     Both,
 }
 
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProviderArg {
+    Builtin(ProviderSelectorArg),
+    Alias(String),
+}
+
+pub(crate) fn parse_provider_arg(raw: &str) -> Result<ProviderArg, String> {
+    if let Ok(selector) = ProviderSelectorArg::from_str(raw, true) {
+        return Ok(ProviderArg::Builtin(selector));
+    }
+    let alias = raw.trim();
+    if alias.is_empty() {
+        return Err("provider must not be empty".to_string());
+    }
+    Ok(ProviderArg::Alias(alias.to_string()))
+}
+
 impl From<ProviderSelectorArg> for ProviderSelector {
     fn from(value: ProviderSelectorArg) -> Self {
         match value {
@@ -234,6 +872,13 @@ impl From<ProviderSelectorArg> for ProviderSelector {
             ProviderSelectorArg::Amp => ProviderSelector::Amp,
             ProviderSelectorArg::Warp => ProviderSelector::Warp,
             ProviderSelectorArg::OpenCode => ProviderSelector::OpenCode,
+            ProviderSelectorArg::AnthropicApi => ProviderSelector::AnthropicApi,
+            ProviderSelectorArg::OpenAIPlatform => ProviderSelector::OpenAIPlatform,
+            ProviderSelectorArg::Mistral => ProviderSelector::Mistral,
+            ProviderSelectorArg::Perplexity => ProviderSelector::Perplexity,
+            ProviderSelectorArg::Aider => ProviderSelector::Aider,
+            ProviderSelectorArg::Cline => ProviderSelector::Cline,
+            ProviderSelectorArg::Ollama => ProviderSelector::Ollama,
             ProviderSelectorArg::All => ProviderSelector::All,
             ProviderSelectorArg::Both => ProviderSelector::Both,
         }
@@ -245,6 +890,39 @@ pub enum CostReportKindArg {
     Daily,
     Monthly,
     Session,
+    Models,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CostReportGroupByArg {
+    Agent,
+    Directory,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ReportSortArg {
+    Cost,
+    Tokens,
+    Date,
+}
+
+impl From<ReportSortArg> for ReportSort {
+    fn from(value: ReportSortArg) -> Self {
+        match value {
+            ReportSortArg::Cost => ReportSort::Cost,
+            ReportSortArg::Tokens => ReportSort::Tokens,
+            ReportSortArg::Date => ReportSort::Date,
+        }
+    }
+}
+
+impl From<CostReportGroupByArg> for CostReportGroupBy {
+    fn from(value: CostReportGroupByArg) -> Self {
+        match value {
+            CostReportGroupByArg::Agent => CostReportGroupBy::Agent,
+            CostReportGroupByArg::Directory => CostReportGroupBy::Directory,
+        }
+    }
 }
 
 impl From<CostReportKindArg> for CostReportKind {
@@ -253,6 +931,102 @@ impl From<CostReportKindArg> for CostReportKind {
             CostReportKindArg::Daily => CostReportKind::Daily,
             CostReportKindArg::Monthly => CostReportKind::Monthly,
             CostReportKindArg::Session => CostReportKind::Session,
+            CostReportKindArg::Models => CostReportKind::Models,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum BudgetPeriodArg {
+    Day,
+    Week,
+    Month,
+}
+
+impl From<BudgetPeriodArg> for BudgetPeriod {
+    fn from(value: BudgetPeriodArg) -> Self {
+        match value {
+            BudgetPeriodArg::Day => BudgetPeriod::Day,
+            BudgetPeriodArg::Week => BudgetPeriod::Week,
+            BudgetPeriodArg::Month => BudgetPeriod::Month,
         }
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum SchemaTargetArg {
+    Payload,
+    UsageSnapshot,
+    Report,
+    Config,
+}
+
+impl From<SchemaTargetArg> for SchemaTarget {
+    fn from(value: SchemaTargetArg) -> Self {
+        match value {
+            SchemaTargetArg::Payload => SchemaTarget::Payload,
+            SchemaTargetArg::UsageSnapshot => SchemaTarget::UsageSnapshot,
+            SchemaTargetArg::Report => SchemaTarget::Report,
+            SchemaTargetArg::Config => SchemaTarget::Config,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuelcheck_core::model::OutputFormat;
+
+    fn usage(args: &[&str], defaults: &Defaults) -> UsageArgs {
+        let cli = Cli::try_parse_from(["fuelcheck-cli", "usage"].iter().chain(args)).unwrap();
+        let Command::Usage(mut args) = cli.command else {
+            panic!("expected usage");
+        };
+        args.apply_defaults(defaults);
+        args
+    }
+
+    #[test]
+    fn cli_flags_turn_off_true_defaults() {
+        let defaults = Defaults {
+            pretty: Some(true),
+            status: Some(true),
+            no_credits: Some(true),
+            ..Defaults::default()
+        };
+        let args = usage(&[], &defaults);
+        assert!(args.pretty && args.status && args.no_credits);
+
+        let args = usage(&["--no-pretty", "--no-status", "--credits"], &defaults);
+        assert!(!args.pretty && !args.status && !args.no_credits);
+
+        // The last of a flag and its negation wins.
+        let args = usage(&["--no-pretty", "--pretty"], &Defaults::default());
+        assert!(args.pretty);
+    }
+
+    #[test]
+    fn json_wins_over_an_ndjson_default() {
+        let defaults = Defaults {
+            format: Some(OutputFormat::Ndjson),
+            ..Defaults::default()
+        };
+        let args = usage(&[], &defaults);
+        assert!(args.stream);
+
+        let args = usage(&["--json"], &defaults);
+        assert!(!args.stream);
+        assert_eq!(args.format, None);
+
+        let args = usage(&["--format", "text"], &defaults);
+        assert!(!args.stream);
+
+        let cli = Cli::try_parse_from(["fuelcheck-cli", "cost", "--report", "daily"]).unwrap();
+        let Command::Cost(mut args) = cli.command else {
+            panic!("expected cost");
+        };
+        args.apply_defaults(&defaults);
+        assert_eq!(args.format, None);
+        assert!(!args.stream);
+    }
+}
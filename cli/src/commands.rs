@@ -1,20 +1,47 @@
-use anyhow::{Result, anyhow};
-use fuelcheck_core::config::{Config, DetectResult};
-use fuelcheck_core::model::{OutputFormat, ProviderErrorPayload, ProviderPayload};
-use fuelcheck_core::providers::{ProviderRegistry, ProviderSelector};
+use anyhow::{Context, Result, anyhow};
+use clap::builder::PossibleValuesParser;
+use clap::{CommandFactory, ValueEnum};
+use fuelcheck_core::config::{Config, Defaults, DetectResult, ProviderLabels};
+use fuelcheck_core::errors::CliError;
+use fuelcheck_core::history::{History, HistoryEvent, USAGE_SAMPLE};
+use fuelcheck_core::http;
+use fuelcheck_core::model::{OutputFormat, OutputSchema, ProviderErrorPayload, ProviderPayload};
+use fuelcheck_core::paths::{AppPaths, codexbar_config_path, default_log_path, native_config_path};
+use fuelcheck_core::providers::{
+    CodexLogin, ProviderId, ProviderRegistry, ProviderSelector, SourcePreference,
+};
+use fuelcheck_core::schema;
 use fuelcheck_core::service::{
-    CostRequest, SetupRequest, UsageRequest, build_cost_report_collection, build_setup_config,
-    collect_cost_outputs, collect_report_provider_ids, collect_usage_outputs,
+    BudgetRequest, CompareRequest, CostRequest, ForecastRequest, LogoutRequest,
+    MonthlyBudgetRequest, ReconcileRequest, SetupRequest, StatusRequest, UsageRequest,
+    WhoamiRequest, apply_provider_setup, build_cost_report_collection, build_reconcile_report,
+    build_setup_config, collect_budget_check, collect_comparisons, collect_cost_outputs,
+    collect_forecast, collect_identity_outputs, collect_monthly_budgets,
+    collect_report_provider_ids, collect_status_outputs, collect_usage_outputs,
+    import_codexbar_config, list_providers, logout_providers, set_fetch_concurrency,
+    validate_reconcile_request,
 };
+use fuelcheck_core::session_tags::SessionTags;
+use fuelcheck_core::snapshots::{SnapshotDb, SnapshotQuery, parse_time_bound};
+use fuelcheck_core::validation::{DiagnosticSeverity, validate_config_json};
 use fuelcheck_ui::reports as ui_reports;
-use fuelcheck_ui::text::{RenderOptions as TextRenderOptions, render_outputs};
-use fuelcheck_ui::tui::{self, UsageArgs as WatchUsageArgs};
+use fuelcheck_ui::text::{RenderOptions as TextRenderOptions, render_identities, render_outputs};
+use fuelcheck_ui::tui::{self, CostWatchArgs, UsageArgs as WatchUsageArgs};
+use notify::Watcher;
 
 use crate::args::{
-    ConfigArgs, ConfigCommand, ConfigCommandArgs, CostArgs, GlobalArgs, SetupArgs, UsageArgs,
+    BudgetArgs, CiGateArgs, Cli, CompareArgs, CompletionsArgs, ConfigArgs, ConfigCommand,
+    ConfigCommandArgs, ConfigMigrateArgs, ConfigValidateArgs, CostArgs, CostReportKindArg,
+    DaemonArgs, ForecastArgs, GlobalArgs, GuardArgs, HistoryArgs, HookAgentArg, HookArgs,
+    LoginArgs, LoginProviderArg, LogoutArgs, OutputFormatArg, PathsArgs, ProviderArg,
+    ProviderSelectorArg, ProvidersArgs, ReconcileArgs, SchemaArgs, SessionsCommand,
+    SessionsCommandArgs, SetupArgs, StatusArgs, UsageArgs, VersionArgs, WhoamiArgs,
+    parse_provider_arg,
 };
-use crate::logger::{self, LogLevel};
+use crate::logger::{self, DEFAULT_LOG_MAX_BYTES, DEFAULT_LOG_MAX_FILES, FileLogConfig, LogLevel};
+use crate::progress;
 
+#[derive(Clone, Copy)]
 pub struct OutputPreferences {
     pub format: OutputFormat,
     pub pretty: bool,
@@ -23,12 +50,33 @@ pub struct OutputPreferences {
 }
 
 impl OutputPreferences {
+    // `--json`, `--stream` and the global `--json-only` all force JSON.
+    pub fn from_args(
+        json: bool,
+        stream: bool,
+        format: Option<OutputFormatArg>,
+        pretty: bool,
+        global: &GlobalArgs,
+    ) -> Self {
+        let format = if json || stream || global.json_only {
+            OutputFormat::Json
+        } else {
+            format.map(Into::into).unwrap_or(OutputFormat::Text)
+        };
+        Self {
+            format,
+            pretty,
+            json_only: global.json_only,
+            no_color: global.no_color,
+        }
+    }
+
     pub fn uses_json_output(&self) -> bool {
         self.json_only || self.format == OutputFormat::Json
     }
 
     pub fn use_color(&self) -> bool {
-        if self.format == OutputFormat::Json {
+        if self.format != OutputFormat::Text {
             return false;
         }
         if self.no_color {
@@ -41,10 +89,13 @@ impl OutputPreferences {
     }
 }
 
-use std::io::IsTerminal;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 pub async fn run_usage(
     args: UsageArgs,
+    prefs: &OutputPreferences,
     registry: &ProviderRegistry,
     global: &GlobalArgs,
 ) -> Result<()> {
@@ -60,19 +111,25 @@ pub async fn run_usage(
         );
     }
 
-    let format = if args.json || global.json_only {
-        OutputFormat::Json
-    } else {
-        args.format.into()
-    };
+    let format = prefs.format;
+    let timezone = args
+        .timezone
+        .as_deref()
+        .map(fuelcheck_core::reports::validate_timezone)
+        .transpose()?;
 
     if args.watch {
-        if format == OutputFormat::Json || global.json_only {
+        if format != OutputFormat::Text || global.json_only {
             return Err(anyhow!("--watch only supports text output"));
         }
+        if let Some(percent) = args.alert_below
+            && !(0.0..=100.0).contains(&percent)
+        {
+            return Err(anyhow!("--alert-below must be a percent from 0 to 100"));
+        }
 
         let watch_args = WatchUsageArgs {
-            providers: args.providers.into_iter().map(Into::into).collect(),
+            providers: resolve_provider_args(&args.providers, &config, registry)?,
             source: args.source.into(),
             status: args.status,
             no_credits: args.no_credits,
@@ -83,13 +140,17 @@ pub async fn run_usage(
             account_index: args.account_index,
             all_accounts: args.all_accounts,
             antigravity_plan_debug: args.antigravity_plan_debug,
-            interval: args.interval,
+            interval: args.interval.unwrap_or(10),
+            timezone,
+            alert_below: args.alert_below,
+            bell: args.bell,
+            notify: args.notify,
         };
         return tui::run_usage_watch(watch_args, registry, config).await;
     }
 
     let request = UsageRequest {
-        providers: args.providers.into_iter().map(Into::into).collect(),
+        providers: resolve_provider_args(&args.providers, &config, registry)?,
         source: args.source.into(),
         status: args.status,
         no_credits: args.no_credits,
@@ -102,177 +163,1451 @@ pub async fn run_usage(
         antigravity_plan_debug: args.antigravity_plan_debug,
     };
 
+    start_stream(args.stream, global.output_schema.into());
+    start_progress(format, global);
     let outputs = collect_usage_outputs(&request, &config, registry).await?;
+    let output = args.output.as_deref();
     let prefs = OutputPreferences {
-        format,
-        pretty: args.pretty,
-        json_only: global.json_only,
-        no_color: global.no_color,
+        no_color: prefs.no_color || writes_to_file(output),
+        ..*prefs
     };
-    print_outputs(&outputs, &prefs)
+    if args.stream {
+        print_stream_summary(outputs.iter().map(|o| o.error.as_ref()));
+    } else {
+        print_outputs(
+            &outputs,
+            &prefs,
+            &config.provider_labels(),
+            timezone.as_deref(),
+            global.output_schema.into(),
+            output,
+        )?;
+    }
+    check_provider_failures(outputs.iter().map(|o| o.error.as_ref()), args.strict)?;
+    if args.fail_on_alert {
+        check_alerts(&outputs)?;
+    }
+    Ok(())
+}
+
+fn check_alerts(outputs: &[ProviderPayload]) -> Result<()> {
+    let windows: Vec<String> = outputs
+        .iter()
+        .flat_map(|output| {
+            output.alerts.iter().map(|alert| {
+                format!(
+                    "{} {} {:.0}%",
+                    output.provider, alert.window, alert.used_percent
+                )
+            })
+        })
+        .collect();
+    if windows.is_empty() {
+        return Ok(());
+    }
+    Err(CliError::UsageAlert {
+        windows: windows.join(", "),
+    }
+    .into())
 }
 
 pub async fn run_cost(
     args: CostArgs,
+    prefs: &OutputPreferences,
     registry: &ProviderRegistry,
     global: &GlobalArgs,
 ) -> Result<()> {
     let config = Config::load(args.config.as_ref())?;
 
-    let format = if args.json || global.json_only {
-        OutputFormat::Json
-    } else {
-        args.format.into()
-    };
+    let format = prefs.format;
+
+    if args.watch {
+        if format != OutputFormat::Text || global.json_only {
+            return Err(anyhow!("--watch only supports text output"));
+        }
+        let watch_args = CostWatchArgs {
+            providers: collect_report_provider_ids(&resolve_provider_args(
+                &args.providers,
+                &config,
+                registry,
+            )?),
+            interval: args.interval.unwrap_or(10),
+            timezone: args
+                .timezone
+                .as_deref()
+                .map(fuelcheck_core::reports::validate_timezone)
+                .transpose()?,
+            compact: args.compact,
+        };
+        return tui::run_cost_watch(watch_args, config).await;
+    }
 
     if let Some(report_kind) = args.report {
-        let providers = collect_report_provider_ids(
-            &args
-                .providers
-                .iter()
-                .copied()
-                .map(Into::into)
-                .collect::<Vec<ProviderSelector>>(),
-        );
-        let report_collection = build_cost_report_collection(
+        if format == OutputFormat::Ndjson {
+            return Err(anyhow!(
+                "--format ndjson does not apply to cost --report; use --json"
+            ));
+        }
+        let providers = collect_report_provider_ids(&resolve_provider_args(
+            &args.providers,
+            &config,
+            registry,
+        )?);
+        if args.follow {
+            if format != OutputFormat::Text || global.json_only {
+                return Err(anyhow!("--follow only supports text output"));
+            }
+            return follow_report(&args, report_kind, providers, &config).await;
+        }
+        let mut report_collection = build_cost_report_collection(
             report_kind.into(),
             providers,
             args.since.as_deref(),
             args.until.as_deref(),
             args.timezone.as_deref(),
-        )?;
+            args.group_by.map(Into::into),
+            &config,
+        )
+        .await?;
+        if let Some(sort) = args.sort {
+            report_collection.sort_rows(sort.into(), args.desc);
+        }
+        if args.show_efficiency {
+            report_collection.attach_efficiency();
+        }
 
+        let output = args.output.as_deref();
         if format == OutputFormat::Json || global.json_only {
             let value = fuelcheck_core::reports::collection_to_json_value(&report_collection)?;
-            if args.pretty {
-                println!("{}", serde_json::to_string_pretty(&value)?);
+            let json = if args.pretty {
+                serde_json::to_string_pretty(&value)?
             } else {
-                println!("{}", serde_json::to_string(&value)?);
-            }
-            return Ok(());
+                serde_json::to_string(&value)?
+            };
+            return write_output(&json, output);
         }
 
-        if !global.json_only {
-            println!(
-                "{}",
-                ui_reports::render_collection_text(
+        if format == OutputFormat::Markdown {
+            write_output(
+                &ui_reports::render_collection_markdown(
                     &report_collection,
                     args.compact,
-                    args.timezone.as_deref()
-                )
-            );
+                    args.timezone.as_deref(),
+                ),
+                output,
+            )?;
+        } else if !global.json_only {
+            write_output(
+                &ui_reports::render_collection_text(
+                    &report_collection,
+                    args.compact,
+                    args.timezone.as_deref(),
+                ),
+                output,
+            )?;
         }
         return Ok(());
     }
 
     let request = CostRequest {
-        providers: args.providers.into_iter().map(Into::into).collect(),
+        providers: resolve_provider_args(&args.providers, &config, registry)?,
     };
+    start_stream(args.stream, global.output_schema.into());
+    start_progress(format, global);
     let outputs = collect_cost_outputs(&request, &config, registry).await?;
 
+    let output = args.output.as_deref();
     let prefs = OutputPreferences {
-        format,
-        pretty: args.pretty,
-        json_only: global.json_only,
-        no_color: global.no_color,
+        no_color: prefs.no_color || writes_to_file(output),
+        ..*prefs
     };
-    print_outputs(&outputs, &prefs)
+    if args.stream {
+        print_stream_summary(outputs.iter().map(|o| o.error.as_ref()));
+    } else {
+        print_outputs(
+            &outputs,
+            &prefs,
+            &config.provider_labels(),
+            None,
+            global.output_schema.into(),
+            output,
+        )?;
+    }
+    check_provider_failures(outputs.iter().map(|o| o.error.as_ref()), args.strict)
+}
+
+// Re-renders the report whenever the provider logs change, until Ctrl-C.
+async fn follow_report(
+    args: &CostArgs,
+    report_kind: CostReportKindArg,
+    providers: Vec<ProviderId>,
+    config: &Config,
+) -> Result<()> {
+    let dirs = fuelcheck_core::reports::report_log_dirs(&providers)?;
+    let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        if let Ok(event) = event
+            && !event.kind.is_access()
+            && event
+                .paths
+                .iter()
+                .any(|path| fuelcheck_core::reports::is_log_path(path))
+        {
+            let _ = tx.send(());
+        }
+    })?;
+    for dir in &dirs {
+        watcher
+            .watch(dir, notify::RecursiveMode::Recursive)
+            .with_context(|| format!("watch {}", dir.display()))?;
+    }
+
+    let mut last = String::new();
+    loop {
+        let mut collection = build_cost_report_collection(
+            report_kind.into(),
+            providers.clone(),
+            args.since.as_deref(),
+            args.until.as_deref(),
+            args.timezone.as_deref(),
+            args.group_by.map(Into::into),
+            config,
+        )
+        .await?;
+        if let Some(sort) = args.sort {
+            collection.sort_rows(sort.into(), args.desc);
+        }
+        if args.show_efficiency {
+            collection.attach_efficiency();
+        }
+        let text =
+            ui_reports::render_collection_text(&collection, args.compact, args.timezone.as_deref());
+        // Sessions write many lines that are not token events; only redraw
+        // when the numbers moved.
+        if text != last {
+            println!("\x1b[2J\x1b[H{}", text);
+            std::io::stdout().flush()?;
+            last = text;
+        }
+        tokio::select! {
+            changed = rx.recv() => {
+                if changed.is_none() {
+                    return Ok(());
+                }
+                // Let a burst of appends settle into one redraw.
+                tokio::time::sleep(Duration::from_millis(250)).await;
+                while rx.try_recv().is_ok() {}
+            }
+            _ = tokio::signal::ctrl_c() => return Ok(()),
+        }
+    }
 }
 
-pub async fn run_config(cmd: ConfigCommandArgs, global: &GlobalArgs) -> Result<()> {
+pub async fn run_config(
+    cmd: ConfigCommandArgs,
+    registry: &ProviderRegistry,
+    global: &GlobalArgs,
+) -> Result<()> {
     let mut command = cmd.command;
     if global.json_only {
         match &mut command {
-            ConfigCommand::Validate(args) => args.format = Some(crate::args::OutputFormatArg::Json),
+            ConfigCommand::Validate(args) => {
+                args.common.format = Some(crate::args::OutputFormatArg::Json)
+            }
             ConfigCommand::Dump(args) => args.format = Some(crate::args::OutputFormatArg::Json),
+            ConfigCommand::Migrate(args) => {
+                args.common.format = Some(crate::args::OutputFormatArg::Json)
+            }
         }
     }
 
     match command {
-        ConfigCommand::Validate(args) => validate_config(args),
+        ConfigCommand::Validate(args) => validate_config(args, registry),
         ConfigCommand::Dump(args) => dump_config(args),
+        ConfigCommand::Migrate(args) => migrate_config(args),
     }
 }
 
-pub async fn run_setup(args: SetupArgs) -> Result<()> {
+pub async fn run_setup(args: SetupArgs, registry: &ProviderRegistry) -> Result<()> {
+    if let Some(source) = &args.import_codexbar {
+        return import_codexbar(&args, source.as_ref());
+    }
+
     let config_path = Config::path(args.config.as_ref())?;
+    let mut secrets = SecretReader::default();
+    let request = SetupRequest {
+        enable_all: args.enable_all,
+        claude_cookie: secrets.read("--claude-cookie", args.claude_cookie.as_deref())?,
+        cursor_cookie: secrets.read("--cursor-cookie", args.cursor_cookie.as_deref())?,
+        factory_cookie: secrets.read("--factory-cookie", args.factory_cookie.as_deref())?,
+        api_key: secrets.read("--api-key", args.api_key.as_deref())?,
+    };
+    let detected = DetectResult::detect();
+
+    if !args.providers.is_empty() {
+        let mut config = Config::load(args.config.as_ref())?;
+        let selectors = resolve_provider_args(&args.providers, &config, registry)?;
+        let providers = registry.expand(&selectors, &config);
+        apply_provider_setup(&mut config, &providers, &request, &detected);
+        config.save(args.config.as_ref())?;
+
+        let names = providers
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(", ");
+        println!("Configured {} in {}", names, config_path.display());
+        print_setup_hints(&args, &detected, &providers);
+        return Ok(());
+    }
+
     if config_path.exists() && !args.force {
         return Err(anyhow!(
-            "Config already exists at {}. Use --force to overwrite.",
+            "Config already exists at {}. Use --force to overwrite, or --provider to set up one provider.",
             config_path.display()
         ));
     }
 
-    let detected = DetectResult::detect();
-    let config = build_setup_config(
-        &SetupRequest {
-            enable_all: args.enable_all,
-            claude_cookie: args.claude_cookie.clone(),
-            cursor_cookie: args.cursor_cookie.clone(),
-            factory_cookie: args.factory_cookie.clone(),
-        },
-        &detected,
-    );
+    let config = build_setup_config(&request, &detected);
     config.save(args.config.as_ref())?;
 
     println!(
         "Setup complete. Config written to {}",
         config_path.display()
     );
-    if !detected.codex_auth {
+    print_setup_hints(&args, &detected, &ProviderId::ordered());
+
+    Ok(())
+}
+
+// Secret flags accept `-` (read stdin) or `@path` (read a file) so values stay
+// out of shell history and process listings.
+#[derive(Default)]
+struct SecretReader {
+    stdin_used_by: Option<&'static str>,
+}
+
+impl SecretReader {
+    fn read(&mut self, flag: &'static str, raw: Option<&str>) -> Result<Option<String>> {
+        let Some(raw) = raw else {
+            return Ok(None);
+        };
+        let value = if raw == "-" {
+            if let Some(previous) = self.stdin_used_by.replace(flag) {
+                return Err(anyhow!(
+                    "{} and {} both read from stdin; pass one of them as @file",
+                    previous,
+                    flag
+                ));
+            }
+            std::io::read_to_string(std::io::stdin())
+                .map_err(|err| anyhow!("read {} from stdin: {}", flag, err))?
+        } else if let Some(path) = raw.strip_prefix('@') {
+            std::fs::read_to_string(path)
+                .map_err(|err| anyhow!("read {} from {}: {}", flag, path, err))?
+        } else {
+            raw.to_string()
+        };
+        let value = value.trim();
+        if value.is_empty() {
+            return Err(anyhow!("{} is empty", flag));
+        }
+        Ok(Some(value.to_string()))
+    }
+}
+
+fn print_setup_hints(args: &SetupArgs, detected: &DetectResult, providers: &[ProviderId]) {
+    if providers.contains(&ProviderId::Codex) && !detected.codex_auth {
         println!("Codex: run `codex` to authenticate (creates ~/.codex/auth.json).");
     }
-    if !detected.claude_oauth && args.claude_cookie.is_none() {
+    if providers.contains(&ProviderId::Claude)
+        && !detected.claude_oauth
+        && args.claude_cookie.is_none()
+    {
         println!("Claude: run `claude` to authenticate (creates ~/.claude/.credentials.json).");
         println!(
             "Claude: or provide a session cookie via `fuelcheck-cli setup --claude-cookie \"sessionKey=...\"`."
         );
     }
-    if !detected.gemini_oauth {
+    if providers.contains(&ProviderId::Gemini) && !detected.gemini_oauth {
         println!("Gemini: run `gemini` to authenticate (creates ~/.gemini/oauth_creds.json).");
     }
-    if args.cursor_cookie.is_none() {
+    if providers.contains(&ProviderId::Cursor) && args.cursor_cookie.is_none() {
         println!("Cursor: add cookie header via `fuelcheck-cli setup --cursor-cookie \"...\"`.");
     }
-    if args.factory_cookie.is_none() {
+    if providers.contains(&ProviderId::Factory) && args.factory_cookie.is_none() {
         println!(
             "Factory (Droid): add cookie header via `fuelcheck-cli setup --factory-cookie \"...\"`."
         );
     }
+}
+
+pub async fn run_whoami(
+    args: WhoamiArgs,
+    prefs: &OutputPreferences,
+    registry: &ProviderRegistry,
+    global: &GlobalArgs,
+) -> Result<()> {
+    let config = Config::load(args.config.as_ref())?;
+    let request = WhoamiRequest {
+        providers: resolve_provider_args(&args.providers, &config, registry)?,
+        source: args.source.into(),
+    };
+    let format = prefs.format;
+    start_stream(args.stream, global.output_schema.into());
+    start_progress(format, global);
+    let outputs = collect_identity_outputs(&request, &config, registry).await?;
+
+    if args.stream {
+        print_stream_summary(outputs.iter().map(|o| o.error.as_ref()));
+    } else if let Some(text) = render_identities(
+        &outputs,
+        &TextRenderOptions {
+            format: prefs.format,
+            pretty: prefs.pretty,
+            json_only: prefs.json_only,
+            use_color: prefs.use_color(),
+            labels: &config.provider_labels(),
+            timezone: None,
+            schema: global.output_schema.into(),
+        },
+    )? {
+        println!("{}", text);
+    }
+
+    check_provider_failures(outputs.iter().map(|o| o.error.as_ref()), args.strict)
+}
 
+pub async fn run_status(
+    args: StatusArgs,
+    prefs: &OutputPreferences,
+    registry: &ProviderRegistry,
+    global: &GlobalArgs,
+) -> Result<()> {
+    let config = Config::load(args.config.as_ref())?;
+    let request = StatusRequest {
+        providers: resolve_provider_args(&args.providers, &config, registry)?,
+        web_timeout: args.web_timeout,
+    };
+    let format = prefs.format;
+    start_stream(args.stream, global.output_schema.into());
+    start_progress(format, global);
+    let outputs = collect_status_outputs(&request, &config, registry).await?;
+
+    if args.stream {
+        print_stream_summary(outputs.iter().map(|o| o.error.as_ref()));
+    } else {
+        print_outputs(
+            &outputs,
+            prefs,
+            &config.provider_labels(),
+            None,
+            global.output_schema.into(),
+            None,
+        )?;
+    }
+    check_provider_failures(outputs.iter().map(|o| o.error.as_ref()), args.strict)
+}
+
+fn start_progress(format: OutputFormat, global: &GlobalArgs) {
+    if format == OutputFormat::Text && !global.json_only && std::io::stderr().is_terminal() {
+        progress::enable_display();
+    }
+}
+
+// `--stream` prints each payload as one JSON line the moment its provider is
+// done, followed by a summary line, instead of a single array at the end.
+fn start_stream(stream: bool, schema: OutputSchema) {
+    if !stream {
+        return;
+    }
+    match schema {
+        OutputSchema::V1 => fuelcheck_core::progress::set_output_observer(print_stream_line_v1),
+        OutputSchema::V2 => fuelcheck_core::progress::set_output_observer(print_stream_line),
+    }
+}
+
+fn print_stream_line(line: &str) {
+    let mut stdout = std::io::stdout().lock();
+    let _ = writeln!(stdout, "{}", line);
+    let _ = stdout.flush();
+}
+
+fn print_stream_line_v1(line: &str) {
+    match serde_json::from_str::<serde_json::Value>(line) {
+        Ok(mut value) => {
+            OutputSchema::V1.convert(&mut value);
+            print_stream_line(&value.to_string());
+        }
+        Err(_) => print_stream_line(line),
+    }
+}
+
+fn print_stream_summary<'a>(errors: impl Iterator<Item = Option<&'a ProviderErrorPayload>>) {
+    let (total, failed) = errors.fold((0, 0), |(total, failed), error| {
+        (total + 1, failed + usize::from(error.is_some()))
+    });
+    print_stream_line(
+        &serde_json::json!({ "summary": { "total": total, "failed": failed } }).to_string(),
+    );
+}
+
+// Runs after outputs are printed: partial failures get their own exit code unless
+// `--strict` makes any provider error fatal.
+fn check_provider_failures<'a>(
+    errors: impl Iterator<Item = Option<&'a ProviderErrorPayload>>,
+    strict: bool,
+) -> Result<()> {
+    let errors: Vec<_> = errors.collect();
+    let total = errors.len();
+    let failures: Vec<&ProviderErrorPayload> = errors.into_iter().flatten().collect();
+    let failed = failures.len();
+    if failed == 0 {
+        return Ok(());
+    }
+    if strict || failed == total {
+        let code = failures.first().map(|err| err.code).unwrap_or(1);
+        return Err(CliError::ProvidersFailed {
+            failed,
+            total,
+            code,
+        }
+        .into());
+    }
+    Err(CliError::PartialFailure { failed, total }.into())
+}
+
+fn import_codexbar(args: &SetupArgs, source: Option<&PathBuf>) -> Result<()> {
+    let source = source
+        .cloned()
+        .or_else(codexbar_config_path)
+        .ok_or(CliError::ConfigPathUnavailable)?;
+    if !source.exists() {
+        return Err(CliError::ConfigMissing(source).into());
+    }
+    // Never write back into CodexBar's own file.
+    let target = args
+        .config
+        .clone()
+        .or_else(native_config_path)
+        .ok_or(CliError::ConfigPathUnavailable)?;
+    if target.exists() && !args.force {
+        return Err(anyhow!(
+            "Config already exists at {}. Use --force to overwrite.",
+            target.display()
+        ));
+    }
+
+    let contents = std::fs::read_to_string(&source)
+        .with_context(|| format!("read CodexBar config {}", source.display()))?;
+    let import = import_codexbar_config(&contents)?;
+    import.config.save(Some(&target))?;
+
+    println!(
+        "Imported {} provider(s) from {} into {}",
+        import.config.providers.as_ref().map_or(0, Vec::len),
+        source.display(),
+        target.display()
+    );
+    for skipped in &import.skipped {
+        println!("Skipped provider {}", skipped);
+    }
     Ok(())
 }
 
-fn validate_config(args: ConfigArgs) -> Result<()> {
-    let path = Config::path(args.config.as_ref())?;
-    let missing = !path.exists();
-    let _config = Config::load(args.config.as_ref())?;
-    match args.format.map(Into::into).unwrap_or(OutputFormat::Text) {
-        OutputFormat::Json => {
-            let output = if missing {
-                serde_json::json!({
-                    "status": "ok",
-                    "missing": true,
-                    "path": path.display().to_string()
+pub fn run_paths(args: PathsArgs, global: &GlobalArgs) -> Result<()> {
+    let paths = AppPaths::resolve(args.config.as_ref())?;
+    if args.json || global.json_only {
+        if args.pretty {
+            println!("{}", serde_json::to_string_pretty(&paths)?);
+        } else {
+            println!("{}", serde_json::to_string(&paths)?);
+        }
+        return Ok(());
+    }
+
+    let config_note = if paths.legacy_config {
+        " (legacy CodexBar location)"
+    } else if !paths.config_file.exists() {
+        " (missing)"
+    } else {
+        ""
+    };
+    println!("config: {}{}", paths.config_file.display(), config_note);
+    println!("config dir: {}", paths.config_dir.display());
+    println!("cache dir: {}", paths.cache_dir.display());
+    println!("data dir: {}", paths.data_dir.display());
+    Ok(())
+}
+
+// `--provider` also takes aliases and groups, which clap cannot know about, so
+// the script lists the ones configured when it is generated.
+pub fn run_schema(args: SchemaArgs) -> Result<()> {
+    let schema = schema::json_schema(args.target.into());
+    println!("{}", serde_json::to_string_pretty(&schema)?);
+    Ok(())
+}
+
+pub fn run_completions(args: CompletionsArgs) -> Result<()> {
+    let config = Config::load(args.config.as_ref())?;
+    let mut names: Vec<String> = ProviderSelectorArg::value_variants()
+        .iter()
+        .filter_map(ValueEnum::to_possible_value)
+        .map(|value| value.get_name().to_string())
+        .collect();
+    names.extend(
+        config
+            .providers
+            .iter()
+            .flatten()
+            .flat_map(|cfg| cfg.aliases.iter().flatten())
+            .map(|alias| alias.trim().to_string()),
+    );
+    names.extend(
+        config
+            .groups
+            .iter()
+            .flatten()
+            .map(|(name, _)| name.trim().to_string()),
+    );
+    names.retain(|name| !name.is_empty());
+    names.dedup();
+
+    let mut command = with_provider_values(Cli::command(), &names);
+    let bin = command.get_name().to_string();
+    let mut script = Vec::new();
+    clap_complete::generate(args.shell, &mut command, bin, &mut script);
+    std::io::stdout().write_all(&script)?;
+    Ok(())
+}
+
+fn with_provider_values(mut command: clap::Command, names: &[String]) -> clap::Command {
+    for id in ["provider", "providers"] {
+        if command.get_arguments().any(|arg| arg.get_id() == id) {
+            command = command.mut_arg(id, |arg| {
+                arg.value_parser(PossibleValuesParser::new(names.to_vec()))
+            });
+        }
+    }
+    let subcommands: Vec<String> = command
+        .get_subcommands()
+        .map(|sub| sub.get_name().to_string())
+        .collect();
+    for name in subcommands {
+        command = command.mut_subcommand(name, |sub| with_provider_values(sub, names));
+    }
+    command
+}
+
+pub fn run_providers(
+    args: ProvidersArgs,
+    registry: &ProviderRegistry,
+    global: &GlobalArgs,
+) -> Result<()> {
+    let config = Config::load(args.config.as_ref())?;
+    let providers = list_providers(&config, registry);
+    if args.json || global.json_only {
+        if args.pretty {
+            println!("{}", serde_json::to_string_pretty(&providers)?);
+        } else {
+            println!("{}", serde_json::to_string(&providers)?);
+        }
+        return Ok(());
+    }
+
+    let flag = |value: bool| if value { "yes" } else { "no" };
+    println!("ID         VERSION     SOURCES         ENABLED  CREDS  ACCOUNTS  COST  WHOAMI");
+    for provider in providers {
+        let capabilities = &provider.capabilities;
+        println!(
+            "{:<10} {:<11} {:<15} {:<8} {:<6} {:<9} {:<5} {}",
+            capabilities.id,
+            capabilities.version,
+            capabilities.sources.join(","),
+            flag(provider.enabled),
+            flag(provider.credentials),
+            flag(capabilities.token_accounts),
+            flag(capabilities.cost_reports),
+            flag(capabilities.identity)
+        );
+    }
+    Ok(())
+}
+
+// Everything a bug report needs to pin down the build and the machine's layout.
+pub fn run_version(
+    args: VersionArgs,
+    registry: &ProviderRegistry,
+    global: &GlobalArgs,
+) -> Result<()> {
+    let paths = AppPaths::resolve(args.config.as_ref())?;
+    let features: Vec<&str> = env!("FUELCHECK_FEATURES")
+        .split(',')
+        .filter(|feature| !feature.is_empty())
+        .collect();
+    let built_at = env!("FUELCHECK_BUILD_EPOCH")
+        .parse::<i64>()
+        .ok()
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0));
+    let providers: Vec<(String, &'static str)> = registry
+        .capabilities(&Config::default())
+        .into_iter()
+        .map(|provider| (provider.id, provider.version))
+        .collect();
+
+    if args.json || global.json_only {
+        let value = serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "commit": env!("FUELCHECK_GIT_COMMIT"),
+            "buildDate": built_at.map(|at| at.to_rfc3339()),
+            "target": env!("FUELCHECK_BUILD_TARGET"),
+            "profile": env!("FUELCHECK_BUILD_PROFILE"),
+            "features": features,
+            "providers": providers
+                .iter()
+                .map(|(id, version)| serde_json::json!({ "id": id, "version": version }))
+                .collect::<Vec<_>>(),
+            "paths": paths,
+        });
+        if args.pretty {
+            println!("{}", serde_json::to_string_pretty(&value)?);
+        } else {
+            println!("{}", serde_json::to_string(&value)?);
+        }
+        return Ok(());
+    }
+
+    println!(
+        "fuelcheck-cli {} ({} {})",
+        env!("CARGO_PKG_VERSION"),
+        env!("FUELCHECK_GIT_COMMIT"),
+        built_at
+            .map(|at| at.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| "unknown date".to_string())
+    );
+    println!(
+        "target: {} ({})",
+        env!("FUELCHECK_BUILD_TARGET"),
+        env!("FUELCHECK_BUILD_PROFILE")
+    );
+    println!(
+        "features: {}",
+        if features.is_empty() {
+            "none".to_string()
+        } else {
+            features.join(", ")
+        }
+    );
+    println!("providers:");
+    for (id, version) in &providers {
+        println!("  {:<10} {}", id, version);
+    }
+    println!("config: {}", paths.config_file.display());
+    println!("cache dir: {}", paths.cache_dir.display());
+    println!("data dir: {}", paths.data_dir.display());
+    Ok(())
+}
+
+// Meant to be wired up as an agent hook, e.g. Codex's
+// `notify = ["fuelcheck-cli", "hook", "codex"]`: records the event, samples the
+// agent's quota, and prints a warning when a window drops below `--warn-below`.
+pub async fn run_hook(args: HookArgs, registry: &ProviderRegistry) -> Result<()> {
+    let raw = match args.payload.as_deref() {
+        None | Some("-") => std::io::read_to_string(std::io::stdin())
+            .map_err(|err| anyhow!("read hook payload from stdin: {}", err))?,
+        Some(raw) => raw.to_string(),
+    };
+    let event: serde_json::Value =
+        serde_json::from_str(&raw).context("hook payload is not valid JSON")?;
+    let provider = match args.agent {
+        HookAgentArg::Codex => ProviderId::Codex,
+    };
+    let history = History::open_default()?;
+    let kind = event
+        .get("type")
+        .and_then(serde_json::Value::as_str)
+        .unwrap_or("event");
+    history.append(&HistoryEvent::new(
+        provider.to_string(),
+        kind,
+        hook_event_summary(event.clone()),
+    ))?;
+
+    let config = Config::load(args.config.as_ref())?;
+    let previous = history.last(&provider.to_string(), USAGE_SAMPLE)?;
+    let outputs = fetch_quota_samples(vec![provider.into()], &config, registry).await?;
+    for output in &outputs {
+        let Some(sample) = HistoryEvent::usage_sample(output) else {
+            continue;
+        };
+        for (window, label) in QUOTA_WINDOWS {
+            let Some(remaining) = sample.remaining(window) else {
+                continue;
+            };
+            let before = previous.as_ref().and_then(|event| event.remaining(window));
+            if remaining >= args.warn_below || before.is_some_and(|b| b < args.warn_below) {
+                continue;
+            }
+            let resets = output
+                .usage
+                .as_ref()
+                .and_then(|usage| match window {
+                    "primary" => usage.primary.as_ref(),
+                    "secondary" => usage.secondary.as_ref(),
+                    _ => usage.tertiary.as_ref(),
                 })
-            } else {
-                serde_json::json!({"status": "ok"})
+                .and_then(|rate| rate.resets_at)
+                .map(|at| {
+                    format!(
+                        " (resets {})",
+                        at.with_timezone(&chrono::Local).format("%a %H:%M")
+                    )
+                })
+                .unwrap_or_default();
+            println!(
+                "fuelcheck: {} {} quota is down to {:.0}% remaining{}",
+                config.provider_labels().display_name(&output.provider),
+                label,
+                remaining,
+                resets
+            );
+        }
+        history.append(&sample)?;
+    }
+    Ok(())
+}
+
+// For git hooks: reads recent usage samples from the history (fetching and
+// recording fresh ones when they are older than `--max-age`) and warns loudly
+// about windows below `--min-remaining`. Only `--block` turns that into a
+// non-zero exit.
+pub async fn run_guard(args: GuardArgs, registry: &ProviderRegistry) -> Result<()> {
+    let config = Config::load(args.config.as_ref())?;
+    let selectors = resolve_provider_args(&args.providers, &config, registry)?;
+    let provider_ids = if selectors.is_empty() {
+        config.enabled_providers_or_default()
+    } else {
+        registry.expand(&selectors, &config)
+    };
+    let history = History::open_default()?;
+    let max_age = chrono::Duration::minutes(args.max_age as i64);
+    let mut samples = Vec::new();
+    let mut stale = Vec::new();
+    for id in provider_ids {
+        match history.last(&id.to_string(), USAGE_SAMPLE)? {
+            Some(sample) if chrono::Utc::now() - sample.at <= max_age => samples.push(sample),
+            _ => stale.push(ProviderSelector::from(id)),
+        }
+    }
+    if !stale.is_empty() {
+        for output in fetch_quota_samples(stale, &config, registry).await? {
+            if let Some(sample) = HistoryEvent::usage_sample(&output) {
+                history.append(&sample)?;
+                samples.push(sample);
+            }
+        }
+    }
+
+    let labels = config.provider_labels();
+    let low: Vec<String> = samples
+        .iter()
+        .flat_map(|sample| {
+            QUOTA_WINDOWS.iter().filter_map(|(window, label)| {
+                let remaining = sample.remaining(window)?;
+                (remaining < args.min_remaining).then(|| {
+                    format!(
+                        "{} {} {:.0}%",
+                        labels.display_name(&sample.provider),
+                        label,
+                        remaining
+                    )
+                })
+            })
+        })
+        .collect();
+    if low.is_empty() {
+        return Ok(());
+    }
+    eprintln!();
+    eprintln!(
+        "!! fuelcheck: quota nearly exhausted (below {}% remaining)",
+        args.min_remaining
+    );
+    for window in &low {
+        eprintln!("!!   {}", window);
+    }
+    eprintln!();
+    if args.block {
+        return Err(CliError::QuotaLow {
+            min_remaining: args.min_remaining,
+            windows: low.join(", "),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+const QUOTA_WINDOWS: [(&str, &str); 3] = [
+    ("primary", "session"),
+    ("secondary", "weekly"),
+    ("tertiary", "tertiary"),
+];
+
+// Hooks and guards run inside other tools, so provider failures are logged and
+// skipped rather than failing the command.
+async fn fetch_quota_samples(
+    providers: Vec<ProviderSelector>,
+    config: &Config,
+    registry: &ProviderRegistry,
+) -> Result<Vec<ProviderPayload>> {
+    let request = UsageRequest {
+        providers,
+        source: SourcePreference::Auto,
+        status: false,
+        no_credits: false,
+        refresh: false,
+        web_debug_dump_html: false,
+        web_timeout: 20,
+        account: None,
+        account_index: None,
+        all_accounts: false,
+        antigravity_plan_debug: false,
+    };
+    let outputs = collect_usage_outputs(&request, config, registry).await?;
+    Ok(outputs
+        .into_iter()
+        .filter(|output| match &output.error {
+            Some(error) => {
+                logger::log(
+                    LogLevel::Warning,
+                    "quota_check_failed",
+                    &error.message,
+                    None,
+                );
+                false
+            }
+            None => true,
+        })
+        .collect())
+}
+
+// Prompts and replies stay out of the history file; only event metadata is kept.
+fn hook_event_summary(mut event: serde_json::Value) -> serde_json::Value {
+    if let Some(fields) = event.as_object_mut() {
+        fields.retain(|key, _| !key.contains("message"));
+    }
+    event
+}
+
+// Fails the pipeline when period-to-date spend is over `--budget`. The JSON form
+// carries a stable `reason` (`within_budget` / `budget_exceeded`) for CI logs.
+pub async fn run_ci_gate(args: CiGateArgs, registry: &ProviderRegistry) -> Result<()> {
+    let config = Config::load(args.config.as_ref())?;
+    let request = BudgetRequest {
+        providers: resolve_provider_args(&args.providers, &config, registry)?,
+        budget: args.budget.clone(),
+        period: args.period.into(),
+        timezone: args.timezone.clone(),
+    };
+    let check = collect_budget_check(&request, &config, registry).await?;
+
+    if args.json {
+        if args.pretty {
+            println!("{}", serde_json::to_string_pretty(&check)?);
+        } else {
+            println!("{}", serde_json::to_string(&check)?);
+        }
+    } else {
+        println!("ci-gate: {} (since {})", check.message, check.since);
+        let labels = config.provider_labels();
+        for spend in &check.providers {
+            println!(
+                "  {}: {:.2} {} ({})",
+                labels.display_name(&spend.provider),
+                spend.amount,
+                check.currency,
+                spend.source
+            );
+        }
+        for skip in &check.skipped {
+            println!(
+                "  {}: not counted ({})",
+                labels.display_name(&skip.provider),
+                skip.reason
+            );
+        }
+    }
+
+    if !check.passed {
+        return Err(CliError::BudgetExceeded {
+            period: check.period.to_string(),
+            spent: format!("{:.2} {}", check.spent, check.currency),
+            budget: args.budget.to_string(),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+// Month-to-date spend against each provider's `monthly_budget`, with the
+// month-end projection at the current daily rate.
+pub async fn run_budget(args: BudgetArgs, registry: &ProviderRegistry) -> Result<()> {
+    let config = Config::load(args.config.as_ref())?;
+    let request = MonthlyBudgetRequest {
+        providers: resolve_provider_args(&args.providers, &config, registry)?,
+        timezone: args.timezone.clone(),
+    };
+    let report = collect_monthly_budgets(&request, &config, registry).await?;
+
+    if args.json {
+        if args.pretty {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            println!("{}", serde_json::to_string(&report)?);
+        }
+    } else {
+        println!("budget: month to date (since {})", report.since);
+        let labels = config.provider_labels();
+        for status in &report.providers {
+            let percent = status
+                .percent_used
+                .map(|percent| format!("{:.0}%", percent))
+                .unwrap_or_else(|| "-".to_string());
+            let outlook = match status.overrun_on {
+                Some(date) if status.exceeded => format!("exceeded on {}", date),
+                Some(date) => format!("projected to exceed on {}", date),
+                None => "on track".to_string(),
             };
-            if args.pretty {
+            println!(
+                "  {}: {:.2} of {:.2} {} ({}), projected {:.2}, {} ({})",
+                labels.display_name(&status.provider),
+                status.spent,
+                status.budget,
+                status.currency,
+                percent,
+                status.projected,
+                outlook,
+                status.source
+            );
+        }
+        for skip in &report.skipped {
+            println!(
+                "  {}: not counted ({})",
+                labels.display_name(&skip.provider),
+                skip.reason
+            );
+        }
+    }
+
+    // The exit error names the first provider over budget; the output above
+    // lists them all.
+    if let Some(status) = report.providers.iter().find(|status| status.exceeded) {
+        return Err(CliError::BudgetExceeded {
+            period: "month".to_string(),
+            spent: format!(
+                "{:.2} {} ({})",
+                status.spent, status.currency, status.provider
+            ),
+            budget: format!("{:.2} {}", status.budget, status.currency),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+pub async fn run_forecast(args: ForecastArgs, registry: &ProviderRegistry) -> Result<()> {
+    let config = Config::load(args.config.as_ref())?;
+    let request = ForecastRequest {
+        providers: resolve_provider_args(&args.providers, &config, registry)?,
+        days: args.days,
+        timezone: args.timezone.clone(),
+    };
+    let report = collect_forecast(&request, &config, registry).await?;
+
+    if args.json {
+        if args.pretty {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            println!("{}", serde_json::to_string(&report)?);
+        }
+        return Ok(());
+    }
+
+    println!(
+        "forecast: burn rate over {} day(s) since {}",
+        report.days, report.since
+    );
+    let labels = config.provider_labels();
+    let local = |at: chrono::DateTime<chrono::Utc>| {
+        at.with_timezone(&chrono::Local)
+            .format("%Y-%m-%d %H:%M")
+            .to_string()
+    };
+    for forecast in &report.providers {
+        println!("  {}:", labels.display_name(&forecast.provider));
+        if let Some(cost) = &forecast.cost {
+            println!(
+                "    cost: {:.2} USD this month, {:.2} USD/day, projected {:.2} USD by {}",
+                cost.month_to_date, cost.daily_rate, cost.projected_month_end, report.month_end
+            );
+        }
+        for window in &forecast.windows {
+            let outlook = match window.exhausted_at {
+                Some(at) => format!("runs out {}", local(at)),
+                None => "lasts until reset".to_string(),
+            };
+            println!(
+                "    {}: {:.0}% used, {} (resets {})",
+                window.window,
+                window.used_percent,
+                outlook,
+                local(window.resets_at)
+            );
+        }
+    }
+    for skip in &report.skipped {
+        println!(
+            "  {}: no forecast ({})",
+            labels.display_name(&skip.provider),
+            skip.reason
+        );
+    }
+    Ok(())
+}
+
+// Two report ranges side by side, e.g. this week against last week.
+pub async fn run_compare(args: CompareArgs, registry: &ProviderRegistry) -> Result<()> {
+    let config = Config::load(args.config.as_ref())?;
+    let request = CompareRequest {
+        providers: resolve_provider_args(&args.providers, &config, registry)?,
+        since: args.since.clone(),
+        until: args.until.clone(),
+        baseline_since: args.baseline_since.clone(),
+        baseline_until: args.baseline_until.clone(),
+        timezone: args.timezone.clone(),
+    };
+    let results = collect_comparisons(&request, &config).await?;
+
+    if args.json {
+        let value = fuelcheck_core::reports::compare::comparisons_to_json_value(&results)?;
+        if args.pretty {
+            println!("{}", serde_json::to_string_pretty(&value)?);
+        } else {
+            println!("{}", serde_json::to_string(&value)?);
+        }
+    } else {
+        println!("{}", ui_reports::render_comparisons_text(&results));
+    }
+    Ok(())
+}
+
+// Reported credit usage comes from balance samples in the history file, which
+// hooks, guard and every reconcile run record.
+pub async fn run_reconcile(args: ReconcileArgs, registry: &ProviderRegistry) -> Result<()> {
+    let config = Config::load(args.config.as_ref())?;
+    let selectors = resolve_provider_args(std::slice::from_ref(&args.provider), &config, registry)?;
+    let provider = match registry.expand(&selectors, &config)[..] {
+        [provider] => provider,
+        _ => return Err(anyhow!("reconcile takes a single provider")),
+    };
+
+    let request = ReconcileRequest {
+        provider,
+        month: args.month.clone(),
+        timezone: args.timezone.clone(),
+        credit_usd: args.credit_usd,
+        tolerance_percent: args.tolerance,
+    };
+    validate_reconcile_request(&request)?;
+
+    let history = History::open_default()?;
+    for output in fetch_quota_samples(vec![provider.into()], &config, registry).await? {
+        if let Some(sample) = HistoryEvent::usage_sample(&output) {
+            history.append(&sample)?;
+        }
+    }
+    let samples = history.events(&provider.to_string(), USAGE_SAMPLE)?;
+    let report = build_reconcile_report(&request, &samples, &config)?;
+
+    if args.json {
+        if args.pretty {
+            println!("{}", serde_json::to_string_pretty(&report)?);
+        } else {
+            println!("{}", serde_json::to_string(&report)?);
+        }
+    } else {
+        println!("{}", ui_reports::render_reconcile_text(&report));
+    }
+    Ok(())
+}
+
+pub fn run_history(args: HistoryArgs, registry: &ProviderRegistry) -> Result<()> {
+    let config = Config::load(args.config.as_ref())?;
+    let providers = if args.providers.is_empty() {
+        Vec::new()
+    } else {
+        let selectors = resolve_provider_args(&args.providers, &config, registry)?;
+        registry
+            .expand(&selectors, &config)
+            .into_iter()
+            .map(|provider| provider.to_string())
+            .collect()
+    };
+    let query = SnapshotQuery {
+        providers,
+        since: args
+            .since
+            .as_deref()
+            .map(|raw| parse_time_bound(raw, false))
+            .transpose()?,
+        until: args
+            .until
+            .as_deref()
+            .map(|raw| parse_time_bound(raw, true))
+            .transpose()?,
+        limit: (args.limit > 0).then_some(args.limit),
+    };
+    let records = SnapshotDb::open_default()?.query(&query)?;
+
+    if args.json {
+        if args.pretty {
+            println!("{}", serde_json::to_string_pretty(&records)?);
+        } else {
+            println!("{}", serde_json::to_string(&records)?);
+        }
+    } else {
+        println!("{}", ui_reports::render_history_text(&records));
+    }
+    Ok(())
+}
+
+pub fn run_sessions(cmd: SessionsCommandArgs) -> Result<()> {
+    let mut tags = SessionTags::load_default()?;
+    match cmd.command {
+        SessionsCommand::Tag(args) => {
+            if args.name.trim().is_empty() {
+                return Err(anyhow!("session name must not be empty"));
+            }
+            tags.set(&args.session, &args.name);
+            tags.save()?;
+            println!("Tagged {} as \"{}\"", args.session.trim(), args.name.trim());
+        }
+        SessionsCommand::Untag(args) => {
+            if !tags.remove(&args.session) {
+                return Err(anyhow!("session {} has no tag", args.session.trim()));
+            }
+            tags.save()?;
+            println!("Removed tag from {}", args.session.trim());
+        }
+    }
+    Ok(())
+}
+
+// Long-running publisher for desktop applets and monitoring so they don't have
+// to poll the CLI: D-Bus (`--dbus`), a Prometheus endpoint (`--listen`) and a
+// JSON state file (`--state-file`), in any combination.
+pub async fn run_daemon(args: DaemonArgs, registry: &ProviderRegistry) -> Result<()> {
+    if !args.dbus && args.listen.is_none() && args.state_file.is_none() {
+        return Err(anyhow!(
+            "nothing to serve; pass --dbus, --listen or --state-file"
+        ));
+    }
+    let config = Config::load(args.config.as_ref())?;
+    let request = UsageRequest {
+        providers: resolve_provider_args(&args.providers, &config, registry)?,
+        source: args.source.into(),
+        status: false,
+        no_credits: args.no_credits,
+        refresh: false,
+        web_debug_dump_html: false,
+        web_timeout: 20,
+        account: None,
+        account_index: None,
+        all_accounts: false,
+        antigravity_plan_debug: false,
+    };
+    let interval = Duration::from_secs(args.interval.max(30));
+    if let Some(listen) = args.listen {
+        return crate::exporter::serve(request, config, registry, listen, interval).await;
+    }
+    if let Some(path) = &args.state_file {
+        return crate::state_file::serve(request, config, registry, path, interval).await;
+    }
+    serve_dbus(request, config, registry, interval).await
+}
+
+#[cfg(all(feature = "dbus", target_os = "linux"))]
+async fn serve_dbus(
+    request: UsageRequest,
+    config: Config,
+    registry: &ProviderRegistry,
+    interval: Duration,
+) -> Result<()> {
+    crate::daemon::serve(request, config, registry, interval).await
+}
+
+#[cfg(not(all(feature = "dbus", target_os = "linux")))]
+async fn serve_dbus(
+    _request: UsageRequest,
+    _config: Config,
+    _registry: &ProviderRegistry,
+    _interval: Duration,
+) -> Result<()> {
+    Err(anyhow!(
+        "this build has no D-Bus support; rebuild on Linux with `--features dbus`"
+    ))
+}
+
+// Gives up on a browser sign-in nobody finishes.
+const LOGIN_TIMEOUT: Duration = Duration::from_secs(600);
+
+pub async fn run_login(args: LoginArgs) -> Result<()> {
+    match args.provider {
+        LoginProviderArg::Codex => {
+            let login = CodexLogin::start().await?;
+            eprintln!(
+                "Sign in to Codex in your browser:\n  {}",
+                login.authorize_url
+            );
+            if !args.no_browser && !open_in_browser(&login.authorize_url) {
+                eprintln!("Open the link above to continue.");
+            }
+            let finish = login.finish(|err| {
+                logger::log(
+                    LogLevel::Warning,
+                    "login_callback_rejected",
+                    err.to_string(),
+                    None,
+                )
+            });
+            let outcome = tokio::time::timeout(LOGIN_TIMEOUT, finish)
+                .await
+                .map_err(|_| anyhow!("timed out waiting for the Codex sign-in"))??;
+            let account = outcome
+                .account_email
+                .as_deref()
+                .unwrap_or("unknown account");
+            match outcome.plan.as_deref().filter(|plan| !plan.is_empty()) {
+                Some(plan) => println!("codex: signed in as {} ({})", account, plan),
+                None => println!("codex: signed in as {}", account),
+            }
+            println!("codex: wrote {}", outcome.auth_path.display());
+        }
+    }
+    Ok(())
+}
+
+fn open_in_browser(url: &str) -> bool {
+    let mut command = if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else if cfg!(windows) {
+        // Not `cmd /C start`, which would split the URL at every `&`.
+        let mut command = std::process::Command::new("rundll32");
+        command.arg("url.dll,FileProtocolHandler");
+        command
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+    command
+        .arg(url)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .is_ok_and(|status| status.success())
+}
+
+pub async fn run_logout(args: LogoutArgs, registry: &ProviderRegistry) -> Result<()> {
+    let mut config = Config::load(args.config.as_ref())?;
+    let outcomes = logout_providers(
+        &LogoutRequest {
+            providers: resolve_provider_args(&args.providers, &config, registry)?,
+            account: args.account,
+            all: args.all,
+            cli_credentials: args.cli_credentials,
+        },
+        &mut config,
+        registry,
+    )?;
+    config.save(args.config.as_ref())?;
+
+    for outcome in outcomes {
+        if outcome.removed.is_empty() {
+            println!("{}: no stored credentials found", outcome.provider);
+        }
+        for item in &outcome.removed {
+            println!("{}: removed {}", outcome.provider, item);
+        }
+        if outcome.disabled {
+            println!("{}: disabled", outcome.provider);
+        }
+    }
+
+    Ok(())
+}
+
+fn validate_config(args: ConfigValidateArgs, registry: &ProviderRegistry) -> Result<()> {
+    let path = Config::path(args.common.config.as_ref())?;
+    let missing = !path.exists();
+    let diagnostics = if missing {
+        Vec::new()
+    } else {
+        let contents = std::fs::read_to_string(&path)
+            .with_context(|| format!("read config {}", path.display()))?;
+        validate_config_json(&contents, registry)
+    };
+    let errors = diagnostics
+        .iter()
+        .filter(|d| d.severity == DiagnosticSeverity::Error)
+        .count();
+    let warnings = diagnostics.len() - errors;
+    let failed = errors > 0 || (args.strict && warnings > 0);
+
+    match args
+        .common
+        .format
+        .map(Into::into)
+        .unwrap_or(OutputFormat::Text)
+    {
+        OutputFormat::Json => {
+            let mut output = serde_json::json!({
+                "status": if failed { "error" } else { "ok" },
+                "path": path.display().to_string(),
+                "strict": args.strict,
+                "diagnostics": diagnostics,
+            });
+            if missing {
+                output["missing"] = serde_json::Value::Bool(true);
+            }
+            if args.common.pretty {
                 println!("{}", serde_json::to_string_pretty(&output)?);
             } else {
                 println!("{}", serde_json::to_string(&output)?);
             }
         }
-        OutputFormat::Text => {
+        _ => {
             if missing {
                 println!("config ok (missing; using defaults): {}", path.display());
+            } else if failed {
+                println!("config invalid: {}", path.display());
             } else {
                 println!("config ok: {}", path.display());
             }
+            for diagnostic in &diagnostics {
+                println!(
+                    "{} {}: {}",
+                    diagnostic.severity, diagnostic.path, diagnostic.message
+                );
+                if let Some(fix) = &diagnostic.fix {
+                    println!("  fix: {}", fix);
+                }
+            }
         }
     }
 
+    if failed {
+        return Err(CliError::ConfigInvalid { errors, warnings }.into());
+    }
+
     Ok(())
 }
 
@@ -286,7 +1621,7 @@ fn dump_config(args: ConfigArgs) -> Result<()> {
                 println!("{}", serde_json::to_string(&config)?);
             }
         }
-        OutputFormat::Text => {
+        _ => {
             println!("{}", serde_json::to_string_pretty(&config)?);
         }
     }
@@ -294,7 +1629,183 @@ fn dump_config(args: ConfigArgs) -> Result<()> {
     Ok(())
 }
 
-fn print_outputs(outputs: &[ProviderPayload], prefs: &OutputPreferences) -> Result<()> {
+fn migrate_config(args: ConfigMigrateArgs) -> Result<()> {
+    let path = Config::path(args.common.config.as_ref())?;
+    if !path.exists() {
+        return Err(CliError::ConfigMissing(path).into());
+    }
+    let (config, report) = Config::load_migrated(&path)?;
+    let written = !report.is_empty() && !args.dry_run;
+    if written {
+        let backup = path.with_extension("json.bak");
+        std::fs::copy(&path, &backup)
+            .with_context(|| format!("back up config to {}", backup.display()))?;
+        config.save(args.common.config.as_ref())?;
+    }
+
+    match args
+        .common
+        .format
+        .map(Into::into)
+        .unwrap_or(OutputFormat::Text)
+    {
+        OutputFormat::Json => {
+            let output = serde_json::json!({
+                "path": path.display().to_string(),
+                "from": report.from,
+                "to": report.to,
+                "applied": report.applied,
+                "written": written,
+            });
+            if args.common.pretty {
+                println!("{}", serde_json::to_string_pretty(&output)?);
+            } else {
+                println!("{}", serde_json::to_string(&output)?);
+            }
+        }
+        _ => {
+            if report.is_empty() {
+                println!(
+                    "config already at version {}: {}",
+                    report.to,
+                    path.display()
+                );
+            } else {
+                for step in &report.applied {
+                    println!("{}", step);
+                }
+                if written {
+                    println!(
+                        "migrated config v{} -> v{}: {}",
+                        report.from,
+                        report.to,
+                        path.display()
+                    );
+                } else {
+                    println!(
+                        "would migrate config v{} -> v{} (dry run): {}",
+                        report.from,
+                        report.to,
+                        path.display()
+                    );
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+pub fn load_defaults(path: Option<&PathBuf>) -> Defaults {
+    Config::load(path)
+        .ok()
+        .and_then(|config| config.defaults)
+        .unwrap_or_default()
+}
+
+pub fn configure_logging(config_path: Option<&PathBuf>) {
+    let Some(logging) = Config::load(config_path)
+        .ok()
+        .and_then(|config| config.logging)
+    else {
+        return;
+    };
+    let Some(path) = logging.path.or_else(default_log_path) else {
+        return;
+    };
+    let level = logging
+        .level
+        .as_deref()
+        .and_then(|level| LogLevel::from_str(level, true).ok())
+        .unwrap_or(LogLevel::Info);
+    let file = FileLogConfig {
+        path: path.clone(),
+        level,
+        max_bytes: logging.max_bytes.unwrap_or(DEFAULT_LOG_MAX_BYTES),
+        max_files: logging.max_files.unwrap_or(DEFAULT_LOG_MAX_FILES),
+    };
+    if let Err(err) = logger::init_file(file) {
+        logger::log(
+            LogLevel::Warning,
+            "log_file_unavailable",
+            format!("cannot write log file {}: {}", path.display(), err),
+            None,
+        );
+    }
+}
+
+pub fn configure_network(global: &GlobalArgs, defaults: &Defaults) {
+    http::set_network_disabled(global.no_network);
+    if let Some(secs) = global.timeout.or(defaults.timeout).filter(|secs| *secs > 0) {
+        http::set_global_timeout(Duration::from_secs(secs));
+    }
+    if let Some(limit) = global.concurrency.or(defaults.concurrency) {
+        set_fetch_concurrency(limit);
+    }
+    let settings = http::ClientSettings {
+        proxy: global.proxy.clone().or_else(|| defaults.proxy.clone()),
+        extra_ca_bundle: defaults.extra_ca_bundle.clone(),
+    };
+    // Falls back to the default client so local sources keep working.
+    if let Err(err) = http::configure_client(&settings) {
+        logger::log(
+            LogLevel::Warning,
+            "network_config_invalid",
+            format!("ignoring proxy/CA settings: {:#}", err),
+            None,
+        );
+    }
+}
+
+fn resolve_provider_args(
+    args: &[ProviderArg],
+    config: &Config,
+    registry: &ProviderRegistry,
+) -> Result<Vec<ProviderSelector>> {
+    let mut selectors = Vec::new();
+    for arg in args {
+        match arg {
+            ProviderArg::Builtin(selector) => selectors.push((*selector).into()),
+            ProviderArg::Alias(name) => {
+                // External providers have no fixed CLI value.
+                if let Some(id) = config
+                    .provider_for_alias(name)
+                    .or_else(|| registry.find(name, config))
+                {
+                    selectors.push(id.into());
+                    continue;
+                }
+                let members = config
+                    .provider_group(name)
+                    .ok_or_else(|| CliError::UnknownProvider(name.clone()))?;
+                // Group members are built-in names or provider aliases; groups don't nest.
+                for member in members {
+                    let selector = match parse_provider_arg(member) {
+                        Ok(ProviderArg::Builtin(selector)) => Some(selector.into()),
+                        Ok(ProviderArg::Alias(alias)) => config
+                            .provider_for_alias(&alias)
+                            .or_else(|| registry.find(&alias, config))
+                            .map(Into::into),
+                        Err(_) => None,
+                    };
+                    selectors.push(selector.ok_or_else(|| {
+                        CliError::UnknownProvider(format!("{} (in group {})", member, name))
+                    })?);
+                }
+            }
+        }
+    }
+    Ok(selectors)
+}
+
+fn print_outputs(
+    outputs: &[ProviderPayload],
+    prefs: &OutputPreferences,
+    labels: &ProviderLabels,
+    timezone: Option<&str>,
+    schema: OutputSchema,
+    output: Option<&Path>,
+) -> Result<()> {
     let rendered = render_outputs(
         outputs,
         &TextRenderOptions {
@@ -302,16 +1813,43 @@ fn print_outputs(outputs: &[ProviderPayload], prefs: &OutputPreferences) -> Resu
             pretty: prefs.pretty,
             json_only: prefs.json_only,
             use_color: prefs.use_color(),
+            labels,
+            timezone,
+            schema,
         },
     )?;
 
     if let Some(text) = rendered {
-        println!("{}", text);
+        write_output(&text, output)?;
     }
 
     Ok(())
 }
 
+// `--output`: stdout for none or `-`, else the file, written then renamed so
+// a reader never sees half a report.
+fn write_output(text: &str, output: Option<&Path>) -> Result<()> {
+    let Some(path) = output.filter(|path| path.as_os_str() != "-") else {
+        println!("{}", text);
+        return Ok(());
+    };
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        std::fs::create_dir_all(dir).with_context(|| format!("create {}", dir.display()))?;
+    }
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(".{}.tmp", std::process::id()));
+    let tmp = Path::new(&tmp);
+    std::fs::write(tmp, format!("{}\n", text))
+        .with_context(|| format!("write {}", tmp.display()))?;
+    std::fs::rename(tmp, path).with_context(|| format!("write {}", path.display()))?;
+    Ok(())
+}
+
+// Colors only belong on a terminal.
+fn writes_to_file(output: Option<&Path>) -> bool {
+    output.is_some_and(|path| path.as_os_str() != "-")
+}
+
 pub fn cli_error_payload(
     code: i32,
     message: String,
@@ -327,3 +1865,38 @@ pub fn cli_error_payload(
         },
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::exit_codes::exit_code_for_error;
+
+    fn exit_code(errors: &[Option<i32>], strict: bool) -> i32 {
+        let payloads: Vec<Option<ProviderErrorPayload>> = errors
+            .iter()
+            .map(|code| {
+                code.map(|code| ProviderErrorPayload {
+                    code,
+                    message: "boom".to_string(),
+                    kind: None,
+                })
+            })
+            .collect();
+        match check_provider_failures(payloads.iter().map(Option::as_ref), strict) {
+            Ok(()) => 0,
+            Err(err) => exit_code_for_error(&err),
+        }
+    }
+
+    #[test]
+    fn partial_failures_exit_5_unless_strict() {
+        assert_eq!(exit_code(&[None, None], false), 0);
+        assert_eq!(exit_code(&[None, None], true), 0);
+        assert_eq!(exit_code(&[None, Some(4)], false), 5);
+        // Strict runs and runs where every provider failed take the first
+        // provider's code.
+        assert_eq!(exit_code(&[None, Some(4), Some(6)], true), 4);
+        assert_eq!(exit_code(&[Some(6), Some(4)], false), 6);
+        assert_eq!(exit_code(&[Some(0)], false), 1);
+    }
+}
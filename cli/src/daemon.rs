@@ -0,0 +1,148 @@
+use anyhow::{Result, anyhow};
+use fuelcheck_core::config::Config;
+use fuelcheck_core::model::ProviderPayload;
+use fuelcheck_core::providers::ProviderRegistry;
+use fuelcheck_core::service::{UsageRequest, collect_usage_outputs};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+use zbus::object_server::SignalEmitter;
+
+use crate::logger::{self, LogLevel};
+
+pub const BUS_NAME: &str = "io.github.chasebuild.Fuelcheck";
+pub const OBJECT_PATH: &str = "/io/github/chasebuild/Fuelcheck";
+
+struct UsageService {
+    outputs: Vec<ProviderPayload>,
+    updated_at: i64,
+    refresh: Arc<Notify>,
+}
+
+#[zbus::interface(name = "io.github.chasebuild.Fuelcheck1")]
+impl UsageService {
+    // The same array `usage --json` prints.
+    #[zbus(property)]
+    fn usage_json(&self) -> String {
+        serde_json::to_string(&self.outputs).unwrap_or_else(|_| "[]".to_string())
+    }
+
+    // Remaining percent keyed "provider.window", e.g. "codex.secondary";
+    // token accounts use "provider/account.window".
+    #[zbus(property)]
+    fn remaining(&self) -> HashMap<String, f64> {
+        let mut remaining = HashMap::new();
+        for output in &self.outputs {
+            let Some(usage) = &output.usage else {
+                continue;
+            };
+            let windows = [
+                ("primary", usage.primary.as_ref()),
+                ("secondary", usage.secondary.as_ref()),
+                ("tertiary", usage.tertiary.as_ref()),
+            ];
+            for (name, window) in windows {
+                if let Some(window) = window {
+                    remaining.insert(
+                        format!("{}.{}", output_key(output), name),
+                        (100.0 - window.used_percent).clamp(0.0, 100.0),
+                    );
+                }
+            }
+        }
+        remaining
+    }
+
+    #[zbus(property)]
+    fn errors(&self) -> HashMap<String, String> {
+        self.outputs
+            .iter()
+            .filter_map(|output| {
+                let error = output.error.as_ref()?;
+                Some((output_key(output), error.message.clone()))
+            })
+            .collect()
+    }
+
+    // Unix seconds of the last completed refresh; 0 until the first one.
+    #[zbus(property)]
+    fn updated_at(&self) -> i64 {
+        self.updated_at
+    }
+
+    async fn refresh(&self) {
+        self.refresh.notify_one();
+    }
+
+    #[zbus(signal)]
+    async fn refreshed(emitter: &SignalEmitter<'_>, updated_at: i64) -> zbus::Result<()>;
+}
+
+async fn register(service: UsageService) -> zbus::Result<zbus::Connection> {
+    zbus::connection::Builder::session()?
+        .name(BUS_NAME)?
+        .serve_at(OBJECT_PATH, service)?
+        .build()
+        .await
+}
+
+fn output_key(output: &ProviderPayload) -> String {
+    match &output.account {
+        Some(account) => format!("{}/{}", output.provider, account),
+        None => output.provider.clone(),
+    }
+}
+
+// Publishes usage on the session bus and refreshes it every `interval`, or
+// sooner when a client calls `Refresh`. Runs until interrupted or terminated.
+pub async fn serve(
+    request: UsageRequest,
+    config: Config,
+    registry: &ProviderRegistry,
+    interval: Duration,
+) -> Result<()> {
+    let refresh = Arc::new(Notify::new());
+    let service = UsageService {
+        outputs: Vec::new(),
+        updated_at: 0,
+        refresh: refresh.clone(),
+    };
+    let connection = register(service)
+        .await
+        .map_err(|err| anyhow!("register {} on the session bus: {}", BUS_NAME, err))?;
+    let iface = connection
+        .object_server()
+        .interface::<_, UsageService>(OBJECT_PATH)
+        .await?;
+    logger::log(
+        LogLevel::Info,
+        "dbus_ready",
+        "Serving usage on the session bus",
+        Some(serde_json::json!({ "name": BUS_NAME, "path": OBJECT_PATH })),
+    );
+
+    let stop = crate::shutdown::requested();
+    tokio::pin!(stop);
+    loop {
+        let outputs = collect_usage_outputs(&request, &config, registry).await?;
+        let updated_at = chrono::Utc::now().timestamp();
+        {
+            let mut service = iface.get_mut().await;
+            service.outputs = outputs;
+            service.updated_at = updated_at;
+            let emitter = iface.signal_emitter();
+            service.usage_json_changed(emitter).await?;
+            service.remaining_changed(emitter).await?;
+            service.errors_changed(emitter).await?;
+            service.updated_at_changed(emitter).await?;
+        }
+        UsageService::refreshed(iface.signal_emitter(), updated_at).await?;
+
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            _ = refresh.notified() => {}
+            _ = &mut stop => return Ok(()),
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use fuelcheck_core::errors::CliError;
+use fuelcheck_core::errors::{CliError, classify_error};
 use fuelcheck_core::model::ErrorKind;
 
 pub fn exit_code_for_error(err: &anyhow::Error) -> i32 {
@@ -6,34 +6,24 @@ pub fn exit_code_for_error(err: &anyhow::Error) -> i32 {
         return match cli_err {
             CliError::UnknownProvider(_) => 2,
             CliError::ProviderNotImplemented(_) => 2,
-            CliError::ConfigMissing(_) | CliError::ConfigPathUnavailable => 3,
-            CliError::ProviderNotConfigured(_) => 2,
+            CliError::IdentityNotSupported(_) => 2,
+            CliError::ConfigMissing(_)
+            | CliError::ConfigPathUnavailable
+            | CliError::ConfigInvalid { .. }
+            | CliError::ConfigVersionUnsupported(_, _) => 3,
+            CliError::ProviderNotConfigured(_) | CliError::MissingCredentials(_) => 2,
             CliError::UnsupportedSource(_, _) => 3,
+            CliError::NetworkDisabled(_) => ErrorKind::Network.exit_code(),
+            CliError::PartialFailure { .. } => 5,
+            CliError::QuotaLow { .. } => 9,
+            CliError::BudgetExceeded { .. } => 10,
+            CliError::UsageAlert { .. } => 11,
+            CliError::ProvidersFailed { code, .. } => (*code).max(1),
         };
     }
-    if let Some(req_err) = err.downcast_ref::<reqwest::Error>()
-        && req_err.is_timeout()
-    {
-        return 4;
-    }
-    if err.downcast_ref::<tokio::time::error::Elapsed>().is_some() {
-        return 4;
-    }
-    if err.downcast_ref::<serde_json::Error>().is_some() {
-        return 3;
-    }
-    1
+    classify_error(err).map(ErrorKind::exit_code).unwrap_or(1)
 }
 
 pub fn error_kind_for_error(err: &anyhow::Error) -> ErrorKind {
-    if let Some(cli_err) = err.downcast_ref::<CliError>() {
-        return match cli_err {
-            CliError::UnknownProvider(_) => ErrorKind::Args,
-            CliError::ProviderNotImplemented(_) => ErrorKind::Provider,
-            CliError::ConfigMissing(_) | CliError::ConfigPathUnavailable => ErrorKind::Config,
-            CliError::ProviderNotConfigured(_) => ErrorKind::Provider,
-            CliError::UnsupportedSource(_, _) => ErrorKind::Args,
-        };
-    }
-    ErrorKind::Runtime
+    classify_error(err).unwrap_or(ErrorKind::Runtime)
 }
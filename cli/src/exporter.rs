@@ -0,0 +1,98 @@
+use anyhow::{Result, anyhow};
+use fuelcheck_core::config::Config;
+use fuelcheck_core::providers::ProviderRegistry;
+use fuelcheck_core::service::{UsageRequest, collect_usage_outputs};
+use fuelcheck_ui::metrics::render_metrics;
+use std::net::SocketAddr;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+use crate::logger::{self, LogLevel};
+use crate::shutdown;
+
+type Metrics = Arc<Mutex<String>>;
+
+// Serves `/metrics` for Prometheus and refreshes it every `interval`. Scrapes
+// read the last refresh, so they never wait on a provider. Runs until
+// interrupted or terminated.
+pub async fn serve(
+    request: UsageRequest,
+    config: Config,
+    registry: &ProviderRegistry,
+    listen: SocketAddr,
+    interval: Duration,
+) -> Result<()> {
+    let listener = TcpListener::bind(listen)
+        .await
+        .map_err(|err| anyhow!("listen on {}: {}", listen, err))?;
+    let metrics = Metrics::default();
+    let server = tokio::spawn(accept(listener, metrics.clone()));
+    logger::log(
+        LogLevel::Info,
+        "exporter_ready",
+        "Serving Prometheus metrics",
+        Some(serde_json::json!({ "listen": listen.to_string() })),
+    );
+
+    let stop = shutdown::requested();
+    tokio::pin!(stop);
+    loop {
+        let outputs = collect_usage_outputs(&request, &config, registry).await?;
+        let text = render_metrics(&outputs, chrono::Utc::now());
+        if let Ok(mut metrics) = metrics.lock() {
+            *metrics = text;
+        }
+
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            _ = &mut stop => {
+                server.abort();
+                return Ok(());
+            }
+        }
+    }
+}
+
+async fn accept(listener: TcpListener, metrics: Metrics) {
+    loop {
+        if let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(respond(stream, metrics.clone()));
+        }
+    }
+}
+
+// Just enough HTTP/1.1 for a scraper: the request line decides, headers and
+// body are ignored, and every connection is closed after one response.
+async fn respond(mut stream: TcpStream, metrics: Metrics) {
+    let mut buf = [0u8; 1024];
+    let Ok(read) = stream.read(&mut buf).await else {
+        return;
+    };
+    let request = String::from_utf8_lossy(&buf[..read]);
+    let mut parts = request.split_whitespace();
+    let (method, path) = (parts.next(), parts.next());
+    let (status, content_type, body) = match (method, path) {
+        (Some("GET"), Some("/metrics")) => (
+            "200 OK",
+            "text/plain; version=0.0.4",
+            metrics.lock().map(|text| text.clone()).unwrap_or_default(),
+        ),
+        (Some("GET"), _) => ("404 Not Found", "text/plain", "not found\n".to_string()),
+        _ => (
+            "405 Method Not Allowed",
+            "text/plain",
+            "method not allowed\n".to_string(),
+        ),
+    };
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
@@ -1,4 +1,10 @@
 pub mod args;
 pub mod commands;
+#[cfg(all(feature = "dbus", target_os = "linux"))]
+pub mod daemon;
 pub mod exit_codes;
+pub mod exporter;
 pub mod logger;
+pub mod progress;
+pub mod shutdown;
+pub mod state_file;
@@ -1,7 +1,11 @@
 use chrono::Utc;
 use clap::ValueEnum;
+use fuelcheck_core::http::{HttpTrace, RetryEvent};
 use serde_json::json;
-use std::sync::OnceLock;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
 pub enum LogLevel {
@@ -47,18 +51,66 @@ pub struct LoggerConfig {
     pub json_only: bool,
 }
 
+pub const DEFAULT_LOG_MAX_BYTES: u64 = 5 * 1024 * 1024;
+pub const DEFAULT_LOG_MAX_FILES: u32 = 3;
+
+#[derive(Debug, Clone)]
+pub struct FileLogConfig {
+    pub path: PathBuf,
+    pub level: LogLevel,
+    pub max_bytes: u64,
+    pub max_files: u32,
+}
+
+struct FileSink {
+    config: FileLogConfig,
+    file: File,
+    size: u64,
+}
+
 static LOGGER: OnceLock<LoggerConfig> = OnceLock::new();
+static FILE_SINK: OnceLock<Mutex<FileSink>> = OnceLock::new();
 
 pub fn init(config: LoggerConfig) {
     let _ = LOGGER.set(config);
 }
 
+// The log file always gets JSONL at its own level, regardless of the stderr flags.
+pub fn init_file(config: FileLogConfig) -> std::io::Result<()> {
+    if let Some(dir) = config.path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let file = open_append(&config.path)?;
+    let size = file.metadata()?.len();
+    let _ = FILE_SINK.set(Mutex::new(FileSink { config, file, size }));
+    Ok(())
+}
+
 pub fn log(
     level: LogLevel,
     event: &str,
     message: impl AsRef<str>,
     context: Option<serde_json::Value>,
 ) {
+    let json_line = || {
+        serde_json::to_string(&json!({
+            "ts": Utc::now().to_rfc3339(),
+            "level": level.as_str(),
+            "event": event,
+            "message": message.as_ref(),
+            "context": context,
+        }))
+        .ok()
+    };
+
+    if let Some(sink) = FILE_SINK.get()
+        && let Ok(mut sink) = sink.lock()
+        && level.priority() >= sink.config.level.priority()
+        && let Some(line) = json_line()
+    {
+        sink.write_line(&line);
+    }
+
     let Some(config) = LOGGER.get() else {
         return;
     };
@@ -66,14 +118,7 @@ pub fn log(
         return;
     }
     if config.json_output {
-        let payload = json!({
-            "ts": Utc::now().to_rfc3339(),
-            "level": level.as_str(),
-            "event": event,
-            "message": message.as_ref(),
-            "context": context,
-        });
-        if let Ok(line) = serde_json::to_string(&payload) {
+        if let Some(line) = json_line() {
             eprintln!("{}", line);
         }
         return;
@@ -85,3 +130,162 @@ pub fn log(
 
     eprintln!("[{}] {}: {}", level.as_str(), event, message.as_ref());
 }
+
+pub fn log_retry(event: &RetryEvent) {
+    let provider = event
+        .provider
+        .map(|id| id.to_string())
+        .unwrap_or_else(|| "-".to_string());
+    log(
+        LogLevel::Verbose,
+        "http_retry",
+        format!(
+            "{} {} ({}); retry {}/{} in {}ms",
+            provider,
+            event.url,
+            event.reason,
+            event.attempt,
+            event.retries,
+            event.delay.as_millis()
+        ),
+        Some(json!({
+            "provider": event.provider.map(|id| id.to_string()),
+            "url": event.url,
+            "reason": event.reason,
+            "attempt": event.attempt,
+            "retries": event.retries,
+            "delay_ms": event.delay.as_millis() as u64,
+        })),
+    );
+}
+
+pub fn log_http_trace(trace: &HttpTrace) {
+    let provider = trace
+        .provider
+        .map(|id| id.to_string())
+        .unwrap_or_else(|| "-".to_string());
+    let outcome = match (trace.status, &trace.error) {
+        (Some(status), _) => status.to_string(),
+        (None, Some(error)) => error.clone(),
+        (None, None) => "-".to_string(),
+    };
+    let mut message = format!(
+        "{} {} {} -> {} in {}ms",
+        provider,
+        trace.method,
+        trace.url,
+        outcome,
+        trace.duration.as_millis()
+    );
+    if let Some(body) = &trace.request_body {
+        message.push_str(&format!("\n  request: {}", body));
+    }
+    if let Some(body) = &trace.response_body {
+        message.push_str(&format!("\n  response: {}", body));
+    }
+    log(
+        LogLevel::Trace,
+        "http_trace",
+        message,
+        Some(json!({
+            "provider": trace.provider.map(|id| id.to_string()),
+            "method": trace.method,
+            "url": trace.url,
+            "attempt": trace.attempt,
+            "status": trace.status,
+            "duration_ms": trace.duration.as_millis() as u64,
+            "error": trace.error,
+            "request_headers": trace.request_headers,
+            "request_body": trace.request_body,
+            "response_body": trace.response_body,
+        })),
+    );
+}
+
+impl FileSink {
+    fn write_line(&mut self, line: &str) {
+        let len = line.len() as u64 + 1;
+        if self.size > 0 && self.size + len > self.config.max_bytes && self.rotate().is_err() {
+            return;
+        }
+        if writeln!(self.file, "{}", line).is_ok() {
+            self.size += len;
+        }
+    }
+
+    // Shifts `log.1` .. `log.N-1` up by one, moves the current file to `log.1`, and
+    // drops anything past `max_files`.
+    fn rotate(&mut self) -> std::io::Result<()> {
+        let path = &self.config.path;
+        if self.config.max_files == 0 {
+            fs::remove_file(path)?;
+        } else {
+            let _ = fs::remove_file(rotated_path(path, self.config.max_files));
+            for index in (1..self.config.max_files).rev() {
+                let from = rotated_path(path, index);
+                if from.exists() {
+                    fs::rename(&from, rotated_path(path, index + 1))?;
+                }
+            }
+            fs::rename(path, rotated_path(path, 1))?;
+        }
+        self.file = open_append(path)?;
+        self.size = 0;
+        Ok(())
+    }
+}
+
+fn open_append(path: &Path) -> std::io::Result<File> {
+    OpenOptions::new().create(true).append(true).open(path)
+}
+
+fn rotated_path(path: &Path, index: u32) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    PathBuf::from(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rotates_before_a_line_would_pass_max_bytes() {
+        let dir = std::env::temp_dir().join(format!("fuelcheck-logger-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("fuelcheck.log");
+        let mut sink = FileSink {
+            config: FileLogConfig {
+                path: path.clone(),
+                level: LogLevel::Info,
+                max_bytes: 10,
+                max_files: 2,
+            },
+            file: open_append(&path).unwrap(),
+            size: 0,
+        };
+
+        // Exactly max_bytes with the newline stays in the current file.
+        sink.write_line("aaaa");
+        sink.write_line("bbbb");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "aaaa\nbbbb\n");
+        assert!(!rotated_path(&path, 1).exists());
+
+        sink.write_line("cccc");
+        sink.write_line("dddd");
+        sink.write_line("eeeeeeeeeeeeeeee");
+        sink.write_line("ffff");
+        assert_eq!(fs::read_to_string(&path).unwrap(), "ffff\n");
+        assert_eq!(
+            fs::read_to_string(rotated_path(&path, 1)).unwrap(),
+            "eeeeeeeeeeeeeeee\n"
+        );
+        assert_eq!(
+            fs::read_to_string(rotated_path(&path, 2)).unwrap(),
+            "cccc\ndddd\n"
+        );
+        assert!(!rotated_path(&path, 3).exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,23 +1,34 @@
 use anyhow::Result;
 use clap::Parser;
+use fuelcheck_core::errors::CliError;
 use fuelcheck_core::providers::ProviderRegistry;
 
-use fuelcheck_core::model::OutputFormat;
+use fuelcheck_core::model::{OutputFormat, OutputSchema};
+use fuelcheck_ui::i18n::{self, Locale};
 
 use fuelcheck_cli::args::{Cli, Command};
 use fuelcheck_cli::commands::{
-    OutputPreferences, cli_error_payload, run_config, run_cost, run_setup, run_usage,
+    OutputPreferences, cli_error_payload, configure_logging, configure_network, load_defaults,
+    run_budget, run_ci_gate, run_compare, run_completions, run_config, run_cost, run_daemon,
+    run_forecast, run_guard, run_history, run_hook, run_login, run_logout, run_paths,
+    run_providers, run_reconcile, run_schema, run_sessions, run_setup, run_status, run_usage,
+    run_version, run_whoami,
 };
 use fuelcheck_cli::exit_codes::{error_kind_for_error, exit_code_for_error};
 use fuelcheck_cli::logger::{self, LogLevel, LoggerConfig};
+use fuelcheck_cli::progress;
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
+    i18n::set_locale(cli.global.lang.unwrap_or_else(Locale::from_env));
     let registry = ProviderRegistry::new();
 
+    let trace_http = cli.global.trace_http || cli.global.trace_http_bodies;
     let log_level = if let Some(level) = cli.global.log_level {
         level
+    } else if trace_http {
+        LogLevel::Trace
     } else if cli.global.verbose {
         LogLevel::Verbose
     } else {
@@ -28,33 +39,49 @@ async fn main() -> Result<()> {
         json_output: cli.global.json_output,
         json_only: cli.global.json_only,
     });
+    fuelcheck_core::http::set_retry_observer(logger::log_retry);
+    progress::init();
+    if trace_http {
+        fuelcheck_core::http::set_trace_observer(
+            logger::log_http_trace,
+            cli.global.trace_http_bodies,
+        );
+    }
 
     let (result, output_prefs) = match cli.command {
-        Command::Usage(args) => {
-            let prefs = OutputPreferences {
-                format: if args.json || cli.global.json_only {
-                    OutputFormat::Json
-                } else {
-                    args.format.into()
-                },
-                pretty: args.pretty,
-                json_only: cli.global.json_only,
-                no_color: cli.global.no_color,
-            };
-            (run_usage(args, &registry, &cli.global).await, Some(prefs))
+        Command::Usage(mut args) => {
+            let defaults = load_defaults(args.config.as_ref());
+            args.apply_defaults(&defaults);
+            configure_network(&cli.global, &defaults);
+            configure_logging(args.config.as_ref());
+            let prefs = OutputPreferences::from_args(
+                args.json,
+                args.stream,
+                args.format,
+                args.pretty,
+                &cli.global,
+            );
+            (
+                run_usage(args, &prefs, &registry, &cli.global).await,
+                Some(prefs),
+            )
         }
-        Command::Cost(args) => {
-            let prefs = OutputPreferences {
-                format: if args.json || cli.global.json_only {
-                    OutputFormat::Json
-                } else {
-                    args.format.into()
-                },
-                pretty: args.pretty,
-                json_only: cli.global.json_only,
-                no_color: cli.global.no_color,
-            };
-            (run_cost(args, &registry, &cli.global).await, Some(prefs))
+        Command::Cost(mut args) => {
+            let defaults = load_defaults(args.config.as_ref());
+            args.apply_defaults(&defaults);
+            configure_network(&cli.global, &defaults);
+            configure_logging(args.config.as_ref());
+            let prefs = OutputPreferences::from_args(
+                args.json,
+                args.stream,
+                args.format,
+                args.pretty,
+                &cli.global,
+            );
+            (
+                run_cost(args, &prefs, &registry, &cli.global).await,
+                Some(prefs),
+            )
         }
         Command::Config(cmd) => {
             let mut format = cmd.command.format();
@@ -67,30 +94,153 @@ async fn main() -> Result<()> {
                 json_only: cli.global.json_only,
                 no_color: cli.global.no_color,
             };
-            (run_config(cmd, &cli.global).await, Some(prefs))
+            (run_config(cmd, &registry, &cli.global).await, Some(prefs))
+        }
+        Command::Setup(args) => (run_setup(args, &registry).await, None),
+        Command::Login(args) => {
+            let defaults = load_defaults(None);
+            configure_network(&cli.global, &defaults);
+            (run_login(args).await, None)
+        }
+        Command::Logout(args) => (run_logout(args, &registry).await, None),
+        Command::Paths(args) => (run_paths(args, &cli.global), None),
+        Command::Providers(args) => (run_providers(args, &registry, &cli.global), None),
+        Command::Version(args) => (run_version(args, &registry, &cli.global), None),
+        Command::Status(mut args) => {
+            let defaults = load_defaults(args.config.as_ref());
+            args.apply_defaults(&defaults);
+            configure_network(&cli.global, &defaults);
+            configure_logging(args.config.as_ref());
+            let prefs = OutputPreferences::from_args(
+                args.json,
+                args.stream,
+                args.format,
+                args.pretty,
+                &cli.global,
+            );
+            (
+                run_status(args, &prefs, &registry, &cli.global).await,
+                Some(prefs),
+            )
+        }
+        Command::Hook(args) => {
+            let defaults = load_defaults(args.config.as_ref());
+            configure_network(&cli.global, &defaults);
+            configure_logging(args.config.as_ref());
+            (run_hook(args, &registry).await, None)
+        }
+        Command::Guard(args) => {
+            let defaults = load_defaults(args.config.as_ref());
+            configure_network(&cli.global, &defaults);
+            configure_logging(args.config.as_ref());
+            (run_guard(args, &registry).await, None)
+        }
+        Command::CiGate(mut args) => {
+            let defaults = load_defaults(args.config.as_ref());
+            args.apply_defaults(&defaults);
+            configure_network(&cli.global, &defaults);
+            configure_logging(args.config.as_ref());
+            (run_ci_gate(args, &registry).await, None)
+        }
+        Command::Budget(mut args) => {
+            let defaults = load_defaults(args.config.as_ref());
+            args.apply_defaults(&defaults);
+            configure_network(&cli.global, &defaults);
+            configure_logging(args.config.as_ref());
+            (run_budget(args, &registry).await, None)
+        }
+        Command::Forecast(mut args) => {
+            let defaults = load_defaults(args.config.as_ref());
+            args.apply_defaults(&defaults);
+            configure_network(&cli.global, &defaults);
+            configure_logging(args.config.as_ref());
+            (run_forecast(args, &registry).await, None)
+        }
+        Command::Compare(mut args) => {
+            let defaults = load_defaults(args.config.as_ref());
+            args.apply_defaults(&defaults);
+            configure_network(&cli.global, &defaults);
+            configure_logging(args.config.as_ref());
+            (run_compare(args, &registry).await, None)
+        }
+        Command::Reconcile(mut args) => {
+            let defaults = load_defaults(args.config.as_ref());
+            args.apply_defaults(&defaults);
+            configure_network(&cli.global, &defaults);
+            configure_logging(args.config.as_ref());
+            (run_reconcile(args, &registry).await, None)
+        }
+        Command::Sessions(cmd) => (run_sessions(cmd), None),
+        Command::Completions(args) => (run_completions(args), None),
+        Command::Schema(args) => (run_schema(args), None),
+        Command::History(args) => (run_history(args, &registry), None),
+        Command::Daemon(args) => {
+            let defaults = load_defaults(args.config.as_ref());
+            configure_network(&cli.global, &defaults);
+            configure_logging(args.config.as_ref());
+            (run_daemon(args, &registry).await, None)
+        }
+        Command::Whoami(mut args) => {
+            let defaults = load_defaults(args.config.as_ref());
+            args.apply_defaults(&defaults);
+            configure_network(&cli.global, &defaults);
+            configure_logging(args.config.as_ref());
+            let prefs = OutputPreferences::from_args(
+                args.json,
+                args.stream,
+                args.format,
+                args.pretty,
+                &cli.global,
+            );
+            (
+                run_whoami(args, &prefs, &registry, &cli.global).await,
+                Some(prefs),
+            )
         }
-        Command::Setup(args) => (run_setup(args).await, None),
     };
 
     if let Err(err) = result {
         let code = exit_code_for_error(&err);
         let kind = error_kind_for_error(&err);
+        // Validation reports and provider failures have already been printed.
+        let reported = matches!(
+            err.downcast_ref::<CliError>(),
+            Some(
+                CliError::ConfigInvalid { .. }
+                    | CliError::PartialFailure { .. }
+                    | CliError::ProvidersFailed { .. }
+                    | CliError::UsageAlert { .. }
+            )
+        );
         if let Some(prefs) = output_prefs {
             if prefs.uses_json_output() {
+                if reported {
+                    std::process::exit(code);
+                }
                 let payload = cli_error_payload(code, err.to_string(), kind);
-                let outputs = vec![payload];
-                if prefs.pretty {
-                    if let Ok(json) = serde_json::to_string_pretty(&outputs) {
-                        println!("{}", json);
-                    }
-                } else if let Ok(json) = serde_json::to_string(&outputs) {
+                if let Ok(json) = OutputSchema::from(cli.global.output_schema)
+                    .payloads_to_string(&[payload], prefs.pretty)
+                {
+                    println!("{}", json);
+                }
+            } else if prefs.format == OutputFormat::Alfred && !reported {
+                // Alfred shows nothing for output it cannot parse, so surface
+                // the failure as an item as well.
+                if let Ok(json) = fuelcheck_ui::text::alfred_error(&i18n::error_message(&err)) {
+                    println!("{}", json);
+                }
+                eprintln!("{}: {}", i18n::t("Error"), i18n::error_message(&err));
+            } else if prefs.format == OutputFormat::Waybar && !reported {
+                // Waybar hides the module on output that is not JSON.
+                if let Ok(json) = fuelcheck_ui::text::waybar_error(&i18n::error_message(&err)) {
                     println!("{}", json);
                 }
+                eprintln!("{}: {}", i18n::t("Error"), i18n::error_message(&err));
             } else {
-                eprintln!("Error: {}", err);
+                eprintln!("{}: {}", i18n::t("Error"), i18n::error_message(&err));
             }
         } else {
-            eprintln!("Error: {}", err);
+            eprintln!("{}: {}", i18n::t("Error"), i18n::error_message(&err));
         }
         std::process::exit(code);
     }
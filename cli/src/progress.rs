@@ -0,0 +1,143 @@
+use crate::logger::{self, LogLevel};
+use fuelcheck_core::progress::{FetchProgress, set_progress_observer};
+use fuelcheck_core::providers::ProviderId;
+use fuelcheck_ui::i18n::{t, tf};
+use serde_json::json;
+use std::io::Write;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+const FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+const TICK: Duration = Duration::from_millis(100);
+
+struct Spinner {
+    // Providers being fetched, in the order they started.
+    providers: Vec<ProviderId>,
+    frame: usize,
+}
+
+static ACTIVE: Mutex<Spinner> = Mutex::new(Spinner {
+    providers: Vec::new(),
+    frame: 0,
+});
+static DISPLAY: AtomicBool = AtomicBool::new(false);
+
+// Fetch outcomes are always logged; failures at info so they reach the log file
+// without showing up on stderr by default.
+pub fn init() {
+    set_progress_observer(on_progress);
+}
+
+// Draws a spinner on stderr for the providers being fetched and leaves one
+// result line per provider. Callers only enable this for text output on a terminal.
+pub fn enable_display() {
+    if DISPLAY.swap(true, Ordering::Relaxed) {
+        return;
+    }
+    tokio::spawn(async {
+        let mut ticker = tokio::time::interval(TICK);
+        loop {
+            ticker.tick().await;
+            if let Ok(mut spinner) = ACTIVE.lock()
+                && !spinner.providers.is_empty()
+            {
+                spinner.frame += 1;
+                draw(&spinner);
+            }
+        }
+    });
+}
+
+fn on_progress(event: &FetchProgress) {
+    if let FetchProgress::Finished {
+        provider,
+        error,
+        elapsed,
+    } = event
+    {
+        log_fetch(*provider, error.as_deref(), *elapsed);
+    }
+    if !DISPLAY.load(Ordering::Relaxed) {
+        return;
+    }
+    let Ok(mut spinner) = ACTIVE.lock() else {
+        return;
+    };
+    match event {
+        FetchProgress::Started { provider } => {
+            spinner.providers.push(*provider);
+            draw(&spinner);
+        }
+        FetchProgress::Finished {
+            provider,
+            error,
+            elapsed,
+        } => {
+            spinner.providers.retain(|active| active != provider);
+            let outcome = if error.is_none() {
+                t("ok")
+            } else {
+                t("failed")
+            };
+            eprintln!(
+                "\r\x1b[2K{}… {} {}",
+                provider,
+                outcome,
+                format_elapsed(*elapsed)
+            );
+            if !spinner.providers.is_empty() {
+                draw(&spinner);
+            }
+        }
+    }
+}
+
+fn log_fetch(provider: ProviderId, error: Option<&str>, elapsed: Duration) {
+    let context = json!({
+        "provider": provider.to_string(),
+        "elapsed_ms": elapsed.as_millis() as u64,
+        "error": error,
+    });
+    match error {
+        Some(error) => logger::log(
+            LogLevel::Info,
+            "provider_failed",
+            format!(
+                "{} failed after {}: {}",
+                provider,
+                format_elapsed(elapsed),
+                error
+            ),
+            Some(context),
+        ),
+        None => logger::log(
+            LogLevel::Verbose,
+            "provider_ok",
+            format!("{} ok in {}", provider, format_elapsed(elapsed)),
+            Some(context),
+        ),
+    }
+}
+
+fn draw(spinner: &Spinner) {
+    let frame = FRAMES[spinner.frame % FRAMES.len()];
+    let providers = spinner
+        .providers
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut stderr = std::io::stderr();
+    let text = tf("fetching {provider}…", &[("provider", &providers)]);
+    let _ = write!(stderr, "\r\x1b[2K{} {}", frame, text);
+    let _ = stderr.flush();
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    if elapsed < Duration::from_secs(1) {
+        format!("{}ms", elapsed.as_millis())
+    } else {
+        format!("{:.1}s", elapsed.as_secs_f64())
+    }
+}
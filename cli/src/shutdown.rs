@@ -0,0 +1,16 @@
+// Resolves on Ctrl-C, or on SIGTERM from a service manager on Unix, so
+// long-running modes can stop between refreshes instead of being killed.
+pub async fn requested() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{SignalKind, signal};
+        if let Ok(mut term) = signal(SignalKind::terminate()) {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => {}
+                _ = term.recv() => {}
+            }
+            return;
+        }
+    }
+    let _ = tokio::signal::ctrl_c().await;
+}
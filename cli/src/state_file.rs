@@ -0,0 +1,59 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use fuelcheck_core::config::Config;
+use fuelcheck_core::model::ProviderPayload;
+use fuelcheck_core::providers::ProviderRegistry;
+use fuelcheck_core::service::{UsageRequest, collect_usage_outputs};
+use std::fs;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::logger::{self, LogLevel};
+use crate::shutdown;
+
+// Rewrites `path` with the latest usage every `interval` for tools that poll
+// a file (menu bar apps, status bars). Runs until interrupted or terminated.
+pub async fn serve(
+    request: UsageRequest,
+    config: Config,
+    registry: &ProviderRegistry,
+    path: &Path,
+    interval: Duration,
+) -> Result<()> {
+    if let Some(dir) = path.parent().filter(|dir| !dir.as_os_str().is_empty()) {
+        fs::create_dir_all(dir).with_context(|| format!("create {}", dir.display()))?;
+    }
+    logger::log(
+        LogLevel::Info,
+        "state_file_ready",
+        "Writing usage to the state file",
+        Some(serde_json::json!({ "path": path.display().to_string() })),
+    );
+
+    let stop = shutdown::requested();
+    tokio::pin!(stop);
+    loop {
+        let outputs = tokio::select! {
+            outputs = collect_usage_outputs(&request, &config, registry) => outputs?,
+            _ = &mut stop => return Ok(()),
+        };
+        write_state(path, &outputs, Utc::now())?;
+
+        tokio::select! {
+            _ = tokio::time::sleep(interval) => {}
+            _ = &mut stop => return Ok(()),
+        }
+    }
+}
+
+// Write then rename, so readers never see half a file.
+fn write_state(path: &Path, outputs: &[ProviderPayload], updated_at: DateTime<Utc>) -> Result<()> {
+    let state = serde_json::json!({ "updatedAt": updated_at, "providers": outputs });
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(".{}.tmp", std::process::id()));
+    let tmp = Path::new(&tmp);
+    fs::write(tmp, serde_json::to_vec_pretty(&state)?)
+        .with_context(|| format!("write {}", tmp.display()))?;
+    fs::rename(tmp, path).with_context(|| format!("write {}", path.display()))?;
+    Ok(())
+}
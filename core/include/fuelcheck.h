@@ -0,0 +1,34 @@
+#ifndef FUELCHECK_H
+#define FUELCHECK_H
+
+/*
+ * C ABI for fuelcheck-core, available when built with the `ffi` feature:
+ *
+ *   cargo rustc -p fuelcheck-core --features ffi --release --crate-type cdylib
+ *
+ * Strings returned by the library are UTF-8 JSON and must be released with
+ * fuelcheck_string_free.
+ */
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/*
+ * Fetches usage and returns the same JSON array as `fuelcheck-cli usage --json`.
+ * `options` is NULL or a JSON object such as
+ *   {"providers": ["claude", "codex"], "source": "auto", "config": "/path/config.json",
+ *    "status": false, "noCredits": false, "webTimeout": 20}
+ * Errors are reported as a one-element array whose entry has an "error" object,
+ * so the result is never NULL. Blocks until every provider has finished.
+ */
+char *fuelcheck_usage_json(const char *options);
+
+/* Frees a string returned by this library. NULL is ignored. */
+void fuelcheck_string_free(char *ptr);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* FUELCHECK_H */
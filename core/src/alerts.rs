@@ -0,0 +1,95 @@
+use crate::model::{ProviderPayload, UsageAlert};
+
+// Flags every window (and the provider's spend limit) whose used percent has
+// reached `threshold`. Earlier alerts are replaced, so payloads reused from a
+// cache are judged against the current config.
+pub fn apply_alerts(output: &mut ProviderPayload, threshold: Option<f64>) {
+    output.alerts.clear();
+    if let (Some(threshold), Some(usage)) = (threshold, &output.usage) {
+        let windows = [
+            ("primary", usage.primary.as_ref().map(|w| w.used_percent)),
+            (
+                "secondary",
+                usage.secondary.as_ref().map(|w| w.used_percent),
+            ),
+            ("tertiary", usage.tertiary.as_ref().map(|w| w.used_percent)),
+            (
+                "cost",
+                usage
+                    .provider_cost
+                    .as_ref()
+                    .filter(|cost| cost.limit > 0.0)
+                    .map(|cost| cost.used / cost.limit * 100.0),
+            ),
+        ];
+        for (window, used_percent) in windows {
+            if let Some(used_percent) = used_percent
+                && used_percent >= threshold
+            {
+                output.alerts.push(UsageAlert {
+                    window: window.to_string(),
+                    used_percent,
+                    threshold,
+                });
+            }
+        }
+    }
+    output.alerting = !output.alerts.is_empty();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{ProviderCostSnapshot, ProviderErrorPayload, RateWindow, UsageSnapshot};
+    use chrono::Utc;
+
+    fn window(used_percent: f64) -> RateWindow {
+        RateWindow {
+            used_percent,
+            window_minutes: None,
+            resets_at: None,
+            reset_description: None,
+        }
+    }
+
+    #[test]
+    fn flags_windows_and_cost_at_or_over_the_threshold() {
+        let mut output = ProviderPayload::error(
+            "claude".to_string(),
+            "oauth".to_string(),
+            ProviderErrorPayload {
+                code: 1,
+                message: String::new(),
+                kind: None,
+            },
+        );
+        output.error = None;
+        output.usage = Some(UsageSnapshot {
+            primary: Some(window(90.0)),
+            secondary: Some(window(40.0)),
+            tertiary: None,
+            provider_cost: Some(ProviderCostSnapshot {
+                used: 19.0,
+                limit: 20.0,
+                currency_code: "USD".to_string(),
+                period: None,
+                resets_at: None,
+                updated_at: Utc::now(),
+            }),
+            updated_at: Utc::now(),
+            identity: None,
+            account_email: None,
+            account_organization: None,
+            login_method: None,
+        });
+
+        apply_alerts(&mut output, Some(90.0));
+        let windows: Vec<_> = output.alerts.iter().map(|a| a.window.as_str()).collect();
+        assert_eq!(windows, ["primary", "cost"]);
+        assert!(output.alerting);
+
+        apply_alerts(&mut output, None);
+        assert!(output.alerts.is_empty());
+        assert!(!output.alerting);
+    }
+}
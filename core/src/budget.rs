@@ -0,0 +1,301 @@
+use anyhow::{Result, anyhow};
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::Serialize;
+use std::fmt;
+use std::str::FromStr;
+
+pub const BUDGET_EXCEEDED: &str = "budget_exceeded";
+pub const WITHIN_BUDGET: &str = "within_budget";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Budget {
+    pub amount: f64,
+    pub currency: String,
+}
+
+// Accepts "50USD", "50 usd", "$50" and a bare "50" (USD).
+impl FromStr for Budget {
+    type Err = anyhow::Error;
+
+    fn from_str(raw: &str) -> Result<Self> {
+        let trimmed = raw.trim();
+        let (number, currency) = match trimmed.strip_prefix('$') {
+            Some(rest) => (rest.trim(), "USD".to_string()),
+            None => {
+                let split = trimmed
+                    .find(|ch: char| ch.is_ascii_alphabetic())
+                    .unwrap_or(trimmed.len());
+                let currency = trimmed[split..].trim();
+                let currency = if currency.is_empty() {
+                    "USD".to_string()
+                } else {
+                    currency.to_ascii_uppercase()
+                };
+                (trimmed[..split].trim(), currency)
+            }
+        };
+        let amount: f64 = number
+            .parse()
+            .map_err(|_| anyhow!("invalid budget \"{}\" (expected e.g. 50USD)", raw))?;
+        if !amount.is_finite() || amount < 0.0 {
+            return Err(anyhow!("budget must be a non-negative amount"));
+        }
+        if currency.len() != 3 || !currency.chars().all(|ch| ch.is_ascii_uppercase()) {
+            return Err(anyhow!("invalid budget currency \"{}\"", currency));
+        }
+        Ok(Self { amount, currency })
+    }
+}
+
+impl fmt::Display for Budget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:.2} {}", self.amount, self.currency)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BudgetPeriod {
+    Day,
+    Week,
+    Month,
+}
+
+impl BudgetPeriod {
+    // First day of the period containing `today`; weeks start on Monday.
+    pub fn start(self, today: NaiveDate) -> NaiveDate {
+        match self {
+            BudgetPeriod::Day => today,
+            BudgetPeriod::Week => {
+                today - Duration::days(today.weekday().num_days_from_monday() as i64)
+            }
+            BudgetPeriod::Month => today.with_day(1).unwrap_or(today),
+        }
+    }
+}
+
+impl fmt::Display for BudgetPeriod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            BudgetPeriod::Day => "day",
+            BudgetPeriod::Week => "week",
+            BudgetPeriod::Month => "month",
+        };
+        f.write_str(label)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetSpend {
+    pub provider: String,
+    // "report" for local usage logs, "snapshot" for provider-reported spend.
+    pub source: String,
+    pub amount: f64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetSkip {
+    pub provider: String,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BudgetCheck {
+    pub passed: bool,
+    pub reason: &'static str,
+    pub message: String,
+    pub period: BudgetPeriod,
+    pub since: NaiveDate,
+    pub budget: f64,
+    pub spent: f64,
+    pub currency: String,
+    pub providers: Vec<BudgetSpend>,
+    pub skipped: Vec<BudgetSkip>,
+}
+
+impl BudgetCheck {
+    pub fn new(
+        budget: &Budget,
+        period: BudgetPeriod,
+        since: NaiveDate,
+        providers: Vec<BudgetSpend>,
+        skipped: Vec<BudgetSkip>,
+    ) -> Self {
+        let spent = providers
+            .iter()
+            .fold(0.0, |total, spend| total + spend.amount);
+        let passed = spent <= budget.amount;
+        let message = format!(
+            "{}-to-date spend {:.2} {} {} budget {}",
+            period,
+            spent,
+            budget.currency,
+            if passed { "is within" } else { "exceeds" },
+            budget
+        );
+        Self {
+            passed,
+            reason: if passed {
+                WITHIN_BUDGET
+            } else {
+                BUDGET_EXCEEDED
+            },
+            message,
+            period,
+            since,
+            budget: budget.amount,
+            spent,
+            currency: budget.currency.clone(),
+            providers,
+            skipped,
+        }
+    }
+}
+
+// One provider's spend this month against its `monthly_budget`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthlyBudgetStatus {
+    pub provider: String,
+    // "report" for local usage logs, "snapshot" for provider-reported spend.
+    pub source: String,
+    pub budget: f64,
+    pub currency: String,
+    pub spent: f64,
+    // `None` for a zero budget.
+    pub percent_used: Option<f64>,
+    // Month-end spend at the average daily rate so far.
+    pub projected: f64,
+    // The day spend went over the budget or, at the current rate, will go
+    // over it this month.
+    pub overrun_on: Option<NaiveDate>,
+    pub exceeded: bool,
+}
+
+impl MonthlyBudgetStatus {
+    // `daily` is spend per day, for finding the day an exceeded budget was
+    // crossed; without it that day is taken to be today.
+    pub fn new(
+        provider: String,
+        source: &str,
+        budget: &Budget,
+        spent: f64,
+        today: NaiveDate,
+        daily: &[(NaiveDate, f64)],
+    ) -> Self {
+        let month_start = BudgetPeriod::Month.start(today);
+        let month_end = month_end(today);
+        let elapsed = (today - month_start).num_days() + 1;
+        let days = (month_end - month_start).num_days() + 1;
+        let rate = spent / elapsed as f64;
+        let exceeded = spent > budget.amount;
+        let overrun_on = if exceeded {
+            let mut total = 0.0;
+            daily
+                .iter()
+                .find(|(_, cost)| {
+                    total += cost;
+                    total > budget.amount
+                })
+                .map(|(date, _)| *date)
+                .or(Some(today))
+        } else if rate > 0.0 {
+            let remaining_days = ((budget.amount - spent) / rate).ceil() as i64;
+            Some(today + Duration::days(remaining_days)).filter(|date| *date <= month_end)
+        } else {
+            None
+        };
+        Self {
+            provider,
+            source: source.to_string(),
+            budget: budget.amount,
+            currency: budget.currency.clone(),
+            spent,
+            percent_used: (budget.amount > 0.0).then(|| spent / budget.amount * 100.0),
+            projected: rate * days as f64,
+            overrun_on,
+            exceeded,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthlyBudgetReport {
+    pub since: NaiveDate,
+    pub today: NaiveDate,
+    pub exceeded: bool,
+    pub providers: Vec<MonthlyBudgetStatus>,
+    pub skipped: Vec<BudgetSkip>,
+}
+
+pub(crate) fn month_end(today: NaiveDate) -> NaiveDate {
+    let (year, month) = if today.month() == 12 {
+        (today.year() + 1, 1)
+    } else {
+        (today.year(), today.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .map(|next| next - Duration::days(1))
+        .unwrap_or(today)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_budgets_and_period_starts() {
+        let budget: Budget = "50USD".parse().unwrap();
+        assert_eq!(budget.amount, 50.0);
+        assert_eq!(budget.currency, "USD");
+        assert_eq!("$12.5".parse::<Budget>().unwrap().amount, 12.5);
+        assert_eq!("20 eur".parse::<Budget>().unwrap().currency, "EUR");
+        assert!("fifty".parse::<Budget>().is_err());
+        assert!("-5USD".parse::<Budget>().is_err());
+
+        let today = NaiveDate::from_ymd_opt(2025, 3, 13).unwrap();
+        assert_eq!(BudgetPeriod::Day.start(today), today);
+        assert_eq!(
+            BudgetPeriod::Week.start(today),
+            NaiveDate::from_ymd_opt(2025, 3, 10).unwrap()
+        );
+        assert_eq!(
+            BudgetPeriod::Month.start(today),
+            NaiveDate::from_ymd_opt(2025, 3, 1).unwrap()
+        );
+    }
+
+    #[test]
+    fn monthly_status_projects_the_overrun_day() {
+        let budget: Budget = "100USD".parse().unwrap();
+        let date = |day| NaiveDate::from_ymd_opt(2025, 4, day).unwrap();
+
+        // 40 spent over 10 days: 4 a day reaches 100 on the 25th, 120 by the 30th.
+        let status =
+            MonthlyBudgetStatus::new("codex".to_string(), "report", &budget, 40.0, date(10), &[]);
+        assert!(!status.exceeded);
+        assert_eq!(status.percent_used, Some(40.0));
+        assert_eq!(status.projected, 120.0);
+        assert_eq!(status.overrun_on, Some(date(25)));
+
+        let slow =
+            MonthlyBudgetStatus::new("codex".to_string(), "report", &budget, 10.0, date(10), &[]);
+        assert_eq!(slow.overrun_on, None);
+
+        let daily = [(date(2), 60.0), (date(5), 50.0), (date(9), 10.0)];
+        let over = MonthlyBudgetStatus::new(
+            "codex".to_string(),
+            "report",
+            &budget,
+            120.0,
+            date(10),
+            &daily,
+        );
+        assert!(over.exceeded);
+        assert_eq!(over.overrun_on, Some(date(5)));
+    }
+}
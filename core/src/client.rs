@@ -0,0 +1,243 @@
+use crate::config::Config;
+use crate::errors::CliError;
+use crate::model::ProviderPayload;
+use crate::providers::{
+    Provider, ProviderCapabilities, ProviderId, ProviderRegistry, ProviderSelector,
+    SourcePreference,
+};
+use crate::service::{CostRequest, UsageRequest, collect_cost_outputs, collect_usage_outputs};
+use anyhow::Result;
+use std::path::Path;
+
+// The embedding API: `usage` and `cost` as the CLI runs them, for programs
+// that would otherwise spawn it (menubar apps, bots). `Client`, `FetchRequest`
+// and the types they return (`model`, `ProviderId`, `SourcePreference`,
+// `Provider`) follow semver; the rest of the crate backs the CLI and may change
+// in any release.
+//
+//     let client = Client::load()?;
+//     let request = FetchRequest::new().provider(ProviderId::Claude).status(true);
+//     for payload in client.usage(&request).await? { ... }
+pub struct Client {
+    config: Config,
+    registry: ProviderRegistry,
+}
+
+impl Client {
+    // The user's config file, as the CLI reads it (missing means defaults).
+    pub fn load() -> Result<Self> {
+        Ok(Self::from_parts(Config::load(None)?))
+    }
+
+    pub fn load_from(path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::from_parts(Config::load(Some(
+            &path.as_ref().to_path_buf(),
+        ))?))
+    }
+
+    // A config built in code.
+    pub fn with_config(config: Config) -> Self {
+        Self::from_parts(config)
+    }
+
+    fn from_parts(config: Config) -> Self {
+        Self {
+            config,
+            registry: ProviderRegistry::new(),
+        }
+    }
+
+    // Adds a provider of the embedding program, or replaces a built-in one.
+    pub fn register(&mut self, provider: Box<dyn Provider>) -> Result<()> {
+        self.registry.register(provider)
+    }
+
+    pub fn config(&self) -> &Config {
+        &self.config
+    }
+
+    pub fn providers(&self) -> Vec<ProviderCapabilities> {
+        self.registry.capabilities(&self.config)
+    }
+
+    // One payload per provider (per account with `all_accounts`). A provider
+    // that fails gets a payload with `error` set; `Err` means the request
+    // itself was unusable, e.g. an unknown provider name.
+    pub async fn usage(&self, request: &FetchRequest) -> Result<Vec<ProviderPayload>> {
+        let usage = UsageRequest {
+            providers: self.resolve(request)?,
+            source: request.source,
+            status: request.status,
+            no_credits: request.no_credits,
+            refresh: request.refresh,
+            web_debug_dump_html: false,
+            web_timeout: request.web_timeout,
+            account: request.account.clone(),
+            account_index: None,
+            all_accounts: request.all_accounts,
+            antigravity_plan_debug: false,
+        };
+        collect_usage_outputs(&usage, &self.config, &self.registry).await
+    }
+
+    // Spend for the providers that report it; the request's source and
+    // account settings don't apply.
+    pub async fn cost(&self, request: &FetchRequest) -> Result<Vec<ProviderPayload>> {
+        let cost = CostRequest {
+            providers: self.resolve(request)?,
+        };
+        collect_cost_outputs(&cost, &self.config, &self.registry).await
+    }
+
+    // Names resolve like `--provider`: config aliases and provider ids, then
+    // groups of those.
+    fn resolve(&self, request: &FetchRequest) -> Result<Vec<ProviderSelector>> {
+        let mut selectors: Vec<ProviderSelector> =
+            request.providers.iter().copied().map(Into::into).collect();
+        let lookup = |name: &str| {
+            self.config
+                .provider_for_alias(name)
+                .or_else(|| self.registry.find(name, &self.config))
+        };
+        for name in &request.names {
+            if let Some(id) = lookup(name) {
+                selectors.push(id.into());
+                continue;
+            }
+            let members = self
+                .config
+                .provider_group(name)
+                .ok_or_else(|| CliError::UnknownProvider(name.clone()))?;
+            for member in members {
+                let id = lookup(member).ok_or_else(|| {
+                    CliError::UnknownProvider(format!("{} (in group {})", member, name))
+                })?;
+                selectors.push(id.into());
+            }
+        }
+        Ok(selectors)
+    }
+}
+
+// What to fetch. With no providers the config's enabled ones (or the
+// defaults) are used, as with the CLI.
+#[derive(Debug, Clone)]
+pub struct FetchRequest {
+    providers: Vec<ProviderId>,
+    names: Vec<String>,
+    source: SourcePreference,
+    status: bool,
+    no_credits: bool,
+    refresh: bool,
+    web_timeout: u64,
+    account: Option<String>,
+    all_accounts: bool,
+}
+
+impl FetchRequest {
+    pub fn new() -> Self {
+        Self {
+            providers: Vec::new(),
+            names: Vec::new(),
+            source: SourcePreference::Auto,
+            status: false,
+            no_credits: false,
+            refresh: false,
+            web_timeout: 20,
+            account: None,
+            all_accounts: false,
+        }
+    }
+
+    pub fn provider(mut self, id: ProviderId) -> Self {
+        self.providers.push(id);
+        self
+    }
+
+    // An id, config alias, or group, resolved when the request runs.
+    pub fn provider_name(mut self, name: impl Into<String>) -> Self {
+        self.names.push(name.into());
+        self
+    }
+
+    pub fn source(mut self, source: SourcePreference) -> Self {
+        self.source = source;
+        self
+    }
+
+    // Adds each provider's status page summary.
+    pub fn status(mut self, status: bool) -> Self {
+        self.status = status;
+        self
+    }
+
+    pub fn no_credits(mut self, no_credits: bool) -> Self {
+        self.no_credits = no_credits;
+        self
+    }
+
+    // Skips results cached under `min_interval_secs`.
+    pub fn refresh(mut self, refresh: bool) -> Self {
+        self.refresh = refresh;
+        self
+    }
+
+    pub fn web_timeout(mut self, secs: u64) -> Self {
+        self.web_timeout = secs;
+        self
+    }
+
+    // A token or cookie account by label or id.
+    pub fn account(mut self, account: impl Into<String>) -> Self {
+        self.account = Some(account.into());
+        self
+    }
+
+    pub fn all_accounts(mut self, all_accounts: bool) -> Self {
+        self.all_accounts = all_accounts;
+        self
+    }
+}
+
+impl Default for FetchRequest {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn provider_names_resolve_like_the_cli() {
+        let mut config: Config = serde_json::from_value(serde_json::json!({
+            "providers": [{ "id": "codex", "aliases": ["work"] }]
+        }))
+        .unwrap();
+        config.groups = Some(BTreeMap::from([(
+            "team".to_string(),
+            vec!["work".to_string(), "copilot".to_string()],
+        )]));
+        let client = Client::with_config(config);
+
+        let request = FetchRequest::new()
+            .provider(ProviderId::Claude)
+            .provider_name("team");
+        let selectors = client.resolve(&request).unwrap();
+        assert_eq!(
+            selectors,
+            [
+                ProviderSelector::Claude,
+                ProviderSelector::Codex,
+                ProviderSelector::Copilot
+            ]
+        );
+        assert!(
+            client
+                .resolve(&FetchRequest::new().provider_name("nope"))
+                .is_err()
+        );
+    }
+}
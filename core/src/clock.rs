@@ -0,0 +1,87 @@
+use crate::warnings::push_warning;
+use chrono::{DateTime, Duration, Utc};
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
+
+// `Date` has one-second resolution and arrives after network latency, so only
+// offsets well beyond that are treated as a drifting local clock.
+const SKEW_THRESHOLD_SECS: i64 = 120;
+const UNKNOWN: i64 = i64::MIN;
+
+static SKEW_MS: AtomicI64 = AtomicI64::new(UNKNOWN);
+static WARNED: AtomicBool = AtomicBool::new(false);
+
+// Records the offset between a provider's `Date` header and the local clock at
+// the time the response arrived. The first fetch that notices skew warns once.
+pub fn observe_server_date(raw: &str, received_at: DateTime<Utc>) {
+    let Some(offset) = server_offset(raw, received_at) else {
+        return;
+    };
+    SKEW_MS.store(offset.num_milliseconds(), Ordering::Relaxed);
+    if let Some(skew) = skew()
+        && !WARNED.swap(true, Ordering::Relaxed)
+    {
+        push_warning(skew_warning(skew));
+    }
+}
+
+// Server time minus local time, once it exceeds the threshold.
+pub fn skew() -> Option<Duration> {
+    let ms = SKEW_MS.load(Ordering::Relaxed);
+    if ms == UNKNOWN || ms.abs() < SKEW_THRESHOLD_SECS * 1000 {
+        return None;
+    }
+    Some(Duration::milliseconds(ms))
+}
+
+// Local time corrected by any detected skew; use it for countdowns against
+// provider-reported reset times.
+pub fn now() -> DateTime<Utc> {
+    Utc::now() + skew().unwrap_or_else(Duration::zero)
+}
+
+fn server_offset(raw: &str, received_at: DateTime<Utc>) -> Option<Duration> {
+    let server = DateTime::parse_from_rfc2822(raw.trim()).ok()?;
+    Some(server.with_timezone(&Utc) - received_at)
+}
+
+fn skew_warning(skew: Duration) -> String {
+    let direction = if skew < Duration::zero() {
+        "ahead of"
+    } else {
+        "behind"
+    };
+    format!(
+        "local clock is {} {} provider time; reset countdowns use provider time",
+        format_offset(skew.num_seconds().abs()),
+        direction
+    )
+}
+
+fn format_offset(secs: i64) -> String {
+    let (hours, minutes) = (secs / 3600, (secs % 3600) / 60);
+    match (hours, minutes) {
+        (0, 0) => format!("{}s", secs),
+        (0, m) => format!("{}m", m),
+        (h, 0) => format!("{}h", h),
+        (h, m) => format!("{}h {}m", h, m),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn offset_from_date_header() {
+        let received = DateTime::parse_from_rfc3339("2025-01-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let offset = server_offset("Wed, 01 Jan 2025 09:30:00 GMT", received).unwrap();
+        assert_eq!(offset.num_seconds(), -9000);
+        assert_eq!(
+            skew_warning(offset),
+            "local clock is 2h 30m ahead of provider time; reset countdowns use provider time"
+        );
+        assert!(server_offset("not a date", received).is_none());
+    }
+}
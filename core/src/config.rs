@@ -1,18 +1,91 @@
 use crate::errors::CliError;
+use crate::http::{DEFAULT_BACKOFF, DEFAULT_RETRIES};
+use crate::model::OutputFormat;
+use crate::paths::default_config_path;
 use crate::providers::{ProviderId, SourcePreference};
 use anyhow::{Context, Result};
 use directories::BaseDirs;
+use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::PathBuf;
+use std::time::Duration;
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub const CONFIG_VERSION: u32 = 1;
+
+type MigrationStep = fn(&mut Value);
+
+// Each entry upgrades a config to `version`; steps run in order starting after
+// the version recorded in the file (a missing version is treated as 0).
+const MIGRATIONS: &[(u32, &str, MigrationStep)] =
+    &[(1, "rename camelCase keys to snake_case", migrate_to_v1)];
+
+const LEGACY_KEY_RENAMES: &[(&str, &str)] = &[
+    ("cookieSource", "cookie_source"),
+    ("cookieHeader", "cookie_header"),
+    ("apiKey", "api_key"),
+    ("workspaceId", "workspace_id"),
+    ("workspaceID", "workspace_id"),
+    ("tokenAccounts", "token_accounts"),
+    ("cookieAccounts", "cookie_accounts"),
+    ("activeIndex", "active_index"),
+    ("addedAt", "added_at"),
+    ("lastUsed", "last_used"),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct Config {
     pub version: Option<u32>,
     pub providers: Option<Vec<ProviderConfig>>,
+    pub defaults: Option<Defaults>,
+    pub groups: Option<BTreeMap<String, Vec<String>>>,
+    pub logging: Option<LoggingConfig>,
+    pub pricing: Option<BTreeMap<String, ModelPrice>>,
+    pub history: Option<HistoryConfig>,
+}
+
+// USD per million tokens for one model, used by `cost --report` ahead of the
+// built-in price tables. Cached input costs the same as input when unset.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+pub struct ModelPrice {
+    pub input: f64,
+    pub cached_input: Option<f64>,
+    pub output: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+pub struct LoggingConfig {
+    pub path: Option<PathBuf>,
+    pub level: Option<String>,
+    pub max_bytes: Option<u64>,
+    pub max_files: Option<u32>,
+}
+
+// Snapshots of each fetch kept for `history`; on unless turned off.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+pub struct HistoryConfig {
+    pub enabled: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+pub struct Defaults {
+    pub format: Option<OutputFormat>,
+    pub pretty: Option<bool>,
+    pub status: Option<bool>,
+    pub no_credits: Option<bool>,
+    pub interval: Option<u64>,
+    pub alert_below: Option<f64>,
+    pub timezone: Option<String>,
+    pub timeout: Option<u64>,
+    // Providers fetched at once; `--concurrency` overrides it.
+    pub concurrency: Option<usize>,
+    pub proxy: Option<String>,
+    pub extra_ca_bundle: Option<PathBuf>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ProviderConfig {
     pub id: ProviderId,
     pub enabled: Option<bool>,
@@ -23,6 +96,62 @@ pub struct ProviderConfig {
     pub region: Option<String>,
     pub workspace_id: Option<String>,
     pub token_accounts: Option<TokenAccounts>,
+    pub cookie_accounts: Option<Vec<CookieAccount>>,
+    pub timeout_secs: Option<u64>,
+    pub retries: Option<u32>,
+    // First retry delay; each further retry doubles it.
+    pub backoff_ms: Option<u64>,
+    pub min_interval_secs: Option<u64>,
+    pub display_name: Option<String>,
+    pub aliases: Option<Vec<String>>,
+    pub alert_at_percent: Option<f64>,
+    // Spend cap for `fuelcheck budget`, e.g. "50USD".
+    pub monthly_budget: Option<String>,
+    // Makes the entry a plugin: this executable answers for the provider id.
+    pub command: Option<String>,
+    pub args: Option<Vec<String>>,
+    // Makes the entry a custom provider read from one HTTP endpoint.
+    pub custom: Option<CustomProviderConfig>,
+}
+
+// Where a custom provider's numbers are in the endpoint's JSON response. Every
+// field is a JSON pointer such as `/quota/used`. Header values, the URL and
+// the body may use `${api_key}`, `${cookie_header}`, `${workspace_id}` and
+// `${env:NAME}`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+pub struct CustomProviderConfig {
+    pub url: String,
+    pub method: Option<String>,
+    pub headers: Option<BTreeMap<String, String>>,
+    pub body: Option<Value>,
+    pub primary: Option<CustomWindowMapping>,
+    pub secondary: Option<CustomWindowMapping>,
+    pub tertiary: Option<CustomWindowMapping>,
+    pub cost: Option<CustomCostMapping>,
+    pub plan: Option<String>,
+    pub account: Option<String>,
+}
+
+// A window needs `used_percent`, or `limit` with `used` or `remaining`.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+pub struct CustomWindowMapping {
+    pub used_percent: Option<String>,
+    pub used: Option<String>,
+    pub remaining: Option<String>,
+    pub limit: Option<String>,
+    pub resets_at: Option<String>,
+    // A fixed window length, not a pointer.
+    pub window_minutes: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+pub struct CustomCostMapping {
+    pub used: String,
+    pub limit: Option<String>,
+    pub resets_at: Option<String>,
+    // Fixed values, not pointers; USD and no period by default.
+    pub currency: Option<String>,
+    pub period: Option<String>,
 }
 
 impl ProviderConfig {
@@ -37,18 +166,98 @@ impl ProviderConfig {
             region: None,
             workspace_id: None,
             token_accounts: None,
+            cookie_accounts: None,
+            timeout_secs: None,
+            retries: None,
+            backoff_ms: None,
+            min_interval_secs: None,
+            display_name: None,
+            aliases: None,
+            alert_at_percent: None,
+            monthly_budget: None,
+            command: None,
+            args: None,
+            custom: None,
+        }
+    }
+
+    // Cookie accounts reuse token-account selection (--account, --account-index,
+    // --all-accounts), with the cookie header standing in for the token.
+    pub fn cookie_token_accounts(&self) -> Option<TokenAccounts> {
+        let accounts = self
+            .cookie_accounts
+            .as_ref()
+            .filter(|accounts| !accounts.is_empty())?;
+        Some(TokenAccounts {
+            version: None,
+            active_index: None,
+            accounts: Some(
+                accounts
+                    .iter()
+                    .map(|account| TokenAccount {
+                        id: None,
+                        label: account.label.clone(),
+                        token: account.cookie_header.clone(),
+                        added_at: None,
+                        last_used: None,
+                    })
+                    .collect(),
+            ),
+        })
+    }
+
+    pub fn network_settings(&self) -> NetworkSettings {
+        NetworkSettings {
+            timeout: self
+                .timeout_secs
+                .filter(|secs| *secs > 0)
+                .map(Duration::from_secs),
+            retries: self.retries.unwrap_or(DEFAULT_RETRIES),
+            backoff: self
+                .backoff_ms
+                .map(Duration::from_millis)
+                .unwrap_or(DEFAULT_BACKOFF),
+            min_interval: self
+                .min_interval_secs
+                .filter(|secs| *secs > 0)
+                .map(Duration::from_secs),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkSettings {
+    pub timeout: Option<Duration>,
+    pub retries: u32,
+    pub backoff: Duration,
+    pub min_interval: Option<Duration>,
+}
+
+impl Default for NetworkSettings {
+    fn default() -> Self {
+        Self {
+            timeout: None,
+            retries: DEFAULT_RETRIES,
+            backoff: DEFAULT_BACKOFF,
+            min_interval: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct TokenAccounts {
     pub version: Option<u32>,
     pub active_index: Option<usize>,
     pub accounts: Option<Vec<TokenAccount>>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
+pub struct CookieAccount {
+    pub label: Option<String>,
+    pub cookie_header: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, Default)]
 pub struct TokenAccount {
     pub id: Option<String>,
     pub label: Option<String>,
@@ -68,11 +277,19 @@ impl Config {
             return Ok(Config::default());
         }
 
+        let (config, _) = Config::load_migrated(&path)?;
+        Ok(config)
+    }
+
+    pub fn load_migrated(path: &PathBuf) -> Result<(Self, MigrationReport)> {
         let contents =
-            fs::read_to_string(&path).with_context(|| format!("read config {}", path.display()))?;
-        let config: Config = serde_json::from_str(&contents)
+            fs::read_to_string(path).with_context(|| format!("read config {}", path.display()))?;
+        let mut value: Value = serde_json::from_str(&contents)
             .with_context(|| format!("parse config {}", path.display()))?;
-        Ok(config)
+        let report = migrate_config_value(&mut value)?;
+        let config: Config = serde_json::from_value(value)
+            .with_context(|| format!("parse config {}", path.display()))?;
+        Ok((config, report))
     }
 
     pub fn path(path_override: Option<&PathBuf>) -> Result<PathBuf> {
@@ -92,26 +309,28 @@ impl Config {
         Ok(())
     }
 
+    // The enabled entries, or with none, the defaults minus the ids an entry
+    // disables (e.g. after `logout -p claude` on a fresh config).
     pub fn enabled_providers_or_default(&self) -> Vec<ProviderId> {
-        let mut enabled: Vec<ProviderId> = self
-            .providers
-            .clone()
-            .unwrap_or_default()
-            .into_iter()
+        let providers = self.providers.clone().unwrap_or_default();
+        let enabled: Vec<ProviderId> = providers
+            .iter()
             .filter(|cfg| cfg.enabled.unwrap_or(true))
             .map(|cfg| cfg.id)
             .collect();
-
-        if enabled.is_empty() {
-            enabled = vec![
-                ProviderId::Codex,
-                ProviderId::Claude,
-                ProviderId::Gemini,
-                ProviderId::Cursor,
-            ];
+        if !enabled.is_empty() {
+            return enabled;
         }
 
-        enabled
+        [
+            ProviderId::Codex,
+            ProviderId::Claude,
+            ProviderId::Gemini,
+            ProviderId::Cursor,
+        ]
+        .into_iter()
+        .filter(|id| !providers.iter().any(|cfg| cfg.id == *id))
+        .collect()
     }
 
     pub fn provider_config(&self, id: ProviderId) -> Option<ProviderConfig> {
@@ -121,6 +340,173 @@ impl Config {
             .into_iter()
             .find(|cfg| cfg.id == id)
     }
+
+    pub fn network_settings(&self, id: ProviderId) -> NetworkSettings {
+        self.provider_config(id)
+            .map(|cfg| cfg.network_settings())
+            .unwrap_or_default()
+    }
+
+    pub fn provider_labels(&self) -> ProviderLabels {
+        let names = self
+            .providers
+            .iter()
+            .flatten()
+            .filter_map(|cfg| {
+                let name = cfg.display_name.as_deref()?.trim();
+                (!name.is_empty()).then(|| (cfg.id.to_string(), name.to_string()))
+            })
+            .collect();
+        ProviderLabels { names }
+    }
+
+    pub fn provider_for_alias(&self, alias: &str) -> Option<ProviderId> {
+        let alias = alias.trim();
+        self.providers
+            .iter()
+            .flatten()
+            .find(|cfg| {
+                cfg.aliases
+                    .iter()
+                    .flatten()
+                    .any(|candidate| candidate.trim().eq_ignore_ascii_case(alias))
+            })
+            .map(|cfg| cfg.id)
+    }
+
+    pub fn provider_group(&self, name: &str) -> Option<&[String]> {
+        let name = name.trim();
+        self.groups
+            .iter()
+            .flatten()
+            .find(|(group, _)| group.trim().eq_ignore_ascii_case(name))
+            .map(|(_, members)| members.as_slice())
+    }
+
+    pub fn provider_config_mut(&mut self, id: ProviderId) -> &mut ProviderConfig {
+        let providers = self.providers.get_or_insert_with(Vec::new);
+        let index = match providers.iter().position(|cfg| cfg.id == id) {
+            Some(index) => index,
+            None => {
+                providers.push(ProviderConfig::default_provider(id));
+                providers.len() - 1
+            }
+        };
+        &mut providers[index]
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ProviderLabels {
+    names: HashMap<String, String>,
+}
+
+impl ProviderLabels {
+    pub fn display_name(&self, provider: &str) -> String {
+        self.names
+            .get(provider)
+            .cloned()
+            .unwrap_or_else(|| default_display_name(provider))
+    }
+}
+
+pub fn default_display_name(provider: &str) -> String {
+    match provider {
+        "codex" => "Codex".to_string(),
+        "claude" => "Claude".to_string(),
+        "gemini" => "Gemini".to_string(),
+        "cursor" => "Cursor".to_string(),
+        "factory" => "Factory".to_string(),
+        "anthropicapi" => "Anthropic API".to_string(),
+        "openaiplatform" => "OpenAI Platform".to_string(),
+        other => {
+            let mut chars = other.chars();
+            match chars.next() {
+                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
+                None => other.to_string(),
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct MigrationReport {
+    pub from: u32,
+    pub to: u32,
+    pub applied: Vec<String>,
+}
+
+impl MigrationReport {
+    pub fn is_empty(&self) -> bool {
+        self.applied.is_empty()
+    }
+}
+
+pub fn migrate_config_value(value: &mut Value) -> Result<MigrationReport> {
+    let from = value
+        .get("version")
+        .and_then(Value::as_u64)
+        .map(|v| v as u32)
+        .unwrap_or(0);
+    if from > CONFIG_VERSION {
+        return Err(CliError::ConfigVersionUnsupported(from, CONFIG_VERSION).into());
+    }
+
+    let mut applied = Vec::new();
+    for (version, description, step) in MIGRATIONS {
+        if *version <= from {
+            continue;
+        }
+        step(value);
+        if let Some(obj) = value.as_object_mut() {
+            obj.insert("version".to_string(), Value::from(*version));
+        }
+        applied.push(format!("v{}: {}", version, description));
+    }
+
+    Ok(MigrationReport {
+        from,
+        to: from.max(CONFIG_VERSION),
+        applied,
+    })
+}
+
+fn migrate_to_v1(value: &mut Value) {
+    let Some(providers) = value.get_mut("providers").and_then(Value::as_array_mut) else {
+        return;
+    };
+    for provider in providers {
+        rename_legacy_keys(provider);
+        if let Some(token_accounts) = provider.get_mut("token_accounts") {
+            rename_legacy_keys(token_accounts);
+            if let Some(accounts) = token_accounts
+                .get_mut("accounts")
+                .and_then(Value::as_array_mut)
+            {
+                accounts.iter_mut().for_each(rename_legacy_keys);
+            }
+        }
+        if let Some(accounts) = provider
+            .get_mut("cookie_accounts")
+            .and_then(Value::as_array_mut)
+        {
+            accounts.iter_mut().for_each(rename_legacy_keys);
+        }
+    }
+}
+
+fn rename_legacy_keys(value: &mut Value) {
+    let Some(obj) = value.as_object_mut() else {
+        return;
+    };
+    for (legacy, current) in LEGACY_KEY_RENAMES {
+        if obj.contains_key(*current) {
+            continue;
+        }
+        if let Some(v) = obj.remove(*legacy) {
+            obj.insert(current.to_string(), v);
+        }
+    }
 }
 
 pub struct DetectResult {
@@ -160,7 +546,54 @@ impl DetectResult {
     }
 }
 
-fn default_config_path() -> Option<PathBuf> {
-    let home = BaseDirs::new()?.home_dir().to_path_buf();
-    Some(home.join(".codexbar").join("config.json"))
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn logging_out_one_default_provider_keeps_the_others() {
+        let mut config = Config::default();
+        let request = crate::service::LogoutRequest {
+            providers: vec![ProviderId::Claude.into()],
+            ..Default::default()
+        };
+        crate::service::logout_providers(
+            &request,
+            &mut config,
+            &crate::providers::ProviderRegistry::new(),
+        )
+        .unwrap();
+        assert_eq!(
+            config.enabled_providers_or_default(),
+            [ProviderId::Codex, ProviderId::Gemini, ProviderId::Cursor]
+        );
+    }
+
+    #[test]
+    fn migrates_unversioned_config_keys() {
+        let mut value = serde_json::json!({
+            "providers": [{
+                "id": "cursor",
+                "cookieHeader": "a=b",
+                "tokenAccounts": {"activeIndex": 0, "accounts": [{"label": "Work", "addedAt": 1}]}
+            }]
+        });
+        let report = migrate_config_value(&mut value).expect("migrate");
+        assert_eq!((report.from, report.to), (0, CONFIG_VERSION));
+        assert_eq!(report.applied.len(), 1);
+
+        let config: Config = serde_json::from_value(value).expect("parse");
+        assert_eq!(config.version, Some(CONFIG_VERSION));
+        let cursor = config.provider_config(ProviderId::Cursor).expect("cursor");
+        assert_eq!(cursor.cookie_header.as_deref(), Some("a=b"));
+        let accounts = cursor.token_accounts.expect("token accounts");
+        assert_eq!(accounts.active_index, Some(0));
+        assert_eq!(accounts.accounts.unwrap()[0].added_at, Some(1));
+    }
+
+    #[test]
+    fn rejects_newer_config_version() {
+        let mut value = serde_json::json!({"version": CONFIG_VERSION + 1});
+        assert!(migrate_config_value(&mut value).is_err());
+    }
 }
@@ -0,0 +1,205 @@
+use anyhow::{Result, anyhow};
+use base64::Engine;
+use base64::engine::general_purpose::STANDARD;
+use std::io::ErrorKind;
+use std::process::Command;
+
+// Reads secrets other tools left in the OS credential store. Each backend shells
+// out to the platform's own tooling so we don't link against keychain libraries:
+// `security` on macOS, `secret-tool` (libsecret) on Linux, and PowerShell with
+// the Win32 Cred* API on Windows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Backend {
+    Keychain,
+    SecretService,
+    CredentialManager,
+}
+
+impl Backend {
+    pub fn current() -> Option<Self> {
+        if cfg!(target_os = "macos") {
+            Some(Backend::Keychain)
+        } else if cfg!(windows) {
+            Some(Backend::CredentialManager)
+        } else if cfg!(unix) {
+            Some(Backend::SecretService)
+        } else {
+            None
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Backend::Keychain => "keychain",
+            Backend::SecretService => "secret-service",
+            Backend::CredentialManager => "credential-manager",
+        }
+    }
+}
+
+// `account` narrows the lookup when the vendor stores one entry per user; with
+// `None` the first entry for `service` wins. On Windows `service` is matched as
+// a target-name prefix, which covers both "service/account" (keytar) and
+// "service:account" (go-keyring) layouts.
+pub fn read_secret(service: &str, account: Option<&str>) -> Result<String> {
+    let backend = Backend::current()
+        .ok_or_else(|| anyhow!("no credential store available on this platform"))?;
+    let output = match backend {
+        Backend::Keychain => {
+            let mut args = vec!["find-generic-password", "-s", service, "-w"];
+            if let Some(account) = account {
+                args.extend(["-a", account]);
+            }
+            Command::new("security").args(args).output()
+        }
+        Backend::SecretService => {
+            let mut args = vec!["lookup", "service", service];
+            if let Some(account) = account {
+                args.extend(["account", account]);
+            }
+            Command::new("secret-tool").args(args).output()
+        }
+        Backend::CredentialManager => windows_command("read", service, account).output(),
+    }
+    .map_err(|err| anyhow!("{} {}: {}", backend.label(), service, err))?;
+    if !output.status.success() {
+        return Err(anyhow!("{} entry {} not found", backend.label(), service));
+    }
+    let raw = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let secret = match backend {
+        Backend::CredentialManager => decode_base64(&raw)?,
+        _ => decode_go_keyring(raw)?,
+    };
+    let secret = secret.trim().to_string();
+    if secret.is_empty() {
+        return Err(anyhow!("{} entry {} empty", backend.label(), service));
+    }
+    Ok(secret)
+}
+
+// Returns whether an entry was removed; a missing store tool counts as nothing
+// to delete rather than an error.
+pub fn delete_secret(service: &str, account: Option<&str>) -> Result<bool> {
+    let Some(backend) = Backend::current() else {
+        return Ok(false);
+    };
+    let output = match backend {
+        Backend::Keychain => {
+            let mut args = vec!["delete-generic-password", "-s", service];
+            if let Some(account) = account {
+                args.extend(["-a", account]);
+            }
+            Command::new("security").args(args).output()
+        }
+        Backend::SecretService => {
+            // `secret-tool clear` succeeds even when nothing matched.
+            if read_secret(service, account).is_err() {
+                return Ok(false);
+            }
+            let mut args = vec!["clear", "service", service];
+            if let Some(account) = account {
+                args.extend(["account", account]);
+            }
+            Command::new("secret-tool").args(args).output()
+        }
+        Backend::CredentialManager => windows_command("delete", service, account).output(),
+    };
+    match output {
+        Ok(output) => Ok(output.status.success()),
+        Err(err) if err.kind() == ErrorKind::NotFound => Ok(false),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn windows_command(mode: &str, service: &str, account: Option<&str>) -> Command {
+    let mut command = Command::new("powershell");
+    command
+        .args(["-NoProfile", "-NonInteractive", "-Command", WINDOWS_SCRIPT])
+        .env("FUELCHECK_CRED_MODE", mode)
+        .env("FUELCHECK_CRED_SERVICE", service)
+        .env("FUELCHECK_CRED_ACCOUNT", account.unwrap_or_default());
+    command
+}
+
+fn decode_base64(raw: &str) -> Result<String> {
+    let bytes = STANDARD
+        .decode(raw)
+        .map_err(|err| anyhow!("credential store returned invalid data: {}", err))?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+// go-keyring (used by gh and other Go CLIs) wraps values it stores on macOS.
+fn decode_go_keyring(raw: String) -> Result<String> {
+    if let Some(encoded) = raw.strip_prefix("go-keyring-base64:") {
+        return decode_base64(encoded);
+    }
+    if let Some(encoded) = raw.strip_prefix("go-keyring-encoded:") {
+        let bytes = (0..encoded.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(encoded.get(i..i + 2).unwrap_or_default(), 16))
+            .collect::<std::result::Result<Vec<u8>, _>>()
+            .map_err(|err| anyhow!("credential store returned invalid data: {}", err))?;
+        return Ok(String::from_utf8_lossy(&bytes).into_owned());
+    }
+    Ok(raw)
+}
+
+// Enumerates generic credentials whose target starts with the service name and
+// prints the first match's blob as base64 (or deletes every match).
+const WINDOWS_SCRIPT: &str = r#"
+$ErrorActionPreference = 'Stop'
+Add-Type -Namespace Fuelcheck -Name Cred -MemberDefinition @'
+[DllImport("advapi32.dll", CharSet = CharSet.Unicode, SetLastError = true)]
+public static extern bool CredEnumerate(string filter, int flags, out int count, out IntPtr credentials);
+[DllImport("advapi32.dll", CharSet = CharSet.Unicode, SetLastError = true)]
+public static extern bool CredDelete(string target, int type, int flags);
+[DllImport("advapi32.dll")]
+public static extern void CredFree(IntPtr buffer);
+[StructLayout(LayoutKind.Sequential, CharSet = CharSet.Unicode)]
+public struct CREDENTIAL {
+    public int Flags; public int Type; public string TargetName; public string Comment;
+    public long LastWritten; public int CredentialBlobSize; public IntPtr CredentialBlob;
+    public int Persist; public int AttributeCount; public IntPtr Attributes;
+    public string TargetAlias; public string UserName;
+}
+'@
+$account = $env:FUELCHECK_CRED_ACCOUNT
+$count = 0
+$list = [IntPtr]::Zero
+if (-not [Fuelcheck.Cred]::CredEnumerate($env:FUELCHECK_CRED_SERVICE + '*', 0, [ref]$count, [ref]$list)) { exit 1 }
+$found = $false
+try {
+    for ($i = 0; $i -lt $count; $i++) {
+        $ptr = [Runtime.InteropServices.Marshal]::ReadIntPtr($list, $i * [IntPtr]::Size)
+        $cred = [Runtime.InteropServices.Marshal]::PtrToStructure($ptr, [type][Fuelcheck.Cred+CREDENTIAL])
+        if ($account -and $cred.UserName -ne $account -and -not $cred.TargetName.EndsWith($account)) { continue }
+        $found = $true
+        if ($env:FUELCHECK_CRED_MODE -eq 'delete') {
+            [void][Fuelcheck.Cred]::CredDelete($cred.TargetName, $cred.Type, 0)
+            continue
+        }
+        $bytes = New-Object byte[] $cred.CredentialBlobSize
+        [Runtime.InteropServices.Marshal]::Copy($cred.CredentialBlob, $bytes, 0, $cred.CredentialBlobSize)
+        [Console]::Out.Write([Convert]::ToBase64String($bytes))
+        break
+    }
+} finally {
+    [Fuelcheck.Cred]::CredFree($list)
+}
+if (-not $found) { exit 1 }
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decodes_go_keyring_wrapped_values() {
+        let plain = decode_go_keyring("gho_plain".to_string()).unwrap();
+        assert_eq!(plain, "gho_plain");
+        let b64 = decode_go_keyring("go-keyring-base64:Z2hvX3Rva2Vu".to_string()).unwrap();
+        assert_eq!(b64, "gho_token");
+        let hex = decode_go_keyring("go-keyring-encoded:67686f5f6869".to_string()).unwrap();
+        assert_eq!(hex, "gho_hi");
+    }
+}
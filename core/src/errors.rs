@@ -1,4 +1,6 @@
+use crate::model::ErrorKind;
 use crate::providers::ProviderId;
+use reqwest::StatusCode;
 use std::path::PathBuf;
 use thiserror::Error;
 
@@ -17,4 +19,137 @@ pub enum CliError {
     UnsupportedSource(ProviderId, String),
     #[error("provider {0} not implemented yet")]
     ProviderNotImplemented(ProviderId),
+    #[error("config version {0} is newer than supported version {1}; upgrade fuelcheck-cli")]
+    ConfigVersionUnsupported(u32, u32),
+    #[error("config validation failed: {errors} error(s), {warnings} warning(s)")]
+    ConfigInvalid { errors: usize, warnings: usize },
+    #[error("provider {0} does not support identity lookup")]
+    IdentityNotSupported(ProviderId),
+    #[error("{0}")]
+    MissingCredentials(String),
+    #[error("network access disabled by --no-network (needed {0})")]
+    NetworkDisabled(String),
+    #[error("{failed} of {total} provider(s) failed")]
+    PartialFailure { failed: usize, total: usize },
+    #[error("quota below {min_remaining}% remaining: {windows}")]
+    QuotaLow { min_remaining: f64, windows: String },
+    #[error("{period}-to-date spend {spent} exceeds budget {budget}")]
+    BudgetExceeded {
+        period: String,
+        spent: String,
+        budget: String,
+    },
+    #[error("usage at alert threshold: {windows}")]
+    UsageAlert { windows: String },
+    #[error("{failed} of {total} provider(s) failed")]
+    ProvidersFailed {
+        failed: usize,
+        total: usize,
+        code: i32,
+    },
+}
+
+#[derive(Debug, Error)]
+#[error("{message}")]
+pub struct HttpStatusError {
+    pub status: u16,
+    pub message: String,
+}
+
+impl HttpStatusError {
+    pub fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status: status.as_u16(),
+            message: message.into(),
+        }
+    }
+}
+
+// Walks the error chain and returns the kind of the first cause we recognize.
+pub fn classify_error(err: &anyhow::Error) -> Option<ErrorKind> {
+    err.chain().find_map(|cause| {
+        if let Some(cli_err) = cause.downcast_ref::<CliError>() {
+            return Some(match cli_err {
+                CliError::UnknownProvider(_) | CliError::UnsupportedSource(_, _) => ErrorKind::Args,
+                CliError::ConfigMissing(_)
+                | CliError::ConfigPathUnavailable
+                | CliError::ConfigInvalid { .. }
+                | CliError::ConfigVersionUnsupported(_, _) => ErrorKind::Config,
+                CliError::ProviderNotConfigured(_) | CliError::MissingCredentials(_) => {
+                    ErrorKind::NotConfigured
+                }
+                CliError::NetworkDisabled(_) => ErrorKind::Network,
+                CliError::ProviderNotImplemented(_)
+                | CliError::IdentityNotSupported(_)
+                | CliError::PartialFailure { .. }
+                | CliError::QuotaLow { .. }
+                | CliError::BudgetExceeded { .. }
+                | CliError::UsageAlert { .. }
+                | CliError::ProvidersFailed { .. } => ErrorKind::Provider,
+            });
+        }
+        if let Some(http_err) = cause.downcast_ref::<HttpStatusError>() {
+            return Some(kind_for_status(http_err.status));
+        }
+        if let Some(req_err) = cause.downcast_ref::<reqwest::Error>() {
+            if req_err.is_timeout() {
+                return Some(ErrorKind::Timeout);
+            }
+            if req_err.is_decode() {
+                return Some(ErrorKind::Parse);
+            }
+            if let Some(status) = req_err.status() {
+                return Some(kind_for_status(status.as_u16()));
+            }
+            if req_err.is_connect() || req_err.is_request() {
+                return Some(ErrorKind::Network);
+            }
+            return None;
+        }
+        if cause.is::<tokio::time::error::Elapsed>() {
+            return Some(ErrorKind::Timeout);
+        }
+        if cause.is::<serde_json::Error>() {
+            return Some(ErrorKind::Parse);
+        }
+        None
+    })
+}
+
+fn kind_for_status(status: u16) -> ErrorKind {
+    match status {
+        401 | 403 => ErrorKind::Auth,
+        429 => ErrorKind::RateLimited,
+        408 | 504 => ErrorKind::Timeout,
+        _ => ErrorKind::Provider,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Context;
+
+    #[test]
+    fn classifies_through_context() {
+        let err = anyhow::Error::new(HttpStatusError::new(
+            StatusCode::UNAUTHORIZED,
+            "cookie expired",
+        ))
+        .context("provider cursor");
+        assert_eq!(classify_error(&err), Some(ErrorKind::Auth));
+
+        let err: anyhow::Error =
+            HttpStatusError::new(StatusCode::TOO_MANY_REQUESTS, "slow down").into();
+        assert_eq!(classify_error(&err), Some(ErrorKind::RateLimited));
+
+        let err = Err::<(), _>(CliError::MissingCredentials("no key".into()))
+            .context("provider zai")
+            .unwrap_err();
+        assert_eq!(classify_error(&err), Some(ErrorKind::NotConfigured));
+
+        let err: anyhow::Error = serde_json::from_str::<u32>("nope").unwrap_err().into();
+        assert_eq!(classify_error(&err), Some(ErrorKind::Parse));
+        assert_eq!(classify_error(&anyhow::anyhow!("other")), None);
+    }
 }
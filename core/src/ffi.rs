@@ -0,0 +1,123 @@
+use crate::client::{Client, FetchRequest};
+use crate::model::ProviderPayload;
+use crate::providers::SourcePreference;
+use crate::service::provider_error_payload;
+use anyhow::{Result, anyhow};
+use serde::Deserialize;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use tokio::runtime::Runtime;
+
+// C ABI for native hosts (menubar apps, editor plugins) that embed the core
+// instead of spawning the CLI. See include/fuelcheck.h.
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct UsageOptions {
+    providers: Vec<String>,
+    source: Option<SourcePreference>,
+    config: Option<PathBuf>,
+    status: bool,
+    no_credits: bool,
+    web_timeout: Option<u64>,
+}
+
+/// Returns the same JSON array `usage --json` prints. `options` is a JSON object
+/// (or NULL for defaults); failures come back as a one-element error array, so
+/// the result is never NULL. Free it with `fuelcheck_string_free`.
+///
+/// # Safety
+/// `options` must be NULL or a valid NUL-terminated string.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fuelcheck_usage_json(options: *const c_char) -> *mut c_char {
+    let raw = if options.is_null() {
+        None
+    } else {
+        // SAFETY: the caller guarantees a valid NUL-terminated string.
+        Some(
+            unsafe { CStr::from_ptr(options) }
+                .to_string_lossy()
+                .into_owned(),
+        )
+    };
+    let result = catch_unwind(AssertUnwindSafe(|| usage_json(raw.as_deref())))
+        .unwrap_or_else(|_| Err(anyhow!("fuelcheck panicked while fetching usage")));
+    let json = result.unwrap_or_else(|err| {
+        let payload = ProviderPayload::error(
+            "ffi".to_string(),
+            "ffi".to_string(),
+            provider_error_payload(&err),
+        );
+        serde_json::to_string(&[payload]).unwrap_or_else(|_| "[]".to_string())
+    });
+    CString::new(json).unwrap_or_default().into_raw()
+}
+
+/// # Safety
+/// `ptr` must be NULL or a pointer returned by this library, freed only once.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn fuelcheck_string_free(ptr: *mut c_char) {
+    if !ptr.is_null() {
+        // SAFETY: the pointer came from `CString::into_raw` in this library.
+        drop(unsafe { CString::from_raw(ptr) });
+    }
+}
+
+fn usage_json(raw: Option<&str>) -> Result<String> {
+    let options: UsageOptions = match raw.map(str::trim).filter(|raw| !raw.is_empty()) {
+        Some(raw) => {
+            serde_json::from_str(raw).map_err(|err| anyhow!("invalid options: {}", err))?
+        }
+        None => UsageOptions::default(),
+    };
+    let client = match &options.config {
+        Some(path) => Client::load_from(path)?,
+        None => Client::load()?,
+    };
+    let mut request = FetchRequest::new()
+        .source(options.source.unwrap_or(SourcePreference::Auto))
+        .status(options.status)
+        .no_credits(options.no_credits)
+        .web_timeout(options.web_timeout.unwrap_or(20));
+    for name in options.providers {
+        request = request.provider_name(name);
+    }
+    let outputs = runtime()?.block_on(client.usage(&request))?;
+    Ok(serde_json::to_string(&outputs)?)
+}
+
+fn runtime() -> Result<&'static Runtime> {
+    static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+    if let Some(runtime) = RUNTIME.get() {
+        return Ok(runtime);
+    }
+    let runtime = tokio::runtime::Builder::new_multi_thread()
+        .enable_all()
+        .build()?;
+    Ok(RUNTIME.get_or_init(|| runtime))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bad_options_return_an_error_array() {
+        let options = CString::new("{not json").unwrap();
+        unsafe {
+            let ptr = fuelcheck_usage_json(options.as_ptr());
+            let json = CStr::from_ptr(ptr).to_str().unwrap().to_string();
+            fuelcheck_string_free(ptr);
+            let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+            assert_eq!(value[0]["provider"], "ffi");
+            assert!(
+                value[0]["error"]["message"]
+                    .as_str()
+                    .unwrap()
+                    .starts_with("invalid options")
+            );
+        }
+    }
+}
@@ -0,0 +1,166 @@
+use crate::budget::{BudgetPeriod, BudgetSkip, month_end};
+use crate::model::{RateWindow, UsageSnapshot};
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use serde::Serialize;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ForecastReport {
+    // The days the burn rate is averaged over, `today` included.
+    pub since: NaiveDate,
+    pub today: NaiveDate,
+    pub month_end: NaiveDate,
+    pub days: u32,
+    pub providers: Vec<ProviderForecast>,
+    pub skipped: Vec<BudgetSkip>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderForecast {
+    pub provider: String,
+    // Only for providers with cost reports.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cost: Option<CostForecast>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub windows: Vec<WindowForecast>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CostForecast {
+    #[serde(rename = "monthToDateUSD")]
+    pub month_to_date: f64,
+    #[serde(rename = "dailyRateUSD")]
+    pub daily_rate: f64,
+    #[serde(rename = "projectedMonthEndUSD")]
+    pub projected_month_end: f64,
+}
+
+impl CostForecast {
+    // `daily` is cost per day from the daily report; rows may repeat a date
+    // when the report splits days by agent.
+    pub fn new(daily: &[(NaiveDate, f64)], today: NaiveDate, days: u32) -> Self {
+        let days = days.max(1);
+        let month_start = BudgetPeriod::Month.start(today);
+        let fit_start = today - Duration::days(days as i64 - 1);
+        let sum_since = |start: NaiveDate| -> f64 {
+            daily
+                .iter()
+                .filter(|(date, _)| *date >= start && *date <= today)
+                .map(|(_, cost)| cost)
+                .sum()
+        };
+        let month_to_date = sum_since(month_start);
+        let daily_rate = sum_since(fit_start) / days as f64;
+        let remaining_days = (month_end(today) - today).num_days();
+        Self {
+            month_to_date,
+            daily_rate,
+            projected_month_end: month_to_date + daily_rate * remaining_days as f64,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WindowForecast {
+    // "primary", "secondary" or "tertiary".
+    pub window: String,
+    pub used_percent: f64,
+    pub resets_at: DateTime<Utc>,
+    // When the window runs out at the rate it has been used since it opened;
+    // unset when it resets first.
+    pub exhausted_at: Option<DateTime<Utc>>,
+}
+
+// Windows that say both how long they are and when they reset, since the rate
+// comes from how much of the window has passed.
+pub fn forecast_windows(usage: &UsageSnapshot, now: DateTime<Utc>) -> Vec<WindowForecast> {
+    [
+        ("primary", &usage.primary),
+        ("secondary", &usage.secondary),
+        ("tertiary", &usage.tertiary),
+    ]
+    .into_iter()
+    .filter_map(|(name, window)| {
+        let window = window.as_ref()?;
+        let resets_at = window.resets_at?;
+        Some(WindowForecast {
+            window: name.to_string(),
+            used_percent: window.used_percent,
+            resets_at,
+            exhausted_at: exhaustion_time(window, resets_at, now),
+        })
+    })
+    .collect()
+}
+
+fn exhaustion_time(
+    window: &RateWindow,
+    resets_at: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Option<DateTime<Utc>> {
+    if window.used_percent >= 100.0 {
+        return Some(now);
+    }
+    let opened = resets_at - Duration::minutes(window.window_minutes?);
+    let elapsed = (now - opened).num_seconds();
+    if window.used_percent <= 0.0 || elapsed <= 0 {
+        return None;
+    }
+    let per_second = window.used_percent / elapsed as f64;
+    let left = ((100.0 - window.used_percent) / per_second).ceil() as i64;
+    Some(now + Duration::seconds(left)).filter(|at| *at < resets_at)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cost_forecast_projects_the_recent_rate_to_month_end() {
+        let date = |day| NaiveDate::from_ymd_opt(2025, 4, day).unwrap();
+        // 30 earlier in the month, then 2 + 4 + 6 over the last three days.
+        let daily = [
+            (date(1), 30.0),
+            (date(8), 2.0),
+            (date(9), 4.0),
+            (date(9), 0.0),
+            (date(10), 6.0),
+        ];
+        let forecast = CostForecast::new(&daily, date(10), 3);
+        assert_eq!(forecast.month_to_date, 42.0);
+        assert_eq!(forecast.daily_rate, 4.0);
+        assert_eq!(forecast.projected_month_end, 42.0 + 4.0 * 20.0);
+    }
+
+    #[test]
+    fn windows_run_out_at_their_rate_so_far() {
+        let now = DateTime::parse_from_rfc3339("2025-04-10T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let window = |used_percent, resets_in_hours| RateWindow {
+            used_percent,
+            window_minutes: Some(300),
+            resets_at: Some(now + Duration::hours(resets_in_hours)),
+            reset_description: None,
+        };
+        let usage = UsageSnapshot {
+            // Two hours in, 50% used: the rest lasts two more hours.
+            primary: Some(window(50.0, 3)),
+            // Four hours in, 20% used: resets long before it runs out.
+            secondary: Some(window(20.0, 1)),
+            tertiary: None,
+            provider_cost: None,
+            updated_at: now,
+            identity: None,
+            account_email: None,
+            account_organization: None,
+            login_method: None,
+        };
+        let windows = forecast_windows(&usage, now);
+        assert_eq!(windows.len(), 2);
+        assert_eq!(windows[0].exhausted_at, Some(now + Duration::hours(2)));
+        assert_eq!(windows[1].exhausted_at, None);
+    }
+}
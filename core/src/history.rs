@@ -0,0 +1,153 @@
+use crate::model::ProviderPayload;
+use crate::paths::default_history_path;
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+
+// Append-only JSONL log of agent events and usage samples in the data dir.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HistoryEvent {
+    pub at: DateTime<Utc>,
+    pub provider: String,
+    pub kind: String,
+    #[serde(default)]
+    pub data: serde_json::Value,
+}
+
+pub const USAGE_SAMPLE: &str = "usage";
+
+impl HistoryEvent {
+    pub fn new(
+        provider: impl Into<String>,
+        kind: impl Into<String>,
+        data: serde_json::Value,
+    ) -> Self {
+        Self {
+            at: Utc::now(),
+            provider: provider.into(),
+            kind: kind.into(),
+            data,
+        }
+    }
+
+    // Remaining percent per window, keyed "primary"/"secondary"/"tertiary",
+    // plus the credit balance under "credits" when the provider reports one.
+    pub fn usage_sample(payload: &ProviderPayload) -> Option<Self> {
+        let usage = payload.usage.as_ref()?;
+        let mut windows = serde_json::Map::new();
+        for (name, window) in [
+            ("primary", &usage.primary),
+            ("secondary", &usage.secondary),
+            ("tertiary", &usage.tertiary),
+        ] {
+            if let Some(window) = window {
+                windows.insert(
+                    name.to_string(),
+                    serde_json::json!((100.0 - window.used_percent).clamp(0.0, 100.0)),
+                );
+            }
+        }
+        if let Some(credits) = &payload.credits {
+            windows.insert("credits".to_string(), serde_json::json!(credits.remaining));
+        }
+        let mut event = Self::new(
+            payload.provider.clone(),
+            USAGE_SAMPLE,
+            serde_json::Value::Object(windows),
+        );
+        event.at = usage.updated_at;
+        Some(event)
+    }
+
+    pub fn remaining(&self, window: &str) -> Option<f64> {
+        self.data.get(window).and_then(serde_json::Value::as_f64)
+    }
+
+    pub fn credits(&self) -> Option<f64> {
+        self.data.get("credits").and_then(serde_json::Value::as_f64)
+    }
+}
+
+pub struct History {
+    path: PathBuf,
+}
+
+impl History {
+    pub fn open_default() -> Result<Self> {
+        let path = default_history_path().ok_or_else(|| anyhow!("history path unavailable"))?;
+        Ok(Self::at(path))
+    }
+
+    pub fn at(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn append(&self, event: &HistoryEvent) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("create history dir {}", parent.display()))?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("open history {}", self.path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(event)?)?;
+        Ok(())
+    }
+
+    // Events of `kind` for `provider` in file order; unreadable lines are skipped.
+    pub fn events(&self, provider: &str, kind: &str) -> Result<Vec<HistoryEvent>> {
+        let file = match fs::File::open(&self.path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(BufReader::new(file)
+            .lines()
+            .map_while(std::result::Result::ok)
+            .filter_map(|line| serde_json::from_str::<HistoryEvent>(&line).ok())
+            .filter(|event| event.provider == provider && event.kind == kind)
+            .collect())
+    }
+
+    pub fn last(&self, provider: &str, kind: &str) -> Result<Option<HistoryEvent>> {
+        Ok(self.events(provider, kind)?.pop())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn last_returns_latest_matching_event() {
+        let dir = std::env::temp_dir().join(format!("fuelcheck-history-{}", uuid::Uuid::new_v4()));
+        let history = History::at(dir.join("history.jsonl"));
+        assert!(history.last("codex", USAGE_SAMPLE).unwrap().is_none());
+        for remaining in [40.0, 12.0] {
+            let data = serde_json::json!({ "primary": remaining });
+            history
+                .append(&HistoryEvent::new("codex", USAGE_SAMPLE, data))
+                .unwrap();
+        }
+        history
+            .append(&HistoryEvent::new(
+                "codex",
+                "agent-turn-complete",
+                serde_json::json!({}),
+            ))
+            .unwrap();
+        let last = history.last("codex", USAGE_SAMPLE).unwrap().unwrap();
+        assert_eq!(last.remaining("primary"), Some(12.0));
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,609 @@
+use crate::clock;
+use crate::errors::CliError;
+use crate::providers::ProviderId;
+use anyhow::{Context, anyhow};
+use chrono::{DateTime, Utc};
+use reqwest::header::HeaderMap;
+use reqwest::{
+    Certificate, Client, Proxy, Request, RequestBuilder, Response, ResponseBuilderExt, StatusCode,
+    Url,
+};
+use std::collections::hash_map::RandomState;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::path::PathBuf;
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_RETRIES: u32 = 2;
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+pub const DEFAULT_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_DELAY: Duration = Duration::from_secs(30);
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(120);
+const TCP_KEEPALIVE: Duration = Duration::from_secs(60);
+const TRACE_BODY_LIMIT: usize = 4096;
+const REDACTED: &str = "REDACTED";
+// Header, query, and body field names containing any of these are never traced.
+const SECRET_MARKERS: &[&str] = &[
+    "authorization",
+    "cookie",
+    "token",
+    "secret",
+    "password",
+    "session",
+    "key",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestPolicy {
+    pub provider: Option<ProviderId>,
+    pub retries: u32,
+    // Base delay of the exponential backoff between retries.
+    pub backoff: Duration,
+    pub timeout: Option<Duration>,
+}
+
+impl Default for RequestPolicy {
+    fn default() -> Self {
+        Self {
+            provider: None,
+            retries: DEFAULT_RETRIES,
+            backoff: DEFAULT_BACKOFF,
+            timeout: None,
+        }
+    }
+}
+
+impl RequestPolicy {
+    // Per-request timeout: the policy's own, else the global `--timeout`, else 30s.
+    pub fn effective_timeout(&self) -> Duration {
+        self.timeout
+            .or_else(|| GLOBAL_TIMEOUT.get().copied())
+            .unwrap_or(DEFAULT_TIMEOUT)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RetryEvent {
+    pub provider: Option<ProviderId>,
+    pub url: String,
+    pub attempt: u32,
+    pub retries: u32,
+    pub delay: Duration,
+    pub reason: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpTrace {
+    pub provider: Option<ProviderId>,
+    pub method: String,
+    pub url: String,
+    pub attempt: u32,
+    pub status: Option<u16>,
+    pub duration: Duration,
+    pub error: Option<String>,
+    pub request_headers: Vec<(String, String)>,
+    pub request_body: Option<String>,
+    pub response_body: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TraceSettings {
+    observer: fn(&HttpTrace),
+    bodies: bool,
+}
+
+tokio::task_local! {
+    static REQUEST_POLICY: RequestPolicy;
+}
+
+static RETRY_OBSERVER: OnceLock<fn(&RetryEvent)> = OnceLock::new();
+static GLOBAL_TIMEOUT: OnceLock<Duration> = OnceLock::new();
+static NETWORK_DISABLED: AtomicBool = AtomicBool::new(false);
+static TRACE: OnceLock<TraceSettings> = OnceLock::new();
+static CLIENT: OnceLock<Client> = OnceLock::new();
+
+// One pooled client for the whole process: providers sharing a host and repeated
+// watch refreshes reuse open (HTTP/2 where offered) connections instead of
+// paying for a new TLS handshake on every request. Cloning is cheap.
+pub fn client() -> Client {
+    CLIENT
+        .get_or_init(|| build_client(&ClientSettings::default()).unwrap_or_default())
+        .clone()
+}
+
+// For networks that intercept TLS: an explicit proxy replaces the
+// HTTP(S)_PROXY environment variables, and the CA bundle is trusted on top
+// of the built-in roots.
+#[derive(Debug, Clone, Default)]
+pub struct ClientSettings {
+    pub proxy: Option<String>,
+    pub extra_ca_bundle: Option<PathBuf>,
+}
+
+// Must run before the first request; the shared client is built only once.
+pub fn configure_client(settings: &ClientSettings) -> anyhow::Result<()> {
+    let client = build_client(settings)?;
+    let _ = CLIENT.set(client);
+    Ok(())
+}
+
+fn build_client(settings: &ClientSettings) -> anyhow::Result<Client> {
+    let mut builder = Client::builder()
+        .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+        .tcp_keepalive(TCP_KEEPALIVE)
+        .gzip(true)
+        .brotli(true);
+    if let Some(proxy) = settings.proxy.as_deref() {
+        let parsed =
+            Proxy::all(proxy).map_err(|err| anyhow!("invalid proxy \"{}\": {}", proxy, err))?;
+        builder = builder.proxy(parsed);
+    }
+    if let Some(path) = &settings.extra_ca_bundle {
+        let pem = std::fs::read(path)
+            .with_context(|| format!("cannot read CA bundle {}", path.display()))?;
+        let certs = Certificate::from_pem_bundle(&pem)
+            .map_err(|err| anyhow!("invalid CA bundle {}: {}", path.display(), err))?;
+        if certs.is_empty() {
+            return Err(anyhow!(
+                "CA bundle {} has no PEM certificates",
+                path.display()
+            ));
+        }
+        for cert in certs {
+            builder = builder.add_root_certificate(cert);
+        }
+    }
+    Ok(builder.build()?)
+}
+
+// Every attempt is reported with secrets redacted; bodies are read (and the
+// response rebuilt) only when `bodies` is set.
+pub fn set_trace_observer(observer: fn(&HttpTrace), bodies: bool) {
+    let _ = TRACE.set(TraceSettings { observer, bodies });
+}
+
+// With `--no-network`, every request fails before it is sent; sources that only
+// read local files keep working.
+pub fn set_network_disabled(disabled: bool) {
+    NETWORK_DISABLED.store(disabled, Ordering::Relaxed);
+}
+
+pub fn network_disabled() -> bool {
+    NETWORK_DISABLED.load(Ordering::Relaxed)
+}
+
+pub fn set_global_timeout(timeout: Duration) {
+    let _ = GLOBAL_TIMEOUT.set(timeout);
+}
+
+// Called once per scheduled retry so the CLI can surface them in verbose logs.
+pub fn set_retry_observer(observer: fn(&RetryEvent)) {
+    let _ = RETRY_OBSERVER.set(observer);
+}
+
+// Every request sent via `send_with_retry` inside `fut` uses `policy`.
+pub async fn with_request_policy<F: Future>(policy: RequestPolicy, fut: F) -> F::Output {
+    REQUEST_POLICY.scope(policy, fut).await
+}
+
+// The timeout `send_with_retry` would use here, for work that isn't an HTTP
+// request (plugin processes).
+pub fn current_timeout() -> Duration {
+    REQUEST_POLICY
+        .try_with(|policy| *policy)
+        .unwrap_or_default()
+        .effective_timeout()
+}
+
+pub trait RequestBuilderExt {
+    fn send_with_retry(self) -> impl Future<Output = anyhow::Result<Response>> + Send;
+}
+
+impl RequestBuilderExt for RequestBuilder {
+    fn send_with_retry(self) -> impl Future<Output = anyhow::Result<Response>> + Send {
+        send_with_retry(self)
+    }
+}
+
+pub async fn send_with_retry(builder: RequestBuilder) -> anyhow::Result<Response> {
+    let policy = REQUEST_POLICY
+        .try_with(|policy| *policy)
+        .unwrap_or_default();
+    let (client, request) = builder.build_split();
+    let mut request = request?;
+    if network_disabled() {
+        let host = request.url().host_str().unwrap_or_default().to_string();
+        return Err(CliError::NetworkDisabled(host).into());
+    }
+    // Requests that set their own timeout (e.g. status pages) keep it.
+    if request.timeout().is_none() {
+        *request.timeout_mut() = Some(policy.effective_timeout());
+    }
+
+    let mut attempt = 0;
+    loop {
+        // Streaming bodies can't be cloned; those requests go out exactly once.
+        let next = if attempt < policy.retries {
+            request.try_clone()
+        } else {
+            None
+        };
+        let result = match TRACE.get() {
+            Some(trace) => {
+                let started = Instant::now();
+                let summary = trace_request(policy.provider, attempt, &request, trace.bodies);
+                let result = client.execute(request).await;
+                trace_response(*trace, summary, started, result).await
+            }
+            None => client.execute(request).await,
+        };
+        if let Ok(resp) = &result
+            && let Some(date) = resp.headers().get(reqwest::header::DATE)
+            && let Ok(date) = date.to_str()
+        {
+            clock::observe_server_date(date, Utc::now());
+        }
+        let Some(retry_request) = next else {
+            return Ok(result?);
+        };
+        let Some((delay, reason, url)) = retry_decision(&result, attempt, policy.backoff) else {
+            return Ok(result?);
+        };
+
+        attempt += 1;
+        if let Some(observer) = RETRY_OBSERVER.get() {
+            observer(&RetryEvent {
+                provider: policy.provider,
+                url,
+                attempt,
+                retries: policy.retries,
+                delay,
+                reason,
+            });
+        }
+        tokio::time::sleep(delay).await;
+        request = retry_request;
+    }
+}
+
+fn trace_request(
+    provider: Option<ProviderId>,
+    attempt: u32,
+    request: &Request,
+    bodies: bool,
+) -> HttpTrace {
+    HttpTrace {
+        provider,
+        method: request.method().to_string(),
+        url: redact_url(request.url()),
+        attempt: attempt + 1,
+        status: None,
+        duration: Duration::ZERO,
+        error: None,
+        request_headers: redact_headers(request.headers()),
+        request_body: request
+            .body()
+            .and_then(|body| body.as_bytes())
+            .filter(|_| bodies)
+            .map(redact_body),
+        response_body: None,
+    }
+}
+
+async fn trace_response(
+    trace: TraceSettings,
+    mut summary: HttpTrace,
+    started: Instant,
+    result: reqwest::Result<Response>,
+) -> reqwest::Result<Response> {
+    let result = match result {
+        Ok(resp) if trace.bodies => {
+            let status = resp.status();
+            let version = resp.version();
+            let url = resp.url().clone();
+            let headers = resp.headers().clone();
+            match resp.bytes().await {
+                Ok(bytes) => {
+                    summary.response_body = Some(redact_body(&bytes));
+                    Ok(rebuild_response(
+                        status,
+                        version,
+                        url,
+                        headers,
+                        bytes.into(),
+                    ))
+                }
+                Err(err) => Err(err),
+            }
+        }
+        other => other,
+    };
+    summary.duration = started.elapsed();
+    match &result {
+        Ok(resp) => summary.status = Some(resp.status().as_u16()),
+        Err(err) => summary.error = Some(err.to_string()),
+    }
+    (trace.observer)(&summary);
+    result
+}
+
+fn rebuild_response(
+    status: StatusCode,
+    version: reqwest::Version,
+    url: Url,
+    headers: HeaderMap,
+    body: reqwest::Body,
+) -> Response {
+    let mut builder = ::http::Response::builder()
+        .status(status)
+        .version(version)
+        .url(url);
+    if let Some(target) = builder.headers_mut() {
+        *target = headers;
+    }
+    match builder.body(body) {
+        Ok(resp) => Response::from(resp),
+        Err(_) => Response::from(::http::Response::new(reqwest::Body::from(Vec::new()))),
+    }
+}
+
+fn is_secret_name(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    SECRET_MARKERS.iter().any(|marker| name.contains(marker))
+}
+
+fn redact_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = if is_secret_name(name.as_str()) {
+                REDACTED.to_string()
+            } else {
+                value.to_str().unwrap_or("<binary>").to_string()
+            };
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+fn redact_url(url: &Url) -> String {
+    let mut url = url.clone();
+    if url.query().is_some() {
+        let pairs: Vec<(String, String)> = url
+            .query_pairs()
+            .map(|(name, value)| {
+                let value = if is_secret_name(&name) {
+                    REDACTED.to_string()
+                } else {
+                    value.into_owned()
+                };
+                (name.into_owned(), value)
+            })
+            .collect();
+        url.query_pairs_mut().clear().extend_pairs(pairs);
+    }
+    url.to_string()
+}
+
+// JSON bodies have secret fields blanked at any depth, form bodies have secret
+// pairs blanked, and anything else is passed through; all are truncated.
+fn redact_body(bytes: &[u8]) -> String {
+    let text = String::from_utf8_lossy(bytes);
+    let redacted = if let Ok(mut value) = serde_json::from_str::<serde_json::Value>(&text) {
+        redact_json(&mut value);
+        value.to_string()
+    } else if !text.contains(char::is_whitespace) && text.contains('=') {
+        text.split('&')
+            .map(|pair| match pair.split_once('=') {
+                Some((name, _)) if is_secret_name(name) => format!("{}={}", name, REDACTED),
+                _ => pair.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("&")
+    } else {
+        text.into_owned()
+    };
+    truncate(redacted, TRACE_BODY_LIMIT)
+}
+
+fn redact_json(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (name, field) in map.iter_mut() {
+                if is_secret_name(name) && !field.is_object() && !field.is_array() {
+                    *field = serde_json::Value::String(REDACTED.to_string());
+                } else {
+                    redact_json(field);
+                }
+            }
+        }
+        serde_json::Value::Array(items) => items.iter_mut().for_each(redact_json),
+        _ => {}
+    }
+}
+
+fn truncate(mut text: String, limit: usize) -> String {
+    if text.len() > limit {
+        let mut end = limit;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text.truncate(end);
+        text.push('…');
+    }
+    text
+}
+
+fn retry_decision(
+    result: &reqwest::Result<Response>,
+    attempt: u32,
+    base: Duration,
+) -> Option<(Duration, String, String)> {
+    match result {
+        Ok(resp) => {
+            let status = resp.status();
+            let url = resp.url().to_string();
+            if status == StatusCode::TOO_MANY_REQUESTS {
+                let delay = retry_after(resp).unwrap_or_else(|| backoff_delay(attempt, base));
+                Some((delay, format!("HTTP {}", status.as_u16()), url))
+            } else if status.is_server_error() {
+                Some((
+                    backoff_delay(attempt, base),
+                    format!("HTTP {}", status.as_u16()),
+                    url,
+                ))
+            } else {
+                None
+            }
+        }
+        Err(err) if err.is_timeout() || err.is_connect() || is_connection_reset(err) => {
+            let url = err.url().map(|u| u.to_string()).unwrap_or_default();
+            let reason = if err.is_timeout() {
+                "timeout"
+            } else if err.is_connect() {
+                "connection failed"
+            } else {
+                "connection reset"
+            };
+            Some((backoff_delay(attempt, base), reason.to_string(), url))
+        }
+        Err(_) => None,
+    }
+}
+
+fn retry_after(resp: &Response) -> Option<Duration> {
+    let raw = resp
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?;
+    parse_retry_after(raw, clock::now())
+}
+
+fn parse_retry_after(raw: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let raw = raw.trim();
+    let delay = if let Ok(secs) = raw.parse::<u64>() {
+        Duration::from_secs(secs)
+    } else {
+        let at = DateTime::parse_from_rfc2822(raw).ok()?.with_timezone(&Utc);
+        (at - now).to_std().unwrap_or(Duration::ZERO)
+    };
+    Some(delay.min(MAX_DELAY))
+}
+
+// A pooled keep-alive connection the server already closed fails the send
+// after connecting, so it is neither a connect error nor a timeout.
+fn is_connection_reset(err: &(dyn std::error::Error + 'static)) -> bool {
+    let mut source = err.source();
+    while let Some(cause) = source {
+        if let Some(io) = cause.downcast_ref::<std::io::Error>()
+            && matches!(
+                io.kind(),
+                std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::BrokenPipe
+                    | std::io::ErrorKind::UnexpectedEof
+            )
+        {
+            return true;
+        }
+        source = cause.source();
+    }
+    false
+}
+
+// Exponential backoff with jitter: a random delay between base/2 and base * 2^attempt.
+fn backoff_delay(attempt: u32, base: Duration) -> Duration {
+    let ceiling = base.saturating_mul(1 << attempt.min(6)).min(MAX_DELAY);
+    let floor = (base / 2).min(MAX_DELAY);
+    let span = ceiling.saturating_sub(floor).as_millis() as u64;
+    let jitter = if span == 0 {
+        0
+    } else {
+        RandomState::new().build_hasher().finish() % (span + 1)
+    };
+    floor + Duration::from_millis(jitter)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[derive(Debug)]
+    struct Wrapped(std::io::Error);
+
+    impl std::fmt::Display for Wrapped {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            f.write_str("request failed")
+        }
+    }
+
+    impl std::error::Error for Wrapped {
+        fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn connection_resets_are_found_in_the_error_chain() {
+        let reset = Wrapped(std::io::ErrorKind::ConnectionReset.into());
+        assert!(is_connection_reset(&reset));
+        let denied = Wrapped(std::io::ErrorKind::PermissionDenied.into());
+        assert!(!is_connection_reset(&denied));
+    }
+
+    #[test]
+    fn retry_after_accepts_seconds_and_dates() {
+        let now = Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap();
+        assert_eq!(parse_retry_after("7", now), Some(Duration::from_secs(7)));
+        assert_eq!(
+            parse_retry_after("Wed, 01 Jan 2025 00:00:05 GMT", now),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(parse_retry_after("3600", now), Some(MAX_DELAY));
+        assert_eq!(parse_retry_after("soon", now), None);
+    }
+
+    #[test]
+    fn backoff_grows_and_stays_bounded() {
+        for attempt in 0..10 {
+            let delay = backoff_delay(attempt, DEFAULT_BACKOFF);
+            assert!(delay >= DEFAULT_BACKOFF / 2);
+            assert!(delay <= MAX_DELAY);
+        }
+        assert!(backoff_delay(0, DEFAULT_BACKOFF) <= DEFAULT_BACKOFF);
+        let slow = Duration::from_secs(4);
+        assert!(backoff_delay(0, slow) >= slow / 2);
+        assert_eq!(backoff_delay(0, Duration::ZERO), Duration::ZERO);
+    }
+
+    #[test]
+    fn trace_redacts_secrets() {
+        let url = Url::parse("https://api.example.com/v1?key=abc&page=2").unwrap();
+        assert_eq!(
+            redact_url(&url),
+            "https://api.example.com/v1?key=REDACTED&page=2"
+        );
+        assert_eq!(
+            redact_body(br#"{"refresh_token":"rt","user":{"email":"a@b.c","sessionKey":"s"}}"#),
+            r#"{"refresh_token":"REDACTED","user":{"email":"a@b.c","sessionKey":"REDACTED"}}"#
+        );
+        assert_eq!(
+            redact_body(b"grant_type=refresh_token&client_secret=xyz"),
+            "grant_type=refresh_token&client_secret=REDACTED"
+        );
+        let mut headers = HeaderMap::new();
+        headers.insert("cookie", "sessionKey=abc".parse().unwrap());
+        headers.insert("accept", "application/json".parse().unwrap());
+        assert_eq!(
+            redact_headers(&headers),
+            vec![
+                ("cookie".to_string(), REDACTED.to_string()),
+                ("accept".to_string(), "application/json".to_string()),
+            ]
+        );
+    }
+}
@@ -1,7 +1,29 @@
 pub mod accounts;
+pub mod alerts;
+pub mod budget;
+pub mod client;
+pub mod clock;
 pub mod config;
+pub mod credential_store;
 pub mod errors;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod forecast;
+pub mod history;
+pub mod http;
 pub mod model;
+pub mod paths;
+pub mod progress;
 pub mod providers;
 pub mod reports;
+pub mod schema;
 pub mod service;
+pub mod session_tags;
+pub mod shared_cache;
+pub mod snapshots;
+#[cfg(test)]
+mod test_env;
+pub mod validation;
+pub mod warnings;
+
+pub use client::{Client, FetchRequest};
@@ -1,15 +1,39 @@
 use chrono::{DateTime, Utc};
-use serde::Serialize;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
 pub enum OutputFormat {
     Text,
     Json,
+    Raycast,
+    Alfred,
+    Share,
+    // Tables for `cost --report`; other output falls back to text.
+    Markdown,
+    // One line per invocation for status bars; waybar gets its JSON form.
+    Statusbar,
+    Waybar,
+    // One JSON payload per line, printed as each provider finishes.
+    Ndjson,
+}
+
+// Bumped whenever a payload field changes meaning or is removed; older shapes
+// stay available through `OutputSchema`.
+pub const PAYLOAD_SCHEMA_VERSION: u32 = 2;
+
+fn current_schema_version() -> u32 {
+    PAYLOAD_SCHEMA_VERSION
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ProviderPayload {
+    // Payloads read without one (plugins, older caches) are taken as current.
+    #[serde(default = "current_schema_version")]
+    pub schema_version: u32,
     pub provider: String,
     pub account: Option<String>,
     pub version: Option<String>,
@@ -20,11 +44,43 @@ pub struct ProviderPayload {
     pub antigravity_plan_info: Option<serde_json::Value>,
     pub openai_dashboard: Option<OpenAIDashboardSnapshot>,
     pub error: Option<ProviderErrorPayload>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+    // Set when a window has reached the provider's `alert_at_percent`.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub alerting: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub alerts: Vec<UsageAlert>,
+    // Models a local runtime (Ollama) has in memory right now.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub loaded_models: Vec<LoadedModel>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct UsageAlert {
+    // "primary", "secondary", "tertiary", or "cost" for the spend limit.
+    pub window: String,
+    pub used_percent: f64,
+    pub threshold: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadedModel {
+    pub name: String,
+    pub size_bytes: u64,
+    // The part of `size_bytes` held in GPU memory; the rest runs on the CPU.
+    pub vram_bytes: u64,
+    pub context_length: Option<u64>,
+    // When the model is unloaded unless it is used again.
+    pub expires_at: Option<DateTime<Utc>>,
 }
 
 impl ProviderPayload {
     pub fn error(provider: String, source: String, error: ProviderErrorPayload) -> Self {
         Self {
+            schema_version: PAYLOAD_SCHEMA_VERSION,
             provider,
             account: None,
             version: None,
@@ -35,20 +91,98 @@ impl ProviderPayload {
             antigravity_plan_info: None,
             openai_dashboard: None,
             error: Some(error),
+            warnings: Vec::new(),
+            alerting: false,
+            alerts: Vec::new(),
+            loaded_models: Vec::new(),
         }
     }
 }
 
-#[derive(Debug, Clone, Serialize)]
+// The payload shape scripts asked for with `--output-schema`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputSchema {
+    // Before payloads were versioned: no `schemaVersion`.
+    V1,
+    #[default]
+    V2,
+}
+
+impl OutputSchema {
+    pub fn payloads_to_string(
+        self,
+        payloads: &[ProviderPayload],
+        pretty: bool,
+    ) -> serde_json::Result<String> {
+        match self {
+            // Straight from the structs, keeping their field order.
+            OutputSchema::V2 => to_json_string(&payloads, pretty),
+            _ => {
+                let mut value = serde_json::to_value(payloads)?;
+                if let Value::Array(items) = &mut value {
+                    items.iter_mut().for_each(|item| self.convert(item));
+                }
+                to_json_string(&value, pretty)
+            }
+        }
+    }
+
+    // One payload as a compact JSON line.
+    pub fn payload_to_line(self, payload: &ProviderPayload) -> serde_json::Result<String> {
+        match self {
+            OutputSchema::V2 => serde_json::to_string(payload),
+            _ => {
+                let mut value = serde_json::to_value(payload)?;
+                self.convert(&mut value);
+                serde_json::to_string(&value)
+            }
+        }
+    }
+
+    // Rewrites one serialized current payload into this version's shape.
+    pub fn convert(self, payload: &mut Value) {
+        let Value::Object(fields) = payload else {
+            return;
+        };
+        match self {
+            OutputSchema::V1 => {
+                fields.remove("schemaVersion");
+            }
+            OutputSchema::V2 => {}
+        }
+    }
+}
+
+fn to_json_string<T: Serialize>(value: &T, pretty: bool) -> serde_json::Result<String> {
+    if pretty {
+        serde_json::to_string_pretty(value)
+    } else {
+        serde_json::to_string(value)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ProviderStatusPayload {
     pub indicator: ProviderStatusIndicator,
     pub description: Option<String>,
     pub updated_at: Option<DateTime<Utc>>,
     pub url: String,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub incidents: Vec<StatusIncident>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct StatusIncident {
+    pub name: String,
+    pub status: String,
+    pub impact: Option<String>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub url: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum ProviderStatusIndicator {
     None,
@@ -59,7 +193,7 @@ pub enum ProviderStatusIndicator {
     Unknown,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ProviderErrorPayload {
     pub code: i32,
@@ -67,17 +201,37 @@ pub struct ProviderErrorPayload {
     pub kind: Option<ErrorKind>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[allow(dead_code)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "kebab-case")]
 pub enum ErrorKind {
     Args,
     Config,
     Provider,
     Runtime,
+    Auth,
+    RateLimited,
+    Network,
+    Timeout,
+    Parse,
+    NotConfigured,
+}
+
+impl ErrorKind {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorKind::Provider | ErrorKind::Runtime => 1,
+            ErrorKind::Args | ErrorKind::NotConfigured => 2,
+            ErrorKind::Config | ErrorKind::Parse => 3,
+            ErrorKind::Timeout => 4,
+            ErrorKind::Auth => 6,
+            ErrorKind::RateLimited => 7,
+            ErrorKind::Network => 8,
+        }
+    }
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct RateWindow {
     pub used_percent: f64,
@@ -86,7 +240,7 @@ pub struct RateWindow {
     pub reset_description: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ProviderIdentitySnapshot {
     #[serde(rename = "providerID")]
@@ -96,7 +250,31 @@ pub struct ProviderIdentitySnapshot {
     pub login_method: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct IdentityPayload {
+    pub provider: String,
+    pub account: Option<String>,
+    pub source: String,
+    pub account_id: Option<String>,
+    pub identity: Option<ProviderIdentitySnapshot>,
+    pub error: Option<ProviderErrorPayload>,
+}
+
+impl IdentityPayload {
+    pub fn error(provider: String, source: String, error: ProviderErrorPayload) -> Self {
+        Self {
+            provider,
+            account: None,
+            source,
+            account_id: None,
+            identity: None,
+            error: Some(error),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct UsageSnapshot {
     pub primary: Option<RateWindow>,
@@ -110,7 +288,7 @@ pub struct UsageSnapshot {
     pub login_method: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ProviderCostSnapshot {
     pub used: f64,
@@ -121,7 +299,7 @@ pub struct ProviderCostSnapshot {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreditEvent {
     pub id: String,
@@ -130,7 +308,7 @@ pub struct CreditEvent {
     pub credits_used: f64,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct CreditsSnapshot {
     pub remaining: f64,
@@ -138,7 +316,7 @@ pub struct CreditsSnapshot {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenAIDashboardSnapshot {
     pub signed_in_email: Option<String>,
@@ -154,7 +332,7 @@ pub struct OpenAIDashboardSnapshot {
     pub updated_at: DateTime<Utc>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenAIDashboardDailyBreakdown {
     pub day: String,
@@ -162,7 +340,7 @@ pub struct OpenAIDashboardDailyBreakdown {
     pub total_credits_used: f64,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct OpenAIDashboardServiceUsage {
     pub service: String,
@@ -197,4 +375,21 @@ mod tests {
         assert!(json.contains("\"providerID\""));
         assert!(!json.contains("\"providerId\""));
     }
+
+    #[test]
+    fn v1_output_drops_the_schema_version() {
+        let payload: ProviderPayload =
+            serde_json::from_str(r#"{"provider":"codex","source":"oauth"}"#).unwrap();
+        assert_eq!(payload.schema_version, PAYLOAD_SCHEMA_VERSION);
+
+        let v2 = OutputSchema::V2
+            .payloads_to_string(std::slice::from_ref(&payload), false)
+            .unwrap();
+        assert!(v2.starts_with(r#"[{"schemaVersion":2,"provider":"codex""#));
+        let v1 = OutputSchema::V1
+            .payloads_to_string(&[payload], false)
+            .unwrap();
+        assert!(!v1.contains("schemaVersion"));
+        assert!(v1.contains(r#""provider":"codex""#));
+    }
 }
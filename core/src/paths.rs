@@ -0,0 +1,105 @@
+use crate::errors::CliError;
+use anyhow::Result;
+use directories::{BaseDirs, ProjectDirs};
+use serde::Serialize;
+use std::path::PathBuf;
+
+const APP_DIR: &str = "fuelcheck";
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppPaths {
+    pub config_file: PathBuf,
+    pub config_dir: PathBuf,
+    pub cache_dir: PathBuf,
+    pub data_dir: PathBuf,
+    pub legacy_config: bool,
+}
+
+impl AppPaths {
+    pub fn resolve(config_override: Option<&PathBuf>) -> Result<Self> {
+        let config_dir = config_dir().ok_or(CliError::ConfigPathUnavailable)?;
+        let cache_dir = cache_dir().ok_or(CliError::ConfigPathUnavailable)?;
+        let data_dir = data_dir().ok_or(CliError::ConfigPathUnavailable)?;
+        let native = config_dir.join("config.json");
+        let legacy = codexbar_config_path().filter(|path| !native.exists() && path.exists());
+        let config_file = config_override
+            .cloned()
+            .or_else(|| legacy.clone())
+            .unwrap_or(native);
+        Ok(Self {
+            legacy_config: config_override.is_none() && legacy.is_some(),
+            config_file,
+            config_dir,
+            cache_dir,
+            data_dir,
+        })
+    }
+}
+
+// Falls back to an existing CodexBar-style `~/.codexbar/config.json` so current
+// installs keep working until they have a config in the platform config dir.
+pub fn default_config_path() -> Option<PathBuf> {
+    let native = native_config_path();
+    if native.as_ref().is_some_and(|path| path.exists()) {
+        return native;
+    }
+    if let Some(legacy) = codexbar_config_path().filter(|path| path.exists()) {
+        return Some(legacy);
+    }
+    native
+}
+
+pub fn native_config_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join("config.json"))
+}
+
+pub fn config_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_CONFIG_HOME").or_else(|| project_dirs().map(|d| d.config_dir().to_path_buf()))
+}
+
+pub fn cache_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_CACHE_HOME").or_else(|| project_dirs().map(|d| d.cache_dir().to_path_buf()))
+}
+
+pub fn data_dir() -> Option<PathBuf> {
+    xdg_dir("XDG_DATA_HOME").or_else(|| project_dirs().map(|d| d.data_dir().to_path_buf()))
+}
+
+pub fn default_log_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("fuelcheck.log"))
+}
+
+pub fn default_history_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("history.jsonl"))
+}
+
+pub fn default_snapshots_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("snapshots.db"))
+}
+
+pub fn default_watch_captures_dir() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("watch"))
+}
+
+pub fn default_session_tags_path() -> Option<PathBuf> {
+    data_dir().map(|dir| dir.join("session-tags.json"))
+}
+
+pub fn codexbar_config_path() -> Option<PathBuf> {
+    let home = BaseDirs::new()?.home_dir().to_path_buf();
+    Some(home.join(".codexbar").join("config.json"))
+}
+
+fn xdg_dir(var: &str) -> Option<PathBuf> {
+    std::env::var(var)
+        .ok()
+        .filter(|v| !v.trim().is_empty())
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .map(|path| path.join(APP_DIR))
+}
+
+fn project_dirs() -> Option<ProjectDirs> {
+    ProjectDirs::from("", "", APP_DIR)
+}
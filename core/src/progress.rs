@@ -0,0 +1,62 @@
+use crate::providers::ProviderId;
+use serde::Serialize;
+use std::future::Future;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone)]
+pub enum FetchProgress {
+    Started {
+        provider: ProviderId,
+    },
+    Finished {
+        provider: ProviderId,
+        error: Option<String>,
+        elapsed: Duration,
+    },
+}
+
+static PROGRESS_OBSERVER: OnceLock<fn(&FetchProgress)> = OnceLock::new();
+static OUTPUT_OBSERVER: OnceLock<fn(&str)> = OnceLock::new();
+
+// Lets the CLI show per-provider progress and log fetch outcomes.
+pub fn set_progress_observer(observer: fn(&FetchProgress)) {
+    let _ = PROGRESS_OBSERVER.set(observer);
+}
+
+// Receives every payload, serialized as compact JSON, as soon as its provider is
+// done, so the CLI can stream results instead of waiting for the whole batch.
+pub fn set_output_observer(observer: fn(&str)) {
+    let _ = OUTPUT_OBSERVER.set(observer);
+}
+
+pub(crate) fn emit_outputs<T: Serialize>(outputs: &[T]) {
+    let Some(observer) = OUTPUT_OBSERVER.get() else {
+        return;
+    };
+    for line in outputs
+        .iter()
+        .filter_map(|output| serde_json::to_string(output).ok())
+    {
+        observer(&line);
+    }
+}
+
+pub async fn track<F: Future>(
+    provider: ProviderId,
+    fut: F,
+    failure: impl FnOnce(&F::Output) -> Option<String>,
+) -> F::Output {
+    let Some(observer) = PROGRESS_OBSERVER.get() else {
+        return fut.await;
+    };
+    observer(&FetchProgress::Started { provider });
+    let started = Instant::now();
+    let output = fut.await;
+    observer(&FetchProgress::Finished {
+        provider,
+        error: failure(&output),
+        elapsed: started.elapsed(),
+    });
+    output
+}
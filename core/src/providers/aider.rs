@@ -0,0 +1,54 @@
+use crate::config::Config;
+use crate::errors::CliError;
+use crate::model::ProviderPayload;
+use crate::providers::{Provider, ProviderId, SourcePreference, daily_spend_usage};
+use crate::reports::aider::today_cost_usd;
+use crate::service::{CostRequest, UsageRequest};
+use anyhow::Result;
+use async_trait::async_trait;
+
+// Aider has no account to query; usage is what its local logs say was spent
+// today (in the report timezone, `TZ` or UTC). The logs are read by
+// `reports::aider`.
+pub struct AiderProvider;
+
+#[async_trait]
+impl Provider for AiderProvider {
+    fn id(&self) -> ProviderId {
+        ProviderId::Aider
+    }
+
+    fn version(&self) -> &'static str {
+        "2025-01-01"
+    }
+
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Local]
+    }
+
+    fn supports_cost_reports(&self) -> bool {
+        true
+    }
+
+    async fn fetch_usage(
+        &self,
+        _args: &UsageRequest,
+        _config: &Config,
+        source: SourcePreference,
+    ) -> Result<ProviderPayload> {
+        let selected = match source {
+            SourcePreference::Auto => SourcePreference::Local,
+            other => other,
+        };
+        if selected != SourcePreference::Local {
+            return Err(CliError::UnsupportedSource(self.id(), selected.to_string()).into());
+        }
+        let (used, resets_at) = today_cost_usd()?;
+        Ok(self.ok_output("local", Some(daily_spend_usage(used, resets_at))))
+    }
+
+    async fn fetch_cost(&self, _args: &CostRequest, _config: &Config) -> Result<ProviderPayload> {
+        let (used, resets_at) = today_cost_usd()?;
+        Ok(self.ok_output("local", Some(daily_spend_usage(used, resets_at))))
+    }
+}
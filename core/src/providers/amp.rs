@@ -1,5 +1,6 @@
 use crate::config::Config;
-use crate::errors::CliError;
+use crate::errors::{CliError, HttpStatusError};
+use crate::http::{self, RequestBuilderExt};
 use crate::model::{ProviderIdentitySnapshot, ProviderPayload, RateWindow, UsageSnapshot};
 use crate::providers::{Provider, ProviderId, SourcePreference, env_var_nonempty};
 use crate::service::UsageRequest;
@@ -20,6 +21,10 @@ impl Provider for AmpProvider {
         "2025-01-01"
     }
 
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Web]
+    }
+
     async fn fetch_usage(
         &self,
         _args: &UsageRequest,
@@ -40,23 +45,32 @@ impl Provider for AmpProvider {
             .and_then(|c| c.cookie_header.clone())
             .or_else(|| env_var_nonempty(&["AMP_COOKIE", "AMP_COOKIE_HEADER"]))
             .ok_or_else(|| {
-                anyhow!("Amp cookie header missing. Set provider cookie_header or AMP_COOKIE.")
+                CliError::MissingCredentials(
+                    "Amp cookie header missing. Set provider cookie_header or AMP_COOKIE."
+                        .to_string(),
+                )
             })?;
 
-        let client = reqwest::Client::new();
+        let client = http::client();
         let resp = client
             .get("https://ampcode.com/settings")
             .header("cookie", cookie)
             .header("accept", "text/html")
-            .send()
+            .send_with_retry()
             .await?;
         let status = resp.status();
         let body = resp.text().await?;
         if status.as_u16() == 401 || status.as_u16() == 403 {
-            return Err(anyhow!("Amp unauthorized. Cookie may be invalid."));
+            return Err(
+                HttpStatusError::new(status, "Amp unauthorized. Cookie may be invalid.").into(),
+            );
         }
         if !status.is_success() {
-            return Err(anyhow!("Amp request failed (HTTP {})", status.as_u16()));
+            return Err(HttpStatusError::new(
+                status,
+                format!("Amp request failed (HTTP {})", status.as_u16()),
+            )
+            .into());
         }
 
         let snapshot = parse_amp_usage(&body)?;
@@ -0,0 +1,241 @@
+use crate::config::Config;
+use crate::errors::{CliError, HttpStatusError};
+use crate::http::{self, RequestBuilderExt};
+use crate::model::{ProviderCostSnapshot, ProviderPayload, UsageSnapshot};
+use crate::providers::{
+    Provider, ProviderId, SourcePreference, env_var_nonempty, fetch_status_payload, normalize_host,
+    value_to_f64,
+};
+use crate::service::{CostRequest, UsageRequest};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Duration, NaiveTime, Utc};
+use serde::Deserialize;
+
+// The Claude subscription is the `claude` provider; this one is the API
+// console, read with an organization Admin API key (`sk-ant-admin...`).
+pub struct AnthropicApiProvider;
+
+const STATUS_PAGE: &str = "https://status.claude.com";
+const DEFAULT_HOST: &str = "https://api.anthropic.com";
+const API_VERSION: &str = "2023-06-01";
+const MAX_PAGES: usize = 20;
+
+#[derive(Deserialize)]
+struct CostReportPage {
+    #[serde(default)]
+    data: Vec<CostBucket>,
+    #[serde(default)]
+    has_more: bool,
+    next_page: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CostBucket {
+    #[serde(default)]
+    results: Vec<CostResult>,
+}
+
+// `amount` is a decimal string in the currency's smallest unit (cents).
+#[derive(Deserialize)]
+struct CostResult {
+    currency: Option<String>,
+    amount: Option<serde_json::Value>,
+}
+
+#[async_trait]
+impl Provider for AnthropicApiProvider {
+    fn id(&self) -> ProviderId {
+        ProviderId::AnthropicApi
+    }
+
+    fn version(&self) -> &'static str {
+        API_VERSION
+    }
+
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Api]
+    }
+
+    fn status_page(&self) -> Option<&'static str> {
+        Some(STATUS_PAGE)
+    }
+
+    async fn fetch_usage(
+        &self,
+        args: &UsageRequest,
+        config: &Config,
+        source: SourcePreference,
+    ) -> Result<ProviderPayload> {
+        let selected = match source {
+            SourcePreference::Auto => SourcePreference::Api,
+            other => other,
+        };
+        if selected != SourcePreference::Api {
+            return Err(CliError::UnsupportedSource(self.id(), selected.to_string()).into());
+        }
+        let spend = fetch_daily_spend(&admin_key(config)?).await?;
+        let usage = UsageSnapshot {
+            primary: None,
+            secondary: None,
+            tertiary: None,
+            provider_cost: Some(spend),
+            updated_at: Utc::now(),
+            identity: None,
+            account_email: None,
+            account_organization: None,
+            login_method: Some("Admin API".to_string()),
+        };
+        let mut payload = self.ok_output("api", Some(usage));
+        if args.status {
+            payload.status = fetch_status_payload(STATUS_PAGE, args.web_timeout).await;
+        }
+        Ok(payload)
+    }
+
+    async fn fetch_cost(&self, _args: &CostRequest, config: &Config) -> Result<ProviderPayload> {
+        let spend = fetch_daily_spend(&admin_key(config)?).await?;
+        let usage = UsageSnapshot {
+            primary: None,
+            secondary: None,
+            tertiary: None,
+            provider_cost: Some(spend),
+            updated_at: Utc::now(),
+            identity: None,
+            account_email: None,
+            account_organization: None,
+            login_method: Some("Admin API".to_string()),
+        };
+        Ok(self.ok_output("api", Some(usage)))
+    }
+}
+
+fn admin_key(config: &Config) -> Result<String> {
+    config
+        .provider_config(ProviderId::AnthropicApi)
+        .and_then(|cfg| cfg.api_key)
+        .or_else(|| env_var_nonempty(&["ANTHROPIC_ADMIN_KEY"]))
+        .ok_or_else(|| {
+            CliError::MissingCredentials(
+                "Anthropic Admin API key missing. Set provider api_key or ANTHROPIC_ADMIN_KEY."
+                    .to_string(),
+            )
+            .into()
+        })
+}
+
+// Today's spend (UTC, as the console bills it) from the cost report. The host
+// override is our own variable: `ANTHROPIC_BASE_URL` often points at a
+// gateway, which must not see the Admin key.
+async fn fetch_daily_spend(key: &str) -> Result<ProviderCostSnapshot> {
+    let host = env_var_nonempty(&["FUELCHECK_ANTHROPIC_ADMIN_URL"])
+        .map(|host| normalize_host(&host))
+        .unwrap_or_else(|| DEFAULT_HOST.to_string());
+    let url = format!(
+        "{}/v1/organizations/cost_report",
+        host.trim_end_matches('/')
+    );
+    let today = Utc::now().date_naive().and_time(NaiveTime::MIN).and_utc();
+    let starting_at = today.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+
+    let mut cents = 0.0;
+    let mut currency = None;
+    let mut page: Option<String> = None;
+    for _ in 0..MAX_PAGES {
+        let mut query = vec![
+            ("starting_at", starting_at.clone()),
+            ("bucket_width", "1d".to_string()),
+        ];
+        if let Some(page) = &page {
+            query.push(("page", page.clone()));
+        }
+        let resp = http::client()
+            .get(&url)
+            .query(&query)
+            .header("x-api-key", key)
+            .header("anthropic-version", API_VERSION)
+            .header("accept", "application/json")
+            .send_with_retry()
+            .await?;
+        let status = resp.status();
+        let data = resp.bytes().await?;
+        if status.as_u16() == 401 || status.as_u16() == 403 {
+            return Err(HttpStatusError::new(
+                status,
+                "Anthropic Admin API unauthorized. The key must be an admin key (sk-ant-admin...).",
+            )
+            .into());
+        }
+        if !status.is_success() {
+            return Err(HttpStatusError::new(
+                status,
+                format!("Anthropic cost report failed (HTTP {})", status.as_u16()),
+            )
+            .into());
+        }
+        let report: CostReportPage = serde_json::from_slice(&data)?;
+        let (page_cents, page_currency) = sum_cost_page(&report);
+        cents += page_cents;
+        currency = currency.or(page_currency);
+        match report.next_page {
+            Some(next) if report.has_more => page = Some(next),
+            _ => break,
+        }
+    }
+
+    Ok(daily_cost(cents, currency, today))
+}
+
+// Sum of a report page's amounts in cents, and the first currency it names.
+fn sum_cost_page(report: &CostReportPage) -> (f64, Option<String>) {
+    let results = report.data.iter().flat_map(|bucket| &bucket.results);
+    let cents = results
+        .clone()
+        .filter_map(|result| result.amount.as_ref().and_then(value_to_f64))
+        .sum();
+    let currency = results.filter_map(|result| result.currency.clone()).next();
+    (cents, currency)
+}
+
+fn daily_cost(cents: f64, currency: Option<String>, today: DateTime<Utc>) -> ProviderCostSnapshot {
+    ProviderCostSnapshot {
+        used: cents / 100.0,
+        limit: 0.0,
+        currency_code: currency.unwrap_or_else(|| "USD".to_string()),
+        period: Some("Daily".to_string()),
+        resets_at: Some(today + Duration::days(1)),
+        updated_at: Utc::now(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cost_report_cents_become_dollars() {
+        let report: CostReportPage = serde_json::from_str(
+            r#"{
+                "data": [
+                    {"results": [
+                        {"currency": "USD", "amount": "1234.5"},
+                        {"currency": "USD", "amount": "65.5"}
+                    ]},
+                    {"results": [{"amount": 200}, {"currency": "USD"}]}
+                ],
+                "has_more": true,
+                "next_page": "page_2"
+            }"#,
+        )
+        .unwrap();
+        let (cents, currency) = sum_cost_page(&report);
+        assert_eq!(cents, 1500.0);
+        assert_eq!(report.next_page.as_deref(), Some("page_2"));
+
+        let today = Utc::now().date_naive().and_time(NaiveTime::MIN).and_utc();
+        let cost = daily_cost(cents, currency, today);
+        assert_eq!(cost.used, 15.0);
+        assert_eq!(cost.currency_code, "USD");
+        assert_eq!(cost.resets_at, Some(today + Duration::days(1)));
+    }
+}
@@ -1,11 +1,17 @@
 use crate::accounts::{AccountSelectionArgs, account_label, select_accounts};
-use crate::config::{Config, TokenAccount};
-use crate::errors::CliError;
+use crate::config::{Config, ProviderConfig, TokenAccount};
+use crate::credential_store;
+use crate::errors::{CliError, HttpStatusError};
+use crate::http::{self, RequestBuilderExt};
 use crate::model::{
-    ProviderCostSnapshot, ProviderIdentitySnapshot, ProviderPayload, RateWindow, UsageSnapshot,
+    IdentityPayload, ProviderCostSnapshot, ProviderIdentitySnapshot, ProviderPayload, RateWindow,
+    UsageSnapshot,
+};
+use crate::providers::{
+    Provider, ProviderId, SourcePreference, fetch_status_payload, remove_credentials_file,
 };
-use crate::providers::{Provider, ProviderId, SourcePreference, fetch_status_payload};
 use crate::service::UsageRequest;
+use crate::warnings::push_warning;
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -13,10 +19,11 @@ use directories::BaseDirs;
 use serde::Deserialize;
 use std::fs;
 use std::path::PathBuf;
-use std::process::Command;
 
 pub struct ClaudeProvider;
 
+const STATUS_PAGE: &str = "https://status.claude.com";
+
 #[async_trait]
 impl Provider for ClaudeProvider {
     fn id(&self) -> ProviderId {
@@ -27,10 +34,74 @@ impl Provider for ClaudeProvider {
         "2025-04-20"
     }
 
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Oauth, SourcePreference::Web]
+    }
+
+    fn status_page(&self) -> Option<&'static str> {
+        Some(STATUS_PAGE)
+    }
+
     fn supports_token_accounts(&self) -> bool {
         true
     }
 
+    fn supports_cost_reports(&self) -> bool {
+        true
+    }
+
+    fn supports_identity(&self) -> bool {
+        true
+    }
+
+    async fn fetch_identity(
+        &self,
+        config: &Config,
+        source: SourcePreference,
+    ) -> Result<IdentityPayload> {
+        let cfg = config.provider_config(self.id());
+        let cookie_header = claude_cookie_header(cfg.as_ref());
+        let selected =
+            select_claude_source(self.resolve_source(cfg, source), cookie_header.is_some());
+
+        match selected {
+            SourcePreference::Oauth => {
+                let creds = ClaudeOAuthCredentials::load()?;
+                let identity = ProviderIdentitySnapshot {
+                    provider_id: Some("claude".to_string()),
+                    account_email: None,
+                    account_organization: None,
+                    login_method: infer_plan(creds.rate_limit_tier.as_deref()),
+                };
+                Ok(self.identity_output("oauth", identity, None))
+            }
+            SourcePreference::Web => {
+                let header = cookie_header.ok_or_else(|| {
+                    CliError::MissingCredentials("Claude cookie header missing. Set provider cookie_header in config or CLAUDE_COOKIE.".to_string())
+                })?;
+                let header = normalize_claude_cookie_header(&header);
+                let org = claude_web_fetch_org(&header).await?;
+                let identity = claude_web_identity(&org, &header).await;
+                Ok(self.identity_output("web", identity, Some(org.uuid)))
+            }
+            other => Err(CliError::UnsupportedSource(self.id(), other.to_string()).into()),
+        }
+    }
+
+    fn clear_local_credentials(&self) -> Result<Vec<String>> {
+        let mut removed = remove_credentials_file(&claude_credentials_path())?;
+        if let Some(backend) = credential_store::Backend::current()
+            && credential_store::delete_secret(CLAUDE_CREDENTIAL_SERVICE, None)?
+        {
+            removed.push(format!(
+                "{}: {}",
+                backend.label(),
+                CLAUDE_CREDENTIAL_SERVICE
+            ));
+        }
+        Ok(removed)
+    }
+
     async fn fetch_usage_all(
         &self,
         args: &UsageRequest,
@@ -43,6 +114,13 @@ impl Provider for ClaudeProvider {
             account_index: args.account_index.map(|idx| idx.saturating_sub(1)),
             all_accounts: args.all_accounts,
         };
+        let effective = self.resolve_source(cfg.clone(), source);
+        if effective == SourcePreference::Web {
+            return self
+                .fetch_cookie_accounts(args, config, cfg.as_ref(), &selection)
+                .await;
+        }
+
         let selected = select_accounts(
             cfg.as_ref().and_then(|c| c.token_accounts.as_ref()),
             &selection,
@@ -51,7 +129,6 @@ impl Provider for ClaudeProvider {
             return Ok(vec![self.fetch_usage(args, config, source).await?]);
         };
 
-        let effective = self.resolve_source(cfg, source);
         let selected_source = match effective {
             SourcePreference::Auto | SourcePreference::Oauth => SourcePreference::Oauth,
             other => other,
@@ -61,7 +138,7 @@ impl Provider for ClaudeProvider {
         }
 
         let status = if args.status {
-            fetch_status_payload("https://status.claude.com", args.web_timeout).await
+            fetch_status_payload(STATUS_PAGE, args.web_timeout).await
         } else {
             None
         };
@@ -87,38 +164,30 @@ impl Provider for ClaudeProvider {
         source: SourcePreference,
     ) -> Result<ProviderPayload> {
         let cfg = config.provider_config(self.id());
-        let cookie_header = cfg
-            .as_ref()
-            .and_then(|c| c.cookie_header.clone())
-            .or_else(|| std::env::var("CLAUDE_COOKIE").ok());
-        let has_cookie = cookie_header
-            .as_ref()
-            .map(|v| !v.trim().is_empty())
-            .unwrap_or(false);
+        let cookie_header = claude_cookie_header(cfg.as_ref());
+        let has_cookie = cookie_header.is_some();
         let effective = self.resolve_source(cfg, source);
-        let selected = match effective {
-            SourcePreference::Auto => {
-                if claude_credentials_file_exists() {
-                    SourcePreference::Oauth
-                } else if has_cookie {
-                    SourcePreference::Web
-                } else {
-                    SourcePreference::Oauth
-                }
-            }
-            other => other,
-        };
+        let selected = select_claude_source(effective, has_cookie);
 
         let status = if args.status {
-            fetch_status_payload("https://status.claude.com", args.web_timeout).await
+            fetch_status_payload(STATUS_PAGE, args.web_timeout).await
         } else {
             None
         };
 
         match selected {
             SourcePreference::Oauth => {
-                let usage = fetch_claude_oauth_usage().await?;
-                let mut payload = self.ok_output("oauth", Some(usage));
+                let (source, usage) = match fetch_claude_oauth_usage().await {
+                    Ok(usage) => ("oauth", usage),
+                    // In auto mode a configured cookie is a usable second route.
+                    Err(err) if effective == SourcePreference::Auto && has_cookie => {
+                        push_warning(format!("Claude OAuth failed ({}); fell back to web", err));
+                        let header = cookie_header.unwrap_or_default();
+                        ("web", fetch_claude_web_usage(&header).await?)
+                    }
+                    Err(err) => return Err(err),
+                };
+                let mut payload = self.ok_output(source, Some(usage));
                 payload.status = status;
                 Ok(payload)
             }
@@ -128,7 +197,7 @@ impl Provider for ClaudeProvider {
             SourcePreference::Web => {
                 let header = cookie_header
                     .ok_or_else(|| {
-                        anyhow!("Claude cookie header missing. Set provider cookie_header in config or CLAUDE_COOKIE.")
+                        CliError::MissingCredentials("Claude cookie header missing. Set provider cookie_header in config or CLAUDE_COOKIE.".to_string())
                     })?;
                 let usage = fetch_claude_web_usage(&header).await?;
                 let mut payload = self.ok_output("web", Some(usage));
@@ -148,6 +217,48 @@ impl Provider for ClaudeProvider {
     }
 }
 
+impl ClaudeProvider {
+    async fn fetch_cookie_accounts(
+        &self,
+        args: &UsageRequest,
+        config: &Config,
+        cfg: Option<&ProviderConfig>,
+        selection: &AccountSelectionArgs,
+    ) -> Result<Vec<ProviderPayload>> {
+        let accounts = cfg.and_then(|c| c.cookie_token_accounts());
+        let Some(selected) = select_accounts(accounts.as_ref(), selection)? else {
+            return Ok(vec![
+                self.fetch_usage(args, config, SourcePreference::Web)
+                    .await?,
+            ]);
+        };
+
+        let status = if args.status {
+            fetch_status_payload(STATUS_PAGE, args.web_timeout).await
+        } else {
+            None
+        };
+
+        let mut outputs = Vec::new();
+        for account in selected {
+            let label = account_label(&account.account, account.index);
+            let header = account
+                .account
+                .token
+                .clone()
+                .filter(|val| !val.trim().is_empty())
+                .ok_or_else(|| anyhow!("Claude account {} missing cookie header", label))?;
+            let usage = fetch_claude_web_usage(&header).await?;
+            let mut payload = self.ok_output("web", Some(usage));
+            payload.status = status.clone();
+            payload.account = Some(label);
+            outputs.push(payload);
+        }
+
+        Ok(outputs)
+    }
+}
+
 #[derive(Debug, Deserialize)]
 struct ClaudeCredentialsFile {
     #[serde(rename = "claudeAiOauth")]
@@ -183,10 +294,10 @@ impl ClaudeOAuthCredentials {
             Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
             Err(err) => return Err(err.into()),
         }
-        if let Ok(data) = load_claude_keychain_credentials() {
+        if let Ok(data) = load_claude_store_credentials() {
             return Self::parse(data);
         }
-        Err(anyhow!("Claude OAuth credentials not found"))
+        Err(CliError::MissingCredentials("Claude OAuth credentials not found".to_string()).into())
     }
 
     fn from_token_account(account: &TokenAccount, index: usize) -> Result<Self> {
@@ -234,6 +345,27 @@ impl ClaudeOAuthCredentials {
     }
 }
 
+fn claude_cookie_header(cfg: Option<&ProviderConfig>) -> Option<String> {
+    cfg.and_then(|c| c.cookie_header.clone())
+        .or_else(|| std::env::var("CLAUDE_COOKIE").ok())
+        .filter(|v| !v.trim().is_empty())
+}
+
+fn select_claude_source(effective: SourcePreference, has_cookie: bool) -> SourcePreference {
+    match effective {
+        SourcePreference::Auto => {
+            if claude_credentials_file_exists() {
+                SourcePreference::Oauth
+            } else if has_cookie {
+                SourcePreference::Web
+            } else {
+                SourcePreference::Oauth
+            }
+        }
+        other => other,
+    }
+}
+
 fn claude_credentials_path() -> PathBuf {
     let home = BaseDirs::new()
         .map(|d| d.home_dir().to_path_buf())
@@ -245,29 +377,11 @@ fn claude_credentials_file_exists() -> bool {
     claude_credentials_path().exists()
 }
 
-fn load_claude_keychain_credentials() -> Result<Vec<u8>> {
-    if !cfg!(target_os = "macos") {
-        return Err(anyhow!(
-            "Claude OAuth keychain read is only supported on macOS"
-        ));
-    }
-    let output = Command::new("security")
-        .args([
-            "find-generic-password",
-            "-s",
-            "Claude Code-credentials",
-            "-w",
-        ])
-        .output()?;
-    if !output.status.success() {
-        return Err(anyhow!("Claude OAuth keychain entry not found"));
-    }
-    let raw = String::from_utf8_lossy(&output.stdout);
-    let trimmed = raw.trim();
-    if trimmed.is_empty() {
-        return Err(anyhow!("Claude OAuth keychain entry empty"));
-    }
-    Ok(trimmed.as_bytes().to_vec())
+const CLAUDE_CREDENTIAL_SERVICE: &str = "Claude Code-credentials";
+
+fn load_claude_store_credentials() -> Result<Vec<u8>> {
+    let secret = credential_store::read_secret(CLAUDE_CREDENTIAL_SERVICE, None)?;
+    Ok(secret.into_bytes())
 }
 
 #[derive(Debug, Deserialize)]
@@ -367,10 +481,19 @@ async fn fetch_claude_oauth_usage() -> Result<UsageSnapshot> {
     let mut creds = ClaudeOAuthCredentials::load()?;
     if creds.is_expired()
         && let Some(refresh_token) = creds.refresh_token.clone()
-        && let Ok(updated) =
-            refresh_claude_token(&refresh_token, &creds.scopes, creds.rate_limit_tier.clone()).await
     {
-        creds = updated;
+        match refresh_claude_token(&refresh_token, &creds.scopes, creds.rate_limit_tier.clone())
+            .await
+        {
+            Ok(updated) => {
+                creds = updated;
+                push_warning("Claude OAuth token was expired and has been refreshed");
+            }
+            Err(err) => push_warning(format!(
+                "Claude OAuth token refresh failed ({}); using the existing token",
+                err
+            )),
+        }
     }
     fetch_claude_oauth_usage_with_creds(&creds).await
 }
@@ -397,21 +520,22 @@ async fn refresh_claude_token(
         refresh_token, client_id
     );
 
-    let client = reqwest::Client::new();
+    let client = http::client();
     let resp = client
         .post(url)
         .header("Content-Type", "application/x-www-form-urlencoded")
         .header("Accept", "application/json")
         .body(body)
-        .send()
+        .send_with_retry()
         .await?;
     let status = resp.status();
     let data = resp.bytes().await?;
     if !status.is_success() {
-        return Err(anyhow!(
-            "Claude OAuth refresh failed (HTTP {})",
-            status.as_u16()
-        ));
+        return Err(HttpStatusError::new(
+            status,
+            format!("Claude OAuth refresh failed (HTTP {})", status.as_u16()),
+        )
+        .into());
     }
     let json: serde_json::Value = serde_json::from_slice(&data)?;
     let access_token = json
@@ -441,7 +565,7 @@ async fn refresh_claude_token(
 
 async fn claude_oauth_fetch(access_token: &str) -> Result<OAuthUsageResponse> {
     let url = "https://api.anthropic.com/api/oauth/usage";
-    let client = reqwest::Client::new();
+    let client = http::client();
     let resp = client
         .get(url)
         .header("Authorization", format!("Bearer {}", access_token))
@@ -449,20 +573,23 @@ async fn claude_oauth_fetch(access_token: &str) -> Result<OAuthUsageResponse> {
         .header("Content-Type", "application/json")
         .header("anthropic-beta", "oauth-2025-04-20")
         .header("User-Agent", "FuelcheckCLI")
-        .send()
+        .send_with_retry()
         .await?;
     let status = resp.status();
     let data = resp.bytes().await?;
     if status.as_u16() == 401 {
-        return Err(anyhow!(
-            "Claude OAuth unauthorized. Run `claude` to re-authenticate."
-        ));
+        return Err(HttpStatusError::new(
+            status,
+            "Claude OAuth unauthorized. Run `claude` to re-authenticate.",
+        )
+        .into());
     }
     if !status.is_success() {
-        return Err(anyhow!(
-            "Claude OAuth usage fetch failed (HTTP {})",
-            status.as_u16()
-        ));
+        return Err(HttpStatusError::new(
+            status,
+            format!("Claude OAuth usage fetch failed (HTTP {})", status.as_u16()),
+        )
+        .into());
     }
     let usage: OAuthUsageResponse = serde_json::from_slice(&data)?;
     Ok(usage)
@@ -558,14 +685,14 @@ async fn fetch_claude_web_usage(cookie_header: &str) -> Result<UsageSnapshot> {
     let cookie_header = normalize_claude_cookie_header(cookie_header);
     let org = claude_web_fetch_org(&cookie_header).await?;
     let usage = claude_web_fetch_usage(&org.uuid, &cookie_header).await?;
-    let extra = claude_web_fetch_overage(&org.uuid, &cookie_header)
-        .await
-        .ok()
-        .flatten();
-    let account = claude_web_fetch_account(&cookie_header, Some(&org.uuid))
-        .await
-        .ok()
-        .flatten();
+    let extra = match claude_web_fetch_overage(&org.uuid, &cookie_header).await {
+        Ok(extra) => extra,
+        Err(err) => {
+            push_warning(format!("Claude extra usage unavailable: {}", err));
+            None
+        }
+    };
+    let identity = claude_web_identity(&org, &cookie_header).await;
 
     let primary = make_web_window(usage.five_hour.as_ref(), 5 * 60)
         .ok_or_else(|| anyhow!("missing session data"))?;
@@ -578,17 +705,6 @@ async fn fetch_claude_web_usage(cookie_header: &str) -> Result<UsageSnapshot> {
         7 * 24 * 60,
     );
 
-    let account_org = sanitize_label(org.name.clone())
-        .or_else(|| account.as_ref().and_then(|info| info.organization.clone()));
-    let login_method = account.as_ref().and_then(|info| info.login_method.clone());
-
-    let identity = ProviderIdentitySnapshot {
-        provider_id: Some("claude".to_string()),
-        account_email: account.as_ref().and_then(|info| info.email.clone()),
-        account_organization: account_org.clone(),
-        login_method: login_method.clone(),
-    };
-
     Ok(UsageSnapshot {
         primary: Some(primary),
         secondary: weekly,
@@ -602,26 +718,53 @@ async fn fetch_claude_web_usage(cookie_header: &str) -> Result<UsageSnapshot> {
     })
 }
 
+async fn claude_web_identity(
+    org: &WebOrganizationResponse,
+    cookie_header: &str,
+) -> ProviderIdentitySnapshot {
+    let account = claude_web_fetch_account(cookie_header, Some(&org.uuid))
+        .await
+        .ok()
+        .flatten();
+    let account_org = sanitize_label(org.name.clone())
+        .or_else(|| account.as_ref().and_then(|info| info.organization.clone()));
+
+    ProviderIdentitySnapshot {
+        provider_id: Some("claude".to_string()),
+        account_email: account.as_ref().and_then(|info| info.email.clone()),
+        account_organization: account_org,
+        login_method: account.as_ref().and_then(|info| info.login_method.clone()),
+    }
+}
+
 async fn claude_web_fetch_org(cookie_header: &str) -> Result<WebOrganizationResponse> {
     let url = "https://claude.ai/api/organizations";
-    let client = reqwest::Client::new();
+    let client = http::client();
     let resp = client
         .get(url)
         .header("Cookie", cookie_header)
         .header("Accept", "application/json")
         .header("User-Agent", "FuelcheckCLI")
-        .send()
+        .send_with_retry()
         .await?;
     let status = resp.status();
     let data = resp.bytes().await?;
     if status.as_u16() == 401 || status.as_u16() == 403 {
-        return Err(anyhow!("Claude web unauthorized. Cookie may be expired."));
+        return Err(HttpStatusError::new(
+            status,
+            "Claude web unauthorized. Cookie may be expired.",
+        )
+        .into());
     }
     if !status.is_success() {
-        return Err(anyhow!(
-            "Claude web organizations fetch failed (HTTP {})",
-            status.as_u16()
-        ));
+        return Err(HttpStatusError::new(
+            status,
+            format!(
+                "Claude web organizations fetch failed (HTTP {})",
+                status.as_u16()
+            ),
+        )
+        .into());
     }
     let orgs: Vec<WebOrganizationResponse> = serde_json::from_slice(&data)?;
     let selected =
@@ -631,24 +774,29 @@ async fn claude_web_fetch_org(cookie_header: &str) -> Result<WebOrganizationResp
 
 async fn claude_web_fetch_usage(org_id: &str, cookie_header: &str) -> Result<WebUsageResponse> {
     let url = format!("https://claude.ai/api/organizations/{}/usage", org_id);
-    let client = reqwest::Client::new();
+    let client = http::client();
     let resp = client
         .get(url)
         .header("Cookie", cookie_header)
         .header("Accept", "application/json")
         .header("User-Agent", "FuelcheckCLI")
-        .send()
+        .send_with_retry()
         .await?;
     let status = resp.status();
     let data = resp.bytes().await?;
     if status.as_u16() == 401 || status.as_u16() == 403 {
-        return Err(anyhow!("Claude web unauthorized. Cookie may be expired."));
+        return Err(HttpStatusError::new(
+            status,
+            "Claude web unauthorized. Cookie may be expired.",
+        )
+        .into());
     }
     if !status.is_success() {
-        return Err(anyhow!(
-            "Claude web usage fetch failed (HTTP {})",
-            status.as_u16()
-        ));
+        return Err(HttpStatusError::new(
+            status,
+            format!("Claude web usage fetch failed (HTTP {})", status.as_u16()),
+        )
+        .into());
     }
     let usage: WebUsageResponse = serde_json::from_slice(&data)?;
     Ok(usage)
@@ -662,13 +810,13 @@ async fn claude_web_fetch_overage(
         "https://claude.ai/api/organizations/{}/overage_spend_limit",
         org_id
     );
-    let client = reqwest::Client::new();
+    let client = http::client();
     let resp = client
         .get(url)
         .header("Cookie", cookie_header)
         .header("Accept", "application/json")
         .header("User-Agent", "FuelcheckCLI")
-        .send()
+        .send_with_retry()
         .await?;
     let status = resp.status();
     if !status.is_success() {
@@ -709,13 +857,13 @@ async fn claude_web_fetch_account(
     org_id: Option<&str>,
 ) -> Result<Option<WebAccountInfo>> {
     let url = "https://claude.ai/api/account";
-    let client = reqwest::Client::new();
+    let client = http::client();
     let resp = client
         .get(url)
         .header("Cookie", cookie_header)
         .header("Accept", "application/json")
         .header("User-Agent", "FuelcheckCLI")
-        .send()
+        .send_with_retry()
         .await?;
     let status = resp.status();
     if !status.is_success() {
@@ -0,0 +1,54 @@
+use crate::config::Config;
+use crate::errors::CliError;
+use crate::model::ProviderPayload;
+use crate::providers::{Provider, ProviderId, SourcePreference, daily_spend_usage};
+use crate::reports::cline::today_cost_usd;
+use crate::service::{CostRequest, UsageRequest};
+use anyhow::Result;
+use async_trait::async_trait;
+
+// The extensions bill through whichever API key they were given, so usage is
+// what the task histories say was spent today (in the report timezone, `TZ`
+// or UTC). Both Cline and Roo Code are read, by `reports::cline`.
+pub struct ClineProvider;
+
+#[async_trait]
+impl Provider for ClineProvider {
+    fn id(&self) -> ProviderId {
+        ProviderId::Cline
+    }
+
+    fn version(&self) -> &'static str {
+        "2025-01-01"
+    }
+
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Local]
+    }
+
+    fn supports_cost_reports(&self) -> bool {
+        true
+    }
+
+    async fn fetch_usage(
+        &self,
+        _args: &UsageRequest,
+        _config: &Config,
+        source: SourcePreference,
+    ) -> Result<ProviderPayload> {
+        let selected = match source {
+            SourcePreference::Auto => SourcePreference::Local,
+            other => other,
+        };
+        if selected != SourcePreference::Local {
+            return Err(CliError::UnsupportedSource(self.id(), selected.to_string()).into());
+        }
+        let (used, resets_at) = today_cost_usd()?;
+        Ok(self.ok_output("local", Some(daily_spend_usage(used, resets_at))))
+    }
+
+    async fn fetch_cost(&self, _args: &CostRequest, _config: &Config) -> Result<ProviderPayload> {
+        let (used, resets_at) = today_cost_usd()?;
+        Ok(self.ok_output("local", Some(daily_spend_usage(used, resets_at))))
+    }
+}
@@ -1,22 +1,36 @@
 use crate::accounts::{AccountSelectionArgs, account_label, select_accounts};
 use crate::config::{Config, TokenAccount};
-use crate::errors::CliError;
+use crate::errors::{CliError, HttpStatusError};
+use crate::http::{self, RequestBuilderExt};
 use crate::model::{
-    CreditsSnapshot, ProviderIdentitySnapshot, ProviderPayload, RateWindow, UsageSnapshot,
+    CreditsSnapshot, IdentityPayload, ProviderIdentitySnapshot, ProviderPayload, RateWindow,
+    UsageSnapshot,
+};
+use crate::providers::{
+    Provider, ProviderId, SourcePreference, fetch_status_payload, remove_credentials_file,
 };
-use crate::providers::{Provider, ProviderId, SourcePreference, fetch_status_payload};
 use crate::service::UsageRequest;
+use crate::warnings::push_warning;
 use anyhow::{Context, Result, anyhow};
 use async_trait::async_trait;
 use base64::{Engine as _, engine::general_purpose::URL_SAFE_NO_PAD};
 use chrono::{DateTime, Utc};
 use directories::BaseDirs;
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
 
 pub struct CodexProvider;
 
+const STATUS_PAGE: &str = "https://status.openai.com";
+const OAUTH_ISSUER: &str = "https://auth.openai.com";
+const OAUTH_CLIENT_ID: &str = "app_EMoamEEZ73f0CkXaXp7hrann";
+// The only redirect the Codex CLI client is registered for.
+const LOGIN_CALLBACK_PORT: u16 = 1455;
+
 #[async_trait]
 impl Provider for CodexProvider {
     fn id(&self) -> ProviderId {
@@ -27,10 +41,50 @@ impl Provider for CodexProvider {
         "2024-06-04"
     }
 
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Oauth]
+    }
+
+    fn status_page(&self) -> Option<&'static str> {
+        Some(STATUS_PAGE)
+    }
+
     fn supports_token_accounts(&self) -> bool {
         true
     }
 
+    fn supports_cost_reports(&self) -> bool {
+        true
+    }
+
+    fn supports_identity(&self) -> bool {
+        true
+    }
+
+    async fn fetch_identity(
+        &self,
+        config: &Config,
+        source: SourcePreference,
+    ) -> Result<IdentityPayload> {
+        let effective = self.resolve_source(config.provider_config(self.id()), source);
+        match effective {
+            SourcePreference::Auto | SourcePreference::Oauth => {}
+            other => return Err(CliError::UnsupportedSource(self.id(), other.to_string()).into()),
+        }
+        let creds = CodexOAuthCredentials::load()?;
+        let identity = ProviderIdentitySnapshot {
+            provider_id: Some("codex".to_string()),
+            account_email: resolve_account_email(creds.id_token.as_deref()),
+            account_organization: None,
+            login_method: resolve_plan_from_token(creds.id_token.as_deref()),
+        };
+        Ok(self.identity_output("oauth", identity, creds.account_id.clone()))
+    }
+
+    fn clear_local_credentials(&self) -> Result<Vec<String>> {
+        remove_credentials_file(&codex_auth_path())
+    }
+
     async fn fetch_usage_all(
         &self,
         args: &UsageRequest,
@@ -61,7 +115,7 @@ impl Provider for CodexProvider {
         }
 
         let status = if args.status {
-            fetch_status_payload("https://status.openai.com", args.web_timeout).await
+            fetch_status_payload(STATUS_PAGE, args.web_timeout).await
         } else {
             None
         };
@@ -102,7 +156,7 @@ impl Provider for CodexProvider {
         };
 
         let status = if args.status {
-            fetch_status_payload("https://status.openai.com", args.web_timeout).await
+            fetch_status_payload(STATUS_PAGE, args.web_timeout).await
         } else {
             None
         };
@@ -310,7 +364,13 @@ async fn fetch_oauth_usage() -> Result<(UsageSnapshot, Option<CreditsSnapshot>)>
     let mut creds = CodexOAuthCredentials::load()?;
     if creds.needs_refresh() && !creds.refresh_token.is_empty() {
         creds = refresh_codex_token(&creds).await?;
-        let _ = creds.save();
+        push_warning("Codex OAuth token was stale and has been refreshed");
+        if let Err(err) = creds.save() {
+            push_warning(format!(
+                "could not save refreshed Codex credentials: {}",
+                err
+            ));
+        }
     }
     fetch_oauth_usage_with_creds(&creds).await
 }
@@ -325,23 +385,24 @@ async fn fetch_oauth_usage_with_creds(
 }
 
 async fn refresh_codex_token(creds: &CodexOAuthCredentials) -> Result<CodexOAuthCredentials> {
-    let url = "https://auth.openai.com/oauth/token";
+    let url = format!("{}/oauth/token", OAUTH_ISSUER);
     let body = serde_json::json!({
-        "client_id": "app_EMoamEEZ73f0CkXaXp7hrann",
+        "client_id": OAUTH_CLIENT_ID,
         "grant_type": "refresh_token",
         "refresh_token": creds.refresh_token,
         "scope": "openid profile email"
     });
 
-    let client = reqwest::Client::new();
-    let resp = client.post(url).json(&body).send().await?;
+    let client = http::client();
+    let resp = client.post(url).json(&body).send_with_retry().await?;
     let status = resp.status();
     let data = resp.bytes().await?;
     if !status.is_success() {
-        return Err(anyhow!(
-            "Codex OAuth refresh failed (HTTP {})",
-            status.as_u16()
-        ));
+        return Err(HttpStatusError::new(
+            status,
+            format!("Codex OAuth refresh failed (HTTP {})", status.as_u16()),
+        )
+        .into());
     }
     let json: serde_json::Value = serde_json::from_slice(&data)?;
     let access_token = json
@@ -370,7 +431,7 @@ async fn refresh_codex_token(creds: &CodexOAuthCredentials) -> Result<CodexOAuth
 
 async fn codex_oauth_fetch(creds: &CodexOAuthCredentials) -> Result<CodexUsageResponse> {
     let url = resolve_codex_usage_url()?;
-    let client = reqwest::Client::new();
+    let client = http::client();
     let mut req = client.get(url);
     req = req
         .header("Authorization", format!("Bearer {}", creds.access_token))
@@ -381,14 +442,15 @@ async fn codex_oauth_fetch(creds: &CodexOAuthCredentials) -> Result<CodexUsageRe
     {
         req = req.header("ChatGPT-Account-Id", account_id.clone());
     }
-    let resp = req.send().await?;
+    let resp = req.send_with_retry().await?;
     let status = resp.status();
     let data = resp.bytes().await?;
     if !status.is_success() {
-        return Err(anyhow!(
-            "Codex OAuth usage fetch failed (HTTP {})",
-            status.as_u16()
-        ));
+        return Err(HttpStatusError::new(
+            status,
+            format!("Codex OAuth usage fetch failed (HTTP {})", status.as_u16()),
+        )
+        .into());
     }
     let usage: CodexUsageResponse = serde_json::from_slice(&data)?;
     Ok(usage)
@@ -527,6 +589,259 @@ fn format_reset_description(reset_at: DateTime<Utc>) -> String {
     }
 }
 
+// Browser sign-in the way `codex login` does it: an authorization code with
+// PKCE, redirected to a one-shot server on localhost. The resulting auth.json
+// is the one the Codex CLI writes, so either tool can use it.
+pub struct CodexLogin {
+    pub authorize_url: String,
+    listener: TcpListener,
+    verifier: String,
+    state: String,
+    redirect_uri: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct CodexLoginOutcome {
+    pub auth_path: PathBuf,
+    pub account_email: Option<String>,
+    pub plan: Option<String>,
+}
+
+impl CodexLogin {
+    pub async fn start() -> Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", LOGIN_CALLBACK_PORT))
+            .await
+            .map_err(|err| {
+                anyhow!(
+                    "cannot listen on localhost:{} for the sign-in redirect ({}); is another login running?",
+                    LOGIN_CALLBACK_PORT,
+                    err
+                )
+            })?;
+        let verifier = format!(
+            "{}{}",
+            uuid::Uuid::new_v4().simple(),
+            uuid::Uuid::new_v4().simple()
+        );
+        let challenge = URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()));
+        let state = uuid::Uuid::new_v4().simple().to_string();
+        let redirect_uri = format!("http://localhost:{}/auth/callback", LOGIN_CALLBACK_PORT);
+        let query = [
+            ("response_type", "code"),
+            ("client_id", OAUTH_CLIENT_ID),
+            ("redirect_uri", redirect_uri.as_str()),
+            ("scope", "openid profile email offline_access"),
+            ("code_challenge", challenge.as_str()),
+            ("code_challenge_method", "S256"),
+            ("id_token_add_organizations", "true"),
+            ("codex_cli_simplified_flow", "true"),
+            ("state", state.as_str()),
+        ]
+        .iter()
+        .map(|(key, value)| format!("{}={}", key, urlencoding::encode(value)))
+        .collect::<Vec<_>>()
+        .join("&");
+        Ok(Self {
+            authorize_url: format!("{}/oauth/authorize?{}", OAUTH_ISSUER, query),
+            listener,
+            verifier,
+            state,
+            redirect_uri,
+        })
+    }
+
+    // Waits for the browser to come back, then swaps the code for tokens and
+    // replaces auth.json (including any API key it held). Connections that are
+    // not a valid redirect for this login (a port probe, a tab left over from
+    // an earlier one) get a 400 and go to `on_rejected`; the wait goes on.
+    pub async fn finish(self, on_rejected: impl Fn(&anyhow::Error)) -> Result<CodexLoginOutcome> {
+        let code = loop {
+            let (stream, _) = self.listener.accept().await?;
+            match handle_login_callback(stream, &self.state).await {
+                Ok(LoginCallback::Code(code)) => break code,
+                Ok(LoginCallback::Denied(message)) => return Err(anyhow!(message)),
+                Ok(LoginCallback::Ignored) => {}
+                Err(err) => on_rejected(&err),
+            }
+        };
+        let creds = exchange_login_code(&code, &self.verifier, &self.redirect_uri).await?;
+        let auth_path = codex_auth_path();
+        write_login_auth(&auth_path, &creds)?;
+        Ok(CodexLoginOutcome {
+            auth_path,
+            account_email: resolve_account_email(creds.id_token.as_deref()),
+            plan: resolve_plan_from_token(creds.id_token.as_deref()),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum LoginCallback {
+    // Not the redirect (a favicon, say).
+    Ignored,
+    Code(String),
+    // The issuer answered this login with an error, e.g. the user declined.
+    Denied(String),
+}
+
+async fn handle_login_callback(mut stream: TcpStream, state: &str) -> Result<LoginCallback> {
+    let result = read_login_callback(&mut stream, state).await;
+    match &result {
+        Ok(LoginCallback::Ignored) => {
+            respond_to_browser(&mut stream, "404 Not Found", "Not found.").await
+        }
+        Ok(LoginCallback::Code(_)) => {
+            respond_to_browser(
+                &mut stream,
+                "200 OK",
+                "Signed in to Codex. You can close this tab.",
+            )
+            .await
+        }
+        Ok(LoginCallback::Denied(message)) => {
+            respond_to_browser(&mut stream, "400 Bad Request", message).await
+        }
+        Err(err) => respond_to_browser(&mut stream, "400 Bad Request", &err.to_string()).await,
+    }
+    result
+}
+
+async fn read_login_callback(stream: &mut TcpStream, state: &str) -> Result<LoginCallback> {
+    let mut buf = [0u8; 4096];
+    let read = stream.read(&mut buf).await?;
+    let request = String::from_utf8_lossy(&buf[..read]);
+    let target = request.split_whitespace().nth(1).unwrap_or("/");
+    let url = reqwest::Url::parse(&format!("http://localhost{}", target))?;
+    if url.path() != "/auth/callback" {
+        return Ok(LoginCallback::Ignored);
+    }
+    let param = |name: &str| {
+        url.query_pairs()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    };
+    if param("state").as_deref() != Some(state) {
+        return Err(anyhow!("sign-in redirect had the wrong state; try again"));
+    }
+    if let Some(error) = param("error") {
+        let description = param("error_description").unwrap_or_default();
+        return Ok(LoginCallback::Denied(format!(
+            "Codex sign-in failed: {} {}",
+            error, description
+        )));
+    }
+    param("code")
+        .map(LoginCallback::Code)
+        .ok_or_else(|| anyhow!("sign-in redirect had no authorization code"))
+}
+
+async fn respond_to_browser(stream: &mut TcpStream, status: &str, message: &str) {
+    let body = format!(
+        "<!doctype html><title>fuelcheck</title><p>{}</p>",
+        message.replace('&', "&amp;").replace('<', "&lt;")
+    );
+    let response = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+async fn exchange_login_code(
+    code: &str,
+    verifier: &str,
+    redirect_uri: &str,
+) -> Result<CodexOAuthCredentials> {
+    let client = http::client();
+    let resp = client
+        .post(format!("{}/oauth/token", OAUTH_ISSUER))
+        .header("content-type", "application/x-www-form-urlencoded")
+        .body(format!(
+            "grant_type=authorization_code&code={}&redirect_uri={}&client_id={}&code_verifier={}",
+            urlencoding::encode(code),
+            urlencoding::encode(redirect_uri),
+            OAUTH_CLIENT_ID,
+            urlencoding::encode(verifier),
+        ))
+        .send_with_retry()
+        .await?;
+    let status = resp.status();
+    let data = resp.bytes().await?;
+    if !status.is_success() {
+        return Err(HttpStatusError::new(
+            status,
+            format!(
+                "Codex sign-in token exchange failed (HTTP {})",
+                status.as_u16()
+            ),
+        )
+        .into());
+    }
+    let json: serde_json::Value = serde_json::from_slice(&data)?;
+    let token = |key: &str| json.get(key).and_then(|v| v.as_str()).map(str::to_string);
+    let access_token =
+        token("access_token").ok_or_else(|| anyhow!("Codex sign-in returned no access_token"))?;
+    let id_token = token("id_token");
+    let account_id = parse_jwt_payload(id_token.as_deref()).and_then(|payload| {
+        payload
+            .get("https://api.openai.com/auth")
+            .and_then(|auth| auth.get("chatgpt_account_id"))
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string())
+    });
+    Ok(CodexOAuthCredentials {
+        access_token,
+        refresh_token: token("refresh_token").unwrap_or_default(),
+        id_token,
+        account_id,
+        last_refresh: Some(Utc::now()),
+    })
+}
+
+fn write_login_auth(path: &Path, creds: &CodexOAuthCredentials) -> Result<()> {
+    let json = serde_json::json!({
+        "OPENAI_API_KEY": null,
+        "tokens": {
+            "id_token": creds.id_token,
+            "access_token": creds.access_token,
+            "refresh_token": creds.refresh_token,
+            "account_id": creds.account_id,
+        },
+        "last_refresh": Utc::now().to_rfc3339(),
+    });
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // Same as the Codex CLI: the tokens are readable by the owner only, from
+    // the moment the file exists. Write then rename, so a failed write keeps
+    // the old auth.json.
+    let mut tmp = path.as_os_str().to_owned();
+    tmp.push(format!(".{}.tmp", std::process::id()));
+    let tmp = Path::new(&tmp);
+    let _ = fs::remove_file(tmp);
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let written = options.open(tmp).and_then(|mut file| {
+        use std::io::Write;
+        file.write_all(&serde_json::to_vec_pretty(&json)?)?;
+        file.sync_all()
+    });
+    if let Err(err) = written {
+        let _ = fs::remove_file(tmp);
+        return Err(err).with_context(|| format!("write {}", tmp.display()));
+    }
+    fs::rename(tmp, path).with_context(|| format!("write {}", path.display()))?;
+    Ok(())
+}
+
 fn resolve_account_email(id_token: Option<&str>) -> Option<String> {
     let payload = parse_jwt_payload(id_token)?;
     payload
@@ -549,6 +864,10 @@ fn resolve_plan(usage: &CodexUsageResponse, id_token: Option<&str>) -> Option<St
     {
         return Some(plan.clone());
     }
+    resolve_plan_from_token(id_token)
+}
+
+fn resolve_plan_from_token(id_token: Option<&str>) -> Option<String> {
     let payload = parse_jwt_payload(id_token)?;
     payload
         .get("https://api.openai.com/auth")
@@ -574,3 +893,73 @@ fn parse_jwt_payload(token: Option<&str>) -> Option<serde_json::Value> {
     let decoded = URL_SAFE_NO_PAD.decode(payload).ok()?;
     serde_json::from_slice(&decoded).ok()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn callback(request: &str) -> Result<LoginCallback> {
+        let listener = TcpListener::bind(("127.0.0.1", 0)).await.unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap())
+            .await
+            .unwrap();
+        client.write_all(request.as_bytes()).await.unwrap();
+        let (stream, _) = listener.accept().await.unwrap();
+        handle_login_callback(stream, "s1").await
+    }
+
+    #[tokio::test]
+    async fn login_callbacks_tell_probes_from_the_redirect() {
+        assert_eq!(
+            callback("GET /favicon.ico HTTP/1.1\r\n\r\n").await.unwrap(),
+            LoginCallback::Ignored
+        );
+        assert!(
+            callback("GET /auth/callback?code=c1&state=old HTTP/1.1\r\n\r\n")
+                .await
+                .is_err()
+        );
+        assert_eq!(
+            callback("GET /auth/callback?code=c1&state=s1 HTTP/1.1\r\n\r\n")
+                .await
+                .unwrap(),
+            LoginCallback::Code("c1".to_string())
+        );
+        assert!(matches!(
+            callback("GET /auth/callback?error=access_denied&state=s1 HTTP/1.1\r\n\r\n")
+                .await
+                .unwrap(),
+            LoginCallback::Denied(_)
+        ));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn login_auth_is_written_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+        let dir =
+            std::env::temp_dir().join(format!("fuelcheck-codex-auth-{}", uuid::Uuid::new_v4()));
+        let path = dir.join("auth.json");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(&path, "{}").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+        let creds = CodexOAuthCredentials {
+            access_token: "at".to_string(),
+            refresh_token: "rt".to_string(),
+            id_token: None,
+            account_id: None,
+            last_refresh: None,
+        };
+
+        write_login_auth(&path, &creds).unwrap();
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        let written: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        let leftovers = fs::read_dir(&dir).unwrap().count();
+        fs::remove_dir_all(dir).unwrap();
+
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(written["tokens"]["refresh_token"], "rt");
+        assert_eq!(leftovers, 1);
+    }
+}
@@ -1,15 +1,19 @@
 use crate::config::Config;
-use crate::errors::CliError;
+use crate::credential_store;
+use crate::errors::{CliError, HttpStatusError};
+use crate::http::{self, RequestBuilderExt};
 use crate::model::{ProviderIdentitySnapshot, ProviderPayload, RateWindow, UsageSnapshot};
 use crate::providers::{Provider, ProviderId, SourcePreference, env_var_nonempty};
 use crate::service::UsageRequest;
-use anyhow::{Result, anyhow};
+use anyhow::Result;
 use async_trait::async_trait;
 use chrono::Utc;
 use serde::Deserialize;
 
 pub struct CopilotProvider;
 
+const STATUS_PAGE: &str = "https://www.githubstatus.com";
+
 #[async_trait]
 impl Provider for CopilotProvider {
     fn id(&self) -> ProviderId {
@@ -20,6 +24,14 @@ impl Provider for CopilotProvider {
         "2025-04-01"
     }
 
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Api]
+    }
+
+    fn status_page(&self) -> Option<&'static str> {
+        Some(STATUS_PAGE)
+    }
+
     async fn fetch_usage(
         &self,
         _args: &UsageRequest,
@@ -31,8 +43,13 @@ impl Provider for CopilotProvider {
             .as_ref()
             .and_then(|c| c.api_key.clone())
             .or_else(|| env_var_nonempty(&["COPILOT_API_TOKEN", "GITHUB_TOKEN"]))
+            // The GitHub CLI keeps its OAuth token under this service name.
+            .or_else(|| credential_store::read_secret("gh:github.com", None).ok())
             .ok_or_else(|| {
-                anyhow!("Copilot API token missing. Set provider api_key or COPILOT_API_TOKEN.")
+                CliError::MissingCredentials(
+                    "Copilot API token missing. Set provider api_key or COPILOT_API_TOKEN, or run `gh auth login`."
+                        .to_string(),
+                )
             })?;
 
         let selected = match source {
@@ -43,7 +60,7 @@ impl Provider for CopilotProvider {
             return Err(CliError::UnsupportedSource(self.id(), selected.to_string()).into());
         }
 
-        let client = reqwest::Client::new();
+        let client = http::client();
         let resp = client
             .get("https://api.github.com/copilot_internal/user")
             .header("authorization", format!("token {}", token))
@@ -52,15 +69,23 @@ impl Provider for CopilotProvider {
             .header("editor-plugin-version", "copilot-chat/0.26.7")
             .header("user-agent", "GitHubCopilotChat/0.26.7")
             .header("x-github-api-version", "2025-04-01")
-            .send()
+            .send_with_retry()
             .await?;
         let status = resp.status();
         let data = resp.bytes().await?;
         if status.as_u16() == 401 || status.as_u16() == 403 {
-            return Err(anyhow!("Copilot unauthorized. Token may be invalid."));
+            return Err(HttpStatusError::new(
+                status,
+                "Copilot unauthorized. Token may be invalid.",
+            )
+            .into());
         }
         if !status.is_success() {
-            return Err(anyhow!("Copilot API error (HTTP {})", status.as_u16()));
+            return Err(HttpStatusError::new(
+                status,
+                format!("Copilot API error (HTTP {})", status.as_u16()),
+            )
+            .into());
         }
         let response: CopilotUsageResponse = serde_json::from_slice(&data)?;
         let usage = map_copilot_usage(response);
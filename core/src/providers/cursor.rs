@@ -1,11 +1,14 @@
 use crate::accounts::{AccountSelectionArgs, account_label, select_accounts};
 use crate::config::{Config, TokenAccount};
-use crate::errors::CliError;
+use crate::errors::{CliError, HttpStatusError};
+use crate::http::{self, RequestBuilderExt};
 use crate::model::{
-    ProviderCostSnapshot, ProviderIdentitySnapshot, ProviderPayload, RateWindow, UsageSnapshot,
+    IdentityPayload, ProviderCostSnapshot, ProviderIdentitySnapshot, ProviderPayload, RateWindow,
+    UsageSnapshot,
 };
 use crate::providers::{Provider, ProviderId, SourcePreference, fetch_status_payload};
 use crate::service::UsageRequest;
+use crate::warnings::push_warning;
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use chrono::{DateTime, Utc};
@@ -13,6 +16,8 @@ use serde::Deserialize;
 
 pub struct CursorProvider;
 
+const STATUS_PAGE: &str = "https://status.cursor.com";
+
 #[async_trait]
 impl Provider for CursorProvider {
     fn id(&self) -> ProviderId {
@@ -23,6 +28,18 @@ impl Provider for CursorProvider {
         "2024-08-01"
     }
 
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Web, SourcePreference::Api]
+    }
+
+    fn supports_cost_reports(&self) -> bool {
+        true
+    }
+
+    fn status_page(&self) -> Option<&'static str> {
+        Some(STATUS_PAGE)
+    }
+
     fn supports_token_accounts(&self) -> bool {
         true
     }
@@ -39,10 +56,11 @@ impl Provider for CursorProvider {
             account_index: args.account_index.map(|idx| idx.saturating_sub(1)),
             all_accounts: args.all_accounts,
         };
-        let selected = select_accounts(
-            cfg.as_ref().and_then(|c| c.token_accounts.as_ref()),
-            &selection,
-        )?;
+        let accounts = cfg.as_ref().and_then(|c| {
+            c.cookie_token_accounts()
+                .or_else(|| c.token_accounts.clone())
+        });
+        let selected = select_accounts(accounts.as_ref(), &selection)?;
         let Some(selected) = selected else {
             return Ok(vec![self.fetch_usage(args, config, source).await?]);
         };
@@ -65,7 +83,7 @@ impl Provider for CursorProvider {
         }
 
         let status = if args.status {
-            fetch_status_payload("https://status.cursor.com", args.web_timeout).await
+            fetch_status_payload(STATUS_PAGE, args.web_timeout).await
         } else {
             None
         };
@@ -89,14 +107,7 @@ impl Provider for CursorProvider {
         config: &Config,
         source: SourcePreference,
     ) -> Result<ProviderPayload> {
-        let cfg = config.provider_config(self.id());
-        let cookie_header = cfg
-            .as_ref()
-            .and_then(|c| c.cookie_header.clone())
-            .or_else(|| std::env::var("CURSOR_COOKIE").ok())
-            .ok_or_else(|| {
-                anyhow!("Cursor cookie header missing. Set provider cookie_header in config.")
-            })?;
+        let cookie_header = cursor_cookie_header(config)?;
 
         let selected = match source {
             SourcePreference::Auto => SourcePreference::Web,
@@ -104,7 +115,7 @@ impl Provider for CursorProvider {
         };
 
         let status = if args.status {
-            fetch_status_payload("https://status.cursor.com", args.web_timeout).await
+            fetch_status_payload(STATUS_PAGE, args.web_timeout).await
         } else {
             None
         };
@@ -119,6 +130,43 @@ impl Provider for CursorProvider {
             _ => Err(CliError::UnsupportedSource(self.id(), selected.to_string()).into()),
         }
     }
+
+    fn supports_identity(&self) -> bool {
+        true
+    }
+
+    async fn fetch_identity(
+        &self,
+        config: &Config,
+        source: SourcePreference,
+    ) -> Result<IdentityPayload> {
+        let cookie_header = cursor_cookie_header(config)?;
+        match source {
+            SourcePreference::Auto | SourcePreference::Web | SourcePreference::Api => {}
+            other => return Err(CliError::UnsupportedSource(self.id(), other.to_string()).into()),
+        }
+        let user = fetch_user_info(&cookie_header).await?;
+        let identity = ProviderIdentitySnapshot {
+            provider_id: Some("cursor".to_string()),
+            account_email: user.email.clone(),
+            account_organization: None,
+            login_method: None,
+        };
+        Ok(self.identity_output("web", identity, user.sub))
+    }
+}
+
+fn cursor_cookie_header(config: &Config) -> Result<String> {
+    config
+        .provider_config(ProviderId::Cursor)
+        .and_then(|c| c.cookie_header)
+        .or_else(|| std::env::var("CURSOR_COOKIE").ok())
+        .ok_or_else(|| {
+            CliError::MissingCredentials(
+                "Cursor cookie header missing. Set provider cookie_header in config.".to_string(),
+            )
+            .into()
+        })
 }
 
 #[derive(Debug, Deserialize)]
@@ -220,7 +268,7 @@ fn token_account_cookie(account: &TokenAccount, index: usize) -> Result<String>
         .filter(|val| !val.trim().is_empty())
         .ok_or_else(|| {
             anyhow!(
-                "Cursor token account {} missing token",
+                "Cursor account {} missing cookie header",
                 account_label(account, index)
             )
         })?;
@@ -229,18 +277,25 @@ fn token_account_cookie(account: &TokenAccount, index: usize) -> Result<String>
 
 async fn fetch_cursor_usage(cookie_header: &str) -> Result<UsageSnapshot> {
     let (summary, _raw) = fetch_usage_summary(cookie_header).await?;
-    let user_info = fetch_user_info(cookie_header).await.ok();
-    let request_usage = if let Some(user) = &user_info {
-        if let Some(sub) = &user.sub {
-            fetch_request_usage(sub, cookie_header).await.ok()
-        } else {
+    let user_info = match fetch_user_info(cookie_header).await {
+        Ok(user) => Some(user),
+        Err(err) => {
+            push_warning(format!("Cursor account info unavailable: {}", err));
             None
         }
-    } else {
-        None
+    };
+    let request_usage = match user_info.as_ref().and_then(|user| user.sub.as_ref()) {
+        Some(sub) => match fetch_request_usage(sub, cookie_header).await {
+            Ok(usage) => Some(usage),
+            Err(err) => {
+                push_warning(format!("Cursor request usage unavailable: {}", err));
+                None
+            }
+        },
+        None => None,
     };
 
-    let billing_cycle_end = summary.billing_cycle_end.as_ref().and_then(parse_iso8601);
+    let billing_cycle_end = summary.billing_cycle_end.as_deref().and_then(parse_iso8601);
 
     let plan_used_raw = summary
         .individual_usage
@@ -355,34 +410,124 @@ async fn fetch_cursor_usage(cookie_header: &str) -> Result<UsageSnapshot> {
 
 async fn fetch_usage_summary(cookie_header: &str) -> Result<(CursorUsageSummary, String)> {
     let url = "https://cursor.com/api/usage-summary";
-    let client = reqwest::Client::new();
+    let client = http::client();
     let resp = client
         .get(url)
         .header("Accept", "application/json")
         .header("Cookie", cookie_header)
-        .send()
+        .send_with_retry()
         .await?;
     let status = resp.status();
     let data = resp.bytes().await?;
     if status.as_u16() == 401 || status.as_u16() == 403 {
-        return Err(anyhow!("Cursor not logged in. Update cookie header."));
+        return Err(
+            HttpStatusError::new(status, "Cursor not logged in. Update cookie header.").into(),
+        );
     }
     if !status.is_success() {
-        return Err(anyhow!("Cursor API error (HTTP {})", status.as_u16()));
+        return Err(HttpStatusError::new(
+            status,
+            format!("Cursor API error (HTTP {})", status.as_u16()),
+        )
+        .into());
     }
     let raw = String::from_utf8_lossy(&data).to_string();
     let summary: CursorUsageSummary = serde_json::from_slice(&data)?;
     Ok((summary, raw))
 }
 
+// One billed request from the dashboard's usage log. Timestamps are epoch
+// milliseconds, sent as strings.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct CursorUsageEvent {
+    pub timestamp: Option<String>,
+    pub model: Option<String>,
+    pub token_usage: Option<CursorTokenUsage>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub(crate) struct CursorTokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cache_write_tokens: u64,
+    pub cache_read_tokens: u64,
+    pub total_cents: f64,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CursorUsageEventsPage {
+    #[serde(default)]
+    total_usage_events_count: usize,
+    #[serde(default)]
+    usage_events_display: Vec<CursorUsageEvent>,
+}
+
+const USAGE_EVENTS_PAGE_SIZE: usize = 100;
+const MAX_USAGE_EVENT_PAGES: usize = 100;
+
+// Usage events between `start` and `end`, newest first as the dashboard lists
+// them. The flag is set when MAX_USAGE_EVENT_PAGES ran out before the end.
+pub(crate) async fn fetch_usage_events(
+    config: &Config,
+    start: DateTime<Utc>,
+    end: DateTime<Utc>,
+) -> Result<(Vec<CursorUsageEvent>, bool)> {
+    let cookie_header = cursor_cookie_header(config)?;
+    let url = "https://cursor.com/api/dashboard/get-filtered-usage-events";
+    let client = http::client();
+    let mut events = Vec::new();
+    for page in 1..=MAX_USAGE_EVENT_PAGES {
+        let body = serde_json::json!({
+            "startDate": start.timestamp_millis().to_string(),
+            "endDate": end.timestamp_millis().to_string(),
+            "page": page,
+            "pageSize": USAGE_EVENTS_PAGE_SIZE,
+        });
+        let resp = client
+            .post(url)
+            .header("Accept", "application/json")
+            .header("Origin", "https://cursor.com")
+            .header("Cookie", &cookie_header)
+            .json(&body)
+            .send_with_retry()
+            .await?;
+        let status = resp.status();
+        let data = resp.bytes().await?;
+        if status.as_u16() == 401 || status.as_u16() == 403 {
+            return Err(HttpStatusError::new(
+                status,
+                "Cursor not logged in. Update cookie header.",
+            )
+            .into());
+        }
+        if !status.is_success() {
+            return Err(HttpStatusError::new(
+                status,
+                format!("Cursor API error (HTTP {})", status.as_u16()),
+            )
+            .into());
+        }
+        let page: CursorUsageEventsPage = serde_json::from_slice(&data)?;
+        let fetched = page.usage_events_display.len();
+        events.extend(page.usage_events_display);
+        if fetched < USAGE_EVENTS_PAGE_SIZE || events.len() >= page.total_usage_events_count {
+            return Ok((events, false));
+        }
+    }
+    Ok((events, true))
+}
+
 async fn fetch_user_info(cookie_header: &str) -> Result<CursorUserInfo> {
     let url = "https://cursor.com/api/auth/me";
-    let client = reqwest::Client::new();
+    let client = http::client();
     let resp = client
         .get(url)
         .header("Accept", "application/json")
         .header("Cookie", cookie_header)
-        .send()
+        .send_with_retry()
         .await?;
     if !resp.status().is_success() {
         return Err(anyhow!("Cursor user info fetch failed"));
@@ -393,12 +538,12 @@ async fn fetch_user_info(cookie_header: &str) -> Result<CursorUserInfo> {
 
 async fn fetch_request_usage(user_id: &str, cookie_header: &str) -> Result<CursorUsageResponse> {
     let url = format!("https://cursor.com/api/usage?user={}", user_id);
-    let client = reqwest::Client::new();
+    let client = http::client();
     let resp = client
         .get(url)
         .header("Accept", "application/json")
         .header("Cookie", cookie_header)
-        .send()
+        .send_with_retry()
         .await?;
     if !resp.status().is_success() {
         return Err(anyhow!("Cursor request usage fetch failed"));
@@ -407,7 +552,7 @@ async fn fetch_request_usage(user_id: &str, cookie_header: &str) -> Result<Curso
     Ok(serde_json::from_slice(&data)?)
 }
 
-fn parse_iso8601(raw: &String) -> Option<DateTime<Utc>> {
+fn parse_iso8601(raw: &str) -> Option<DateTime<Utc>> {
     DateTime::parse_from_rfc3339(raw)
         .ok()
         .map(|dt| dt.with_timezone(&Utc))
@@ -0,0 +1,229 @@
+use crate::config::{
+    Config, CustomCostMapping, CustomProviderConfig, CustomWindowMapping, ProviderConfig,
+};
+use crate::errors::{CliError, HttpStatusError};
+use crate::http::{self, RequestBuilderExt};
+use crate::model::{ProviderCostSnapshot, ProviderPayload, RateWindow, UsageSnapshot};
+use crate::providers::{
+    Provider, ProviderId, SourcePreference, env_var_nonempty, parse_epoch, parse_rfc3339,
+    used_percent_from, used_percent_from_remaining, value_to_f64, value_to_i64,
+};
+use crate::service::{CostRequest, UsageRequest};
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use reqwest::Method;
+use serde_json::Value;
+
+// A provider entry with a `custom` block reads one JSON endpoint, typically an
+// internal LLM gateway, and maps its fields onto the usual windows and cost.
+pub struct CustomProvider {
+    name: &'static str,
+}
+
+impl CustomProvider {
+    pub(crate) fn new(name: &'static str) -> Self {
+        Self { name }
+    }
+}
+
+#[async_trait]
+impl Provider for CustomProvider {
+    fn id(&self) -> ProviderId {
+        ProviderId::External(self.name)
+    }
+
+    fn version(&self) -> &'static str {
+        "custom"
+    }
+
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Api]
+    }
+
+    async fn fetch_usage(
+        &self,
+        _args: &UsageRequest,
+        config: &Config,
+        source: SourcePreference,
+    ) -> Result<ProviderPayload> {
+        let selected = match source {
+            SourcePreference::Auto => SourcePreference::Api,
+            other => other,
+        };
+        if selected != SourcePreference::Api {
+            return Err(CliError::UnsupportedSource(self.id(), selected.to_string()).into());
+        }
+        let usage = self.fetch(config).await?;
+        Ok(self.ok_output("api", Some(usage)))
+    }
+
+    async fn fetch_cost(&self, _args: &CostRequest, config: &Config) -> Result<ProviderPayload> {
+        let usage = self.fetch(config).await?;
+        if usage.provider_cost.is_none() {
+            return Err(CliError::ProviderNotImplemented(self.id()).into());
+        }
+        Ok(self.ok_output("api", Some(usage)))
+    }
+}
+
+impl CustomProvider {
+    async fn fetch(&self, config: &Config) -> Result<UsageSnapshot> {
+        let cfg = config
+            .provider_config(self.id())
+            .ok_or(CliError::ProviderNotConfigured(self.id()))?;
+        let custom = cfg
+            .custom
+            .clone()
+            .ok_or(CliError::ProviderNotConfigured(self.id()))?;
+
+        let method = match custom.method.as_deref() {
+            Some(raw) => Method::from_bytes(raw.trim().to_ascii_uppercase().as_bytes())
+                .map_err(|_| anyhow!("custom provider {}: invalid method {}", self.name, raw))?,
+            None => Method::GET,
+        };
+        let url = expand(&custom.url, &cfg, self.name)?;
+        let mut request = http::client()
+            .request(method, url)
+            .header("accept", "application/json");
+        for (name, value) in custom.headers.iter().flatten() {
+            request = request.header(name.as_str(), expand(value, &cfg, self.name)?);
+        }
+        if let Some(body) = &custom.body {
+            let body = expand(&serde_json::to_string(body)?, &cfg, self.name)?;
+            request = request
+                .header("content-type", "application/json")
+                .body(body);
+        }
+
+        let resp = request.send_with_retry().await?;
+        let status = resp.status();
+        let data = resp.bytes().await?;
+        if status.as_u16() == 401 || status.as_u16() == 403 {
+            return Err(HttpStatusError::new(
+                status,
+                format!("{} unauthorized. Check its headers and api_key.", self.name),
+            )
+            .into());
+        }
+        if !status.is_success() {
+            return Err(HttpStatusError::new(
+                status,
+                format!("{} request failed (HTTP {})", self.name, status.as_u16()),
+            )
+            .into());
+        }
+        let json: Value = serde_json::from_slice(&data)?;
+        map_usage(&json, &custom).ok_or_else(|| {
+            anyhow!(
+                "{}: none of the mapped fields are in the response",
+                self.name
+            )
+        })
+    }
+}
+
+// Replaces `${api_key}`, `${cookie_header}`, `${workspace_id}` and
+// `${env:NAME}` with the provider entry's values and the environment.
+fn expand(template: &str, cfg: &ProviderConfig, name: &str) -> Result<String> {
+    let mut out = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("${") {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        let key = &rest[start + 2..start + len];
+        let value = match key {
+            "api_key" => cfg.api_key.clone(),
+            "cookie_header" => cfg.cookie_header.clone(),
+            "workspace_id" => cfg.workspace_id.clone(),
+            _ => match key.strip_prefix("env:") {
+                Some(var) => env_var_nonempty(&[var]),
+                None => return Err(anyhow!("custom provider {}: unknown ${{{}}}", name, key)),
+            },
+        };
+        let value = value.ok_or_else(|| {
+            CliError::MissingCredentials(format!(
+                "custom provider {} needs {}, which is not set",
+                name, key
+            ))
+        })?;
+        out.push_str(&rest[..start]);
+        out.push_str(&value);
+        rest = &rest[start + len + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+fn map_usage(json: &Value, custom: &CustomProviderConfig) -> Option<UsageSnapshot> {
+    let primary = custom.primary.as_ref().and_then(|m| map_window(json, m));
+    let secondary = custom.secondary.as_ref().and_then(|m| map_window(json, m));
+    let tertiary = custom.tertiary.as_ref().and_then(|m| map_window(json, m));
+    let provider_cost = custom.cost.as_ref().and_then(|m| map_cost(json, m));
+    if primary.is_none() && secondary.is_none() && tertiary.is_none() && provider_cost.is_none() {
+        return None;
+    }
+    let text = |pointer: &Option<String>| {
+        pointer
+            .as_deref()
+            .and_then(|pointer| json.pointer(pointer))
+            .and_then(|value| match value {
+                Value::String(raw) => Some(raw.clone()),
+                Value::Number(number) => Some(number.to_string()),
+                _ => None,
+            })
+    };
+    Some(UsageSnapshot {
+        primary,
+        secondary,
+        tertiary,
+        provider_cost,
+        updated_at: Utc::now(),
+        identity: None,
+        account_email: text(&custom.account),
+        account_organization: None,
+        login_method: text(&custom.plan),
+    })
+}
+
+fn map_window(json: &Value, mapping: &CustomWindowMapping) -> Option<RateWindow> {
+    let number = |pointer: &Option<String>| number_at(json, pointer);
+    let limit = number(&mapping.limit);
+    let used_percent = number(&mapping.used_percent)
+        .or_else(|| used_percent_from(number(&mapping.used), limit))
+        .or_else(|| used_percent_from_remaining(number(&mapping.remaining), limit))?;
+    Some(RateWindow {
+        used_percent: used_percent.clamp(0.0, 100.0),
+        window_minutes: mapping.window_minutes,
+        resets_at: time_at(json, &mapping.resets_at),
+        reset_description: None,
+    })
+}
+
+fn map_cost(json: &Value, mapping: &CustomCostMapping) -> Option<ProviderCostSnapshot> {
+    let used = json.pointer(&mapping.used).and_then(value_to_f64)?;
+    Some(ProviderCostSnapshot {
+        used,
+        limit: number_at(json, &mapping.limit).unwrap_or(0.0),
+        currency_code: mapping
+            .currency
+            .clone()
+            .unwrap_or_else(|| "USD".to_string()),
+        period: mapping.period.clone(),
+        resets_at: time_at(json, &mapping.resets_at),
+        updated_at: Utc::now(),
+    })
+}
+
+fn number_at(json: &Value, pointer: &Option<String>) -> Option<f64> {
+    json.pointer(pointer.as_deref()?).and_then(value_to_f64)
+}
+
+// RFC 3339 strings or epoch seconds/milliseconds.
+fn time_at(json: &Value, pointer: &Option<String>) -> Option<DateTime<Utc>> {
+    match json.pointer(pointer.as_deref()?)? {
+        Value::String(raw) => parse_rfc3339(raw).or_else(|| raw.parse().ok().and_then(parse_epoch)),
+        other => value_to_i64(other).and_then(parse_epoch),
+    }
+}
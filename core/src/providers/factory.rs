@@ -1,6 +1,10 @@
+use crate::accounts::{AccountSelectionArgs, account_label, select_accounts};
 use crate::config::Config;
-use crate::errors::CliError;
-use crate::model::{ProviderIdentitySnapshot, ProviderPayload, RateWindow, UsageSnapshot};
+use crate::errors::{CliError, HttpStatusError};
+use crate::http::{self, RequestBuilderExt};
+use crate::model::{
+    IdentityPayload, ProviderIdentitySnapshot, ProviderPayload, RateWindow, UsageSnapshot,
+};
 use crate::providers::{Provider, ProviderId, SourcePreference, fetch_status_payload};
 use crate::service::UsageRequest;
 use anyhow::{Result, anyhow};
@@ -10,6 +14,8 @@ use serde::Deserialize;
 
 pub struct FactoryProvider;
 
+const STATUS_PAGE: &str = "https://status.factory.ai";
+
 #[async_trait]
 impl Provider for FactoryProvider {
     fn id(&self) -> ProviderId {
@@ -20,39 +26,86 @@ impl Provider for FactoryProvider {
         "2026-02-16"
     }
 
-    async fn fetch_usage(
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Web, SourcePreference::Api]
+    }
+
+    fn status_page(&self) -> Option<&'static str> {
+        Some(STATUS_PAGE)
+    }
+
+    fn supports_token_accounts(&self) -> bool {
+        true
+    }
+
+    async fn fetch_usage_all(
         &self,
         args: &UsageRequest,
         config: &Config,
         source: SourcePreference,
-    ) -> Result<ProviderPayload> {
+    ) -> Result<Vec<ProviderPayload>> {
         let cfg = config.provider_config(self.id());
-        let cookie_header = cfg
-            .as_ref()
-            .and_then(|c| c.cookie_header.clone())
-            .or_else(|| std::env::var("FACTORY_COOKIE").ok())
-            .or_else(|| std::env::var("DROID_COOKIE").ok())
-            .ok_or_else(|| {
-                anyhow!(
-                    "Factory (Droid) cookie header missing. Set provider cookie_header in config."
-                )
-            })?;
+        let selection = AccountSelectionArgs {
+            account: args.account.clone(),
+            account_index: args.account_index.map(|idx| idx.saturating_sub(1)),
+            all_accounts: args.all_accounts,
+        };
+        let accounts = cfg.as_ref().and_then(|c| c.cookie_token_accounts());
+        let Some(selected) = select_accounts(accounts.as_ref(), &selection)? else {
+            return Ok(vec![self.fetch_usage(args, config, source).await?]);
+        };
 
-        let bearer_token = cfg
-            .as_ref()
-            .and_then(|c| c.api_key.clone())
-            .or_else(|| std::env::var("FACTORY_BEARER_TOKEN").ok())
-            .or_else(|| extract_access_token(&cookie_header));
+        match source {
+            SourcePreference::Auto | SourcePreference::Web | SourcePreference::Api => {}
+            other => return Err(CliError::UnsupportedSource(self.id(), other.to_string()).into()),
+        }
 
-        let base_url = std::env::var("FACTORY_BASE_URL")
-            .unwrap_or_else(|_| "https://app.factory.ai".to_string());
+        let status = if args.status {
+            fetch_status_payload(STATUS_PAGE, args.web_timeout).await
+        } else {
+            None
+        };
+        let base_url = factory_base_url();
+
+        let mut outputs = Vec::new();
+        for account in selected {
+            let label = account_label(&account.account, account.index);
+            let cookie_header = account
+                .account
+                .token
+                .clone()
+                .filter(|val| !val.trim().is_empty())
+                .ok_or_else(|| anyhow!("Factory account {} missing cookie header", label))?;
+            let bearer_token = extract_access_token(&cookie_header);
+            let usage =
+                fetch_factory_usage(&cookie_header, bearer_token.as_deref(), &base_url).await?;
+            let mut payload = self.ok_output("web", Some(usage));
+            payload.status = status.clone();
+            payload.account = Some(label);
+            outputs.push(payload);
+        }
+
+        Ok(outputs)
+    }
+
+    async fn fetch_usage(
+        &self,
+        args: &UsageRequest,
+        config: &Config,
+        source: SourcePreference,
+    ) -> Result<ProviderPayload> {
+        let FactoryCredentials {
+            cookie_header,
+            bearer_token,
+            base_url,
+        } = FactoryCredentials::resolve(config)?;
         let selected = match source {
             SourcePreference::Auto => SourcePreference::Web,
             other => other,
         };
 
         let status = if args.status {
-            fetch_status_payload("https://status.factory.ai", args.web_timeout).await
+            fetch_status_payload(STATUS_PAGE, args.web_timeout).await
         } else {
             None
         };
@@ -68,6 +121,69 @@ impl Provider for FactoryProvider {
             _ => Err(CliError::UnsupportedSource(self.id(), selected.to_string()).into()),
         }
     }
+
+    fn supports_identity(&self) -> bool {
+        true
+    }
+
+    async fn fetch_identity(
+        &self,
+        config: &Config,
+        source: SourcePreference,
+    ) -> Result<IdentityPayload> {
+        let creds = FactoryCredentials::resolve(config)?;
+        match source {
+            SourcePreference::Auto | SourcePreference::Web | SourcePreference::Api => {}
+            other => return Err(CliError::UnsupportedSource(self.id(), other.to_string()).into()),
+        }
+        let auth = fetch_factory_auth(
+            &creds.cookie_header,
+            creds.bearer_token.as_deref(),
+            &creds.base_url,
+        )
+        .await?;
+        let account_id = auth.organization.as_ref().and_then(|o| o.id.clone());
+        Ok(self.identity_output("web", factory_identity(&auth), account_id))
+    }
+}
+
+struct FactoryCredentials {
+    cookie_header: String,
+    bearer_token: Option<String>,
+    base_url: String,
+}
+
+impl FactoryCredentials {
+    fn resolve(config: &Config) -> Result<Self> {
+        let cfg = config.provider_config(ProviderId::Factory);
+        let cookie_header = cfg
+            .as_ref()
+            .and_then(|c| c.cookie_header.clone())
+            .or_else(|| std::env::var("FACTORY_COOKIE").ok())
+            .or_else(|| std::env::var("DROID_COOKIE").ok())
+            .ok_or_else(|| {
+                CliError::MissingCredentials(
+                    "Factory (Droid) cookie header missing. Set provider cookie_header in config."
+                        .to_string(),
+                )
+            })?;
+
+        let bearer_token = cfg
+            .as_ref()
+            .and_then(|c| c.api_key.clone())
+            .or_else(|| std::env::var("FACTORY_BEARER_TOKEN").ok())
+            .or_else(|| extract_access_token(&cookie_header));
+
+        Ok(Self {
+            cookie_header,
+            bearer_token,
+            base_url: factory_base_url(),
+        })
+    }
+}
+
+fn factory_base_url() -> String {
+    std::env::var("FACTORY_BASE_URL").unwrap_or_else(|_| "https://app.factory.ai".to_string())
 }
 
 #[derive(Debug, Deserialize)]
@@ -93,27 +209,22 @@ struct FactorySubscription {
 #[serde(rename_all = "camelCase")]
 struct FactoryOrbSubscription {
     plan: Option<FactoryPlan>,
-    status: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct FactoryPlan {
     name: Option<String>,
-    id: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct FactoryUsageResponse {
     usage: Option<FactoryUsageData>,
-    source: Option<String>,
-    user_id: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
 struct FactoryUsageData {
-    start_date: Option<i64>,
     end_date: Option<i64>,
     standard: Option<FactoryTokenUsage>,
     premium: Option<FactoryTokenUsage>,
@@ -123,12 +234,8 @@ struct FactoryUsageData {
 #[serde(rename_all = "camelCase")]
 struct FactoryTokenUsage {
     user_tokens: Option<i64>,
-    org_total_tokens_used: Option<i64>,
     total_allowance: Option<i64>,
     used_ratio: Option<f64>,
-    org_overage_used: Option<i64>,
-    basic_allowance: Option<i64>,
-    org_overage_limit: Option<i64>,
 }
 
 async fn fetch_factory_usage(
@@ -148,7 +255,7 @@ async fn fetch_factory_auth(
     base_url: &str,
 ) -> Result<FactoryAuthResponse> {
     let url = format!("{}/api/app/auth/me", base_url.trim_end_matches('/'));
-    let client = reqwest::Client::new();
+    let client = http::client();
     let mut request = client
         .get(url)
         .header("Accept", "application/json")
@@ -164,19 +271,25 @@ async fn fetch_factory_auth(
         request = request.header("Authorization", format!("Bearer {}", token));
     }
 
-    let resp = request.send().await?;
+    let resp = request.send_with_retry().await?;
     let status = resp.status();
     let data = resp.bytes().await?;
 
     if status.as_u16() == 401 || status.as_u16() == 403 {
-        return Err(anyhow!("Factory not logged in. Update cookie header."));
+        return Err(
+            HttpStatusError::new(status, "Factory not logged in. Update cookie header.").into(),
+        );
     }
     if !status.is_success() {
-        return Err(anyhow!(
-            "Factory API error (HTTP {}{})",
-            status.as_u16(),
-            response_snippet(&data)
-        ));
+        return Err(HttpStatusError::new(
+            status,
+            format!(
+                "Factory API error (HTTP {}{})",
+                status.as_u16(),
+                response_snippet(&data)
+            ),
+        )
+        .into());
     }
 
     serde_json::from_slice(&data).map_err(|err| {
@@ -198,7 +311,7 @@ async fn fetch_factory_subscription_usage(
         "{}/api/organization/subscription/usage",
         base_url.trim_end_matches('/')
     );
-    let client = reqwest::Client::new();
+    let client = http::client();
     let mut request = client
         .post(url)
         .header("Accept", "application/json")
@@ -224,19 +337,25 @@ async fn fetch_factory_subscription_usage(
         );
     }
 
-    let resp = request.json(&body).send().await?;
+    let resp = request.json(&body).send_with_retry().await?;
     let status = resp.status();
     let data = resp.bytes().await?;
 
     if status.as_u16() == 401 || status.as_u16() == 403 {
-        return Err(anyhow!("Factory not logged in. Update cookie header."));
+        return Err(
+            HttpStatusError::new(status, "Factory not logged in. Update cookie header.").into(),
+        );
     }
     if !status.is_success() {
-        return Err(anyhow!(
-            "Factory API error (HTTP {}{})",
-            status.as_u16(),
-            response_snippet(&data)
-        ));
+        return Err(HttpStatusError::new(
+            status,
+            format!(
+                "Factory API error (HTTP {}{})",
+                status.as_u16(),
+                response_snippet(&data)
+            ),
+        )
+        .into());
     }
 
     serde_json::from_slice(&data).map_err(|err| {
@@ -250,7 +369,6 @@ async fn fetch_factory_subscription_usage(
 
 fn build_snapshot(auth: FactoryAuthResponse, usage: FactoryUsageResponse) -> UsageSnapshot {
     let usage_data = usage.usage.unwrap_or(FactoryUsageData {
-        start_date: None,
         end_date: None,
         standard: None,
         premium: None,
@@ -297,27 +415,7 @@ fn build_snapshot(auth: FactoryAuthResponse, usage: FactoryUsageResponse) -> Usa
         reset_description,
     };
 
-    let org_name = auth.organization.as_ref().and_then(|o| o.name.clone());
-    let tier = auth
-        .organization
-        .as_ref()
-        .and_then(|o| o.subscription.as_ref())
-        .and_then(|s| s.factory_tier.clone());
-    let plan = auth
-        .organization
-        .as_ref()
-        .and_then(|o| o.subscription.as_ref())
-        .and_then(|s| s.orb_subscription.as_ref())
-        .and_then(|o| o.plan.as_ref())
-        .and_then(|p| p.name.clone());
-
-    let login_method = format_login_method(tier.as_deref(), plan.as_deref());
-    let identity = ProviderIdentitySnapshot {
-        provider_id: Some("factory".to_string()),
-        account_email: None,
-        account_organization: org_name.clone(),
-        login_method: login_method.clone(),
-    };
+    let identity = factory_identity(&auth);
 
     UsageSnapshot {
         primary: Some(primary),
@@ -326,8 +424,8 @@ fn build_snapshot(auth: FactoryAuthResponse, usage: FactoryUsageResponse) -> Usa
         provider_cost: None,
         updated_at: Utc::now(),
         account_email: None,
-        account_organization: org_name,
-        login_method,
+        account_organization: identity.account_organization.clone(),
+        login_method: identity.login_method.clone(),
         identity: Some(identity),
     }
 }
@@ -388,6 +486,29 @@ fn format_reset_description(reset_at: DateTime<Utc>) -> String {
     }
 }
 
+fn factory_identity(auth: &FactoryAuthResponse) -> ProviderIdentitySnapshot {
+    let org_name = auth.organization.as_ref().and_then(|o| o.name.clone());
+    let tier = auth
+        .organization
+        .as_ref()
+        .and_then(|o| o.subscription.as_ref())
+        .and_then(|s| s.factory_tier.clone());
+    let plan = auth
+        .organization
+        .as_ref()
+        .and_then(|o| o.subscription.as_ref())
+        .and_then(|s| s.orb_subscription.as_ref())
+        .and_then(|o| o.plan.as_ref())
+        .and_then(|p| p.name.clone());
+
+    ProviderIdentitySnapshot {
+        provider_id: Some("factory".to_string()),
+        account_email: None,
+        account_organization: org_name,
+        login_method: format_login_method(tier.as_deref(), plan.as_deref()),
+    }
+}
+
 fn format_login_method(tier: Option<&str>, plan: Option<&str>) -> Option<String> {
     let mut parts: Vec<String> = Vec::new();
     if let Some(tier) = tier.map(|t| t.trim()).filter(|t| !t.is_empty()) {
@@ -1,8 +1,12 @@
 use crate::config::Config;
-use crate::errors::CliError;
-use crate::model::{ProviderIdentitySnapshot, ProviderPayload, RateWindow, UsageSnapshot};
-use crate::providers::{Provider, ProviderId, SourcePreference};
+use crate::errors::{CliError, HttpStatusError};
+use crate::http::{self, RequestBuilderExt};
+use crate::model::{
+    IdentityPayload, ProviderIdentitySnapshot, ProviderPayload, RateWindow, UsageSnapshot,
+};
+use crate::providers::{Provider, ProviderId, SourcePreference, remove_credentials_file};
 use crate::service::UsageRequest;
+use crate::warnings::push_warning;
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use base64::Engine;
@@ -25,6 +29,42 @@ impl Provider for GeminiProvider {
         "2024-12-01"
     }
 
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Api]
+    }
+
+    fn supports_cost_reports(&self) -> bool {
+        true
+    }
+
+    fn supports_identity(&self) -> bool {
+        true
+    }
+
+    async fn fetch_identity(
+        &self,
+        _config: &Config,
+        source: SourcePreference,
+    ) -> Result<IdentityPayload> {
+        match source {
+            SourcePreference::Auto | SourcePreference::Api => {}
+            other => return Err(CliError::UnsupportedSource(self.id(), other.to_string()).into()),
+        }
+        let creds = load_oauth_credentials()?;
+        let (email, hosted_domain) = extract_claims(creds.id_token.as_deref());
+        let identity = ProviderIdentitySnapshot {
+            provider_id: Some("gemini".to_string()),
+            account_email: email,
+            account_organization: hosted_domain,
+            login_method: None,
+        };
+        Ok(self.identity_output("api", identity, None))
+    }
+
+    fn clear_local_credentials(&self) -> Result<Vec<String>> {
+        remove_credentials_file(&gemini_home().join("oauth_creds.json"))
+    }
+
     async fn fetch_usage(
         &self,
         _args: &UsageRequest,
@@ -116,9 +156,10 @@ async fn fetch_gemini_usage() -> Result<UsageSnapshot> {
 
     let mut creds = load_oauth_credentials()?;
     if creds.access_token.is_none() {
-        return Err(anyhow!(
-            "Gemini not logged in. Run `gemini` to authenticate."
-        ));
+        return Err(CliError::MissingCredentials(
+            "Gemini not logged in. Run `gemini` to authenticate.".to_string(),
+        )
+        .into());
     }
     if let Some(expiry) = creds.expiry_date
         && expiry < Utc::now()
@@ -126,6 +167,7 @@ async fn fetch_gemini_usage() -> Result<UsageSnapshot> {
     {
         let new_token = refresh_access_token(&refresh).await?;
         creds.access_token = Some(new_token);
+        push_warning("Gemini OAuth token was expired and has been refreshed");
     }
 
     let access_token = creds
@@ -171,7 +213,9 @@ fn read_gemini_auth_type() -> Result<Option<String>> {
 fn load_oauth_credentials() -> Result<OAuthCredentials> {
     let path = gemini_home().join("oauth_creds.json");
     if !path.exists() {
-        return Err(anyhow!("Gemini credentials not found"));
+        return Err(
+            CliError::MissingCredentials("Gemini credentials not found".to_string()).into(),
+        );
     }
     let data = fs::read(&path)?;
     let json: serde_json::Value = serde_json::from_slice(&data)?;
@@ -214,20 +258,21 @@ async fn refresh_access_token(refresh_token: &str) -> Result<String> {
         "client_id={}&client_secret={}&refresh_token={}&grant_type=refresh_token",
         client_id, client_secret, refresh_token
     );
-    let client = reqwest::Client::new();
+    let client = http::client();
     let resp = client
         .post(url)
         .header("Content-Type", "application/x-www-form-urlencoded")
         .body(body)
-        .send()
+        .send_with_retry()
         .await?;
     let status = resp.status();
     let data = resp.bytes().await?;
     if !status.is_success() {
-        return Err(anyhow!(
-            "Gemini token refresh failed (HTTP {})",
-            status.as_u16()
-        ));
+        return Err(HttpStatusError::new(
+            status,
+            format!("Gemini token refresh failed (HTTP {})", status.as_u16()),
+        )
+        .into());
     }
     let json: serde_json::Value = serde_json::from_slice(&data)?;
     let token = json
@@ -285,13 +330,13 @@ fn which(bin: &str) -> Option<PathBuf> {
 
 async fn load_code_assist(access_token: &str) -> Result<(Option<String>, Option<String>)> {
     let url = "https://cloudcode-pa.googleapis.com/v1internal:loadCodeAssist";
-    let client = reqwest::Client::new();
+    let client = http::client();
     let resp = client
         .post(url)
         .header("Authorization", format!("Bearer {}", access_token))
         .header("Content-Type", "application/json")
         .body("{\"metadata\":{\"ideType\":\"GEMINI_CLI\",\"pluginType\":\"GEMINI\"}}")
-        .send()
+        .send_with_retry()
         .await?;
     if !resp.status().is_success() {
         return Ok((None, None));
@@ -313,11 +358,11 @@ async fn load_code_assist(access_token: &str) -> Result<(Option<String>, Option<
 
 async fn discover_project_id(access_token: &str) -> Result<Option<String>> {
     let url = "https://cloudresourcemanager.googleapis.com/v1/projects";
-    let client = reqwest::Client::new();
+    let client = http::client();
     let resp = client
         .get(url)
         .header("Authorization", format!("Bearer {}", access_token))
-        .send()
+        .send_with_retry()
         .await?;
     if !resp.status().is_success() {
         return Ok(None);
@@ -351,18 +396,22 @@ async fn fetch_quota(access_token: &str, project_id: Option<&str>) -> Result<Quo
     } else {
         serde_json::json!({})
     };
-    let client = reqwest::Client::new();
+    let client = http::client();
     let resp = client
         .post(url)
         .header("Authorization", format!("Bearer {}", access_token))
         .header("Content-Type", "application/json")
         .json(&body)
-        .send()
+        .send_with_retry()
         .await?;
     let status = resp.status();
     let data = resp.bytes().await?;
     if !status.is_success() {
-        return Err(anyhow!("Gemini quota API error (HTTP {})", status.as_u16()));
+        return Err(HttpStatusError::new(
+            status,
+            format!("Gemini quota API error (HTTP {})", status.as_u16()),
+        )
+        .into());
     }
     Ok(serde_json::from_slice(&data)?)
 }
@@ -23,6 +23,10 @@ impl Provider for JetBrainsProvider {
         "2025-01-01"
     }
 
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Local]
+    }
+
     async fn fetch_usage(
         &self,
         _args: &UsageRequest,
@@ -1,5 +1,6 @@
 use crate::config::Config;
-use crate::errors::CliError;
+use crate::errors::{CliError, HttpStatusError};
+use crate::http::{self, RequestBuilderExt};
 use crate::model::{ProviderIdentitySnapshot, ProviderPayload, RateWindow, UsageSnapshot};
 use crate::providers::{Provider, ProviderId, SourcePreference, env_var_nonempty, parse_rfc3339};
 use crate::service::UsageRequest;
@@ -20,6 +21,10 @@ impl Provider for KimiProvider {
         "2025-01-01"
     }
 
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Api]
+    }
+
     async fn fetch_usage(
         &self,
         _args: &UsageRequest,
@@ -32,7 +37,9 @@ impl Provider for KimiProvider {
             .and_then(|c| c.api_key.clone())
             .or_else(|| env_var_nonempty(&["KIMI_AUTH_TOKEN"]))
             .ok_or_else(|| {
-                anyhow!("Kimi auth token missing. Set provider api_key or KIMI_AUTH_TOKEN.")
+                CliError::MissingCredentials(
+                    "Kimi auth token missing. Set provider api_key or KIMI_AUTH_TOKEN.".to_string(),
+                )
             })?;
 
         let selected = match source {
@@ -43,20 +50,26 @@ impl Provider for KimiProvider {
             return Err(CliError::UnsupportedSource(self.id(), selected.to_string()).into());
         }
 
-        let client = reqwest::Client::new();
+        let client = http::client();
         let resp = client
             .post("https://www.kimi.com/apiv2/kimi.gateway.billing.v1.BillingService/GetUsages")
             .header("authorization", format!("Bearer {}", token))
             .header("accept", "application/json")
-            .send()
+            .send_with_retry()
             .await?;
         let status = resp.status();
         let data = resp.bytes().await?;
         if status.as_u16() == 401 || status.as_u16() == 403 {
-            return Err(anyhow!("Kimi unauthorized. Token may be invalid."));
+            return Err(
+                HttpStatusError::new(status, "Kimi unauthorized. Token may be invalid.").into(),
+            );
         }
         if !status.is_success() {
-            return Err(anyhow!("Kimi API error (HTTP {})", status.as_u16()));
+            return Err(HttpStatusError::new(
+                status,
+                format!("Kimi API error (HTTP {})", status.as_u16()),
+            )
+            .into());
         }
         let response: KimiUsageResponse = serde_json::from_slice(&data)?;
         let usage = map_kimi_usage(response)?;
@@ -80,7 +93,6 @@ struct KimiUsageScope {
 struct KimiUsageDetail {
     limit: Option<String>,
     used: Option<String>,
-    remaining: Option<String>,
     #[serde(rename = "resetTime")]
     reset_time: Option<String>,
 }
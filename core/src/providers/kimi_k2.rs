@@ -1,9 +1,10 @@
 use crate::config::Config;
-use crate::errors::CliError;
+use crate::errors::{CliError, HttpStatusError};
+use crate::http::{self, RequestBuilderExt};
 use crate::model::{ProviderIdentitySnapshot, ProviderPayload, RateWindow, UsageSnapshot};
 use crate::providers::{Provider, ProviderId, SourcePreference, env_var_nonempty, value_to_f64};
 use crate::service::UsageRequest;
-use anyhow::{Result, anyhow};
+use anyhow::Result;
 use async_trait::async_trait;
 use chrono::Utc;
 use serde_json::Value;
@@ -20,6 +21,10 @@ impl Provider for KimiK2Provider {
         "2025-01-01"
     }
 
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Api]
+    }
+
     async fn fetch_usage(
         &self,
         _args: &UsageRequest,
@@ -32,7 +37,9 @@ impl Provider for KimiK2Provider {
             .and_then(|c| c.api_key.clone())
             .or_else(|| env_var_nonempty(&["KIMI_K2_API_KEY", "KIMI_API_KEY", "KIMI_KEY"]))
             .ok_or_else(|| {
-                anyhow!("Kimi K2 API key missing. Set provider api_key or KIMI_K2_API_KEY.")
+                CliError::MissingCredentials(
+                    "Kimi K2 API key missing. Set provider api_key or KIMI_K2_API_KEY.".to_string(),
+                )
             })?;
 
         let selected = match source {
@@ -43,21 +50,29 @@ impl Provider for KimiK2Provider {
             return Err(CliError::UnsupportedSource(self.id(), selected.to_string()).into());
         }
 
-        let client = reqwest::Client::new();
+        let client = http::client();
         let resp = client
             .get("https://kimi-k2.ai/api/user/credits")
             .header("authorization", format!("Bearer {}", token))
             .header("accept", "application/json")
-            .send()
+            .send_with_retry()
             .await?;
         let status = resp.status();
         let headers = resp.headers().clone();
         let data = resp.bytes().await?;
         if status.as_u16() == 401 || status.as_u16() == 403 {
-            return Err(anyhow!("Kimi K2 unauthorized. API key may be invalid."));
+            return Err(HttpStatusError::new(
+                status,
+                "Kimi K2 unauthorized. API key may be invalid.",
+            )
+            .into());
         }
         if !status.is_success() {
-            return Err(anyhow!("Kimi K2 API error (HTTP {})", status.as_u16()));
+            return Err(HttpStatusError::new(
+                status,
+                format!("Kimi K2 API error (HTTP {})", status.as_u16()),
+            )
+            .into());
         }
         let json: Value = serde_json::from_slice(&data)?;
         let usage = map_kimi_k2_usage(&json, &headers)?;
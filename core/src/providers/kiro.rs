@@ -1,5 +1,6 @@
 use crate::config::Config;
 use crate::errors::CliError;
+use crate::http;
 use crate::model::{ProviderIdentitySnapshot, ProviderPayload, RateWindow, UsageSnapshot};
 use crate::providers::Provider;
 use crate::providers::{ProviderId, SourcePreference};
@@ -22,6 +23,10 @@ impl Provider for KiroProvider {
         "2025-01-01"
     }
 
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Cli]
+    }
+
     async fn fetch_usage(
         &self,
         _args: &UsageRequest,
@@ -35,6 +40,10 @@ impl Provider for KiroProvider {
         if selected != SourcePreference::Cli {
             return Err(CliError::UnsupportedSource(self.id(), selected.to_string()).into());
         }
+        // kiro-cli talks to the Kiro service itself.
+        if http::network_disabled() {
+            return Err(CliError::NetworkDisabled("kiro-cli".to_string()).into());
+        }
 
         let output = tokio::time::timeout(
             std::time::Duration::from_secs(20),
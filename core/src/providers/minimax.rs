@@ -1,5 +1,6 @@
 use crate::config::Config;
-use crate::errors::CliError;
+use crate::errors::{CliError, HttpStatusError};
+use crate::http::{self, RequestBuilderExt};
 use crate::model::{ProviderIdentitySnapshot, ProviderPayload, RateWindow, UsageSnapshot};
 use crate::providers::{
     Provider, ProviderId, SourcePreference, env_var_nonempty, normalize_host, parse_epoch,
@@ -22,6 +23,10 @@ impl Provider for MiniMaxProvider {
         "2025-01-01"
     }
 
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Api, SourcePreference::Web]
+    }
+
     async fn fetch_usage(
         &self,
         _args: &UsageRequest,
@@ -51,45 +56,61 @@ impl Provider for MiniMaxProvider {
 
         match selected {
             SourcePreference::Api => {
-                let token = api_key.ok_or_else(|| anyhow!("MiniMax API key missing."))?;
+                let token = api_key.ok_or_else(|| {
+                    CliError::MissingCredentials("MiniMax API key missing.".to_string())
+                })?;
                 let url = minimax_api_url();
-                let client = reqwest::Client::new();
+                let client = http::client();
                 let resp = client
                     .get(url)
                     .header("authorization", format!("Bearer {}", token))
                     .header("accept", "application/json")
-                    .send()
+                    .send_with_retry()
                     .await?;
                 let status = resp.status();
                 let data = resp.bytes().await?;
                 if status.as_u16() == 401 || status.as_u16() == 403 {
-                    return Err(anyhow!("MiniMax unauthorized. API key may be invalid."));
+                    return Err(HttpStatusError::new(
+                        status,
+                        "MiniMax unauthorized. API key may be invalid.",
+                    )
+                    .into());
                 }
                 if !status.is_success() {
-                    return Err(anyhow!("MiniMax API error (HTTP {})", status.as_u16()));
+                    return Err(HttpStatusError::new(
+                        status,
+                        format!("MiniMax API error (HTTP {})", status.as_u16()),
+                    )
+                    .into());
                 }
                 let payload: MiniMaxCodingPlanPayload = serde_json::from_slice(&data)?;
                 let usage = map_minimax_usage(payload)?;
                 Ok(self.ok_output("api", Some(usage)))
             }
             SourcePreference::Web => {
-                let cookie_header = cookie_header.ok_or_else(|| anyhow!(
-                    "MiniMax cookie header missing. Set provider cookie_header or MINIMAX_COOKIE."
-                ))?;
+                let cookie_header = cookie_header.ok_or_else(|| CliError::MissingCredentials("MiniMax cookie header missing. Set provider cookie_header or MINIMAX_COOKIE.".to_string()))?;
                 let url = minimax_remains_url(cfg.as_ref());
-                let mut req = reqwest::Client::new().get(url);
+                let mut req = http::client().get(url);
                 req = req.header("cookie", cookie_header.clone());
                 if let Some(token) = extract_cookie_token(&cookie_header) {
                     req = req.header("authorization", format!("Bearer {}", token));
                 }
-                let resp = req.send().await?;
+                let resp = req.send_with_retry().await?;
                 let status = resp.status();
                 let data = resp.bytes().await?;
                 if status.as_u16() == 401 || status.as_u16() == 403 {
-                    return Err(anyhow!("MiniMax unauthorized. Cookie may be invalid."));
+                    return Err(HttpStatusError::new(
+                        status,
+                        "MiniMax unauthorized. Cookie may be invalid.",
+                    )
+                    .into());
                 }
                 if !status.is_success() {
-                    return Err(anyhow!("MiniMax API error (HTTP {})", status.as_u16()));
+                    return Err(HttpStatusError::new(
+                        status,
+                        format!("MiniMax API error (HTTP {})", status.as_u16()),
+                    )
+                    .into());
                 }
                 let payload: MiniMaxCodingPlanPayload = serde_json::from_slice(&data)?;
                 let usage = map_minimax_usage(payload)?;
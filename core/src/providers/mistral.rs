@@ -0,0 +1,291 @@
+use crate::config::Config;
+use crate::errors::{CliError, HttpStatusError};
+use crate::http::{self, RequestBuilderExt};
+use crate::model::{ProviderPayload, RateWindow, UsageSnapshot};
+use crate::providers::{
+    Provider, ProviderId, SourcePreference, env_var_nonempty, normalize_host, parse_epoch,
+    parse_rfc3339, used_percent_from, used_percent_from_remaining, value_to_f64, value_to_i64,
+};
+use crate::service::UsageRequest;
+use crate::warnings::push_warning;
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Months, NaiveTime, Utc};
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+
+// La Plateforme (API keys) and Le Chat (the chat app, signed in with a
+// cookie) are separate accounts with separate limits; one source each.
+pub struct MistralProvider;
+
+const DEFAULT_API_HOST: &str = "https://api.mistral.ai";
+const DEFAULT_CHAT_HOST: &str = "https://chat.mistral.ai";
+
+#[async_trait]
+impl Provider for MistralProvider {
+    fn id(&self) -> ProviderId {
+        ProviderId::Mistral
+    }
+
+    fn version(&self) -> &'static str {
+        "v1"
+    }
+
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Api, SourcePreference::Web]
+    }
+
+    async fn fetch_usage(
+        &self,
+        _args: &UsageRequest,
+        config: &Config,
+        source: SourcePreference,
+    ) -> Result<ProviderPayload> {
+        let cfg = config.provider_config(self.id());
+        let api_key = cfg
+            .as_ref()
+            .and_then(|c| c.api_key.clone())
+            .or_else(|| env_var_nonempty(&["MISTRAL_API_KEY"]));
+        let cookie_header = cfg
+            .as_ref()
+            .and_then(|c| c.cookie_header.clone())
+            .or_else(|| env_var_nonempty(&["MISTRAL_COOKIE", "LECHAT_COOKIE"]));
+
+        let selected = match source {
+            SourcePreference::Auto => {
+                if api_key.is_some() || cookie_header.is_none() {
+                    SourcePreference::Api
+                } else {
+                    SourcePreference::Web
+                }
+            }
+            other => other,
+        };
+
+        match selected {
+            SourcePreference::Api => {
+                let key = api_key.ok_or_else(|| {
+                    CliError::MissingCredentials(
+                        "Mistral API key missing. Set provider api_key or MISTRAL_API_KEY."
+                            .to_string(),
+                    )
+                })?;
+                let usage = fetch_workspace_limits(&key).await?;
+                Ok(self.ok_output("api", Some(usage)))
+            }
+            SourcePreference::Web => {
+                let cookie = cookie_header.ok_or_else(|| {
+                    CliError::MissingCredentials(
+                        "Le Chat cookie header missing. Set provider cookie_header or MISTRAL_COOKIE."
+                            .to_string(),
+                    )
+                })?;
+                let usage = fetch_chat_limits(&cookie).await?;
+                Ok(self.ok_output("web", Some(usage)))
+            }
+            _ => Err(CliError::UnsupportedSource(self.id(), selected.to_string()).into()),
+        }
+    }
+}
+
+fn host(env: &str, default: &str) -> String {
+    env_var_nonempty(&[env])
+        .map(|host| normalize_host(&host))
+        .unwrap_or_else(|| default.to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
+// The workspace limits come back as headers on any authenticated call, so the
+// cheapest one (listing models) is enough; no tokens are spent.
+async fn fetch_workspace_limits(key: &str) -> Result<UsageSnapshot> {
+    let host = host("MISTRAL_BASE_URL", DEFAULT_API_HOST);
+    let host = host.strip_suffix("/v1").unwrap_or(&host);
+    let resp = http::client()
+        .get(format!("{}/v1/models", host))
+        .header("authorization", format!("Bearer {}", key))
+        .header("accept", "application/json")
+        .send_with_retry()
+        .await?;
+    let status = resp.status();
+    let headers = resp.headers().clone();
+    if status.as_u16() == 401 || status.as_u16() == 403 {
+        return Err(
+            HttpStatusError::new(status, "Mistral unauthorized. API key may be invalid.").into(),
+        );
+    }
+    if !status.is_success() {
+        return Err(HttpStatusError::new(
+            status,
+            format!("Mistral API error (HTTP {})", status.as_u16()),
+        )
+        .into());
+    }
+
+    let primary = header_window(&headers, "minute", Some(1), None);
+    let next_month = Utc::now()
+        .date_naive()
+        .with_day(1)
+        .and_then(|first| first.checked_add_months(Months::new(1)))
+        .map(|first| first.and_time(NaiveTime::MIN).and_utc());
+    let secondary = header_window(&headers, "month", None, next_month);
+    if primary.is_none() && secondary.is_none() {
+        push_warning("Mistral did not report workspace limits for this key");
+    }
+    Ok(UsageSnapshot {
+        primary,
+        secondary,
+        tertiary: None,
+        provider_cost: None,
+        updated_at: Utc::now(),
+        identity: None,
+        account_email: None,
+        account_organization: None,
+        login_method: Some("La Plateforme".to_string()),
+    })
+}
+
+// Token limits: `x-ratelimitbysize-{limit,remaining}-<period>`.
+fn header_window(
+    headers: &HeaderMap,
+    period: &str,
+    window_minutes: Option<i64>,
+    resets_at: Option<DateTime<Utc>>,
+) -> Option<RateWindow> {
+    let number = |kind: &str| {
+        headers
+            .get(format!("x-ratelimitbysize-{}-{}", kind, period))
+            .and_then(|value| value.to_str().ok())
+            .and_then(|raw| raw.trim().parse::<f64>().ok())
+    };
+    let used_percent = used_percent_from_remaining(number("remaining"), number("limit"))?;
+    Some(RateWindow {
+        used_percent: used_percent.clamp(0.0, 100.0),
+        window_minutes,
+        resets_at,
+        reset_description: None,
+    })
+}
+
+async fn fetch_chat_limits(cookie: &str) -> Result<UsageSnapshot> {
+    let url = env_var_nonempty(&["MISTRAL_CHAT_USAGE_URL"]).unwrap_or_else(|| {
+        format!(
+            "{}/api/user/limits",
+            host("MISTRAL_CHAT_HOST", DEFAULT_CHAT_HOST)
+        )
+    });
+    let resp = http::client()
+        .get(url)
+        .header("cookie", cookie)
+        .header("accept", "application/json")
+        .send_with_retry()
+        .await?;
+    let status = resp.status();
+    let data = resp.bytes().await?;
+    if status.as_u16() == 401 || status.as_u16() == 403 {
+        return Err(
+            HttpStatusError::new(status, "Le Chat unauthorized. Cookie may be invalid.").into(),
+        );
+    }
+    if !status.is_success() {
+        return Err(HttpStatusError::new(
+            status,
+            format!("Le Chat request failed (HTTP {})", status.as_u16()),
+        )
+        .into());
+    }
+    let json: Value = serde_json::from_slice(&data)?;
+    parse_chat_limits(&json)
+}
+
+// Le Chat lists one quota per feature, either as an array of objects with a
+// `name` or keyed by name. Messages are the main window; of the rest (flash
+// answers, images, research) the one closest to its cap is the tertiary.
+fn parse_chat_limits(json: &Value) -> Result<UsageSnapshot> {
+    let root = json.get("data").unwrap_or(json);
+    let entries: Vec<(String, &Value)> = match root.get("limits").unwrap_or(root) {
+        Value::Array(items) => items
+            .iter()
+            .filter_map(|item| {
+                let name = item
+                    .get("name")
+                    .or_else(|| item.get("feature"))
+                    .and_then(Value::as_str)?;
+                Some((name.to_ascii_lowercase(), item))
+            })
+            .collect(),
+        Value::Object(map) => map
+            .iter()
+            .filter(|(_, value)| value.is_object())
+            .map(|(name, value)| (name.to_ascii_lowercase(), value))
+            .collect(),
+        _ => Vec::new(),
+    };
+    let mut windows: Vec<(String, RateWindow)> = entries
+        .into_iter()
+        .filter_map(|(name, value)| Some((name, chat_window(value)?)))
+        .collect();
+    if windows.is_empty() {
+        return Err(anyhow!("Le Chat usage data missing"));
+    }
+
+    let primary = windows
+        .iter()
+        .position(|(name, _)| name.contains("message"))
+        .map(|index| windows.remove(index).1);
+    windows.sort_by(|a, b| b.1.used_percent.total_cmp(&a.1.used_percent));
+    let tertiary = windows.into_iter().next().map(|(_, window)| window);
+    let plan = root
+        .get("plan")
+        .or_else(|| root.get("subscription"))
+        .and_then(Value::as_str)
+        .map(|plan| format!("Le Chat {}", capitalize(plan)));
+
+    Ok(UsageSnapshot {
+        primary,
+        secondary: None,
+        tertiary,
+        provider_cost: None,
+        updated_at: Utc::now(),
+        identity: None,
+        account_email: None,
+        account_organization: None,
+        login_method: plan.or_else(|| Some("Le Chat".to_string())),
+    })
+}
+
+fn chat_window(value: &Value) -> Option<RateWindow> {
+    let number = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| value.get(*key).and_then(value_to_f64))
+    };
+    let limit = number(&["limit", "max", "quota"]);
+    let used_percent = match number(&["used", "usage", "count"]) {
+        Some(used) => used_percent_from(Some(used), limit),
+        None => used_percent_from_remaining(number(&["remaining"]), limit),
+    }?;
+    let resets_at = ["resetAt", "reset_at", "resetsAt", "resets_at"]
+        .iter()
+        .find_map(|key| value.get(*key))
+        .and_then(|reset| match reset {
+            Value::String(raw) => parse_rfc3339(raw),
+            other => value_to_i64(other).and_then(parse_epoch),
+        });
+    let window_minutes = ["windowMinutes", "window_minutes"]
+        .iter()
+        .find_map(|key| value.get(*key).and_then(value_to_i64));
+    Some(RateWindow {
+        used_percent: used_percent.clamp(0.0, 100.0),
+        window_minutes,
+        resets_at,
+        reset_description: None,
+    })
+}
+
+fn capitalize(raw: &str) -> String {
+    let mut chars = raw.trim().chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().collect::<String>() + &chars.as_str().to_lowercase(),
+        None => String::new(),
+    }
+}
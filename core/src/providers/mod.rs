@@ -1,19 +1,30 @@
 use crate::config::{Config, ProviderConfig};
-use crate::errors::CliError;
-use crate::model::{ProviderPayload, UsageSnapshot};
+use crate::errors::{CliError, HttpStatusError};
+use crate::http::{self, RequestBuilderExt};
+use crate::model::{
+    IdentityPayload, PAYLOAD_SCHEMA_VERSION, ProviderIdentitySnapshot, ProviderPayload,
+    ProviderStatusIndicator, ProviderStatusPayload, StatusIncident, UsageSnapshot,
+};
 use crate::service::{CostRequest, UsageRequest};
 use anyhow::Result;
 use async_trait::async_trait;
+use schemars::{JsonSchema, Schema, SchemaGenerator, json_schema};
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt;
+use std::sync::RwLock;
 use std::time::Duration;
 
+mod aider;
 mod amp;
+mod anthropic_api;
 mod claude;
+mod cline;
 mod codex;
 mod copilot;
 mod cursor;
+mod custom;
 mod factory;
 mod gemini;
 mod jetbrains;
@@ -21,17 +32,27 @@ mod kimi;
 mod kimi_k2;
 mod kiro;
 mod minimax;
+mod mistral;
+mod ollama;
+mod openai_platform;
 mod opencode;
+mod perplexity;
+mod plugin;
 mod utils;
 mod vertexai;
 mod warp;
 mod zai;
 
+pub use aider::AiderProvider;
 pub use amp::AmpProvider;
+pub use anthropic_api::AnthropicApiProvider;
 pub use claude::ClaudeProvider;
-pub use codex::CodexProvider;
+pub use cline::ClineProvider;
+pub use codex::{CodexLogin, CodexLoginOutcome, CodexProvider};
 pub use copilot::CopilotProvider;
 pub use cursor::CursorProvider;
+pub(crate) use cursor::{CursorUsageEvent, fetch_usage_events as fetch_cursor_usage_events};
+pub use custom::CustomProvider;
 pub use factory::FactoryProvider;
 pub use gemini::GeminiProvider;
 pub use jetbrains::JetBrainsProvider;
@@ -39,25 +60,27 @@ pub use kimi::KimiProvider;
 pub use kimi_k2::KimiK2Provider;
 pub use kiro::KiroProvider;
 pub use minimax::MiniMaxProvider;
+pub use mistral::MistralProvider;
+pub use ollama::OllamaProvider;
+pub use openai_platform::OpenAIPlatformProvider;
 pub use opencode::OpenCodeProvider;
+pub use perplexity::PerplexityProvider;
+pub use plugin::{PLUGIN_PROTOCOL, PluginProvider};
 pub(crate) use utils::*;
 pub use vertexai::VertexAIProvider;
 pub use warp::WarpProvider;
 pub use zai::ZaiProvider;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
-#[serde(rename_all = "kebab-case")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ProviderId {
     Codex,
     Claude,
     Gemini,
     Cursor,
-    #[serde(alias = "droid")]
     Factory,
     Zai,
     MiniMax,
     Kimi,
-    #[serde(rename = "kimik2")]
     KimiK2,
     Copilot,
     Kiro,
@@ -66,8 +89,45 @@ pub enum ProviderId {
     Amp,
     Warp,
     OpenCode,
+    AnthropicApi,
+    OpenAIPlatform,
+    Mistral,
+    Perplexity,
+    Aider,
+    Cline,
+    Ollama,
+    // Provider plugged in by a downstream crate via `ProviderRegistry::register`,
+    // or defined by a config entry (plugin executable or custom HTTP endpoint).
+    // Parsed names are interned so ids stay `Copy`.
+    External(&'static str),
 }
 
+const BUILTIN_SERDE_NAMES: &[&str] = &[
+    "codex",
+    "claude",
+    "gemini",
+    "cursor",
+    "factory",
+    "zai",
+    "mini-max",
+    "kimi",
+    "kimik2",
+    "copilot",
+    "kiro",
+    "vertex-ai",
+    "jet-brains",
+    "amp",
+    "warp",
+    "open-code",
+    "anthropic-api",
+    "openai-platform",
+    "mistral",
+    "perplexity",
+    "aider",
+    "cline",
+    "ollama",
+];
+
 impl fmt::Display for ProviderId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         let label = match self {
@@ -87,14 +147,87 @@ impl fmt::Display for ProviderId {
             ProviderId::Amp => "amp",
             ProviderId::Warp => "warp",
             ProviderId::OpenCode => "opencode",
+            ProviderId::AnthropicApi => "anthropicapi",
+            ProviderId::OpenAIPlatform => "openaiplatform",
+            ProviderId::Mistral => "mistral",
+            ProviderId::Perplexity => "perplexity",
+            ProviderId::Aider => "aider",
+            ProviderId::Cline => "cline",
+            ProviderId::Ollama => "ollama",
+            ProviderId::External(name) => name,
         };
         write!(f, "{}", label)
     }
 }
 
+// Config files spell a few built-in ids with dashes ("mini-max"); keep that
+// format stable now that external ids rule out a derived impl.
+impl Serialize for ProviderId {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            ProviderId::External(name) => serializer.serialize_str(name),
+            builtin => {
+                let index = ProviderId::builtin()
+                    .iter()
+                    .position(|id| id == builtin)
+                    .unwrap_or_default();
+                serializer.serialize_str(BUILTIN_SERDE_NAMES[index])
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for ProviderId {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+        if raw == "droid" {
+            return Ok(ProviderId::Factory);
+        }
+        if let Some(index) = BUILTIN_SERDE_NAMES.iter().position(|name| *name == raw) {
+            return Ok(ProviderId::builtin()[index]);
+        }
+        configured(&raw)
+            .map(|provider| ProviderId::External(provider.name))
+            .map_err(|_| serde::de::Error::unknown_variant(&raw, BUILTIN_SERDE_NAMES))
+    }
+}
+
+// Any string: plugin and custom providers take their ids from the config.
+impl JsonSchema for ProviderId {
+    fn schema_name() -> Cow<'static, str> {
+        "ProviderId".into()
+    }
+
+    fn json_schema(_generator: &mut SchemaGenerator) -> Schema {
+        json_schema!({
+            "type": "string",
+            "examples": BUILTIN_SERDE_NAMES,
+        })
+    }
+}
+
 impl ProviderId {
+    pub fn from_name(raw: &str) -> Option<ProviderId> {
+        let normalized: String = raw
+            .chars()
+            .filter(|c| *c != '_' && *c != '-' && !c.is_whitespace())
+            .flat_map(char::to_lowercase)
+            .collect();
+        if normalized == "droid" {
+            return Some(ProviderId::Factory);
+        }
+        ProviderId::ordered()
+            .into_iter()
+            .find(|id| id.to_string() == normalized)
+    }
+
+    // The built-in providers. `ProviderRegistry::ids` adds the external ones.
     pub fn ordered() -> Vec<ProviderId> {
-        vec![
+        ProviderId::builtin().to_vec()
+    }
+
+    fn builtin() -> [ProviderId; 23] {
+        [
             ProviderId::Codex,
             ProviderId::Claude,
             ProviderId::Gemini,
@@ -111,6 +244,13 @@ impl ProviderId {
             ProviderId::Amp,
             ProviderId::Warp,
             ProviderId::OpenCode,
+            ProviderId::AnthropicApi,
+            ProviderId::OpenAIPlatform,
+            ProviderId::Mistral,
+            ProviderId::Perplexity,
+            ProviderId::Aider,
+            ProviderId::Cline,
+            ProviderId::Ollama,
         ]
     }
 }
@@ -133,14 +273,23 @@ pub enum ProviderSelector {
     Amp,
     Warp,
     OpenCode,
+    AnthropicApi,
+    OpenAIPlatform,
+    Mistral,
+    Perplexity,
+    Aider,
+    Cline,
+    Ollama,
+    External(&'static str),
     All,
     Both,
 }
 
 impl ProviderSelector {
-    pub fn expand(self) -> Vec<ProviderId> {
+    // `all` is what `All` stands for, usually `ProviderRegistry::ids`.
+    pub fn expand(self, all: &[ProviderId]) -> Vec<ProviderId> {
         match self {
-            ProviderSelector::All => ProviderId::ordered(),
+            ProviderSelector::All => all.to_vec(),
             ProviderSelector::Both => vec![ProviderId::Codex, ProviderId::Claude],
             ProviderSelector::Codex => vec![ProviderId::Codex],
             ProviderSelector::Claude => vec![ProviderId::Claude],
@@ -158,6 +307,14 @@ impl ProviderSelector {
             ProviderSelector::Amp => vec![ProviderId::Amp],
             ProviderSelector::Warp => vec![ProviderId::Warp],
             ProviderSelector::OpenCode => vec![ProviderId::OpenCode],
+            ProviderSelector::AnthropicApi => vec![ProviderId::AnthropicApi],
+            ProviderSelector::OpenAIPlatform => vec![ProviderId::OpenAIPlatform],
+            ProviderSelector::Mistral => vec![ProviderId::Mistral],
+            ProviderSelector::Perplexity => vec![ProviderId::Perplexity],
+            ProviderSelector::Aider => vec![ProviderId::Aider],
+            ProviderSelector::Cline => vec![ProviderId::Cline],
+            ProviderSelector::Ollama => vec![ProviderId::Ollama],
+            ProviderSelector::External(name) => vec![ProviderId::External(name)],
         }
     }
 }
@@ -181,6 +338,14 @@ impl fmt::Display for ProviderSelector {
             ProviderSelector::Amp => "amp",
             ProviderSelector::Warp => "warp",
             ProviderSelector::OpenCode => "opencode",
+            ProviderSelector::AnthropicApi => "anthropicapi",
+            ProviderSelector::OpenAIPlatform => "openaiplatform",
+            ProviderSelector::Mistral => "mistral",
+            ProviderSelector::Perplexity => "perplexity",
+            ProviderSelector::Aider => "aider",
+            ProviderSelector::Cline => "cline",
+            ProviderSelector::Ollama => "ollama",
+            ProviderSelector::External(name) => name,
             ProviderSelector::All => "all",
             ProviderSelector::Both => "both",
         };
@@ -188,11 +353,45 @@ impl fmt::Display for ProviderSelector {
     }
 }
 
-pub fn expand_provider_selectors(selectors: &[ProviderSelector]) -> Vec<ProviderId> {
+impl From<ProviderId> for ProviderSelector {
+    fn from(value: ProviderId) -> Self {
+        match value {
+            ProviderId::Codex => ProviderSelector::Codex,
+            ProviderId::Claude => ProviderSelector::Claude,
+            ProviderId::Gemini => ProviderSelector::Gemini,
+            ProviderId::Cursor => ProviderSelector::Cursor,
+            ProviderId::Factory => ProviderSelector::Factory,
+            ProviderId::Zai => ProviderSelector::Zai,
+            ProviderId::MiniMax => ProviderSelector::MiniMax,
+            ProviderId::Kimi => ProviderSelector::Kimi,
+            ProviderId::KimiK2 => ProviderSelector::KimiK2,
+            ProviderId::Copilot => ProviderSelector::Copilot,
+            ProviderId::Kiro => ProviderSelector::Kiro,
+            ProviderId::VertexAI => ProviderSelector::VertexAI,
+            ProviderId::JetBrains => ProviderSelector::JetBrains,
+            ProviderId::Amp => ProviderSelector::Amp,
+            ProviderId::Warp => ProviderSelector::Warp,
+            ProviderId::OpenCode => ProviderSelector::OpenCode,
+            ProviderId::AnthropicApi => ProviderSelector::AnthropicApi,
+            ProviderId::OpenAIPlatform => ProviderSelector::OpenAIPlatform,
+            ProviderId::Mistral => ProviderSelector::Mistral,
+            ProviderId::Perplexity => ProviderSelector::Perplexity,
+            ProviderId::Aider => ProviderSelector::Aider,
+            ProviderId::Cline => ProviderSelector::Cline,
+            ProviderId::Ollama => ProviderSelector::Ollama,
+            ProviderId::External(name) => ProviderSelector::External(name),
+        }
+    }
+}
+
+pub fn expand_provider_selectors(
+    selectors: &[ProviderSelector],
+    all: &[ProviderId],
+) -> Vec<ProviderId> {
     let mut ordered = Vec::new();
     let mut seen: std::collections::HashSet<ProviderId> = std::collections::HashSet::new();
     for selector in selectors {
-        for provider in selector.expand() {
+        for provider in selector.expand(all) {
             if seen.insert(provider) {
                 ordered.push(provider);
             }
@@ -201,7 +400,7 @@ pub fn expand_provider_selectors(selectors: &[ProviderSelector]) -> Vec<Provider
     ordered
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "kebab-case")]
 pub enum SourcePreference {
     Auto,
@@ -230,9 +429,35 @@ impl fmt::Display for SourcePreference {
 pub trait Provider: Send + Sync {
     fn id(&self) -> ProviderId;
     fn version(&self) -> &'static str;
+    fn supported_sources(&self) -> &'static [SourcePreference];
     fn supports_token_accounts(&self) -> bool {
         false
     }
+    fn supports_cost_reports(&self) -> bool {
+        false
+    }
+    fn supports_identity(&self) -> bool {
+        false
+    }
+    fn status_page(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            id: self.id().to_string(),
+            version: self.version(),
+            sources: self
+                .supported_sources()
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            token_accounts: self.supports_token_accounts(),
+            cost_reports: self.supports_cost_reports(),
+            identity: self.supports_identity(),
+            status_page: self.status_page(),
+        }
+    }
 
     async fn fetch_usage(
         &self,
@@ -254,6 +479,18 @@ pub trait Provider: Send + Sync {
         Err(CliError::ProviderNotImplemented(self.id()).into())
     }
 
+    async fn fetch_identity(
+        &self,
+        _config: &Config,
+        _source: SourcePreference,
+    ) -> Result<IdentityPayload> {
+        Err(CliError::IdentityNotSupported(self.id()).into())
+    }
+
+    fn clear_local_credentials(&self) -> Result<Vec<String>> {
+        Ok(Vec::new())
+    }
+
     fn resolve_source(
         &self,
         config: Option<ProviderConfig>,
@@ -269,6 +506,7 @@ pub trait Provider: Send + Sync {
 
     fn ok_output(&self, source: &str, usage: Option<UsageSnapshot>) -> ProviderPayload {
         ProviderPayload {
+            schema_version: PAYLOAD_SCHEMA_VERSION,
             provider: self.id().to_string(),
             account: None,
             version: Some(self.version().to_string()),
@@ -279,12 +517,46 @@ pub trait Provider: Send + Sync {
             antigravity_plan_info: None,
             openai_dashboard: None,
             error: None,
+            warnings: Vec::new(),
+            alerting: false,
+            alerts: Vec::new(),
+            loaded_models: Vec::new(),
         }
     }
+
+    fn identity_output(
+        &self,
+        source: &str,
+        identity: ProviderIdentitySnapshot,
+        account_id: Option<String>,
+    ) -> IdentityPayload {
+        IdentityPayload {
+            provider: self.id().to_string(),
+            account: None,
+            source: source.to_string(),
+            account_id,
+            identity: Some(identity),
+            error: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderCapabilities {
+    pub id: String,
+    pub version: &'static str,
+    pub sources: Vec<String>,
+    pub token_accounts: bool,
+    pub cost_reports: bool,
+    pub identity: bool,
+    pub status_page: Option<&'static str>,
 }
 
 pub struct ProviderRegistry {
     providers: HashMap<ProviderId, Box<dyn Provider>>,
+    // External ids in the order they were registered.
+    external: Vec<ProviderId>,
 }
 
 impl ProviderRegistry {
@@ -306,71 +578,328 @@ impl ProviderRegistry {
         providers.insert(ProviderId::Amp, Box::new(AmpProvider));
         providers.insert(ProviderId::Warp, Box::new(WarpProvider));
         providers.insert(ProviderId::OpenCode, Box::new(OpenCodeProvider));
-        Self { providers }
+        providers.insert(ProviderId::AnthropicApi, Box::new(AnthropicApiProvider));
+        providers.insert(ProviderId::OpenAIPlatform, Box::new(OpenAIPlatformProvider));
+        providers.insert(ProviderId::Mistral, Box::new(MistralProvider));
+        providers.insert(ProviderId::Perplexity, Box::new(PerplexityProvider));
+        providers.insert(ProviderId::Aider, Box::new(AiderProvider));
+        providers.insert(ProviderId::Cline, Box::new(ClineProvider));
+        providers.insert(ProviderId::Ollama, Box::new(OllamaProvider));
+        Self {
+            providers,
+            external: Vec::new(),
+        }
     }
 
+    // Adds a provider, or replaces the built-in one with the same id. External ids
+    // become selectable by name (`--provider`, config, groups) once registered.
+    pub fn register(&mut self, provider: Box<dyn Provider>) -> Result<()> {
+        let id = provider.id();
+        if let ProviderId::External(name) = id {
+            validate_external_name(name)?;
+            if !self.external.contains(&id) {
+                self.external.push(id);
+            }
+        }
+        self.providers.insert(id, provider);
+        Ok(())
+    }
+
+    pub fn is_registered(&self, id: &ProviderId) -> bool {
+        self.providers.contains_key(id)
+    }
+
+    // The external ids registered in code, in registration order.
+    pub fn external(&self) -> &[ProviderId] {
+        &self.external
+    }
+
+    // External ids without a registered provider are config-defined; whether
+    // the config defines them is checked when names are resolved.
     pub fn get(&self, id: &ProviderId) -> Option<&dyn Provider> {
-        self.providers.get(id).map(Box::as_ref)
+        match (self.providers.get(id), id) {
+            (Some(provider), _) => Some(provider.as_ref()),
+            (None, ProviderId::External(name)) => configured(name)
+                .ok()
+                .map(|provider| provider as &dyn Provider),
+            (None, _) => None,
+        }
+    }
+
+    // Every provider `--provider all` selects: the built-in ones, the registered
+    // external ones, then the ones the config defines with a `command` or a
+    // `custom` endpoint.
+    pub fn ids(&self, config: &Config) -> Vec<ProviderId> {
+        let mut ids = ProviderId::ordered();
+        ids.extend(self.external.iter().copied());
+        for cfg in config.providers.iter().flatten() {
+            if matches!(cfg.id, ProviderId::External(_))
+                && (cfg.command.is_some() || cfg.custom.is_some())
+                && !ids.contains(&cfg.id)
+            {
+                ids.push(cfg.id);
+            }
+        }
+        ids
+    }
+
+    // A provider id typed by the user, built-in or one of `ids`.
+    pub fn find(&self, name: &str, config: &Config) -> Option<ProviderId> {
+        ProviderId::from_name(name).or_else(|| {
+            let name = name.trim().to_lowercase();
+            self.ids(config)
+                .into_iter()
+                .find(|id| matches!(id, ProviderId::External(external) if *external == name))
+        })
+    }
+
+    pub fn expand(&self, selectors: &[ProviderSelector], config: &Config) -> Vec<ProviderId> {
+        expand_provider_selectors(selectors, &self.ids(config))
+    }
+
+    pub fn capabilities(&self, config: &Config) -> Vec<ProviderCapabilities> {
+        self.ids(config)
+            .iter()
+            .filter_map(|id| self.get(id))
+            .map(|provider| provider.capabilities())
+            .collect()
+    }
+}
+
+// External names must round-trip through `ProviderId::from_name`, so they are
+// lowercase ASCII without separators and can't shadow a built-in id.
+fn validate_external_name(name: &str) -> Result<()> {
+    if name.is_empty()
+        || !name
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+    {
+        return Err(anyhow::anyhow!(
+            "external provider id \"{}\" must be lowercase letters and digits",
+            name
+        ));
+    }
+    if ProviderId::builtin()
+        .iter()
+        .any(|id| id.to_string() == name)
+        || name == "droid"
+        || name == "all"
+        || name == "both"
+    {
+        return Err(anyhow::anyhow!(
+            "external provider id \"{}\" is reserved",
+            name
+        ));
+    }
+    Ok(())
+}
+
+// External names, each interned once together with the provider that serves it
+// when the config defines the id. Whether that runs a plugin or reads a custom
+// endpoint is decided by the config of each fetch, so the same entry serves
+// every config a process loads.
+static CONFIGURED_PROVIDERS: RwLock<Vec<&'static ConfiguredProvider>> = RwLock::new(Vec::new());
+
+fn configured(name: &str) -> Result<&'static ConfiguredProvider> {
+    let find = |providers: &[&'static ConfiguredProvider]| {
+        providers
+            .iter()
+            .copied()
+            .find(|provider| provider.name == name)
+    };
+    if let Some(provider) = CONFIGURED_PROVIDERS.read().ok().and_then(|p| find(&p)) {
+        return Ok(provider);
+    }
+    validate_external_name(name)?;
+    let mut providers = CONFIGURED_PROVIDERS
+        .write()
+        .map_err(|_| anyhow::anyhow!("provider registry poisoned"))?;
+    if let Some(provider) = find(&providers) {
+        return Ok(provider);
+    }
+    let name: &'static str = Box::leak(name.to_string().into_boxed_str());
+    let provider: &'static ConfiguredProvider = Box::leak(Box::new(ConfiguredProvider::new(name)));
+    providers.push(provider);
+    Ok(provider)
+}
+
+// A config-defined id: a plugin when its entry has a `command`, otherwise a
+// custom endpoint.
+struct ConfiguredProvider {
+    name: &'static str,
+    plugin: PluginProvider,
+    custom: CustomProvider,
+}
+
+impl ConfiguredProvider {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            plugin: PluginProvider::new(name),
+            custom: CustomProvider::new(name),
+        }
+    }
+
+    fn entry<'a>(&'a self, config: &Config) -> &'a dyn Provider {
+        let is_plugin = config
+            .provider_config(self.id())
+            .is_some_and(|cfg| cfg.command.is_some());
+        if is_plugin {
+            &self.plugin
+        } else {
+            &self.custom
+        }
+    }
+}
+
+#[async_trait]
+impl Provider for ConfiguredProvider {
+    fn id(&self) -> ProviderId {
+        ProviderId::External(self.name)
+    }
+
+    fn version(&self) -> &'static str {
+        "config"
+    }
+
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        self.plugin.supported_sources()
+    }
+
+    async fn fetch_usage(
+        &self,
+        args: &UsageRequest,
+        config: &Config,
+        source: SourcePreference,
+    ) -> Result<ProviderPayload> {
+        self.entry(config).fetch_usage(args, config, source).await
+    }
+
+    async fn fetch_cost(&self, args: &CostRequest, config: &Config) -> Result<ProviderPayload> {
+        self.entry(config).fetch_cost(args, config).await
+    }
+}
+
+impl Default for ProviderRegistry {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
 pub async fn fetch_status_payload(
     base_url: &str,
     timeout_secs: u64,
-) -> Option<crate::model::ProviderStatusPayload> {
+) -> Option<ProviderStatusPayload> {
     let api_url = format!("{}/api/v2/status.json", base_url.trim_end_matches('/'));
-    let client = reqwest::Client::builder()
+    let resp = http::client()
+        .get(api_url)
         .timeout(Duration::from_secs(timeout_secs.max(1)))
-        .build()
+        .send_with_retry()
+        .await
         .ok()?;
-    let resp = client.get(api_url).send().await.ok()?;
     let status = resp.status();
     if !status.is_success() {
-        return Some(crate::model::ProviderStatusPayload {
-            indicator: crate::model::ProviderStatusIndicator::Unknown,
+        return Some(ProviderStatusPayload {
+            indicator: ProviderStatusIndicator::Unknown,
             description: Some(format!("HTTP {}", status.as_u16())),
             updated_at: None,
             url: base_url.to_string(),
+            incidents: Vec::new(),
         });
     }
     let body = resp.bytes().await.ok()?;
-    #[derive(Deserialize)]
-    struct StatusResponse {
-        status: StatusBlock,
-        page: Option<PageBlock>,
-    }
-    #[derive(Deserialize)]
-    struct StatusBlock {
-        indicator: String,
-        description: Option<String>,
-    }
-    #[derive(Deserialize)]
-    struct PageBlock {
-        #[serde(rename = "updated_at")]
-        updated_at: Option<String>,
-    }
-
-    let parsed: StatusResponse = serde_json::from_slice(&body).ok()?;
-    let indicator = match parsed.status.indicator.as_str() {
-        "none" => crate::model::ProviderStatusIndicator::None,
-        "minor" => crate::model::ProviderStatusIndicator::Minor,
-        "major" => crate::model::ProviderStatusIndicator::Major,
-        "critical" => crate::model::ProviderStatusIndicator::Critical,
-        "maintenance" => crate::model::ProviderStatusIndicator::Maintenance,
-        _ => crate::model::ProviderStatusIndicator::Unknown,
-    };
-    let updated_at = parsed
-        .page
-        .and_then(|p| p.updated_at)
-        .and_then(|raw| chrono::DateTime::parse_from_rfc3339(&raw).ok())
-        .map(|dt| dt.with_timezone(&chrono::Utc));
-
-    Some(crate::model::ProviderStatusPayload {
-        indicator,
-        description: parsed.status.description,
-        updated_at,
-        url: base_url.to_string(),
-    })
+    let parsed: StatusPageResponse = serde_json::from_slice(&body).ok()?;
+    Some(parsed.into_payload(base_url))
+}
+
+// Statuspage summary: the overall indicator plus any unresolved incidents.
+pub async fn fetch_status_summary(
+    base_url: &str,
+    timeout_secs: u64,
+) -> Result<ProviderStatusPayload> {
+    let api_url = format!("{}/api/v2/summary.json", base_url.trim_end_matches('/'));
+    let resp = http::client()
+        .get(api_url)
+        .timeout(Duration::from_secs(timeout_secs.max(1)))
+        .send_with_retry()
+        .await?;
+    let status = resp.status();
+    let body = resp.bytes().await?;
+    if !status.is_success() {
+        return Err(HttpStatusError::new(
+            status,
+            format!("status page {} failed (HTTP {})", base_url, status.as_u16()),
+        )
+        .into());
+    }
+    let parsed: StatusPageResponse = serde_json::from_slice(&body)?;
+    Ok(parsed.into_payload(base_url))
+}
+
+#[derive(Deserialize)]
+struct StatusPageResponse {
+    status: StatusBlock,
+    page: Option<PageBlock>,
+    #[serde(default)]
+    incidents: Vec<IncidentBlock>,
+}
+
+#[derive(Deserialize)]
+struct StatusBlock {
+    indicator: String,
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct PageBlock {
+    #[serde(rename = "updated_at")]
+    updated_at: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct IncidentBlock {
+    name: String,
+    status: String,
+    impact: Option<String>,
+    #[serde(rename = "updated_at")]
+    updated_at: Option<String>,
+    shortlink: Option<String>,
+}
+
+impl StatusPageResponse {
+    fn into_payload(self, base_url: &str) -> ProviderStatusPayload {
+        let indicator = match self.status.indicator.as_str() {
+            "none" => ProviderStatusIndicator::None,
+            "minor" => ProviderStatusIndicator::Minor,
+            "major" => ProviderStatusIndicator::Major,
+            "critical" => ProviderStatusIndicator::Critical,
+            "maintenance" => ProviderStatusIndicator::Maintenance,
+            _ => ProviderStatusIndicator::Unknown,
+        };
+        let incidents = self
+            .incidents
+            .into_iter()
+            .map(|incident| StatusIncident {
+                name: incident.name,
+                status: incident.status,
+                impact: incident.impact.filter(|impact| impact != "none"),
+                updated_at: incident.updated_at.as_deref().and_then(parse_rfc3339),
+                url: incident.shortlink,
+            })
+            .collect();
+
+        ProviderStatusPayload {
+            indicator,
+            description: self.status.description,
+            updated_at: self
+                .page
+                .and_then(|p| p.updated_at)
+                .as_deref()
+                .and_then(parse_rfc3339),
+            url: base_url.to_string(),
+            incidents,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -379,14 +908,16 @@ mod tests {
 
     #[test]
     fn expand_provider_selectors_all() {
-        let expanded = expand_provider_selectors(&[ProviderSelector::All]);
+        let expanded = expand_provider_selectors(&[ProviderSelector::All], &ProviderId::ordered());
         assert_eq!(expanded, ProviderId::ordered());
     }
 
     #[test]
     fn expand_provider_selectors_both_plus_extra() {
-        let expanded =
-            expand_provider_selectors(&[ProviderSelector::Both, ProviderSelector::Gemini]);
+        let expanded = expand_provider_selectors(
+            &[ProviderSelector::Both, ProviderSelector::Gemini],
+            &ProviderId::ordered(),
+        );
         assert_eq!(
             expanded,
             vec![ProviderId::Codex, ProviderId::Claude, ProviderId::Gemini]
@@ -395,14 +926,79 @@ mod tests {
 
     #[test]
     fn expand_provider_selectors_dedup_preserves_order() {
-        let expanded = expand_provider_selectors(&[
-            ProviderSelector::Gemini,
-            ProviderSelector::Both,
-            ProviderSelector::Gemini,
-        ]);
+        let expanded = expand_provider_selectors(
+            &[
+                ProviderSelector::Gemini,
+                ProviderSelector::Both,
+                ProviderSelector::Gemini,
+            ],
+            &ProviderId::ordered(),
+        );
         assert_eq!(
             expanded,
             vec![ProviderId::Gemini, ProviderId::Codex, ProviderId::Claude]
         );
     }
+
+    #[test]
+    fn builtin_ids_keep_config_spelling() {
+        let json = serde_json::to_string(&[ProviderId::MiniMax, ProviderId::VertexAI]).unwrap();
+        assert_eq!(json, r#"["mini-max","vertex-ai"]"#);
+        let parsed: ProviderId = serde_json::from_str(r#""droid""#).unwrap();
+        assert_eq!(parsed, ProviderId::Factory);
+        assert!(serde_json::from_str::<ProviderId>(r#""minimax""#).is_err());
+        assert_eq!(ProviderId::External("acme").to_string(), "acme");
+        assert!(validate_external_name("acme2").is_ok());
+        assert!(validate_external_name("claude").is_err());
+        assert!(validate_external_name("Acme-AI").is_err());
+    }
+
+    #[test]
+    fn status_summary_keeps_unresolved_incidents() {
+        let parsed: StatusPageResponse = serde_json::from_str(
+            r#"{"status":{"indicator":"minor","description":"Partially Degraded Service"},
+                "page":{"updated_at":"2025-01-01T00:00:00Z"},
+                "incidents":[{"name":"Elevated errors","status":"investigating","impact":"minor",
+                "updated_at":"2025-01-01T00:00:00Z","shortlink":"https://stspg.io/x"}]}"#,
+        )
+        .unwrap();
+        let payload = parsed.into_payload("https://status.example.com");
+        assert!(matches!(payload.indicator, ProviderStatusIndicator::Minor));
+        assert_eq!(payload.incidents.len(), 1);
+        assert_eq!(payload.incidents[0].impact.as_deref(), Some("minor"));
+        assert!(payload.updated_at.is_some());
+    }
+
+    #[test]
+    fn config_providers_are_made_once_and_follow_each_config() {
+        let custom = serde_json::json!({
+            "providers": [{"id": "gateway7", "custom": {"url": "https://llm.example.com/usage"}}]
+        });
+        let plugin = serde_json::json!({
+            "providers": [{"id": "gateway7", "command": "/usr/local/bin/gateway7"}]
+        });
+        let custom: Config = serde_json::from_value(custom).unwrap();
+        let plugin: Config = serde_json::from_value(plugin).unwrap();
+        let count = || {
+            CONFIGURED_PROVIDERS
+                .read()
+                .unwrap()
+                .iter()
+                .filter(|provider| provider.name == "gateway7")
+                .count()
+        };
+        assert_eq!(count(), 1);
+
+        let registry = ProviderRegistry::new();
+        let id = ProviderId::External("gateway7");
+        assert_eq!(registry.find("Gateway7", &custom), Some(id));
+        assert_eq!(registry.find("gateway7", &Config::default()), None);
+        assert!(!registry.ids(&Config::default()).contains(&id));
+        assert_eq!(registry.ids(&plugin).last(), Some(&id));
+
+        let provider = configured("gateway7").unwrap();
+        assert_eq!(provider.entry(&custom).version(), "custom");
+        assert_eq!(provider.entry(&plugin).version(), "plugin-v1");
+        assert_eq!(count(), 1);
+    }
 }
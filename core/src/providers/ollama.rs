@@ -0,0 +1,150 @@
+use crate::config::Config;
+use crate::errors::{CliError, HttpStatusError};
+use crate::http::{self, RequestBuilderExt};
+use crate::model::{LoadedModel, ProviderPayload, UsageSnapshot};
+use crate::providers::{Provider, ProviderId, SourcePreference, env_var_nonempty, parse_rfc3339};
+use crate::service::UsageRequest;
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::Utc;
+use reqwest::Url;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+
+// Ollama runs models on this machine and bills nothing. Its server only
+// knows what it is doing now: its version and the models it has loaded, with
+// how much of each sits in GPU memory and when it unloads. It keeps no
+// history of requests or tokens.
+pub struct OllamaProvider;
+
+const DEFAULT_HOST: &str = "http://127.0.0.1:11434";
+const DEFAULT_PORT: u16 = 11434;
+
+#[derive(Deserialize)]
+struct VersionResponse {
+    version: String,
+}
+
+#[derive(Deserialize)]
+struct ProcessResponse {
+    #[serde(default)]
+    models: Vec<RunningModel>,
+}
+
+#[derive(Deserialize)]
+struct RunningModel {
+    name: String,
+    #[serde(default)]
+    size: u64,
+    #[serde(default)]
+    size_vram: u64,
+    #[serde(default)]
+    context_length: Option<u64>,
+    #[serde(default)]
+    expires_at: Option<String>,
+}
+
+#[async_trait]
+impl Provider for OllamaProvider {
+    fn id(&self) -> ProviderId {
+        ProviderId::Ollama
+    }
+
+    fn version(&self) -> &'static str {
+        "2025-01-01"
+    }
+
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Local]
+    }
+
+    async fn fetch_usage(
+        &self,
+        _args: &UsageRequest,
+        _config: &Config,
+        source: SourcePreference,
+    ) -> Result<ProviderPayload> {
+        let selected = match source {
+            SourcePreference::Auto => SourcePreference::Local,
+            other => other,
+        };
+        if selected != SourcePreference::Local {
+            return Err(CliError::UnsupportedSource(self.id(), selected.to_string()).into());
+        }
+
+        let base = base_url()?;
+        let version: VersionResponse = get_json(&base, "api/version").await?;
+        let running: ProcessResponse = get_json(&base, "api/ps").await?;
+        let mut payload = self.ok_output(
+            "local",
+            Some(UsageSnapshot {
+                primary: None,
+                secondary: None,
+                tertiary: None,
+                provider_cost: None,
+                updated_at: Utc::now(),
+                identity: None,
+                account_email: None,
+                account_organization: None,
+                login_method: Some(format!("Ollama {}", version.version)),
+            }),
+        );
+        payload.loaded_models = running
+            .models
+            .into_iter()
+            .map(|model| LoadedModel {
+                name: model.name,
+                size_bytes: model.size,
+                vram_bytes: model.size_vram,
+                context_length: model.context_length,
+                expires_at: model.expires_at.as_deref().and_then(parse_rfc3339),
+            })
+            .collect();
+        Ok(payload)
+    }
+}
+
+// `OLLAMA_HOST` is the variable the Ollama CLI reads, so it may be a bare
+// `host[:port]` or the `0.0.0.0` the server was told to listen on.
+fn base_url() -> Result<Url> {
+    let raw = env_var_nonempty(&["OLLAMA_HOST"]).unwrap_or_else(|| DEFAULT_HOST.to_string());
+    let raw = raw.trim();
+    let with_scheme = if raw.contains("://") {
+        raw.to_string()
+    } else {
+        format!("http://{}", raw)
+    };
+    let mut url =
+        Url::parse(&with_scheme).with_context(|| format!("invalid OLLAMA_HOST {}", raw))?;
+    if url.host_str() == Some("0.0.0.0") {
+        let _ = url.set_host(Some("127.0.0.1"));
+    }
+    if !raw.contains("://") && url.port().is_none() {
+        let _ = url.set_port(Some(DEFAULT_PORT));
+    }
+    if !url.path().ends_with('/') {
+        let path = format!("{}/", url.path());
+        url.set_path(&path);
+    }
+    Ok(url)
+}
+
+async fn get_json<T: DeserializeOwned>(base: &Url, path: &str) -> Result<T> {
+    let url = base.join(path)?;
+    let resp = http::client()
+        .get(url)
+        .header("accept", "application/json")
+        .send_with_retry()
+        .await
+        .with_context(|| format!("Ollama server not reachable at {}", base))?;
+    let status = resp.status();
+    let data = resp.bytes().await?;
+    if !status.is_success() {
+        return Err(HttpStatusError::new(
+            status,
+            format!("Ollama request failed (HTTP {})", status.as_u16()),
+        )
+        .into());
+    }
+    Ok(serde_json::from_slice(&data)?)
+}
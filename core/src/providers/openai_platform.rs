@@ -0,0 +1,434 @@
+use crate::config::Config;
+use crate::errors::{CliError, HttpStatusError};
+use crate::http::{self, RequestBuilderExt};
+use crate::model::{
+    OpenAIDashboardDailyBreakdown, OpenAIDashboardServiceUsage, OpenAIDashboardSnapshot,
+    ProviderCostSnapshot, ProviderPayload, UsageSnapshot,
+};
+use crate::providers::{
+    Provider, ProviderId, SourcePreference, env_var_nonempty, fetch_status_payload, normalize_host,
+    value_to_f64,
+};
+use crate::service::{CostRequest, UsageRequest};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Utc};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::collections::BTreeMap;
+
+// API spend on platform.openai.com; ChatGPT plans and Codex limits are the
+// `codex` provider. Either an organization Admin API key (`sk-admin-...`) or
+// the dashboard's session token (`sess-...`) can read the usage endpoints.
+pub struct OpenAIPlatformProvider;
+
+const STATUS_PAGE: &str = "https://status.openai.com";
+const DEFAULT_HOST: &str = "https://api.openai.com";
+const BILLING_URL: &str = "https://platform.openai.com/settings/organization/billing/overview";
+// The breakdowns cover this many days, and at least the current month.
+const BREAKDOWN_DAYS: i64 = 30;
+const MAX_PAGES: usize = 20;
+
+#[derive(Deserialize)]
+struct Page<T> {
+    #[serde(default = "Vec::new")]
+    data: Vec<Bucket<T>>,
+    #[serde(default)]
+    has_more: bool,
+    next_page: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct Bucket<T> {
+    start_time: i64,
+    #[serde(default = "Vec::new")]
+    results: Vec<T>,
+}
+
+#[derive(Deserialize)]
+struct CostResult {
+    amount: Option<CostAmount>,
+    line_item: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CostAmount {
+    value: Option<serde_json::Value>,
+    currency: Option<String>,
+}
+
+struct Credentials {
+    token: String,
+    organization: Option<String>,
+    source: SourcePreference,
+}
+
+#[async_trait]
+impl Provider for OpenAIPlatformProvider {
+    fn id(&self) -> ProviderId {
+        ProviderId::OpenAIPlatform
+    }
+
+    fn version(&self) -> &'static str {
+        "v1"
+    }
+
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Api, SourcePreference::Web]
+    }
+
+    fn status_page(&self) -> Option<&'static str> {
+        Some(STATUS_PAGE)
+    }
+
+    async fn fetch_usage(
+        &self,
+        args: &UsageRequest,
+        config: &Config,
+        source: SourcePreference,
+    ) -> Result<ProviderPayload> {
+        let credentials = self.credentials(config, source)?;
+        let mut payload = fetch_spend(self, &credentials).await?;
+        if args.status {
+            payload.status = fetch_status_payload(STATUS_PAGE, args.web_timeout).await;
+        }
+        Ok(payload)
+    }
+
+    async fn fetch_cost(&self, _args: &CostRequest, config: &Config) -> Result<ProviderPayload> {
+        let source = config
+            .provider_config(self.id())
+            .and_then(|cfg| cfg.source)
+            .unwrap_or(SourcePreference::Auto);
+        let credentials = self.credentials(config, source)?;
+        fetch_spend(self, &credentials).await
+    }
+}
+
+impl OpenAIPlatformProvider {
+    fn credentials(&self, config: &Config, source: SourcePreference) -> Result<Credentials> {
+        let cfg = config.provider_config(self.id());
+        let api_key = cfg
+            .as_ref()
+            .and_then(|c| c.api_key.clone())
+            .or_else(|| env_var_nonempty(&["OPENAI_ADMIN_KEY"]));
+        let session = cfg
+            .as_ref()
+            .and_then(|c| c.cookie_header.clone())
+            .or_else(|| env_var_nonempty(&["OPENAI_SESSION_KEY"]));
+        let organization = cfg
+            .as_ref()
+            .and_then(|c| c.workspace_id.clone())
+            .or_else(|| env_var_nonempty(&["OPENAI_ORG_ID"]));
+
+        let selected = match source {
+            SourcePreference::Auto => {
+                if api_key.is_some() || session.is_none() {
+                    SourcePreference::Api
+                } else {
+                    SourcePreference::Web
+                }
+            }
+            other => other,
+        };
+        let token = match selected {
+            SourcePreference::Api => api_key.ok_or_else(|| {
+                CliError::MissingCredentials(
+                    "OpenAI Admin API key missing. Set provider api_key or OPENAI_ADMIN_KEY."
+                        .to_string(),
+                )
+            })?,
+            SourcePreference::Web => session.map(|raw| session_token(&raw)).ok_or_else(|| {
+                CliError::MissingCredentials(
+                    "OpenAI session token missing. Set provider cookie_header or OPENAI_SESSION_KEY."
+                        .to_string(),
+                )
+            })?,
+            _ => return Err(CliError::UnsupportedSource(self.id(), selected.to_string()).into()),
+        };
+        Ok(Credentials {
+            token,
+            organization,
+            source: selected,
+        })
+    }
+}
+
+// The dashboard keeps a `sess-...` bearer token; accept it bare, with its
+// `Bearer` prefix, or inside a pasted `name=value; ...` list.
+fn session_token(raw: &str) -> String {
+    let raw = raw.trim();
+    let raw = raw.strip_prefix("Bearer ").unwrap_or(raw);
+    raw.split(';')
+        .map(|part| part.trim())
+        .map(|part| part.split_once('=').map(|(_, value)| value).unwrap_or(part))
+        .find(|value| value.starts_with("sess-"))
+        .unwrap_or(raw)
+        .to_string()
+}
+
+async fn fetch_spend(
+    provider: &OpenAIPlatformProvider,
+    credentials: &Credentials,
+) -> Result<ProviderPayload> {
+    let now = Utc::now();
+    let today = now.date_naive();
+    let month_start = today.with_day(1).unwrap_or(today);
+    let since = month_start.min(today - Duration::days(BREAKDOWN_DAYS - 1));
+    let start_time = start_of_day(since).timestamp();
+
+    let costs: Vec<Bucket<CostResult>> = fetch_buckets(
+        credentials,
+        "costs",
+        &[("group_by", "line_item".to_string())],
+        start_time,
+    )
+    .await?;
+
+    let spend = sum_costs(costs, month_start);
+
+    let next_month = month_start
+        .checked_add_months(chrono::Months::new(1))
+        .unwrap_or(today + Duration::days(1));
+    let login_method = match credentials.source {
+        SourcePreference::Web => "Session",
+        _ => "Admin API",
+    };
+    let usage = UsageSnapshot {
+        primary: None,
+        secondary: None,
+        tertiary: None,
+        provider_cost: Some(ProviderCostSnapshot {
+            used: spend.month,
+            limit: 0.0,
+            currency_code: spend
+                .currency
+                .map(|code| code.to_ascii_uppercase())
+                .unwrap_or_else(|| "USD".to_string()),
+            period: Some("Monthly".to_string()),
+            resets_at: Some(start_of_day(next_month)),
+            updated_at: now,
+        }),
+        updated_at: now,
+        identity: None,
+        account_email: None,
+        account_organization: credentials.organization.clone(),
+        login_method: Some(login_method.to_string()),
+    };
+    let source = match credentials.source {
+        SourcePreference::Web => "web",
+        _ => "api",
+    };
+    let mut payload = provider.ok_output(source, Some(usage));
+    payload.openai_dashboard = Some(OpenAIDashboardSnapshot {
+        signed_in_email: None,
+        code_review_remaining_percent: None,
+        credit_events: Vec::new(),
+        daily_breakdown: breakdown(spend.by_day),
+        usage_breakdown: Vec::new(),
+        credits_purchase_url: Some(BILLING_URL.to_string()),
+        primary_limit: None,
+        secondary_limit: None,
+        credits_remaining: None,
+        account_plan: None,
+        updated_at: now,
+    });
+    Ok(payload)
+}
+
+// Daily buckets of `/v1/organization/<endpoint>` from `start_time` on,
+// following `next_page` until the API reports no more.
+async fn fetch_buckets<T: DeserializeOwned>(
+    credentials: &Credentials,
+    endpoint: &str,
+    extra: &[(&str, String)],
+    start_time: i64,
+) -> Result<Vec<Bucket<T>>> {
+    // Not `OPENAI_BASE_URL`: that often points at OpenRouter or a local
+    // server, which must not see the admin key or session token.
+    let host = env_var_nonempty(&["FUELCHECK_OPENAI_ADMIN_URL"])
+        .map(|host| normalize_host(&host))
+        .unwrap_or_else(|| DEFAULT_HOST.to_string());
+    let host = host.trim_end_matches('/');
+    let host = host.strip_suffix("/v1").unwrap_or(host);
+    let url = format!("{}/v1/organization/{}", host, endpoint);
+
+    let mut buckets = Vec::new();
+    let mut page: Option<String> = None;
+    for _ in 0..MAX_PAGES {
+        let mut query = vec![
+            ("start_time", start_time.to_string()),
+            ("bucket_width", "1d".to_string()),
+            ("limit", (BREAKDOWN_DAYS + 1).to_string()),
+        ];
+        query.extend(extra.iter().map(|(key, value)| (*key, value.clone())));
+        if let Some(page) = &page {
+            query.push(("page", page.clone()));
+        }
+        let mut req = http::client()
+            .get(&url)
+            .query(&query)
+            .header("authorization", format!("Bearer {}", credentials.token))
+            .header("accept", "application/json");
+        if let Some(organization) = &credentials.organization {
+            req = req.header("openai-organization", organization);
+        }
+        let resp = req.send_with_retry().await?;
+        let status = resp.status();
+        let data = resp.bytes().await?;
+        if status.as_u16() == 401 || status.as_u16() == 403 {
+            let message = match credentials.source {
+                SourcePreference::Web => "OpenAI unauthorized. The session token may have expired.",
+                _ => "OpenAI unauthorized. The key must be an admin key (sk-admin-...).",
+            };
+            return Err(HttpStatusError::new(status, message).into());
+        }
+        if !status.is_success() {
+            return Err(HttpStatusError::new(
+                status,
+                format!(
+                    "OpenAI {} request failed (HTTP {})",
+                    endpoint,
+                    status.as_u16()
+                ),
+            )
+            .into());
+        }
+        let report: Page<T> = serde_json::from_slice(&data)?;
+        page = next_page(&report);
+        buckets.extend(report.data);
+        if page.is_none() {
+            break;
+        }
+    }
+    Ok(buckets)
+}
+
+fn next_page<T>(report: &Page<T>) -> Option<String> {
+    report.next_page.clone().filter(|_| report.has_more)
+}
+
+struct Spend {
+    month: f64,
+    currency: Option<String>,
+    by_day: BTreeMap<String, BTreeMap<String, f64>>,
+}
+
+// The month's total, plus every fetched day's spend per line item.
+fn sum_costs(costs: Vec<Bucket<CostResult>>, month_start: NaiveDate) -> Spend {
+    let mut spend = Spend {
+        month: 0.0,
+        currency: None,
+        by_day: BTreeMap::new(),
+    };
+    for bucket in costs {
+        let Some(day) = bucket_day(bucket.start_time) else {
+            continue;
+        };
+        for result in bucket.results {
+            let Some(amount) = result.amount else {
+                continue;
+            };
+            let value = amount.value.as_ref().and_then(value_to_f64).unwrap_or(0.0);
+            if spend.currency.is_none() {
+                spend.currency = amount.currency;
+            }
+            if day >= month_start {
+                spend.month += value;
+            }
+            let line_item = result.line_item.unwrap_or_else(|| "other".to_string());
+            *spend
+                .by_day
+                .entry(day.format("%Y-%m-%d").to_string())
+                .or_default()
+                .entry(line_item)
+                .or_default() += value;
+        }
+    }
+    spend
+}
+
+fn breakdown(days: BTreeMap<String, BTreeMap<String, f64>>) -> Vec<OpenAIDashboardDailyBreakdown> {
+    days.into_iter()
+        .map(|(day, services)| {
+            let mut services: Vec<OpenAIDashboardServiceUsage> = services
+                .into_iter()
+                .map(|(service, credits_used)| OpenAIDashboardServiceUsage {
+                    service,
+                    credits_used,
+                })
+                .collect();
+            services.sort_by(|a, b| b.credits_used.total_cmp(&a.credits_used));
+            OpenAIDashboardDailyBreakdown {
+                day,
+                total_credits_used: services.iter().map(|s| s.credits_used).sum(),
+                services,
+            }
+        })
+        .collect()
+}
+
+fn bucket_day(start_time: i64) -> Option<NaiveDate> {
+    DateTime::<Utc>::from_timestamp(start_time, 0).map(|at| at.date_naive())
+}
+
+fn start_of_day(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(NaiveTime::MIN).and_utc()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn session_token_is_found_in_pasted_headers() {
+        assert_eq!(session_token(" sess-abc "), "sess-abc");
+        assert_eq!(session_token("Bearer sess-abc"), "sess-abc");
+        assert_eq!(
+            session_token("_ga=GA1.1; __Secure-next-auth=sess-abc; theme=dark"),
+            "sess-abc"
+        );
+        assert_eq!(session_token("not-a-session"), "not-a-session");
+    }
+
+    #[test]
+    fn paging_stops_without_has_more() {
+        let more: Page<CostResult> =
+            serde_json::from_str(r#"{"data": [], "has_more": true, "next_page": "p2"}"#).unwrap();
+        assert_eq!(next_page(&more).as_deref(), Some("p2"));
+        let last: Page<CostResult> =
+            serde_json::from_str(r#"{"data": [], "has_more": false, "next_page": "p3"}"#).unwrap();
+        assert_eq!(next_page(&last), None);
+    }
+
+    #[test]
+    fn costs_sum_the_month_and_break_down_by_day() {
+        let page: Page<CostResult> = serde_json::from_str(
+            r#"{"data": [
+                {"start_time": 1738281600, "results": [
+                    {"amount": {"value": 4.0, "currency": "usd"}, "line_item": "gpt-4o, input"}
+                ]},
+                {"start_time": 1738368000, "results": [
+                    {"amount": {"value": 1.25, "currency": "usd"}, "line_item": "gpt-4o, input"},
+                    {"amount": {"value": "0.75", "currency": "usd"}},
+                    {"line_item": "no amount"}
+                ]},
+                {"start_time": 1738454400, "results": [
+                    {"amount": {"value": 2.0, "currency": "usd"}, "line_item": "gpt-4o, input"}
+                ]}
+            ]}"#,
+        )
+        .unwrap();
+        let month_start = NaiveDate::from_ymd_opt(2025, 2, 1).unwrap();
+        let spend = sum_costs(page.data, month_start);
+
+        assert_eq!(spend.month, 4.0);
+        assert_eq!(spend.currency.as_deref(), Some("usd"));
+        let days: Vec<&str> = spend.by_day.keys().map(String::as_str).collect();
+        assert_eq!(days, ["2025-01-31", "2025-02-01", "2025-02-02"]);
+        let first = &spend.by_day["2025-02-01"];
+        assert_eq!(first["gpt-4o, input"], 1.25);
+        assert_eq!(first["other"], 0.75);
+        assert!(!first.contains_key("no amount"));
+    }
+}
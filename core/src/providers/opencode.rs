@@ -1,5 +1,6 @@
 use crate::config::Config;
-use crate::errors::CliError;
+use crate::errors::{CliError, HttpStatusError};
+use crate::http::{self, RequestBuilderExt};
 use crate::model::{ProviderIdentitySnapshot, ProviderPayload, RateWindow, UsageSnapshot};
 use crate::providers::{Provider, ProviderId, SourcePreference, env_var_nonempty};
 use crate::service::UsageRequest;
@@ -22,6 +23,10 @@ impl Provider for OpenCodeProvider {
         "2025-01-01"
     }
 
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Web]
+    }
+
     async fn fetch_usage(
         &self,
         args: &UsageRequest,
@@ -42,7 +47,9 @@ impl Provider for OpenCodeProvider {
             .and_then(|c| c.cookie_header.clone())
             .or_else(|| env_var_nonempty(&["OPENCODE_COOKIE", "OPENCODE_COOKIE_HEADER"]))
             .ok_or_else(|| {
-                anyhow!("OpenCode cookie header missing. Set provider cookie_header.")
+                CliError::MissingCredentials(
+                    "OpenCode cookie header missing. Set provider cookie_header.".to_string(),
+                )
             })?;
         let workspace_override = cfg
             .as_ref()
@@ -135,7 +142,7 @@ async fn fetch_server_text(
     referer: &str,
 ) -> Result<String> {
     let url = server_request_url(base_url, server_id, args, method);
-    let client = reqwest::Client::new();
+    let client = http::client();
     let mut req = match method {
         "POST" => client.post(url),
         _ => client.get(url),
@@ -157,14 +164,20 @@ async fn fetch_server_text(
     {
         req = req.header("content-type", "application/json").json(args);
     }
-    let resp = req.send().await?;
+    let resp = req.send_with_retry().await?;
     let status = resp.status();
     let body = resp.text().await?;
     if status.as_u16() == 401 || status.as_u16() == 403 {
-        return Err(anyhow!("OpenCode unauthorized. Cookie may be invalid."));
+        return Err(
+            HttpStatusError::new(status, "OpenCode unauthorized. Cookie may be invalid.").into(),
+        );
     }
     if !status.is_success() {
-        return Err(anyhow!("OpenCode API error (HTTP {})", status.as_u16()));
+        return Err(HttpStatusError::new(
+            status,
+            format!("OpenCode API error (HTTP {})", status.as_u16()),
+        )
+        .into());
     }
     Ok(body)
 }
@@ -0,0 +1,233 @@
+use crate::config::Config;
+use crate::errors::{CliError, HttpStatusError};
+use crate::http::{self, RequestBuilderExt};
+use crate::model::{CreditsSnapshot, ProviderPayload, RateWindow, UsageSnapshot};
+use crate::providers::{
+    Provider, ProviderId, SourcePreference, env_var_nonempty, normalize_host, used_percent_from,
+    value_to_f64,
+};
+use crate::service::UsageRequest;
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use chrono::Utc;
+use serde_json::Value;
+
+// The API (prepaid credits, API key) and Perplexity Pro (daily Pro search
+// quota, signed-in cookie) are billed apart; one source each.
+pub struct PerplexityProvider;
+
+const DEFAULT_API_HOST: &str = "https://api.perplexity.ai";
+const DEFAULT_WEB_HOST: &str = "https://www.perplexity.ai";
+// Pro searches are counted over a rolling day.
+const PRO_WINDOW_MINUTES: i64 = 24 * 60;
+
+#[async_trait]
+impl Provider for PerplexityProvider {
+    fn id(&self) -> ProviderId {
+        ProviderId::Perplexity
+    }
+
+    fn version(&self) -> &'static str {
+        "2025-01-01"
+    }
+
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Api, SourcePreference::Web]
+    }
+
+    async fn fetch_usage(
+        &self,
+        args: &UsageRequest,
+        config: &Config,
+        source: SourcePreference,
+    ) -> Result<ProviderPayload> {
+        let cfg = config.provider_config(self.id());
+        let api_key = cfg
+            .as_ref()
+            .and_then(|c| c.api_key.clone())
+            .or_else(|| env_var_nonempty(&["PERPLEXITY_API_KEY", "PPLX_API_KEY"]));
+        let cookie_header = cfg
+            .as_ref()
+            .and_then(|c| c.cookie_header.clone())
+            .or_else(|| env_var_nonempty(&["PERPLEXITY_COOKIE"]));
+
+        let selected = match source {
+            SourcePreference::Auto => {
+                if cookie_header.is_some() || api_key.is_none() {
+                    SourcePreference::Web
+                } else {
+                    SourcePreference::Api
+                }
+            }
+            other => other,
+        };
+
+        match selected {
+            SourcePreference::Api => {
+                let key = api_key.ok_or_else(|| {
+                    CliError::MissingCredentials(
+                        "Perplexity API key missing. Set provider api_key or PERPLEXITY_API_KEY."
+                            .to_string(),
+                    )
+                })?;
+                let remaining = fetch_api_balance(&key).await?;
+                let mut payload = self.ok_output(
+                    "api",
+                    Some(UsageSnapshot {
+                        primary: None,
+                        secondary: None,
+                        tertiary: None,
+                        provider_cost: None,
+                        updated_at: Utc::now(),
+                        identity: None,
+                        account_email: None,
+                        account_organization: None,
+                        login_method: Some("API".to_string()),
+                    }),
+                );
+                if !args.no_credits {
+                    payload.credits = Some(CreditsSnapshot {
+                        remaining,
+                        events: Vec::new(),
+                        updated_at: Utc::now(),
+                    });
+                }
+                Ok(payload)
+            }
+            SourcePreference::Web => {
+                let cookie = cookie_header.ok_or_else(|| {
+                    CliError::MissingCredentials(
+                        "Perplexity cookie header missing. Set provider cookie_header or PERPLEXITY_COOKIE."
+                            .to_string(),
+                    )
+                })?;
+                let usage = fetch_pro_quota(&cookie).await?;
+                Ok(self.ok_output("web", Some(usage)))
+            }
+            _ => Err(CliError::UnsupportedSource(self.id(), selected.to_string()).into()),
+        }
+    }
+}
+
+fn host(env: &str, default: &str) -> String {
+    env_var_nonempty(&[env])
+        .map(|host| normalize_host(&host))
+        .unwrap_or_else(|| default.to_string())
+        .trim_end_matches('/')
+        .to_string()
+}
+
+// Prepaid API credit in USD.
+async fn fetch_api_balance(key: &str) -> Result<f64> {
+    let url = format!(
+        "{}/v1/credits",
+        host("PERPLEXITY_BASE_URL", DEFAULT_API_HOST)
+    );
+    let resp = http::client()
+        .get(url)
+        .header("authorization", format!("Bearer {}", key))
+        .header("accept", "application/json")
+        .send_with_retry()
+        .await?;
+    let status = resp.status();
+    let data = resp.bytes().await?;
+    if status.as_u16() == 401 || status.as_u16() == 403 {
+        return Err(HttpStatusError::new(
+            status,
+            "Perplexity unauthorized. API key may be invalid.",
+        )
+        .into());
+    }
+    if !status.is_success() {
+        return Err(HttpStatusError::new(
+            status,
+            format!("Perplexity API error (HTTP {})", status.as_u16()),
+        )
+        .into());
+    }
+    let json: Value = serde_json::from_slice(&data)?;
+    let root = json.get("data").unwrap_or(&json);
+    ["balance", "credits", "remaining", "balance_usd"]
+        .iter()
+        .find_map(|key| root.get(*key).and_then(value_to_f64))
+        .ok_or_else(|| anyhow!("Perplexity credit balance missing"))
+}
+
+async fn fetch_pro_quota(cookie: &str) -> Result<UsageSnapshot> {
+    let url = format!(
+        "{}/rest/user/settings",
+        host("PERPLEXITY_WEB_HOST", DEFAULT_WEB_HOST)
+    );
+    let resp = http::client()
+        .get(url)
+        .header("cookie", cookie)
+        .header("accept", "application/json")
+        .send_with_retry()
+        .await?;
+    let status = resp.status();
+    let data = resp.bytes().await?;
+    if status.as_u16() == 401 || status.as_u16() == 403 {
+        return Err(HttpStatusError::new(
+            status,
+            "Perplexity unauthorized. Cookie may be invalid.",
+        )
+        .into());
+    }
+    if !status.is_success() {
+        return Err(HttpStatusError::new(
+            status,
+            format!("Perplexity request failed (HTTP {})", status.as_u16()),
+        )
+        .into());
+    }
+    let json: Value = serde_json::from_slice(&data)?;
+    parse_settings(&json)
+}
+
+// `query_count_copilot` counts the Pro searches of the current day and
+// `gpt4_limit` is the daily cap (it predates the Pro search name).
+fn parse_settings(json: &Value) -> Result<UsageSnapshot> {
+    let number = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| json.get(*key).and_then(value_to_f64))
+    };
+    let used = number(&["query_count_copilot", "pro_search_count"]);
+    let limit = number(&["gpt4_limit", "pro_search_limit"]);
+    let used_percent =
+        used_percent_from(used, limit).ok_or_else(|| anyhow!("Perplexity usage data missing"))?;
+    let plan = json
+        .get("subscription_tier")
+        .and_then(Value::as_str)
+        .filter(|tier| !tier.is_empty() && *tier != "none")
+        .map(|tier| {
+            let mut chars = tier.chars();
+            match chars.next() {
+                Some(first) => format!(
+                    "Perplexity {}{}",
+                    first.to_ascii_uppercase(),
+                    chars.as_str()
+                ),
+                None => "Perplexity".to_string(),
+            }
+        })
+        .or_else(|| {
+            (json.get("subscription_status").and_then(Value::as_str) == Some("active"))
+                .then(|| "Perplexity Pro".to_string())
+        });
+    Ok(UsageSnapshot {
+        primary: Some(RateWindow {
+            used_percent: used_percent.clamp(0.0, 100.0),
+            window_minutes: Some(PRO_WINDOW_MINUTES),
+            resets_at: None,
+            reset_description: None,
+        }),
+        secondary: None,
+        tertiary: None,
+        provider_cost: None,
+        updated_at: Utc::now(),
+        identity: None,
+        account_email: None,
+        account_organization: None,
+        login_method: plan,
+    })
+}
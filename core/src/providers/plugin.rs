@@ -0,0 +1,248 @@
+use crate::config::Config;
+use crate::http;
+use crate::model::ProviderPayload;
+use crate::providers::{Provider, ProviderId, SourcePreference};
+use crate::service::{CostRequest, UsageRequest};
+use anyhow::{Result, anyhow};
+use async_trait::async_trait;
+use serde_json::{Value, json};
+use std::process::Stdio;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+// A provider entry with a `command` runs that executable for every fetch. It
+// gets one JSON request on stdin and answers with one ProviderPayload on
+// stdout; anything on stderr is only shown when it fails. The request carries
+// the plugin's whole provider entry, `api_key` and `cookie_header` included.
+pub struct PluginProvider {
+    name: &'static str,
+}
+
+// Bumped when the request format changes incompatibly.
+pub const PLUGIN_PROTOCOL: u32 = 1;
+
+impl PluginProvider {
+    pub(crate) fn new(name: &'static str) -> Self {
+        Self { name }
+    }
+}
+
+#[async_trait]
+impl Provider for PluginProvider {
+    fn id(&self) -> ProviderId {
+        ProviderId::External(self.name)
+    }
+
+    // The protocol version; plugins report their own in each payload.
+    fn version(&self) -> &'static str {
+        "plugin-v1"
+    }
+
+    // Which sources mean anything is up to the plugin.
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[
+            SourcePreference::Oauth,
+            SourcePreference::Web,
+            SourcePreference::Cli,
+            SourcePreference::Api,
+            SourcePreference::Local,
+        ]
+    }
+
+    async fn fetch_usage(
+        &self,
+        args: &UsageRequest,
+        config: &Config,
+        source: SourcePreference,
+    ) -> Result<ProviderPayload> {
+        let request = json!({
+            "protocol": PLUGIN_PROTOCOL,
+            "command": "usage",
+            "provider": self.name,
+            "source": source,
+            "noCredits": args.no_credits,
+            "account": args.account,
+            "offline": http::network_disabled(),
+            "config": config.provider_config(self.id()),
+        });
+        self.run(&request, config, source).await
+    }
+
+    async fn fetch_cost(&self, _args: &CostRequest, config: &Config) -> Result<ProviderPayload> {
+        let source = config
+            .provider_config(self.id())
+            .and_then(|cfg| cfg.source)
+            .unwrap_or(SourcePreference::Auto);
+        let request = json!({
+            "protocol": PLUGIN_PROTOCOL,
+            "command": "cost",
+            "provider": self.name,
+            "source": source,
+            "offline": http::network_disabled(),
+            "config": config.provider_config(self.id()),
+        });
+        self.run(&request, config, source).await
+    }
+}
+
+impl PluginProvider {
+    async fn run(
+        &self,
+        request: &Value,
+        config: &Config,
+        source: SourcePreference,
+    ) -> Result<ProviderPayload> {
+        let cfg = config.provider_config(self.id());
+        let command = cfg
+            .as_ref()
+            .and_then(|c| c.command.clone())
+            .ok_or_else(|| anyhow!("plugin {} has no command", self.name))?;
+        let args = cfg.and_then(|c| c.args).unwrap_or_default();
+        let mut child = Command::new(&command)
+            .args(&args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|err| anyhow!("plugin {} could not start {}: {}", self.name, command, err))?;
+        let mut line = serde_json::to_vec(request)?;
+        line.push(b'\n');
+        let stdin = child.stdin.take();
+        let exchange = async move {
+            if let Some(mut stdin) = stdin {
+                // A plugin that exits without reading explains itself through
+                // its exit status, which says more than the broken pipe.
+                match stdin.write_all(&line).await {
+                    Err(err) if err.kind() != std::io::ErrorKind::BrokenPipe => {
+                        return Err(anyhow!(
+                            "plugin {} did not take its request: {}",
+                            self.name,
+                            err
+                        ));
+                    }
+                    _ => {}
+                }
+            }
+            child
+                .wait_with_output()
+                .await
+                .map_err(|err| anyhow!("plugin {} could not be read: {}", self.name, err))
+        };
+
+        let timeout = http::current_timeout();
+        let output = tokio::time::timeout(timeout, exchange)
+            .await
+            .map_err(|_| {
+                anyhow!(
+                    "plugin {} timed out after {}s",
+                    self.name,
+                    timeout.as_secs()
+                )
+            })??;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            let detail = stderr.lines().rev().find(|line| !line.trim().is_empty());
+            return Err(anyhow!(
+                "plugin {} failed ({}){}",
+                self.name,
+                output.status,
+                detail
+                    .map(|line| format!(": {}", line.trim()))
+                    .unwrap_or_default()
+            ));
+        }
+
+        let mut payload: Value = serde_json::from_slice(&output.stdout)
+            .map_err(|err| anyhow!("plugin {} printed invalid JSON: {}", self.name, err))?;
+        let Some(fields) = payload.as_object_mut() else {
+            return Err(anyhow!("plugin {} must print a JSON object", self.name));
+        };
+        // The id is whatever the config calls the plugin, not what it claims.
+        fields.insert("provider".to_string(), json!(self.name));
+        let source = match source {
+            SourcePreference::Auto => "plugin".to_string(),
+            other => other.to_string(),
+        };
+        fields.entry("source").or_insert_with(|| json!(source));
+        serde_json::from_value(payload)
+            .map_err(|err| anyhow!("plugin {} printed an invalid payload: {}", self.name, err))
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::config::ProviderConfig;
+    use crate::http::{RequestPolicy, with_request_policy};
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use std::path::Path;
+    use std::time::Duration;
+
+    async fn run_script(dir: &Path, name: &'static str, body: &str) -> Result<ProviderPayload> {
+        let script = dir.join(name);
+        fs::write(&script, format!("#!/bin/sh\n{}\n", body)).unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o755)).unwrap();
+        let id = ProviderId::External(name);
+        let mut entry = ProviderConfig::default_provider(id);
+        entry.command = Some(script.display().to_string());
+        let config = Config {
+            providers: Some(vec![entry]),
+            ..Config::default()
+        };
+        let policy = RequestPolicy {
+            timeout: Some(Duration::from_secs(1)),
+            ..RequestPolicy::default()
+        };
+        let provider = PluginProvider::new(name);
+        with_request_policy(
+            policy,
+            provider.fetch_cost(&CostRequest::default(), &config),
+        )
+        .await
+    }
+
+    #[tokio::test]
+    async fn plugin_runs_report_each_failure_by_name() {
+        let dir = std::env::temp_dir().join(format!("fuelcheck-plugin-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+
+        let payload = run_script(
+            &dir,
+            "ok",
+            r#"cat > /dev/null; echo '{"usage":{"updatedAt":"2026-01-01T00:00:00Z"}}'"#,
+        )
+        .await
+        .unwrap();
+        assert_eq!(payload.provider, "ok");
+        assert_eq!(payload.source, "plugin");
+        assert!(payload.usage.is_some());
+
+        // Exits without reading stdin.
+        let err = run_script(&dir, "failing", "echo 'token expired' >&2; exit 3")
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string().starts_with("plugin failing failed"),
+            "{}",
+            err
+        );
+        assert!(err.to_string().ends_with(": token expired"), "{}", err);
+
+        let err = run_script(&dir, "garbled", "cat > /dev/null; echo 'not json'")
+            .await
+            .unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("plugin garbled printed invalid JSON"),
+            "{}",
+            err
+        );
+
+        let err = run_script(&dir, "slow", "sleep 5").await.unwrap_err();
+        assert_eq!(err.to_string(), "plugin slow timed out after 1s");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,5 +1,8 @@
+use crate::model::{ProviderCostSnapshot, UsageSnapshot};
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde_json::Value;
+use std::path::Path;
 
 pub fn env_var_nonempty(names: &[&str]) -> Option<String> {
     for name in names {
@@ -30,14 +33,6 @@ pub fn parse_epoch(value: i64) -> Option<DateTime<Utc>> {
     }
 }
 
-pub fn parse_epoch_f64(value: f64) -> Option<DateTime<Utc>> {
-    if value <= 0.0 {
-        return None;
-    }
-    let as_i64 = value.round() as i64;
-    parse_epoch(as_i64)
-}
-
 pub fn used_percent_from(used: Option<f64>, limit: Option<f64>) -> Option<f64> {
     let used = used?;
     let limit = limit?;
@@ -80,3 +75,35 @@ pub fn normalize_host(host: &str) -> String {
         format!("https://{}", trimmed)
     }
 }
+
+// Today's spend from local logs as a cost with no limit, for providers that
+// have no account to ask.
+pub fn daily_spend_usage(used: f64, resets_at: Option<DateTime<Utc>>) -> UsageSnapshot {
+    let now = Utc::now();
+    UsageSnapshot {
+        primary: None,
+        secondary: None,
+        tertiary: None,
+        provider_cost: Some(ProviderCostSnapshot {
+            used,
+            limit: 0.0,
+            currency_code: "USD".to_string(),
+            period: Some("Daily".to_string()),
+            resets_at,
+            updated_at: now,
+        }),
+        updated_at: now,
+        identity: None,
+        account_email: None,
+        account_organization: None,
+        login_method: None,
+    }
+}
+
+pub fn remove_credentials_file(path: &Path) -> Result<Vec<String>> {
+    match std::fs::remove_file(path) {
+        Ok(()) => Ok(vec![path.display().to_string()]),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err).with_context(|| format!("remove {}", path.display())),
+    }
+}
@@ -1,8 +1,10 @@
 use crate::config::Config;
-use crate::errors::CliError;
+use crate::errors::{CliError, HttpStatusError};
+use crate::http::{self, RequestBuilderExt};
 use crate::model::{ProviderIdentitySnapshot, ProviderPayload, RateWindow, UsageSnapshot};
 use crate::providers::{Provider, ProviderId, SourcePreference, parse_rfc3339};
 use crate::service::UsageRequest;
+use crate::warnings::push_warning;
 use anyhow::{Result, anyhow};
 use async_trait::async_trait;
 use base64::Engine;
@@ -23,6 +25,10 @@ impl Provider for VertexAIProvider {
         "2025-01-01"
     }
 
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Oauth]
+    }
+
     async fn fetch_usage(
         &self,
         _args: &UsageRequest,
@@ -40,6 +46,7 @@ impl Provider for VertexAIProvider {
         let mut creds = VertexAIOAuthCredentials::load()?;
         if creds.needs_refresh() {
             creds = refresh_vertex_token(&creds).await?;
+            push_warning("gcloud access token was stale and has been refreshed");
         }
 
         let usage = fetch_vertex_usage(&creds).await;
@@ -72,7 +79,9 @@ impl VertexAIOAuthCredentials {
     }
 
     fn load() -> Result<Self> {
-        let path = adc_credentials_path().ok_or_else(|| anyhow!("gcloud credentials not found"))?;
+        let path = adc_credentials_path().ok_or_else(|| {
+            CliError::MissingCredentials("gcloud credentials not found".to_string())
+        })?;
         let data = std::fs::read(&path)?;
         let json: serde_json::Value = serde_json::from_slice(&data)?;
         let client_id = json
@@ -117,7 +126,7 @@ impl VertexAIOAuthCredentials {
 async fn refresh_vertex_token(
     creds: &VertexAIOAuthCredentials,
 ) -> Result<VertexAIOAuthCredentials> {
-    let client = reqwest::Client::new();
+    let client = http::client();
     let resp = client
         .post("https://oauth2.googleapis.com/token")
         .header("content-type", "application/x-www-form-urlencoded")
@@ -127,15 +136,16 @@ async fn refresh_vertex_token(
             urlencoding::encode(&creds.client_secret),
             urlencoding::encode(&creds.refresh_token),
         ))
-        .send()
+        .send_with_retry()
         .await?;
     let status = resp.status();
     let data = resp.bytes().await?;
     if !status.is_success() {
-        return Err(anyhow!(
-            "Vertex AI token refresh failed (HTTP {})",
-            status.as_u16()
-        ));
+        return Err(HttpStatusError::new(
+            status,
+            format!("Vertex AI token refresh failed (HTTP {})", status.as_u16()),
+        )
+        .into());
     }
     let json: serde_json::Value = serde_json::from_slice(&data)?;
     let access_token = json
@@ -312,27 +322,33 @@ async fn fetch_time_series(
             "https://monitoring.googleapis.com/v3/projects/{}/timeSeries",
             project_id
         );
-        let client = reqwest::Client::new();
+        let client = http::client();
         let resp = client
             .get(url)
             .bearer_auth(access_token)
             .query(&params)
-            .send()
+            .send_with_retry()
             .await?;
         let status = resp.status();
         let data = resp.bytes().await?;
         if status.as_u16() == 401 || status.as_u16() == 403 {
-            return Err(anyhow!(
-                "Vertex AI unauthorized. Re-run gcloud auth application-default login."
-            ));
+            return Err(HttpStatusError::new(
+                status,
+                "Vertex AI unauthorized. Re-run gcloud auth application-default login.",
+            )
+            .into());
         }
         if !status.is_success() {
             let body = String::from_utf8_lossy(&data);
-            return Err(anyhow!(
-                "Vertex AI monitoring error (HTTP {}): {}",
-                status.as_u16(),
-                body
-            ));
+            return Err(HttpStatusError::new(
+                status,
+                format!(
+                    "Vertex AI monitoring error (HTTP {}): {}",
+                    status.as_u16(),
+                    body
+                ),
+            )
+            .into());
         }
         let decoded: MonitoringTimeSeriesResponse = serde_json::from_slice(&data)?;
         if let Some(series) = decoded.time_series {
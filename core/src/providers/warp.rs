@@ -1,5 +1,6 @@
 use crate::config::Config;
-use crate::errors::CliError;
+use crate::errors::{CliError, HttpStatusError};
+use crate::http::{self, RequestBuilderExt};
 use crate::model::{ProviderIdentitySnapshot, ProviderPayload, RateWindow, UsageSnapshot};
 use crate::providers::{Provider, ProviderId, SourcePreference, env_var_nonempty, parse_rfc3339};
 use crate::service::UsageRequest;
@@ -20,6 +21,10 @@ impl Provider for WarpProvider {
         "2025-01-01"
     }
 
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Api]
+    }
+
     async fn fetch_usage(
         &self,
         _args: &UsageRequest,
@@ -32,7 +37,9 @@ impl Provider for WarpProvider {
             .and_then(|c| c.api_key.clone())
             .or_else(|| env_var_nonempty(&["WARP_API_KEY", "WARP_TOKEN"]))
             .ok_or_else(|| {
-                anyhow!("Warp API key missing. Set provider api_key or WARP_API_KEY.")
+                CliError::MissingCredentials(
+                    "Warp API key missing. Set provider api_key or WARP_API_KEY.".to_string(),
+                )
             })?;
 
         let selected = match source {
@@ -44,7 +51,7 @@ impl Provider for WarpProvider {
         }
 
         let payload = warp_graphql_payload();
-        let client = reqwest::Client::new();
+        let client = http::client();
         let resp = client
             .post("https://app.warp.dev/graphql/v2?op=GetRequestLimitInfo")
             .header("content-type", "application/json")
@@ -56,15 +63,21 @@ impl Provider for WarpProvider {
             .header("authorization", format!("Bearer {}", api_key))
             .header("user-agent", "Warp/1.0")
             .json(&payload)
-            .send()
+            .send_with_retry()
             .await?;
         let status = resp.status();
         let data = resp.bytes().await?;
         if status.as_u16() == 401 || status.as_u16() == 403 {
-            return Err(anyhow!("Warp unauthorized. API key may be invalid."));
+            return Err(
+                HttpStatusError::new(status, "Warp unauthorized. API key may be invalid.").into(),
+            );
         }
         if !status.is_success() {
-            return Err(anyhow!("Warp API error (HTTP {})", status.as_u16()));
+            return Err(HttpStatusError::new(
+                status,
+                format!("Warp API error (HTTP {})", status.as_u16()),
+            )
+            .into());
         }
         let json: Value = serde_json::from_slice(&data)?;
         let usage = parse_warp_usage(&json)?;
@@ -1,12 +1,13 @@
 use crate::config::Config;
-use crate::errors::CliError;
+use crate::errors::{CliError, HttpStatusError};
+use crate::http::{self, RequestBuilderExt};
 use crate::model::{ProviderIdentitySnapshot, ProviderPayload, RateWindow, UsageSnapshot};
 use crate::providers::{
     Provider, ProviderId, SourcePreference, env_var_nonempty, normalize_host, value_to_f64,
     value_to_i64,
 };
 use crate::service::UsageRequest;
-use anyhow::{Result, anyhow};
+use anyhow::Result;
 use async_trait::async_trait;
 use chrono::Utc;
 use serde_json::Value;
@@ -23,6 +24,10 @@ impl Provider for ZaiProvider {
         "2025-01-01"
     }
 
+    fn supported_sources(&self) -> &'static [SourcePreference] {
+        &[SourcePreference::Api]
+    }
+
     async fn fetch_usage(
         &self,
         _args: &UsageRequest,
@@ -35,7 +40,9 @@ impl Provider for ZaiProvider {
             .and_then(|c| c.api_key.clone())
             .or_else(|| env_var_nonempty(&["Z_AI_API_KEY"]))
             .ok_or_else(|| {
-                anyhow!("z.ai API token missing. Set provider api_key or Z_AI_API_KEY.")
+                CliError::MissingCredentials(
+                    "z.ai API token missing. Set provider api_key or Z_AI_API_KEY.".to_string(),
+                )
             })?;
 
         let selected = match source {
@@ -47,20 +54,26 @@ impl Provider for ZaiProvider {
         }
 
         let url = resolve_zai_quota_url(cfg.as_ref());
-        let client = reqwest::Client::new();
+        let client = http::client();
         let resp = client
             .get(url)
             .header("authorization", format!("Bearer {}", token))
             .header("accept", "application/json")
-            .send()
+            .send_with_retry()
             .await?;
         let status = resp.status();
         let data = resp.bytes().await?;
         if status.as_u16() == 401 || status.as_u16() == 403 {
-            return Err(anyhow!("z.ai unauthorized. Token may be invalid."));
+            return Err(
+                HttpStatusError::new(status, "z.ai unauthorized. Token may be invalid.").into(),
+            );
         }
         if !status.is_success() {
-            return Err(anyhow!("z.ai quota API error (HTTP {})", status.as_u16()));
+            return Err(HttpStatusError::new(
+                status,
+                format!("z.ai quota API error (HTTP {})", status.as_u16()),
+            )
+            .into());
         }
         let json: Value = serde_json::from_slice(&data)?;
         let usage = parse_zai_usage(&json)?;
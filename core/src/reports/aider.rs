@@ -0,0 +1,368 @@
+use crate::reports::codex::resolve_timezone;
+use crate::reports::priced::{PricedUsage, build_priced_report, today_cost};
+use crate::reports::types::{CostReportGroupBy, CostReportKind, ProviderReport};
+use crate::reports::{LogLine, MAX_LOG_LINE_BYTES, open_log, read_log_line};
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use globwalk::GlobWalkerBuilder;
+use serde_json::Value;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
+
+pub struct AiderReportOptions<'a> {
+    pub report: CostReportKind,
+    pub since: Option<&'a str>,
+    pub until: Option<&'a str>,
+    pub timezone: Option<&'a str>,
+    pub group_by: Option<CostReportGroupBy>,
+}
+
+// Where Aider's usage is read from. Aider prices every message itself, so
+// no pricing table is needed.
+pub struct AiderSources {
+    // `--analytics-log` files: one JSON event per line, with exact tokens.
+    pub analytics_logs: Vec<PathBuf>,
+    // Project directories searched for `.aider.chat.history.md`.
+    pub history_roots: Vec<PathBuf>,
+}
+
+const HISTORY_FILE: &str = ".aider.chat.history.md";
+// Projects are looked for this many directories below each root.
+const HISTORY_DEPTH: usize = 4;
+const AGENT: &str = "aider";
+
+impl AiderSources {
+    // `AIDER_ANALYTICS_LOG` is the variable Aider itself reads for
+    // `--analytics-log`. Chat histories are searched under
+    // `AIDER_HISTORY_DIRS` (comma-separated) or the current directory.
+    pub fn from_env() -> Self {
+        let list = |name: &str| -> Vec<PathBuf> {
+            std::env::var(name)
+                .unwrap_or_default()
+                .split(',')
+                .map(str::trim)
+                .filter(|path| !path.is_empty())
+                .map(PathBuf::from)
+                .collect()
+        };
+        let mut history_roots = list("AIDER_HISTORY_DIRS");
+        if history_roots.is_empty() {
+            history_roots.extend(std::env::current_dir().ok());
+        }
+        Self {
+            analytics_logs: list("AIDER_ANALYTICS_LOG"),
+            history_roots,
+        }
+    }
+}
+
+pub fn build_report(options: &AiderReportOptions<'_>) -> Result<ProviderReport> {
+    build_report_from(&AiderSources::from_env(), options)
+}
+
+fn build_report_from(
+    sources: &AiderSources,
+    options: &AiderReportOptions<'_>,
+) -> Result<ProviderReport> {
+    let timezone = resolve_timezone(options.timezone)?;
+    let (usages, warnings) = load_usages(sources, timezone)?;
+    let report = build_priced_report(
+        options.report,
+        &usages,
+        options.since,
+        options.until,
+        timezone,
+        options.group_by == Some(CostReportGroupBy::Agent),
+    );
+    Ok(report.with_warnings(warnings))
+}
+
+// Spend so far today in the report timezone, for `usage` and `cost`, and
+// when that day ends.
+pub fn today_cost_usd() -> Result<(f64, Option<DateTime<Utc>>)> {
+    let timezone = resolve_timezone(None)?;
+    let (usages, _) = load_usages(&AiderSources::from_env(), timezone)?;
+    Ok(today_cost(&usages, timezone))
+}
+
+// The analytics log is exact, so chat histories are only read without one;
+// reading both would count every message twice.
+fn load_usages(sources: &AiderSources, timezone: Tz) -> Result<(Vec<PricedUsage>, Vec<String>)> {
+    let mut usages = Vec::new();
+    let mut warnings = Vec::new();
+    let logs: Vec<&PathBuf> = sources
+        .analytics_logs
+        .iter()
+        .filter(|path| path.exists())
+        .collect();
+    if logs.is_empty() {
+        for path in history_files(&sources.history_roots)? {
+            match open_log(&path) {
+                Ok(reader) => parse_history(reader, &path, timezone, &mut usages),
+                Err(err) => warnings.push(err.to_string()),
+            }
+        }
+    } else {
+        for path in logs {
+            parse_analytics(open_log(path)?, path, &mut usages);
+        }
+    }
+    usages.sort_by_key(|usage| usage.timestamp);
+    Ok((usages, warnings))
+}
+
+fn history_files(roots: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+    for root in roots.iter().filter(|root| root.is_dir()) {
+        let walker = GlobWalkerBuilder::from_patterns(root, &[format!("**/{}", HISTORY_FILE)])
+            .max_depth(HISTORY_DEPTH)
+            .build()
+            .map_err(|err| anyhow!("failed to scan aider projects: {}", err))?;
+        paths.extend(walker.flatten().map(|entry| entry.path().to_path_buf()));
+    }
+    paths.sort();
+    paths.dedup();
+    Ok(paths)
+}
+
+// `message_send` events carry the model, tokens and cost of one request; a
+// `launched` event starts a new session.
+fn parse_analytics(mut reader: Box<dyn BufRead>, path: &Path, usages: &mut Vec<PricedUsage>) {
+    let stem = path
+        .file_stem()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut session = String::new();
+    let mut buf = Vec::new();
+    loop {
+        match read_log_line(&mut *reader, &mut buf, MAX_LOG_LINE_BYTES) {
+            Ok(Some(LogLine::Line)) => {}
+            Ok(Some(LogLine::TooLong)) => continue,
+            Ok(None) | Err(_) => break,
+        }
+        let Ok(event) = serde_json::from_slice::<Value>(&buf) else {
+            continue;
+        };
+        let Some(timestamp) = event
+            .get("time")
+            .and_then(Value::as_i64)
+            .and_then(|secs| DateTime::from_timestamp(secs, 0))
+        else {
+            continue;
+        };
+        match event.get("event").and_then(Value::as_str) {
+            Some("launched") => session = timestamp.format("%Y%m%dT%H%M%S").to_string(),
+            Some("message_send") => {
+                let Some(props) = event.get("properties") else {
+                    continue;
+                };
+                if session.is_empty() {
+                    session = timestamp.format("%Y%m%dT%H%M%S").to_string();
+                }
+                let tokens = |key: &str| props.get(key).and_then(Value::as_u64).unwrap_or(0);
+                usages.push(PricedUsage {
+                    session_id: format!("{}/{}", stem, session),
+                    agent: AGENT.to_string(),
+                    timestamp,
+                    model: props
+                        .get("main_model")
+                        .and_then(Value::as_str)
+                        .unwrap_or("unknown")
+                        .to_string(),
+                    input_tokens: tokens("prompt_tokens"),
+                    cached_input_tokens: 0,
+                    output_tokens: tokens("completion_tokens"),
+                    reasoning_output_tokens: 0,
+                    requests: None,
+                    cost_usd: props.get("cost").and_then(Value::as_f64).unwrap_or(0.0),
+                });
+            }
+            _ => {}
+        }
+    }
+}
+
+// The chat history only timestamps the start of each session (local time),
+// so every message is dated then. Each reply is followed by a line like
+// `> Tokens: 2.3k sent, 5.6k cache hit, 150 received. Cost: $0.0091 message, ...`.
+fn parse_history(
+    reader: Box<dyn BufRead>,
+    path: &Path,
+    timezone: Tz,
+    usages: &mut Vec<PricedUsage>,
+) {
+    let project = path
+        .parent()
+        .and_then(Path::file_name)
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let mut started: Option<DateTime<Utc>> = None;
+    let mut model = String::from("unknown");
+    for line in reader.lines().map_while(Result::ok) {
+        let line = line.trim_start_matches('>').trim();
+        if let Some(raw) = line.strip_prefix("# aider chat started at ") {
+            started = NaiveDateTime::parse_from_str(raw.trim(), "%Y-%m-%d %H:%M:%S")
+                .ok()
+                .and_then(|local| timezone.from_local_datetime(&local).earliest())
+                .map(|at| at.with_timezone(&Utc));
+            model = String::from("unknown");
+        } else if let Some(rest) = line
+            .strip_prefix("Main model: ")
+            .or_else(|| line.strip_prefix("Model: "))
+        {
+            model = rest
+                .split_whitespace()
+                .next()
+                .unwrap_or("unknown")
+                .to_string();
+        } else if let Some(rest) = line.strip_prefix("Tokens: ")
+            && let Some(timestamp) = started
+        {
+            let (tokens, cost) = rest.split_once("Cost:").unwrap_or((rest, ""));
+            let mut sent = 0;
+            let mut cache_hit = 0;
+            let mut received = 0;
+            for part in tokens.trim_end_matches(['.', ' ']).split(',') {
+                let Some((count, label)) = part.trim().split_once(' ') else {
+                    continue;
+                };
+                let count = parse_token_count(count);
+                match label.trim() {
+                    "sent" => sent = count,
+                    "cache hit" => cache_hit = count,
+                    "received" => received = count,
+                    _ => {}
+                }
+            }
+            let cost_usd = cost
+                .trim()
+                .strip_prefix('$')
+                .and_then(|raw| raw.split_whitespace().next())
+                .and_then(|raw| raw.parse::<f64>().ok())
+                .unwrap_or(0.0);
+            usages.push(PricedUsage {
+                session_id: format!("{}/{}", project, timestamp.format("%Y%m%dT%H%M%S")),
+                agent: AGENT.to_string(),
+                timestamp,
+                model: model.clone(),
+                input_tokens: sent,
+                cached_input_tokens: cache_hit.min(sent),
+                output_tokens: received,
+                reasoning_output_tokens: 0,
+                requests: None,
+                cost_usd,
+            });
+        }
+    }
+}
+
+// Aider abbreviates counts: "950", "2.3k", "23k", "1.2M".
+fn parse_token_count(raw: &str) -> u64 {
+    let raw = raw.trim();
+    let (number, scale) = if let Some(number) = raw.strip_suffix('k') {
+        (number, 1_000.0)
+    } else if let Some(number) = raw.strip_suffix('M') {
+        (number, 1_000_000.0)
+    } else {
+        (raw, 1.0)
+    };
+    number
+        .parse::<f64>()
+        .map(|value| (value * scale).round() as u64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn reads_chat_histories_without_an_analytics_log() {
+        let dir =
+            std::env::temp_dir().join(format!("fuelcheck-aider-report-{}", uuid::Uuid::new_v4()));
+        let project = dir.join("app");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(
+            project.join(HISTORY_FILE),
+            [
+                "",
+                "# aider chat started at 2025-09-11 18:25:30",
+                "",
+                "> Aider v0.86.1",
+                "> Main model: anthropic/claude-sonnet-4-5 with diff edit format, infinite output",
+                "",
+                "#### fix the parser",
+                "",
+                "> Tokens: 12k sent, 2.5k cache hit, 850 received. Cost: $0.05 message, $0.05 session.",
+                "> Tokens: 1.2M sent, 1.5k received. Cost: $3.62 message, $3.67 session.",
+                "",
+                "# aider chat started at 2025-09-12 09:00:00",
+                "> Model: gpt-4o with diff edit format",
+                "> Tokens: 950 sent, 10 received. Cost: $0.0025 message, $0.0025 session.",
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+
+        let sources = AiderSources {
+            analytics_logs: Vec::new(),
+            history_roots: vec![dir.clone()],
+        };
+        let options = |report| AiderReportOptions {
+            report,
+            since: None,
+            until: None,
+            timezone: Some("UTC"),
+            group_by: None,
+        };
+        let ProviderReport::Daily(daily) =
+            build_report_from(&sources, &options(CostReportKind::Daily)).unwrap()
+        else {
+            panic!("expected daily report");
+        };
+        assert_eq!(daily.daily.len(), 2);
+        let first = &daily.daily[0];
+        assert_eq!(first.date, "2025-09-11");
+        assert_eq!(first.input_tokens, 1_212_000);
+        assert_eq!(first.cached_input_tokens, 2_500);
+        assert_eq!(first.output_tokens, 2_350);
+        assert!((first.cost_usd - 3.67).abs() < 1e-9);
+        assert!(first.models.contains_key("anthropic/claude-sonnet-4-5"));
+
+        let ProviderReport::Session(sessions) =
+            build_report_from(&sources, &options(CostReportKind::Session)).unwrap()
+        else {
+            panic!("expected session report");
+        };
+        assert_eq!(sessions.sessions.len(), 2);
+        assert_eq!(sessions.sessions[0].directory, "app");
+        assert_eq!(sessions.sessions[0].session_file, "20250911T182530");
+
+        // With an analytics log, only its events count.
+        let log = dir.join("analytics.jsonl");
+        fs::write(
+            &log,
+            [
+                r#"{"event":"launched","properties":{},"user_id":"u","time":1757615100}"#,
+                r#"{"event":"message_send","properties":{"main_model":"gpt-5","prompt_tokens":1000,"completion_tokens":200,"total_tokens":1200,"cost":0.25,"total_cost":0.25},"user_id":"u","time":1757615140}"#,
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+        let sources = AiderSources {
+            analytics_logs: vec![log],
+            history_roots: vec![dir.clone()],
+        };
+        let ProviderReport::Daily(daily) =
+            build_report_from(&sources, &options(CostReportKind::Daily)).unwrap()
+        else {
+            panic!("expected daily report");
+        };
+        assert_eq!(daily.daily.len(), 1);
+        assert_eq!(daily.totals.input_tokens, 1000);
+        assert!((daily.totals.cost_usd - 0.25).abs() < 1e-9);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
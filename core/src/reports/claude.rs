@@ -0,0 +1,336 @@
+use crate::config::ModelPrice;
+use crate::reports::codex::resolve_timezone;
+use crate::reports::priced::{PricedUsage, build_priced_report};
+use crate::reports::types::{CostReportGroupBy, CostReportKind, ProviderReport};
+use crate::reports::{
+    LOG_PATTERNS, LogLine, MAX_LOG_LINE_BYTES, configured_price, open_log, read_log_line,
+    strip_log_extension,
+};
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use directories::BaseDirs;
+use globwalk::GlobWalkerBuilder;
+use serde_json::Value;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::path::{Path, PathBuf};
+
+pub struct ClaudeReportOptions<'a> {
+    pub report: CostReportKind,
+    pub since: Option<&'a str>,
+    pub until: Option<&'a str>,
+    pub timezone: Option<&'a str>,
+    pub group_by: Option<CostReportGroupBy>,
+    pub pricing: Option<&'a BTreeMap<String, ModelPrice>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ModelPricing {
+    input_cost_per_m_token: f64,
+    cache_write_cost_per_m_token: f64,
+    cache_read_cost_per_m_token: f64,
+    output_cost_per_m_token: f64,
+}
+
+pub fn build_report(options: &ClaudeReportOptions<'_>) -> Result<ProviderReport> {
+    build_report_from(&projects_dirs(), options)
+}
+
+fn build_report_from(
+    dirs: &[PathBuf],
+    options: &ClaudeReportOptions<'_>,
+) -> Result<ProviderReport> {
+    let timezone = resolve_timezone(options.timezone)?;
+    let (messages, warnings) = load_messages(dirs, options.pricing)?;
+    let report = build_priced_report(
+        options.report,
+        &messages,
+        options.since,
+        options.until,
+        timezone,
+        options.group_by == Some(CostReportGroupBy::Agent),
+    );
+    Ok(report.with_warnings(warnings))
+}
+
+// `CLAUDE_CONFIG_DIR` (comma-separated) wins; otherwise both the XDG and the
+// legacy home locations Claude Code has used are read.
+pub fn projects_dirs() -> Vec<PathBuf> {
+    let configured: Vec<PathBuf> = std::env::var("CLAUDE_CONFIG_DIR")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .collect();
+    let roots = if configured.is_empty() {
+        BaseDirs::new()
+            .map(|dirs| {
+                vec![
+                    dirs.home_dir().join(".config").join("claude"),
+                    dirs.home_dir().join(".claude"),
+                ]
+            })
+            .unwrap_or_default()
+    } else {
+        configured
+    };
+    roots
+        .into_iter()
+        .map(|root| root.join("projects"))
+        .collect()
+}
+
+// Messages from every project log, oldest first. Claude Code copies earlier
+// messages into the new file when a session is resumed, so each message is
+// only counted the first time its id is seen.
+fn load_messages(
+    dirs: &[PathBuf],
+    pricing: Option<&BTreeMap<String, ModelPrice>>,
+) -> Result<(Vec<PricedUsage>, Vec<String>)> {
+    let mut messages = Vec::new();
+    let mut seen = HashSet::new();
+    let mut unpriced = BTreeSet::new();
+    for dir in dirs.iter().filter(|dir| dir.exists()) {
+        let walker = GlobWalkerBuilder::from_patterns(dir, &LOG_PATTERNS)
+            .build()
+            .map_err(|err| anyhow!("failed to scan claude projects: {}", err))?;
+        let mut paths: Vec<PathBuf> = walker
+            .flatten()
+            .map(|entry| entry.path().to_path_buf())
+            .collect();
+        paths.sort();
+        for path in paths {
+            parse_messages_from_file(&path, dir, pricing, &mut seen, &mut unpriced, &mut messages)?;
+        }
+    }
+    messages.sort_by_key(|message| message.timestamp);
+    let warnings = unpriced
+        .into_iter()
+        .map(|model| {
+            format!(
+                "no pricing for model {}; its tokens are counted at $0",
+                model
+            )
+        })
+        .collect();
+    Ok((messages, warnings))
+}
+
+fn parse_messages_from_file(
+    path: &Path,
+    projects_dir: &Path,
+    pricing: Option<&BTreeMap<String, ModelPrice>>,
+    seen: &mut HashSet<String>,
+    unpriced: &mut BTreeSet<String>,
+    messages: &mut Vec<PricedUsage>,
+) -> Result<()> {
+    let mut reader = open_log(path)?;
+    let relative = path
+        .strip_prefix(projects_dir)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/");
+    let session_id = strip_log_extension(&relative).to_string();
+
+    let mut buf = Vec::new();
+    loop {
+        match read_log_line(&mut *reader, &mut buf, MAX_LOG_LINE_BYTES) {
+            Ok(Some(LogLine::Line)) => {}
+            Ok(Some(LogLine::TooLong)) => continue,
+            Ok(None) | Err(_) => break,
+        }
+        // Only assistant messages carry usage.
+        if !buf.windows(7).any(|window| window == b"\"usage\"") {
+            continue;
+        }
+        let Ok(parsed) = serde_json::from_slice::<Value>(&buf) else {
+            continue;
+        };
+        if parsed.get("type").and_then(Value::as_str) != Some("assistant") {
+            continue;
+        }
+        let Some(message) = parsed.get("message") else {
+            continue;
+        };
+        let Some(usage) = message.get("usage") else {
+            continue;
+        };
+        let Some(timestamp) = parsed
+            .get("timestamp")
+            .and_then(Value::as_str)
+            .and_then(|raw| DateTime::parse_from_rfc3339(raw).ok())
+        else {
+            continue;
+        };
+        let model = message
+            .get("model")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .trim();
+        // Client-side placeholders for interrupted turns; never billed.
+        if model.is_empty() || model == "<synthetic>" {
+            continue;
+        }
+        if let Some(id) = message.get("id").and_then(Value::as_str) {
+            let request = parsed
+                .get("requestId")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            if !seen.insert(format!("{}:{}", id, request)) {
+                continue;
+            }
+        }
+
+        // Cache reads and writes are counted apart from fresh input; the
+        // report's input is all three.
+        let tokens = |key: &str| usage.get(key).and_then(Value::as_u64).unwrap_or(0);
+        let input = tokens("input_tokens");
+        let cache_write = tokens("cache_creation_input_tokens");
+        let cache_read = tokens("cache_read_input_tokens");
+        let output = tokens("output_tokens");
+        if input + cache_write + cache_read + output == 0 {
+            continue;
+        }
+
+        let cost_usd = match model_pricing(model, pricing) {
+            Some(pricing) => {
+                let per_m = |count: u64, rate: f64| count as f64 / 1_000_000.0 * rate;
+                per_m(input, pricing.input_cost_per_m_token)
+                    + per_m(cache_write, pricing.cache_write_cost_per_m_token)
+                    + per_m(cache_read, pricing.cache_read_cost_per_m_token)
+                    + per_m(output, pricing.output_cost_per_m_token)
+            }
+            None => {
+                unpriced.insert(model.to_string());
+                0.0
+            }
+        };
+        messages.push(PricedUsage {
+            session_id: session_id.clone(),
+            agent: parsed
+                .get("entrypoint")
+                .and_then(Value::as_str)
+                .unwrap_or(UNKNOWN_AGENT)
+                .to_string(),
+            timestamp: timestamp.with_timezone(&Utc),
+            model: model.to_string(),
+            input_tokens: input + cache_write + cache_read,
+            cached_input_tokens: cache_read,
+            output_tokens: output,
+            reasoning_output_tokens: 0,
+            requests: None,
+            cost_usd,
+        });
+    }
+    Ok(())
+}
+
+const UNKNOWN_AGENT: &str = "unknown";
+
+// Anthropic list prices in USD per million tokens unless the config sets one.
+// Cache writes are the 5-minute tier (1.25x input), cache reads 0.1x input;
+// a configured price reads cache at its cached input rate.
+fn model_pricing(
+    model: &str,
+    configured: Option<&BTreeMap<String, ModelPrice>>,
+) -> Option<ModelPricing> {
+    if let Some(price) = configured_price(configured, model) {
+        return Some(ModelPricing {
+            input_cost_per_m_token: price.input,
+            cache_write_cost_per_m_token: price.input * 1.25,
+            cache_read_cost_per_m_token: price.cached_input.unwrap_or(price.input),
+            output_cost_per_m_token: price.output,
+        });
+    }
+    let model = model.trim().to_ascii_lowercase();
+    let model = model
+        .rsplit('/')
+        .next()
+        .unwrap_or_default()
+        .trim_start_matches("anthropic.");
+    let (input, output) = if model.contains("opus-4-5") || model.contains("opus-4.5") {
+        (5.0, 25.0)
+    } else if model.contains("opus") {
+        (15.0, 75.0)
+    } else if model.contains("sonnet") {
+        (3.0, 15.0)
+    } else if model.contains("haiku-4") {
+        (1.0, 5.0)
+    } else if model.contains("3-5-haiku") || model.contains("haiku-3-5") {
+        (0.8, 4.0)
+    } else if model.contains("haiku") {
+        (0.25, 1.25)
+    } else {
+        return None;
+    };
+    Some(ModelPricing {
+        input_cost_per_m_token: input,
+        cache_write_cost_per_m_token: input * 1.25,
+        cache_read_cost_per_m_token: input * 0.1,
+        output_cost_per_m_token: output,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn prices_assistant_messages_once_per_message_id() {
+        let dir =
+            std::env::temp_dir().join(format!("fuelcheck-claude-report-{}", uuid::Uuid::new_v4()));
+        let project = dir.join("projects").join("-home-me-app");
+        fs::create_dir_all(&project).unwrap();
+        let message = r#"{"type":"assistant","timestamp":"2025-09-11T18:25:40.000Z","requestId":"req_1","entrypoint":"cli","message":{"id":"msg_1","model":"claude-sonnet-4-5-20250929","usage":{"input_tokens":1000,"cache_creation_input_tokens":2000,"cache_read_input_tokens":10000,"output_tokens":500}}}"#;
+        fs::write(
+            project.join("a1.jsonl"),
+            [
+                r#"{"type":"user","timestamp":"2025-09-11T18:25:30.000Z","message":{"role":"user","content":"hi"}}"#,
+                message,
+                r#"{"type":"assistant","timestamp":"2025-09-12T09:00:00.000Z","requestId":"req_2","message":{"id":"msg_2","model":"<synthetic>","usage":{"input_tokens":0,"output_tokens":0}}}"#,
+                r#"{"type":"assistant","timestamp":"2025-09-12T09:00:00.000Z","requestId":"req_3","message":{"id":"msg_3","model":"claude-next-1","usage":{"input_tokens":10,"output_tokens":10}}}"#,
+            ]
+            .join("\n"),
+        )
+        .unwrap();
+        // A resumed session repeats the first message.
+        fs::write(project.join("b2.jsonl"), message).unwrap();
+
+        let options = |report| ClaudeReportOptions {
+            report,
+            since: None,
+            until: None,
+            timezone: Some("UTC"),
+            group_by: None,
+            pricing: None,
+        };
+        let dirs = [dir.join("projects")];
+        let ProviderReport::Daily(daily) =
+            build_report_from(&dirs, &options(CostReportKind::Daily)).unwrap()
+        else {
+            panic!("expected daily report");
+        };
+        assert_eq!(daily.daily.len(), 2);
+        let first = &daily.daily[0];
+        assert_eq!(first.input_tokens, 13000);
+        assert_eq!(first.cached_input_tokens, 10000);
+        // 1000 * 3 + 2000 * 3.75 + 10000 * 0.3 + 500 * 15, per million.
+        assert!((first.cost_usd - 0.021).abs() < 1e-9);
+        assert_eq!(daily.daily[1].cost_usd, 0.0);
+        assert_eq!(
+            daily.warnings,
+            ["no pricing for model claude-next-1; its tokens are counted at $0"]
+        );
+
+        let ProviderReport::Session(sessions) =
+            build_report_from(&dirs, &options(CostReportKind::Session)).unwrap()
+        else {
+            panic!("expected session report");
+        };
+        assert_eq!(sessions.sessions.len(), 1);
+        assert_eq!(sessions.sessions[0].directory, "-home-me-app");
+        assert_eq!(sessions.sessions[0].session_file, "a1");
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
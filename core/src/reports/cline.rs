@@ -0,0 +1,293 @@
+use crate::reports::codex::resolve_timezone;
+use crate::reports::priced::{PricedUsage, build_priced_report, today_cost};
+use crate::reports::types::{CostReportGroupBy, CostReportKind, ProviderReport};
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use directories::BaseDirs;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+pub struct ClineReportOptions<'a> {
+    pub report: CostReportKind,
+    pub since: Option<&'a str>,
+    pub until: Option<&'a str>,
+    pub timezone: Option<&'a str>,
+    pub group_by: Option<CostReportGroupBy>,
+}
+
+// Cline and its fork Roo Code keep every task under the extension's VS Code
+// global storage, in `tasks/<task id>/`. The agent is the extension.
+const EXTENSIONS: [(&str, &str); 2] = [
+    ("saoudrizwan.claude-dev", "cline"),
+    ("rooveterinaryinc.roo-cline", "roo-code"),
+];
+// VS Code and the forks that install extensions from the same marketplace.
+const EDITORS: [&str; 5] = ["Code", "Code - Insiders", "VSCodium", "Cursor", "Windsurf"];
+
+// One entry of `ui_messages.json`. Every API request is a `say` message of
+// kind `api_req_started` whose text is a JSON object with its tokens and the
+// cost Cline computed.
+#[derive(Deserialize)]
+struct UiMessage {
+    ts: i64,
+    #[serde(default)]
+    say: Option<String>,
+    #[serde(default)]
+    text: Option<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(rename_all = "camelCase", default)]
+struct ApiRequest {
+    tokens_in: u64,
+    tokens_out: u64,
+    cache_writes: u64,
+    cache_reads: u64,
+    cost: Option<f64>,
+}
+
+// `task_metadata.json` records the model in use each time it changed.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct TaskMetadata {
+    model_usage: Vec<ModelUsageEntry>,
+}
+
+#[derive(Deserialize)]
+struct ModelUsageEntry {
+    ts: i64,
+    model_id: Option<String>,
+}
+
+pub fn build_report(options: &ClineReportOptions<'_>) -> Result<ProviderReport> {
+    build_report_from(&storage_dirs(), options)
+}
+
+fn build_report_from(
+    dirs: &[(PathBuf, &'static str)],
+    options: &ClineReportOptions<'_>,
+) -> Result<ProviderReport> {
+    let timezone = resolve_timezone(options.timezone)?;
+    let (usages, warnings) = load_usages(dirs);
+    let report = build_priced_report(
+        options.report,
+        &usages,
+        options.since,
+        options.until,
+        timezone,
+        options.group_by == Some(CostReportGroupBy::Agent),
+    );
+    Ok(report.with_warnings(warnings))
+}
+
+// Spend so far today in the report timezone, and when that day ends.
+pub fn today_cost_usd() -> Result<(f64, Option<DateTime<Utc>>)> {
+    let timezone = resolve_timezone(None)?;
+    let (usages, _) = load_usages(&storage_dirs());
+    Ok(today_cost(&usages, timezone))
+}
+
+// The extensions' storage dirs that exist, with the agent each belongs to.
+// `CLINE_STORAGE_DIRS` (comma-separated `globalStorage` dirs) replaces the
+// editors' default locations.
+pub fn storage_dirs() -> Vec<(PathBuf, &'static str)> {
+    let configured: Vec<PathBuf> = std::env::var("CLINE_STORAGE_DIRS")
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .collect();
+    let roots = if configured.is_empty() {
+        BaseDirs::new()
+            .map(|dirs| {
+                EDITORS
+                    .iter()
+                    .map(|editor| {
+                        dirs.config_dir()
+                            .join(editor)
+                            .join("User")
+                            .join("globalStorage")
+                    })
+                    .collect()
+            })
+            .unwrap_or_default()
+    } else {
+        configured
+    };
+    roots
+        .iter()
+        .flat_map(|root| {
+            EXTENSIONS
+                .iter()
+                .map(move |(extension, agent)| (root.join(extension), *agent))
+        })
+        .filter(|(dir, _)| dir.join("tasks").is_dir())
+        .collect()
+}
+
+fn load_usages(dirs: &[(PathBuf, &'static str)]) -> (Vec<PricedUsage>, Vec<String>) {
+    let mut usages = Vec::new();
+    let mut warnings = Vec::new();
+    for (dir, agent) in dirs {
+        let Ok(entries) = std::fs::read_dir(dir.join("tasks")) else {
+            continue;
+        };
+        let mut tasks: Vec<PathBuf> = entries
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir())
+            .collect();
+        tasks.sort();
+        for task in tasks {
+            // The extension rewrites the file as the task runs; skip it this time.
+            if let Err(err) = parse_task(&task, agent, &mut usages) {
+                let name = task.file_name().unwrap_or_default().to_string_lossy();
+                warnings.push(format!("skipped {} task {}: {}", agent, name, err));
+            }
+        }
+    }
+    usages.sort_by_key(|usage| usage.timestamp);
+    (usages, warnings)
+}
+
+fn parse_task(task: &Path, agent: &'static str, usages: &mut Vec<PricedUsage>) -> Result<()> {
+    let path = task.join("ui_messages.json");
+    if !path.exists() {
+        return Ok(());
+    }
+    let messages: Vec<UiMessage> = read_json(&path)?;
+    let mut models: Vec<ModelUsageEntry> =
+        read_json::<TaskMetadata>(&task.join("task_metadata.json"))
+            .map(|metadata| metadata.model_usage)
+            .unwrap_or_default();
+    models.sort_by_key(|entry| entry.ts);
+    let task_id = task
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    for message in messages {
+        if message.say.as_deref() != Some("api_req_started") {
+            continue;
+        }
+        let Some(request) = message
+            .text
+            .as_deref()
+            .and_then(|text| serde_json::from_str::<ApiRequest>(text).ok())
+        else {
+            continue;
+        };
+        let input = request.tokens_in + request.cache_writes + request.cache_reads;
+        // Requests still streaming or cancelled before any tokens have no cost yet.
+        if input + request.tokens_out == 0 && request.cost.is_none() {
+            continue;
+        }
+        let Some(timestamp) = DateTime::from_timestamp_millis(message.ts) else {
+            continue;
+        };
+        let model = models
+            .iter()
+            .rev()
+            .find(|entry| entry.ts <= message.ts)
+            .or(models.first())
+            .and_then(|entry| entry.model_id.clone())
+            .unwrap_or_else(|| "unknown".to_string());
+        usages.push(PricedUsage {
+            session_id: format!("{}/{}", agent, task_id),
+            agent: agent.to_string(),
+            timestamp,
+            model,
+            input_tokens: input,
+            cached_input_tokens: request.cache_reads,
+            output_tokens: request.tokens_out,
+            reasoning_output_tokens: 0,
+            requests: None,
+            cost_usd: request.cost.unwrap_or(0.0),
+        });
+    }
+    Ok(())
+}
+
+fn read_json<T: DeserializeOwned>(path: &Path) -> Result<T> {
+    let file = File::open(path).map_err(|err| anyhow!("read {}: {}", path.display(), err))?;
+    Ok(serde_json::from_reader(BufReader::new(file))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn prices_api_requests_per_task() {
+        let dir =
+            std::env::temp_dir().join(format!("fuelcheck-cline-report-{}", uuid::Uuid::new_v4()));
+        let task = dir.join("tasks").join("1757615100000");
+        fs::create_dir_all(&task).unwrap();
+        let request = |tokens_in: u64, cost: f64| {
+            serde_json::json!({
+                "request": "<task>fix it</task>",
+                "tokensIn": tokens_in,
+                "tokensOut": 500,
+                "cacheWrites": 2000,
+                "cacheReads": 10000,
+                "cost": cost,
+            })
+            .to_string()
+        };
+        fs::write(
+            task.join("ui_messages.json"),
+            serde_json::json!([
+                {"ts": 1757615100000i64, "type": "say", "say": "task", "text": "fix it"},
+                {"ts": 1757615140000i64, "type": "say", "say": "api_req_started", "text": request(1000, 0.02)},
+                {"ts": 1757667600000i64, "type": "say", "say": "api_req_started", "text": request(100, 0.01)},
+                {"ts": 1757667700000i64, "type": "say", "say": "api_req_started", "text": "{\"request\":\"streaming\"}"},
+            ])
+            .to_string(),
+        )
+        .unwrap();
+        fs::write(
+            task.join("task_metadata.json"),
+            r#"{"files_in_context":[],"model_usage":[
+                {"ts":1757615100000,"model_id":"claude-sonnet-4-5","model_provider_id":"anthropic","mode":"act"},
+                {"ts":1757667000000,"model_id":"gpt-5","model_provider_id":"openai","mode":"act"}
+            ]}"#,
+        )
+        .unwrap();
+
+        let options = |report| ClineReportOptions {
+            report,
+            since: None,
+            until: None,
+            timezone: Some("UTC"),
+            group_by: None,
+        };
+        let dirs = [(dir.clone(), "roo-code")];
+        let ProviderReport::Daily(daily) =
+            build_report_from(&dirs, &options(CostReportKind::Daily)).unwrap()
+        else {
+            panic!("expected daily report");
+        };
+        assert_eq!(daily.daily.len(), 2);
+        let first = &daily.daily[0];
+        assert_eq!(first.input_tokens, 13000);
+        assert_eq!(first.cached_input_tokens, 10000);
+        assert!(first.models.contains_key("claude-sonnet-4-5"));
+        assert!(daily.daily[1].models.contains_key("gpt-5"));
+        assert!((daily.totals.cost_usd - 0.03).abs() < 1e-9);
+
+        let ProviderReport::Session(sessions) =
+            build_report_from(&dirs, &options(CostReportKind::Session)).unwrap()
+        else {
+            panic!("expected session report");
+        };
+        assert_eq!(sessions.sessions.len(), 1);
+        assert_eq!(sessions.sessions[0].directory, "roo-code");
+        assert_eq!(sessions.sessions[0].session_file, "1757615100000");
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,32 +1,47 @@
-use crate::reports::normalize_model_name;
+use crate::config::ModelPrice;
+use crate::paths;
 use crate::reports::types::{
-    CostReportKind, DailyReportResponse, DailyReportRow, ModelUsage, MonthlyReportResponse,
-    MonthlyReportRow, ProviderReport, ReportTotals, SessionReportResponse, SessionReportRow,
+    CostReportGroupBy, CostReportKind, DailyReportResponse, DailyReportRow, ModelReportResponse,
+    ModelReportRow, ModelUsage, MonthlyReportResponse, MonthlyReportRow, ProviderReport,
+    ReportTotals, SessionReportResponse, SessionReportRow,
+};
+use crate::reports::{
+    LOG_PATTERNS, LogLine, MAX_LOG_LINE_BYTES, configured_price, normalize_model_name, open_log,
+    read_log_line, strip_log_extension,
 };
 use anyhow::{Result, anyhow};
 use chrono::{DateTime, SecondsFormat, Utc};
 use chrono_tz::Tz;
 use directories::BaseDirs;
 use globwalk::GlobWalkerBuilder;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use std::collections::hash_map::{DefaultHasher, Entry};
 use std::collections::{BTreeMap, HashMap, HashSet};
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::fs::{self, File};
+use std::hash::{Hash, Hasher};
+use std::io::{self, BufRead, BufReader, Read, Seek, SeekFrom};
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 pub struct CodexReportOptions<'a> {
     pub report: CostReportKind,
     pub since: Option<&'a str>,
     pub until: Option<&'a str>,
     pub timezone: Option<&'a str>,
+    pub group_by: Option<CostReportGroupBy>,
+    // The config's `pricing` section.
+    pub pricing: Option<&'a BTreeMap<String, ModelPrice>>,
 }
 
-#[cfg(test)]
-pub(crate) static CODEX_ENV_TEST_MUTEX: std::sync::Mutex<()> = std::sync::Mutex::new(());
-
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct TokenUsageEvent {
+    #[serde(skip)]
     session_id: String,
+    agent: String,
+    cwd: Option<String>,
     timestamp: DateTime<Utc>,
     model: String,
     input_tokens: u64,
@@ -37,7 +52,8 @@ struct TokenUsageEvent {
     is_fallback_model: bool,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
 struct RawUsage {
     input_tokens: u64,
     cached_input_tokens: u64,
@@ -55,19 +71,24 @@ struct ModelPricing {
 
 pub fn build_report(options: &CodexReportOptions<'_>) -> Result<ProviderReport> {
     let timezone = resolve_timezone(options.timezone)?;
-    let events = load_token_usage_events()?;
-
-    match options.report {
-        CostReportKind::Daily => {
-            build_daily_report(&events, options.since, options.until, timezone)
-        }
-        CostReportKind::Monthly => {
-            build_monthly_report(&events, options.since, options.until, timezone)
-        }
-        CostReportKind::Session => {
-            build_session_report(&events, options.since, options.until, timezone)
-        }
-    }
+    let (events, warnings) = load_token_usage_events()?;
+    let by_agent = options.group_by == Some(CostReportGroupBy::Agent);
+
+    let build = match options.report {
+        CostReportKind::Daily => build_daily_report,
+        CostReportKind::Monthly => build_monthly_report,
+        CostReportKind::Session => build_session_report,
+        CostReportKind::Models => build_models_report,
+    };
+    let report = build(
+        &events,
+        options.since,
+        options.until,
+        timezone,
+        by_agent,
+        options.pricing,
+    )?;
+    Ok(report.with_warnings(warnings))
 }
 
 fn build_daily_report(
@@ -75,8 +96,10 @@ fn build_daily_report(
     since: Option<&str>,
     until: Option<&str>,
     timezone: Tz,
+    by_agent: bool,
+    pricing: Option<&BTreeMap<String, ModelPrice>>,
 ) -> Result<ProviderReport> {
-    let mut summaries: HashMap<String, UsageSummary> = HashMap::new();
+    let mut summaries: HashMap<(String, Option<String>), UsageSummary> = HashMap::new();
 
     for event in events {
         let date_key = to_date_key(event.timestamp, timezone);
@@ -84,15 +107,14 @@ fn build_daily_report(
             continue;
         }
 
-        let summary = summaries
-            .entry(date_key.clone())
-            .or_insert_with(UsageSummary::default);
+        let agent = by_agent.then(|| event.agent.clone());
+        let summary = summaries.entry((date_key, agent)).or_default();
         add_event(summary, event);
     }
 
-    let model_pricing = resolve_model_pricing(&summaries)?;
+    let model_pricing = resolve_model_pricing(&summaries, pricing)?;
 
-    let mut keys: Vec<String> = summaries.keys().cloned().collect();
+    let mut keys: Vec<(String, Option<String>)> = summaries.keys().cloned().collect();
     keys.sort();
 
     let mut rows = Vec::new();
@@ -101,18 +123,22 @@ fn build_daily_report(
     for key in keys {
         let summary = summaries
             .get(&key)
-            .ok_or_else(|| anyhow!("missing daily summary for {}", key))?;
+            .ok_or_else(|| anyhow!("missing daily summary for {}", key.0))?;
         let cost = calculate_summary_cost(summary, &model_pricing)?;
         let row_models = to_sorted_models(&summary.models);
 
+        let (date, agent) = key;
         let row = DailyReportRow {
-            date: key,
+            date,
+            agent,
             input_tokens: summary.input_tokens,
             cached_input_tokens: summary.cached_input_tokens,
             output_tokens: summary.output_tokens,
             reasoning_output_tokens: summary.reasoning_output_tokens,
             total_tokens: summary.total_tokens,
+            requests: None,
             cost_usd: cost,
+            efficiency: None,
             models: row_models,
         };
 
@@ -123,6 +149,7 @@ fn build_daily_report(
     Ok(ProviderReport::Daily(DailyReportResponse {
         daily: rows,
         totals,
+        warnings: Vec::new(),
     }))
 }
 
@@ -131,8 +158,10 @@ fn build_monthly_report(
     since: Option<&str>,
     until: Option<&str>,
     timezone: Tz,
+    by_agent: bool,
+    pricing: Option<&BTreeMap<String, ModelPrice>>,
 ) -> Result<ProviderReport> {
-    let mut summaries: HashMap<String, UsageSummary> = HashMap::new();
+    let mut summaries: HashMap<(String, Option<String>), UsageSummary> = HashMap::new();
 
     for event in events {
         let date_key = to_date_key(event.timestamp, timezone);
@@ -141,15 +170,14 @@ fn build_monthly_report(
         }
 
         let month_key = to_month_key(event.timestamp, timezone);
-        let summary = summaries
-            .entry(month_key.clone())
-            .or_insert_with(UsageSummary::default);
+        let agent = by_agent.then(|| event.agent.clone());
+        let summary = summaries.entry((month_key, agent)).or_default();
         add_event(summary, event);
     }
 
-    let model_pricing = resolve_model_pricing(&summaries)?;
+    let model_pricing = resolve_model_pricing(&summaries, pricing)?;
 
-    let mut keys: Vec<String> = summaries.keys().cloned().collect();
+    let mut keys: Vec<(String, Option<String>)> = summaries.keys().cloned().collect();
     keys.sort();
 
     let mut rows = Vec::new();
@@ -158,18 +186,22 @@ fn build_monthly_report(
     for key in keys {
         let summary = summaries
             .get(&key)
-            .ok_or_else(|| anyhow!("missing monthly summary for {}", key))?;
+            .ok_or_else(|| anyhow!("missing monthly summary for {}", key.0))?;
         let cost = calculate_summary_cost(summary, &model_pricing)?;
         let row_models = to_sorted_models(&summary.models);
 
+        let (month, agent) = key;
         let row = MonthlyReportRow {
-            month: key,
+            month,
+            agent,
             input_tokens: summary.input_tokens,
             cached_input_tokens: summary.cached_input_tokens,
             output_tokens: summary.output_tokens,
             reasoning_output_tokens: summary.reasoning_output_tokens,
             total_tokens: summary.total_tokens,
+            requests: None,
             cost_usd: cost,
+            efficiency: None,
             models: row_models,
         };
 
@@ -186,6 +218,75 @@ fn build_monthly_report(
     Ok(ProviderReport::Monthly(MonthlyReportResponse {
         monthly: rows,
         totals,
+        warnings: Vec::new(),
+    }))
+}
+
+fn build_models_report(
+    events: &[TokenUsageEvent],
+    since: Option<&str>,
+    until: Option<&str>,
+    timezone: Tz,
+    by_agent: bool,
+    pricing: Option<&BTreeMap<String, ModelPrice>>,
+) -> Result<ProviderReport> {
+    let mut summaries: HashMap<(String, Option<String>), UsageSummary> = HashMap::new();
+
+    for event in events {
+        let date_key = to_date_key(event.timestamp, timezone);
+        if !is_within_range(&date_key, since, until) {
+            continue;
+        }
+
+        let agent = by_agent.then(|| event.agent.clone());
+        let summary = summaries.entry((event.model.clone(), agent)).or_default();
+        add_event(summary, event);
+    }
+
+    let model_pricing = resolve_model_pricing(&summaries, pricing)?;
+
+    let mut rows = Vec::new();
+    let mut totals = ReportTotals::default();
+
+    for ((model, agent), summary) in summaries {
+        let cost = calculate_summary_cost(&summary, &model_pricing)?;
+        let row = ModelReportRow {
+            is_fallback: summary
+                .models
+                .get(&model)
+                .and_then(|usage| usage.is_fallback),
+            model,
+            agent,
+            input_tokens: summary.input_tokens,
+            cached_input_tokens: summary.cached_input_tokens,
+            output_tokens: summary.output_tokens,
+            reasoning_output_tokens: summary.reasoning_output_tokens,
+            total_tokens: summary.total_tokens,
+            requests: None,
+            cost_usd: cost,
+            efficiency: None,
+        };
+
+        totals.input_tokens += row.input_tokens;
+        totals.cached_input_tokens += row.cached_input_tokens;
+        totals.output_tokens += row.output_tokens;
+        totals.reasoning_output_tokens += row.reasoning_output_tokens;
+        totals.total_tokens += row.total_tokens;
+        totals.cost_usd += row.cost_usd;
+
+        rows.push(row);
+    }
+    rows.sort_by(|a, b| {
+        b.cost_usd
+            .total_cmp(&a.cost_usd)
+            .then_with(|| a.model.cmp(&b.model))
+            .then_with(|| a.agent.cmp(&b.agent))
+    });
+
+    Ok(ProviderReport::Models(ModelReportResponse {
+        models: rows,
+        totals,
+        warnings: Vec::new(),
     }))
 }
 
@@ -194,6 +295,8 @@ fn build_session_report(
     since: Option<&str>,
     until: Option<&str>,
     timezone: Tz,
+    by_agent: bool,
+    pricing: Option<&BTreeMap<String, ModelPrice>>,
 ) -> Result<ProviderReport> {
     let mut summaries: HashMap<String, SessionSummary> = HashMap::new();
 
@@ -208,9 +311,14 @@ fn build_session_report(
             .or_insert_with(|| SessionSummary {
                 usage: UsageSummary::default(),
                 last_activity: event.timestamp,
+                agent: event.agent.clone(),
+                cwd: None,
             });
 
         add_event(&mut summary.usage, event);
+        if event.cwd.is_some() {
+            summary.cwd = event.cwd.clone();
+        }
         if event.timestamp > summary.last_activity {
             summary.last_activity = event.timestamp;
         }
@@ -220,7 +328,7 @@ fn build_session_report(
         .iter()
         .map(|(session, summary)| (session.clone(), summary.usage.clone()))
         .collect();
-    let model_pricing = resolve_model_pricing(&usage_map)?;
+    let model_pricing = resolve_model_pricing(&usage_map, pricing)?;
 
     let mut rows = Vec::new();
     let mut totals = ReportTotals::default();
@@ -239,12 +347,17 @@ fn build_session_report(
                 .to_rfc3339_opts(SecondsFormat::Millis, true),
             session_file,
             directory,
+            project: summary.cwd.clone(),
+            sessions: None,
+            name: None,
+            agent: by_agent.then(|| summary.agent.clone()),
             input_tokens: summary.usage.input_tokens,
             cached_input_tokens: summary.usage.cached_input_tokens,
             output_tokens: summary.usage.output_tokens,
             reasoning_output_tokens: summary.usage.reasoning_output_tokens,
             total_tokens: summary.usage.total_tokens,
             cost_usd: cost,
+            efficiency: None,
             models: to_sorted_models(&summary.usage.models),
         };
 
@@ -261,6 +374,7 @@ fn build_session_report(
     Ok(ProviderReport::Session(SessionReportResponse {
         sessions: rows,
         totals,
+        warnings: Vec::new(),
     }))
 }
 
@@ -278,6 +392,8 @@ struct UsageSummary {
 struct SessionSummary {
     usage: UsageSummary,
     last_activity: DateTime<Utc>,
+    agent: String,
+    cwd: Option<String>,
 }
 
 fn add_event(summary: &mut UsageSummary, event: &TokenUsageEvent) {
@@ -287,10 +403,7 @@ fn add_event(summary: &mut UsageSummary, event: &TokenUsageEvent) {
     summary.reasoning_output_tokens += event.reasoning_output_tokens;
     summary.total_tokens += event.total_tokens;
 
-    let model_usage = summary
-        .models
-        .entry(event.model.clone())
-        .or_insert_with(ModelUsage::default);
+    let model_usage = summary.models.entry(event.model.clone()).or_default();
     model_usage.input_tokens += event.input_tokens;
     model_usage.cached_input_tokens += event.cached_input_tokens;
     model_usage.output_tokens += event.output_tokens;
@@ -318,8 +431,9 @@ fn add_row_to_totals(totals: &mut ReportTotals, row: &DailyReportRow) {
     totals.cost_usd += row.cost_usd;
 }
 
-fn resolve_model_pricing(
-    summaries: &HashMap<String, UsageSummary>,
+fn resolve_model_pricing<K>(
+    summaries: &HashMap<K, UsageSummary>,
+    configured: Option<&BTreeMap<String, ModelPrice>>,
 ) -> Result<HashMap<String, ModelPricing>> {
     let mut models = HashSet::new();
     for summary in summaries.values() {
@@ -330,7 +444,15 @@ fn resolve_model_pricing(
 
     let mut pricing = HashMap::new();
     for model in models {
-        pricing.insert(model.clone(), resolve_model_pricing_entry(&model)?);
+        let entry = match configured_price(configured, &model) {
+            Some(price) => ModelPricing {
+                input_cost_per_m_token: price.input,
+                cached_input_cost_per_m_token: price.cached_input.unwrap_or(price.input),
+                output_cost_per_m_token: price.output,
+            },
+            None => resolve_model_pricing_entry(&model)?,
+        };
+        pricing.insert(model, entry);
     }
 
     Ok(pricing)
@@ -383,7 +505,10 @@ fn resolve_model_pricing_entry(model: &str) -> Result<ModelPricing> {
             output_cost_per_m_token: 0.8,
         },
         _ => {
-            return Err(anyhow!("pricing not found for model {}", model));
+            return Err(anyhow!(
+                "pricing not found for model {}; add it to `pricing` in the config",
+                model
+            ));
         }
     };
 
@@ -407,21 +532,21 @@ fn canonicalize_model_name(model: &str) -> String {
     normalized
 }
 
-fn to_date_key(timestamp: DateTime<Utc>, timezone: Tz) -> String {
+pub(crate) fn to_date_key(timestamp: DateTime<Utc>, timezone: Tz) -> String {
     timestamp
         .with_timezone(&timezone)
         .format("%Y-%m-%d")
         .to_string()
 }
 
-fn to_month_key(timestamp: DateTime<Utc>, timezone: Tz) -> String {
+pub(crate) fn to_month_key(timestamp: DateTime<Utc>, timezone: Tz) -> String {
     timestamp
         .with_timezone(&timezone)
         .format("%Y-%m")
         .to_string()
 }
 
-fn is_within_range(date_key: &str, since: Option<&str>, until: Option<&str>) -> bool {
+pub(crate) fn is_within_range(date_key: &str, since: Option<&str>, until: Option<&str>) -> bool {
     let value = date_key.replace('-', "");
     let since_value = since.map(|v| v.replace('-', ""));
     let until_value = until.map(|v| v.replace('-', ""));
@@ -439,7 +564,7 @@ fn is_within_range(date_key: &str, since: Option<&str>, until: Option<&str>) ->
     true
 }
 
-fn resolve_timezone(raw: Option<&str>) -> Result<Tz> {
+pub(crate) fn resolve_timezone(raw: Option<&str>) -> Result<Tz> {
     if let Some(value) = raw {
         return value
             .trim()
@@ -459,7 +584,7 @@ fn resolve_timezone(raw: Option<&str>) -> Result<Tz> {
     Ok(chrono_tz::UTC)
 }
 
-fn split_session_path(session_id: &str) -> (String, String) {
+pub(crate) fn split_session_path(session_id: &str) -> (String, String) {
     if let Some(index) = session_id.rfind('/') {
         (
             session_id[..index].to_string(),
@@ -470,28 +595,126 @@ fn split_session_path(session_id: &str) -> (String, String) {
     }
 }
 
-fn load_token_usage_events() -> Result<Vec<TokenUsageEvent>> {
-    let sessions_dir = codex_sessions_dir()?;
+// Token events from every session file, plus a warning for each file skipped
+// as a duplicate of another (e.g. copied between machines or restored from a
+// backup next to the original).
+fn load_token_usage_events() -> Result<(Vec<TokenUsageEvent>, Vec<String>)> {
+    let sessions_dir = sessions_dir()?;
+    load_events_with_index(&sessions_dir, session_index_path().as_deref())
+}
+
+fn load_events_with_index(
+    sessions_dir: &Path,
+    index_path: Option<&Path>,
+) -> Result<(Vec<TokenUsageEvent>, Vec<String>)> {
     if !sessions_dir.exists() {
-        return Ok(Vec::new());
+        return Ok((Vec::new(), Vec::new()));
     }
 
-    let walker = GlobWalkerBuilder::from_patterns(&sessions_dir, &["**/*.jsonl"])
+    let walker = GlobWalkerBuilder::from_patterns(sessions_dir, &LOG_PATTERNS)
         .build()
         .map_err(|err| anyhow!("failed to scan codex sessions: {}", err))?;
+    let mut paths: Vec<PathBuf> = walker
+        .flatten()
+        .map(|entry| entry.path().to_path_buf())
+        .collect();
+    paths.sort();
+
+    let mut previous = index_path
+        .and_then(|path| SessionIndex::load(path, sessions_dir))
+        .unwrap_or_default();
+    let mut index = SessionIndex {
+        version: SESSION_INDEX_VERSION,
+        sessions_dir: sessions_dir.to_path_buf(),
+        files: BTreeMap::new(),
+    };
+    // Files in walk order, with the events of a line still being written
+    // when there is one; those are not in the index. Each file is read on its
+    // own, so they are read in parallel; the results keep the walk order, and
+    // the first of two duplicates is the one kept below.
+    let jobs: Vec<(String, &PathBuf, Option<IndexedFile>)> = paths
+        .iter()
+        .map(|path| {
+            let relative = path
+                .strip_prefix(sessions_dir)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            let cached = previous.files.remove(&relative);
+            (relative, path, cached)
+        })
+        .collect();
+    let results = jobs
+        .into_par_iter()
+        .map(|(relative, path, cached)| {
+            let session_id = session_id_from_path(path, sessions_dir);
+            scan_session_file(path, &session_id, cached)
+                .map(|(entry, partial)| (relative, entry, partial))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let mut scanned = Vec::with_capacity(results.len());
+    for (relative, entry, partial) in results {
+        index.files.insert(relative.clone(), entry);
+        scanned.push((relative, partial));
+    }
+    // The index only saves work; a run that cannot write it still reports.
+    if let Some(path) = index_path {
+        let _ = index.save(path);
+    }
 
     let mut events = Vec::new();
-    for entry in walker.flatten() {
-        let path = entry.path();
-        let mut file_events = parse_events_from_file(path, &sessions_dir)?;
+    let mut warnings = Vec::new();
+    let mut seen: HashMap<u64, String> = HashMap::new();
+    for (relative, partial) in scanned {
+        let Some(entry) = index.files.remove(&relative) else {
+            continue;
+        };
+        let SessionParser {
+            meta_id,
+            events: mut file_events,
+            ..
+        } = partial.unwrap_or(entry.parser);
+        if let Some(first) = file_events.first() {
+            let (_, file_name) = split_session_path(&first.session_id);
+            let identity = meta_id.unwrap_or(file_name);
+            match seen.entry(session_fingerprint(&identity, &file_events)) {
+                Entry::Occupied(original) => {
+                    warnings.push(format!(
+                        "skipped duplicate session file {} (same as {})",
+                        relative,
+                        original.get()
+                    ));
+                    continue;
+                }
+                Entry::Vacant(slot) => {
+                    slot.insert(relative);
+                }
+            }
+        }
         events.append(&mut file_events);
     }
 
     events.sort_by_key(|event| event.timestamp);
-    Ok(events)
+    Ok((events, warnings))
 }
 
-fn codex_sessions_dir() -> Result<PathBuf> {
+// Two files are the same session when they share the session id (from
+// `session_meta`, else the file name) and every token event's timestamp and
+// counts.
+fn session_fingerprint(session: &str, events: &[TokenUsageEvent]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    session.hash(&mut hasher);
+    for event in events {
+        event.timestamp.hash(&mut hasher);
+        event.input_tokens.hash(&mut hasher);
+        event.cached_input_tokens.hash(&mut hasher);
+        event.output_tokens.hash(&mut hasher);
+        event.reasoning_output_tokens.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+pub fn sessions_dir() -> Result<PathBuf> {
     let codex_home = std::env::var("CODEX_HOME")
         .ok()
         .map(|value| value.trim().to_string())
@@ -503,30 +726,235 @@ fn codex_sessions_dir() -> Result<PathBuf> {
     Ok(codex_home.join("sessions"))
 }
 
-fn parse_events_from_file(path: &Path, sessions_dir: &Path) -> Result<Vec<TokenUsageEvent>> {
-    let file = File::open(path).map_err(|err| anyhow!("read {}: {}", path.display(), err))?;
-    let reader = BufReader::new(file);
-    let session_id = session_id_from_path(path, sessions_dir);
+// What was parsed from each session file on the last run, in the cache dir,
+// so a report only reads the logs that are new or have grown since.
+#[derive(Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionIndex {
+    version: u32,
+    sessions_dir: PathBuf,
+    // By path relative to the sessions dir.
+    files: BTreeMap<String, IndexedFile>,
+}
 
-    let mut events = Vec::new();
-    let mut previous_totals: Option<RawUsage> = None;
-    let mut current_model: Option<String> = None;
-    let mut current_model_is_fallback = false;
+// Bumped when the parser or the event format changes, which throws away every
+// index written before.
+const SESSION_INDEX_VERSION: u32 = 1;
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IndexedFile {
+    modified: Option<SystemTime>,
+    len: u64,
+    // Where the last complete line ends; the parser state is as of there.
+    offset: u64,
+    parser: SessionParser,
+}
 
-    for line in reader.lines() {
-        let line = match line {
-            Ok(value) => value,
-            Err(_) => continue,
+fn session_index_path() -> Option<PathBuf> {
+    // Tests point CODEX_HOME at temp dirs; keep them out of the real cache.
+    if cfg!(test) {
+        return None;
+    }
+    paths::cache_dir().map(|dir| dir.join("codex-sessions.json"))
+}
+
+impl SessionIndex {
+    fn load(path: &Path, sessions_dir: &Path) -> Option<Self> {
+        let raw = fs::read(path).ok()?;
+        let mut index: SessionIndex = serde_json::from_slice(&raw).ok()?;
+        if index.version != SESSION_INDEX_VERSION || index.sessions_dir != sessions_dir {
+            return None;
+        }
+        // Every event of a file has its session id, so it is not stored.
+        for (relative, entry) in &mut index.files {
+            let session_id = session_id_from_path(&sessions_dir.join(relative), sessions_dir);
+            for event in &mut entry.parser.events {
+                event.session_id = session_id.clone();
+            }
+        }
+        Some(index)
+    }
+
+    fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        // Write then rename, so a report running alongside never reads half.
+        let tmp = path.with_extension(format!("json.{}", std::process::id()));
+        fs::write(&tmp, serde_json::to_vec(self)?)?;
+        fs::rename(&tmp, path)?;
+        Ok(())
+    }
+}
+
+// Reads what changed in one session file since `cached` was indexed. The
+// second value is set when the file ends in a line still being written: its
+// events count for this run, but the index stops before it so the next run
+// reads the whole line.
+fn scan_session_file(
+    path: &Path,
+    session_id: &str,
+    cached: Option<IndexedFile>,
+) -> Result<(IndexedFile, Option<SessionParser>)> {
+    let metadata = fs::metadata(path).map_err(|err| anyhow!("read {}: {}", path.display(), err))?;
+    let modified = metadata.modified().ok();
+    let len = metadata.len();
+    // Compressed logs are always read whole, so their offset counts
+    // decompressed bytes and only the size and time tell a change.
+    let resumable = !is_compressed_log(path);
+    let cached = match cached {
+        Some(entry)
+            if modified.is_some()
+                && entry.modified == modified
+                && entry.len == len
+                && (entry.offset == len || !resumable) =>
+        {
+            return Ok((entry, None));
+        }
+        other => other,
+    };
+
+    // Codex only appends to a session log, so a plain log that grew picks up
+    // where the last run stopped. Compressed logs and logs that shrank are
+    // read again from the start.
+    let resume = cached.filter(|entry| resumable && entry.offset <= len);
+    let (mut parser, mut offset) = match resume {
+        Some(entry) => (entry.parser, entry.offset),
+        None => (SessionParser::new(), 0),
+    };
+    let mut reader = if offset > 0 {
+        let mut file =
+            File::open(path).map_err(|err| anyhow!("read {}: {}", path.display(), err))?;
+        file.seek(SeekFrom::Start(offset))?;
+        Box::new(BufReader::new(file))
+    } else {
+        open_log(path)?
+    };
+    let mut reader = CountingReader {
+        inner: &mut *reader,
+        count: 0,
+    };
+    let start = offset;
+
+    let mut partial = None;
+    let mut buf = Vec::new();
+    loop {
+        // A read error (e.g. a truncated archive) ends the file but keeps the
+        // events read so far.
+        match read_log_line(&mut reader, &mut buf, MAX_LOG_LINE_BYTES) {
+            Ok(Some(LogLine::Line)) => {}
+            Ok(Some(LogLine::TooLong)) => {
+                offset = start + reader.count;
+                continue;
+            }
+            Ok(None) | Err(_) => break,
+        }
+        if resumable && buf.last() != Some(&b'\n') {
+            let mut rest = parser.clone();
+            rest.parse_line(&buf, session_id);
+            partial = Some(rest);
+            break;
+        }
+        parser.parse_line(&buf, session_id);
+        offset = start + reader.count;
+    }
+
+    let entry = IndexedFile {
+        modified,
+        len,
+        offset,
+        parser,
+    };
+    Ok((entry, partial))
+}
+
+fn is_compressed_log(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("gz" | "zst")
+    )
+}
+
+// Counts the bytes `read_log_line` consumes, for the index offsets.
+struct CountingReader<'a> {
+    inner: &'a mut dyn BufRead,
+    count: u64,
+}
+
+impl Read for CountingReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.count += read as u64;
+        Ok(read)
+    }
+}
+
+impl BufRead for CountingReader<'_> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.count += amount as u64;
+        self.inner.consume(amount);
+    }
+}
+
+// Consecutive token events with the same model, agent and directory are
+// summed per quarter hour, keeping the latest timestamp. Every time zone
+// offset is a multiple of 15 minutes, so a summary never spans two local days;
+// this keeps the index and the event list small for long sessions.
+const SUMMARY_SLOT_SECS: i64 = 15 * 60;
+
+// The state of reading one session file: the `session_meta` id, what the
+// lines so far set, and the token events.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SessionParser {
+    meta_id: Option<String>,
+    agent: String,
+    cwd: Option<String>,
+    previous_totals: Option<RawUsage>,
+    current_model: Option<String>,
+    current_model_is_fallback: bool,
+    events: Vec<TokenUsageEvent>,
+}
+
+impl SessionParser {
+    fn new() -> Self {
+        Self {
+            meta_id: None,
+            agent: UNKNOWN_AGENT.to_string(),
+            cwd: None,
+            previous_totals: None,
+            current_model: None,
+            current_model_is_fallback: false,
+            events: Vec::new(),
+        }
+    }
+
+    fn parse_line(&mut self, buf: &[u8], session_id: &str) {
+        // Most lines are messages and tool output; only these three kinds
+        // matter, so skip the rest before paying for a JSON parse.
+        if !RELEVANT_ENTRY_TYPES
+            .iter()
+            .any(|needle| contains_bytes(buf, needle))
+        {
+            return;
+        }
+        let Ok(line) = std::str::from_utf8(buf) else {
+            return;
         };
 
         let trimmed = line.trim();
         if trimmed.is_empty() {
-            continue;
+            return;
         }
 
         let parsed: Value = match serde_json::from_str(trimmed) {
             Ok(value) => value,
-            Err(_) => continue,
+            Err(_) => return,
         };
 
         let entry_type = parsed
@@ -535,33 +963,52 @@ fn parse_events_from_file(path: &Path, sessions_dir: &Path) -> Result<Vec<TokenU
             .unwrap_or_default();
         let payload = parsed.get("payload");
 
+        if entry_type == "session_meta" {
+            if let Some(found) = payload.and_then(agent_from_session_meta) {
+                self.agent = found;
+            }
+            if let Some(id) = payload
+                .and_then(|payload| payload.get("id"))
+                .and_then(Value::as_str)
+            {
+                self.meta_id = Some(id.to_string());
+            }
+            if let Some(found) = payload.and_then(cwd_from_payload) {
+                self.cwd = Some(found);
+            }
+            return;
+        }
+
         if entry_type == "turn_context" {
             if let Some(model) = payload.and_then(extract_model) {
-                current_model = Some(model);
-                current_model_is_fallback = false;
+                self.current_model = Some(model);
+                self.current_model_is_fallback = false;
             }
-            continue;
+            if let Some(found) = payload.and_then(cwd_from_payload) {
+                self.cwd = Some(found);
+            }
+            return;
         }
 
         if entry_type != "event_msg" {
-            continue;
+            return;
         }
 
         let Some(payload) = payload else {
-            continue;
+            return;
         };
 
         if payload.get("type").and_then(Value::as_str) != Some("token_count") {
-            continue;
+            return;
         }
 
         let timestamp_raw = match parsed.get("timestamp").and_then(Value::as_str) {
             Some(value) => value,
-            None => continue,
+            None => return,
         };
         let timestamp = match DateTime::parse_from_rfc3339(timestamp_raw) {
             Ok(value) => value.with_timezone(&Utc),
-            Err(_) => continue,
+            Err(_) => return,
         };
 
         let info = payload.get("info");
@@ -569,20 +1016,16 @@ fn parse_events_from_file(path: &Path, sessions_dir: &Path) -> Result<Vec<TokenU
         let total_usage =
             normalize_raw_usage(info.and_then(|value| value.get("total_token_usage")));
 
-        let raw_usage = if let Some(last_usage) = last_usage {
-            Some(last_usage)
-        } else if let Some(total_usage) = total_usage {
-            Some(subtract_raw_usage(total_usage, previous_totals))
-        } else {
-            None
-        };
+        let raw_usage = last_usage.or_else(|| {
+            total_usage.map(|total_usage| subtract_raw_usage(total_usage, self.previous_totals))
+        });
 
         if let Some(total_usage) = total_usage {
-            previous_totals = Some(total_usage);
+            self.previous_totals = Some(total_usage);
         }
 
         let Some(raw_usage) = raw_usage else {
-            continue;
+            return;
         };
 
         let delta = convert_to_delta(raw_usage);
@@ -591,28 +1034,46 @@ fn parse_events_from_file(path: &Path, sessions_dir: &Path) -> Result<Vec<TokenU
             && delta.output_tokens == 0
             && delta.reasoning_output_tokens == 0
         {
-            continue;
+            return;
         }
 
         let extracted_model = extract_model(payload).or_else(|| info.and_then(extract_model));
         if let Some(model) = extracted_model.clone() {
-            current_model = Some(model);
-            current_model_is_fallback = false;
+            self.current_model = Some(model);
+            self.current_model_is_fallback = false;
         }
 
         let (model, is_fallback_model) = if let Some(model) = extracted_model {
             (model, false)
-        } else if let Some(model) = current_model.clone() {
-            (model, current_model_is_fallback)
+        } else if let Some(model) = self.current_model.clone() {
+            (model, self.current_model_is_fallback)
         } else {
-            current_model_is_fallback = true;
+            self.current_model_is_fallback = true;
             let fallback = "gpt-5".to_string();
-            current_model = Some(fallback.clone());
+            self.current_model = Some(fallback.clone());
             (fallback, true)
         };
 
-        events.push(TokenUsageEvent {
-            session_id: session_id.clone(),
+        if let Some(last) = self.events.last_mut()
+            && last.model == model
+            && last.is_fallback_model == is_fallback_model
+            && last.agent == self.agent
+            && last.cwd == self.cwd
+            && last.timestamp.timestamp().div_euclid(SUMMARY_SLOT_SECS)
+                == timestamp.timestamp().div_euclid(SUMMARY_SLOT_SECS)
+        {
+            last.timestamp = last.timestamp.max(timestamp);
+            last.input_tokens += delta.input_tokens;
+            last.cached_input_tokens += delta.cached_input_tokens;
+            last.output_tokens += delta.output_tokens;
+            last.reasoning_output_tokens += delta.reasoning_output_tokens;
+            last.total_tokens += delta.total_tokens;
+            return;
+        }
+        self.events.push(TokenUsageEvent {
+            session_id: session_id.to_string(),
+            agent: self.agent.clone(),
+            cwd: self.cwd.clone(),
             timestamp,
             model,
             input_tokens: delta.input_tokens,
@@ -623,17 +1084,47 @@ fn parse_events_from_file(path: &Path, sessions_dir: &Path) -> Result<Vec<TokenU
             is_fallback_model,
         });
     }
+}
+
+const UNKNOWN_AGENT: &str = "unknown";
+
+const RELEVANT_ENTRY_TYPES: [&[u8]; 3] = [b"token_count", b"turn_context", b"session_meta"];
 
-    Ok(events)
+fn contains_bytes(haystack: &[u8], needle: &[u8]) -> bool {
+    haystack
+        .windows(needle.len())
+        .any(|window| window == needle)
+}
+
+// Both `session_meta` and `turn_context` carry the directory Codex runs in.
+fn cwd_from_payload(payload: &Value) -> Option<String> {
+    payload
+        .get("cwd")
+        .and_then(Value::as_str)
+        .filter(|cwd| !cwd.is_empty())
+        .map(str::to_string)
+}
+
+// Which Codex front end wrote the session, from its `session_meta` line.
+// Newer logs carry `source`; older ones only the `originator` client name.
+fn agent_from_session_meta(payload: &Value) -> Option<String> {
+    let raw = payload
+        .get("source")
+        .and_then(Value::as_str)
+        .or_else(|| payload.get("originator").and_then(Value::as_str))?;
+    let agent = match raw.to_ascii_lowercase().as_str() {
+        "cli" | "codex_cli_rs" => "cli",
+        "exec" | "codex_exec" => "exec",
+        "vscode" | "codex_vscode" | "ide" => "ide",
+        other => return Some(other.to_string()),
+    };
+    Some(agent.to_string())
 }
 
 fn session_id_from_path(path: &Path, sessions_dir: &Path) -> String {
     let relative = path.strip_prefix(sessions_dir).unwrap_or(path);
-    let mut session_id = relative.to_string_lossy().replace('\\', "/");
-    if let Some(stripped) = session_id.strip_suffix(".jsonl") {
-        session_id = stripped.to_string();
-    }
-    session_id
+    let session_id = relative.to_string_lossy().replace('\\', "/");
+    strip_log_extension(&session_id).to_string()
 }
 
 fn normalize_raw_usage(value: Option<&Value>) -> Option<RawUsage> {
@@ -761,46 +1252,9 @@ fn as_non_empty_string(value: &Value) -> Option<String> {
 mod tests {
     use super::*;
     use crate::reports::types::ProviderReport;
+    use crate::test_env::{self, EnvVarGuard};
     use std::fs;
 
-    struct EnvVarGuard {
-        key: String,
-        prev: Option<String>,
-    }
-
-    impl EnvVarGuard {
-        fn set(key: &str, value: &str) -> Self {
-            let prev = std::env::var(key).ok();
-            // SAFETY: tests run in a controlled process and this key is restored on Drop.
-            unsafe {
-                std::env::set_var(key, value);
-            }
-            Self {
-                key: key.to_string(),
-                prev,
-            }
-        }
-    }
-
-    impl Drop for EnvVarGuard {
-        fn drop(&mut self) {
-            match &self.prev {
-                Some(value) => {
-                    // SAFETY: restoring env var for this process in test teardown.
-                    unsafe {
-                        std::env::set_var(&self.key, value);
-                    }
-                }
-                None => {
-                    // SAFETY: restoring env var for this process in test teardown.
-                    unsafe {
-                        std::env::remove_var(&self.key);
-                    }
-                }
-            }
-        }
-    }
-
     struct TempDirGuard {
         path: PathBuf,
     }
@@ -834,7 +1288,7 @@ mod tests {
 
     #[test]
     fn parses_turn_context_and_token_events() {
-        let _lock = CODEX_ENV_TEST_MUTEX.lock().expect("lock env mutex");
+        let _lock = test_env::lock();
         let temp = TempDirGuard::new();
         write_session_file(
             temp.path(),
@@ -854,6 +1308,8 @@ mod tests {
             since: None,
             until: None,
             timezone: Some("UTC"),
+            group_by: None,
+            pricing: None,
         })
         .expect("build report");
 
@@ -868,7 +1324,7 @@ mod tests {
 
     #[test]
     fn applies_fallback_model_for_legacy_sessions() {
-        let _lock = CODEX_ENV_TEST_MUTEX.lock().expect("lock env mutex");
+        let _lock = test_env::lock();
         let temp = TempDirGuard::new();
         write_session_file(
             temp.path(),
@@ -883,6 +1339,8 @@ mod tests {
             since: None,
             until: None,
             timezone: Some("UTC"),
+            group_by: None,
+            pricing: None,
         })
         .expect("build report");
 
@@ -895,9 +1353,48 @@ mod tests {
         assert_eq!(usage.is_fallback, Some(true));
     }
 
+    #[test]
+    fn models_report_orders_models_by_cost() {
+        let _lock = test_env::lock();
+        let temp = TempDirGuard::new();
+        write_session_file(
+            temp.path(),
+            "models.jsonl",
+            &[
+                r#"{"timestamp":"2025-09-11T10:00:00.000Z","type":"turn_context","payload":{"model":"gpt-5-mini"}}"#,
+                r#"{"timestamp":"2025-09-11T10:00:10.000Z","type":"event_msg","payload":{"type":"token_count","info":{"last_token_usage":{"input_tokens":1000,"cached_input_tokens":0,"output_tokens":100,"reasoning_output_tokens":0,"total_tokens":1100}}}}"#,
+                r#"{"timestamp":"2025-09-12T10:00:00.000Z","type":"turn_context","payload":{"model":"gpt-5"}}"#,
+                r#"{"timestamp":"2025-09-12T10:00:10.000Z","type":"event_msg","payload":{"type":"token_count","info":{"last_token_usage":{"input_tokens":1000,"cached_input_tokens":0,"output_tokens":100,"reasoning_output_tokens":0,"total_tokens":1100}}}}"#,
+                r#"{"timestamp":"2025-09-13T10:00:10.000Z","type":"event_msg","payload":{"type":"token_count","info":{"last_token_usage":{"input_tokens":500,"cached_input_tokens":0,"output_tokens":50,"reasoning_output_tokens":0,"total_tokens":550}}}}"#,
+            ]
+            .join("\n"),
+        );
+
+        let _guard = EnvVarGuard::set("CODEX_HOME", &temp.path().display().to_string());
+
+        let report = build_report(&CodexReportOptions {
+            report: CostReportKind::Models,
+            since: None,
+            until: None,
+            timezone: Some("UTC"),
+            group_by: None,
+            pricing: None,
+        })
+        .expect("build report");
+
+        let ProviderReport::Models(data) = report else {
+            panic!("expected models report");
+        };
+
+        let models: Vec<_> = data.models.iter().map(|row| row.model.as_str()).collect();
+        assert_eq!(models, ["gpt-5", "gpt-5-mini"]);
+        assert_eq!(data.models[0].input_tokens, 1500);
+        assert_eq!(data.totals.input_tokens, 2500);
+    }
+
     #[test]
     fn filters_by_range_and_timezone() {
-        let _lock = CODEX_ENV_TEST_MUTEX.lock().expect("lock env mutex");
+        let _lock = test_env::lock();
         let temp = TempDirGuard::new();
         write_session_file(
             temp.path(),
@@ -917,6 +1414,8 @@ mod tests {
             since: Some("2025-09-11"),
             until: Some("2025-09-11"),
             timezone: Some("America/Los_Angeles"),
+            group_by: None,
+            pricing: None,
         })
         .expect("build report");
 
@@ -929,9 +1428,245 @@ mod tests {
         assert_eq!(data.daily[0].input_tokens, 300);
     }
 
+    #[test]
+    fn groups_rows_by_agent() {
+        let _lock = test_env::lock();
+        let temp = TempDirGuard::new();
+        let token_count = r#"{"timestamp":"2025-09-11T10:00:10.000Z","type":"event_msg","payload":{"type":"token_count","info":{"last_token_usage":{"input_tokens":100,"cached_input_tokens":0,"output_tokens":10,"reasoning_output_tokens":0,"total_tokens":110}}}}"#;
+        write_session_file(
+            temp.path(),
+            "cli.jsonl",
+            &[
+                r#"{"timestamp":"2025-09-11T10:00:00.000Z","type":"session_meta","payload":{"originator":"codex_cli_rs","source":"cli"}}"#,
+                r#"{"timestamp":"2025-09-11T10:00:00.000Z","type":"turn_context","payload":{"model":"gpt-5"}}"#,
+                token_count,
+            ]
+            .join("\n"),
+        );
+        write_session_file(
+            temp.path(),
+            "ide.jsonl",
+            &[
+                r#"{"timestamp":"2025-09-11T10:00:00.000Z","type":"session_meta","payload":{"originator":"codex_vscode"}}"#,
+                r#"{"timestamp":"2025-09-11T10:00:00.000Z","type":"turn_context","payload":{"model":"gpt-5"}}"#,
+                token_count,
+            ]
+            .join("\n"),
+        );
+
+        let _guard = EnvVarGuard::set("CODEX_HOME", &temp.path().display().to_string());
+
+        let report = build_report(&CodexReportOptions {
+            report: CostReportKind::Daily,
+            since: None,
+            until: None,
+            timezone: Some("UTC"),
+            group_by: Some(CostReportGroupBy::Agent),
+            pricing: None,
+        })
+        .expect("build report");
+
+        let ProviderReport::Daily(data) = report else {
+            panic!("expected daily report");
+        };
+
+        let agents: Vec<_> = data.daily.iter().map(|row| row.agent.as_deref()).collect();
+        assert_eq!(agents, [Some("cli"), Some("ide")]);
+        assert_eq!(data.totals.input_tokens, 200);
+    }
+
+    #[test]
+    fn skips_duplicate_session_files() {
+        let _lock = test_env::lock();
+        let temp = TempDirGuard::new();
+        let session = [
+            r#"{"timestamp":"2025-09-11T10:00:00.000Z","type":"session_meta","payload":{"id":"0199a213-81c0-7800-8aa1-bbab2a035a53"}}"#,
+            r#"{"timestamp":"2025-09-11T10:00:00.000Z","type":"turn_context","payload":{"model":"gpt-5"}}"#,
+            r#"{"timestamp":"2025-09-11T10:00:10.000Z","type":"event_msg","payload":{"type":"token_count","info":{"last_token_usage":{"input_tokens":100,"cached_input_tokens":0,"output_tokens":10,"reasoning_output_tokens":0,"total_tokens":110}}}}"#,
+        ]
+        .join("\n");
+        write_session_file(temp.path(), "2025/09/11/rollout-a.jsonl", &session);
+        write_session_file(temp.path(), "restored/rollout-a.jsonl", &session);
+
+        let _guard = EnvVarGuard::set("CODEX_HOME", &temp.path().display().to_string());
+
+        let report = build_report(&CodexReportOptions {
+            report: CostReportKind::Daily,
+            since: None,
+            until: None,
+            timezone: Some("UTC"),
+            group_by: None,
+            pricing: None,
+        })
+        .expect("build report");
+
+        assert_eq!(
+            report.warnings(),
+            [
+                "skipped duplicate session file restored/rollout-a.jsonl (same as 2025/09/11/rollout-a.jsonl)"
+            ]
+        );
+        let ProviderReport::Daily(data) = report else {
+            panic!("expected daily report");
+        };
+        assert_eq!(data.totals.input_tokens, 100);
+    }
+
+    #[test]
+    fn duplicate_warnings_follow_walk_order() {
+        let temp = TempDirGuard::new();
+        let session = [
+            r#"{"timestamp":"2025-09-11T10:00:00.000Z","type":"session_meta","payload":{"id":"0199a213-81c0-7800-8aa1-bbab2a035a53"}}"#,
+            r#"{"timestamp":"2025-09-11T10:00:10.000Z","type":"event_msg","payload":{"type":"token_count","info":{"last_token_usage":{"input_tokens":100,"cached_input_tokens":0,"output_tokens":10,"reasoning_output_tokens":0,"total_tokens":110}}}}"#,
+        ]
+        .join("\n");
+        // Enough copies for the scan to be split across threads.
+        for copy in 0..48 {
+            write_session_file(
+                temp.path(),
+                &format!("copy-{:02}/rollout-a.jsonl", copy),
+                &session,
+            );
+        }
+        let expected: Vec<String> = (1..48)
+            .map(|copy| {
+                format!(
+                    "skipped duplicate session file copy-{:02}/rollout-a.jsonl (same as copy-00/rollout-a.jsonl)",
+                    copy
+                )
+            })
+            .collect();
+
+        for _ in 0..5 {
+            let (events, warnings) =
+                load_events_with_index(&temp.path().join("sessions"), None).expect("load events");
+            assert_eq!(events.len(), 1);
+            assert_eq!(warnings, expected);
+        }
+    }
+
+    #[test]
+    fn reads_compressed_session_logs() {
+        let _lock = test_env::lock();
+        let temp = TempDirGuard::new();
+        let session = |day: &str| {
+            [
+                format!(r#"{{"timestamp":"2025-09-{day}T10:00:00.000Z","type":"turn_context","payload":{{"model":"gpt-5"}}}}"#),
+                format!(r#"{{"timestamp":"2025-09-{day}T10:00:10.000Z","type":"event_msg","payload":{{"type":"token_count","info":{{"last_token_usage":{{"input_tokens":100,"cached_input_tokens":0,"output_tokens":10,"reasoning_output_tokens":0,"total_tokens":110}}}}}}}}"#),
+            ]
+            .join("\n")
+        };
+        let dir = temp.path().join("sessions");
+        fs::create_dir_all(&dir).expect("create sessions dir");
+        let mut gz = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        std::io::Write::write_all(&mut gz, session("11").as_bytes()).expect("gzip");
+        fs::write(dir.join("old.jsonl.gz"), gz.finish().expect("gzip")).expect("write gz");
+        let zst = zstd::encode_all(session("12").as_bytes(), 0).expect("zstd");
+        fs::write(dir.join("older.jsonl.zst"), zst).expect("write zst");
+
+        let _guard = EnvVarGuard::set("CODEX_HOME", &temp.path().display().to_string());
+
+        let report = build_report(&CodexReportOptions {
+            report: CostReportKind::Session,
+            since: None,
+            until: None,
+            timezone: Some("UTC"),
+            group_by: None,
+            pricing: None,
+        })
+        .expect("build report");
+
+        let ProviderReport::Session(data) = report else {
+            panic!("expected session report");
+        };
+        let sessions: Vec<_> = data
+            .sessions
+            .iter()
+            .map(|row| row.session_id.as_str())
+            .collect();
+        assert_eq!(sessions, ["old", "older"]);
+        assert_eq!(data.totals.input_tokens, 200);
+    }
+
+    #[test]
+    fn index_reads_only_what_was_appended() {
+        let temp = TempDirGuard::new();
+        let sessions = temp.path().join("sessions");
+        let index_path = temp.path().join("index.json");
+        let event = |hour: u32, total: u64| {
+            format!(
+                r#"{{"timestamp":"2025-09-11T{hour:02}:00:00.000Z","type":"event_msg","payload":{{"type":"token_count","info":{{"total_token_usage":{{"input_tokens":{total},"cached_input_tokens":0,"output_tokens":0,"reasoning_output_tokens":0,"total_tokens":{total}}}}}}}}}"#
+            )
+        };
+        let log = [
+            r#"{"timestamp":"2025-09-11T10:00:00.000Z","type":"turn_context","payload":{"model":"gpt-5"}}"#.to_string(),
+            event(10, 100),
+        ]
+        .join("\n")
+            + "\n";
+        write_session_file(temp.path(), "a.jsonl", &log);
+        let inputs = |events: &[TokenUsageEvent]| -> Vec<u64> {
+            events.iter().map(|event| event.input_tokens).collect()
+        };
+
+        let (events, _) = load_events_with_index(&sessions, Some(&index_path)).unwrap();
+        assert_eq!(inputs(&events), [100]);
+
+        // Half a line: counted now, read again in full once it is finished.
+        let path = sessions.join("a.jsonl");
+        let line = event(11, 250);
+        let (head, tail) = line.split_at(40);
+        fs::write(&path, format!("{}{}", log, head)).unwrap();
+        let (events, _) = load_events_with_index(&sessions, Some(&index_path)).unwrap();
+        assert_eq!(inputs(&events), [100]);
+        fs::write(&path, format!("{}{}{}\n", log, head, tail)).unwrap();
+        let (events, _) = load_events_with_index(&sessions, Some(&index_path)).unwrap();
+        assert_eq!(inputs(&events), [100, 150]);
+
+        let index = SessionIndex::load(&index_path, &sessions).unwrap();
+        assert_eq!(
+            index.files["a.jsonl"].offset,
+            fs::metadata(&path).unwrap().len()
+        );
+        assert_eq!(index.files["a.jsonl"].parser.events[1].session_id, "a");
+        let (fresh, _) = load_events_with_index(&sessions, None).unwrap();
+        assert_eq!(inputs(&fresh), inputs(&events));
+    }
+
+    #[test]
+    fn skips_oversized_lines_without_buffering_them() {
+        let giant = format!(
+            r#"{{"type":"response_item","payload":{{"output":"{}"}}}}"#,
+            "x".repeat(MAX_LOG_LINE_BYTES + 1)
+        );
+        let log = [
+            r#"{"timestamp":"2025-09-11T10:00:00.000Z","type":"turn_context","payload":{"model":"gpt-5"}}"#,
+            giant.as_str(),
+            r#"{"timestamp":"2025-09-11T10:00:10.000Z","type":"event_msg","payload":{"type":"token_count","info":{"last_token_usage":{"input_tokens":100,"cached_input_tokens":0,"output_tokens":10,"reasoning_output_tokens":0,"total_tokens":110}}}}"#,
+        ]
+        .join("\n");
+
+        let mut reader = std::io::Cursor::new(log.as_bytes());
+        let mut buf = Vec::new();
+        let mut kinds = Vec::new();
+        while let Some(line) = read_log_line(&mut reader, &mut buf, MAX_LOG_LINE_BYTES).unwrap() {
+            kinds.push(matches!(line, LogLine::TooLong));
+            assert!(buf.len() <= MAX_LOG_LINE_BYTES);
+        }
+        assert_eq!(kinds, [false, true, false]);
+
+        let temp = TempDirGuard::new();
+        let path = temp.path().join("big.jsonl");
+        fs::write(&path, &log).expect("write log");
+        let (entry, partial) = scan_session_file(&path, "big", None).expect("parse");
+        let events = partial.unwrap_or(entry.parser).events;
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].model, "gpt-5");
+    }
+
     #[test]
     fn unknown_model_returns_error() {
-        let _lock = CODEX_ENV_TEST_MUTEX.lock().expect("lock env mutex");
+        let _lock = test_env::lock();
         let temp = TempDirGuard::new();
         write_session_file(
             temp.path(),
@@ -950,6 +1685,8 @@ mod tests {
             since: None,
             until: None,
             timezone: Some("UTC"),
+            group_by: None,
+            pricing: None,
         })
         .expect_err("expected pricing error");
 
@@ -957,5 +1694,28 @@ mod tests {
             err.to_string()
                 .contains("pricing not found for model mystery-model")
         );
+
+        let pricing = BTreeMap::from([(
+            "Mystery-Model".to_string(),
+            ModelPrice {
+                input: 2.0,
+                cached_input: None,
+                output: 8.0,
+            },
+        )]);
+        let report = build_report(&CodexReportOptions {
+            report: CostReportKind::Daily,
+            since: None,
+            until: None,
+            timezone: Some("UTC"),
+            group_by: None,
+            pricing: Some(&pricing),
+        })
+        .expect("build report");
+        let ProviderReport::Daily(data) = report else {
+            panic!("expected daily report");
+        };
+        // 100 * 2 + 10 * 8, per million.
+        assert!((data.totals.cost_usd - 0.00028).abs() < 1e-12);
     }
 }
@@ -0,0 +1,251 @@
+use crate::model::ProviderErrorPayload;
+use crate::reports::types::{
+    CostReportCollection, ModelReportResponse, ProviderReport, ProviderReportOutcome,
+};
+use anyhow::{Result, anyhow};
+use serde::Serialize;
+use serde_json::{Map, Value, json};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CompareRange {
+    pub since: String,
+    pub until: String,
+}
+
+// Deltas are current minus baseline; percents are `None` when the baseline
+// is zero.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CompareFigures {
+    #[serde(rename = "baselineCostUSD")]
+    pub baseline_cost_usd: f64,
+    #[serde(rename = "currentCostUSD")]
+    pub current_cost_usd: f64,
+    #[serde(rename = "costDeltaUSD")]
+    pub cost_delta_usd: f64,
+    #[serde(rename = "costDeltaPercent")]
+    pub cost_delta_percent: Option<f64>,
+    #[serde(rename = "baselineTokens")]
+    pub baseline_tokens: u64,
+    #[serde(rename = "currentTokens")]
+    pub current_tokens: u64,
+    #[serde(rename = "tokensDelta")]
+    pub tokens_delta: i64,
+    #[serde(rename = "tokensDeltaPercent")]
+    pub tokens_delta_percent: Option<f64>,
+}
+
+// Cost in USD and total tokens of one model or range.
+type Spend = (f64, u64);
+
+impl CompareFigures {
+    fn new(baseline: Spend, current: Spend) -> Self {
+        let percent =
+            |before: f64, after: f64| (before > 0.0).then(|| (after - before) / before * 100.0);
+        Self {
+            baseline_cost_usd: baseline.0,
+            current_cost_usd: current.0,
+            cost_delta_usd: current.0 - baseline.0,
+            cost_delta_percent: percent(baseline.0, current.0),
+            baseline_tokens: baseline.1,
+            current_tokens: current.1,
+            tokens_delta: current.1 as i64 - baseline.1 as i64,
+            tokens_delta_percent: percent(baseline.1 as f64, current.1 as f64),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CompareRow {
+    pub model: String,
+    #[serde(flatten)]
+    pub figures: CompareFigures,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CompareReport {
+    pub current: CompareRange,
+    pub baseline: CompareRange,
+    pub models: Vec<CompareRow>,
+    pub totals: CompareFigures,
+}
+
+#[derive(Debug, Clone)]
+pub enum CompareOutcome {
+    Report(CompareReport),
+    Error(ProviderErrorPayload),
+}
+
+#[derive(Debug, Clone)]
+pub struct ProviderCompareResult {
+    pub provider: String,
+    pub outcome: CompareOutcome,
+}
+
+// Every model used in either range gets a row, most expensive now first.
+pub fn compare_models(
+    current_range: &CompareRange,
+    baseline_range: &CompareRange,
+    current: &ModelReportResponse,
+    baseline: &ModelReportResponse,
+) -> CompareReport {
+    let mut models: BTreeMap<&str, (Spend, Spend)> = BTreeMap::new();
+    for row in &baseline.models {
+        let entry = &mut models.entry(&row.model).or_default().0;
+        entry.0 += row.cost_usd;
+        entry.1 += row.total_tokens;
+    }
+    for row in &current.models {
+        let entry = &mut models.entry(&row.model).or_default().1;
+        entry.0 += row.cost_usd;
+        entry.1 += row.total_tokens;
+    }
+    let mut rows: Vec<CompareRow> = models
+        .into_iter()
+        .map(|(model, (before, after))| CompareRow {
+            model: model.to_string(),
+            figures: CompareFigures::new(before, after),
+        })
+        .collect();
+    rows.sort_by(|a, b| {
+        b.figures
+            .current_cost_usd
+            .total_cmp(&a.figures.current_cost_usd)
+            .then_with(|| {
+                b.figures
+                    .baseline_cost_usd
+                    .total_cmp(&a.figures.baseline_cost_usd)
+            })
+    });
+    CompareReport {
+        current: current_range.clone(),
+        baseline: baseline_range.clone(),
+        models: rows,
+        totals: CompareFigures::new(
+            (baseline.totals.cost_usd, baseline.totals.total_tokens),
+            (current.totals.cost_usd, current.totals.total_tokens),
+        ),
+    }
+}
+
+// Pairs up two model reports of the same providers; a provider that failed in
+// either range carries that error.
+pub fn compare_collections(
+    current_range: &CompareRange,
+    baseline_range: &CompareRange,
+    current: CostReportCollection,
+    baseline: CostReportCollection,
+) -> Result<Vec<ProviderCompareResult>> {
+    let mut baseline: BTreeMap<String, ProviderReportOutcome> = baseline
+        .providers
+        .into_iter()
+        .map(|result| (result.provider, result.outcome))
+        .collect();
+    let mut results = Vec::new();
+    for result in current.providers {
+        let before = baseline
+            .remove(&result.provider)
+            .ok_or_else(|| anyhow!("no baseline report for {}", result.provider))?;
+        let outcome = match (result.outcome, before) {
+            (
+                ProviderReportOutcome::Report(ProviderReport::Models(after)),
+                ProviderReportOutcome::Report(ProviderReport::Models(before)),
+            ) => CompareOutcome::Report(compare_models(
+                current_range,
+                baseline_range,
+                &after,
+                &before,
+            )),
+            (ProviderReportOutcome::Error(error), _) | (_, ProviderReportOutcome::Error(error)) => {
+                CompareOutcome::Error(error)
+            }
+            (ProviderReportOutcome::Report(report), _) => {
+                return Err(anyhow!("unexpected {} report", report.kind()));
+            }
+        };
+        results.push(ProviderCompareResult {
+            provider: result.provider,
+            outcome,
+        });
+    }
+    Ok(results)
+}
+
+// Shaped like `collection_to_json_value`: one provider's comparison as is,
+// several keyed by id under `providers`.
+pub fn comparisons_to_json_value(results: &[ProviderCompareResult]) -> Result<Value> {
+    let to_value = |outcome: &CompareOutcome| -> Result<Value> {
+        Ok(match outcome {
+            CompareOutcome::Report(report) => serde_json::to_value(report)?,
+            CompareOutcome::Error(error) => json!({ "error": error }),
+        })
+    };
+    if let [single] = results {
+        return to_value(&single.outcome);
+    }
+    let mut providers = Map::new();
+    for result in results {
+        providers.insert(result.provider.clone(), to_value(&result.outcome)?);
+    }
+    Ok(json!({ "providers": providers }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reports::types::{ModelReportRow, ReportTotals};
+
+    fn report(rows: &[(&str, f64, u64)]) -> ModelReportResponse {
+        let row = |(model, cost_usd, total_tokens): &(&str, f64, u64)| ModelReportRow {
+            model: model.to_string(),
+            agent: None,
+            input_tokens: *total_tokens,
+            cached_input_tokens: 0,
+            output_tokens: 0,
+            reasoning_output_tokens: 0,
+            total_tokens: *total_tokens,
+            requests: None,
+            cost_usd: *cost_usd,
+            efficiency: None,
+            is_fallback: None,
+        };
+        ModelReportResponse {
+            models: rows.iter().map(row).collect(),
+            totals: ReportTotals {
+                total_tokens: rows.iter().map(|row| row.2).sum(),
+                cost_usd: rows.iter().map(|row| row.1).sum(),
+                ..Default::default()
+            },
+            warnings: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn compares_models_present_in_either_range() {
+        let range = |since: &str, until: &str| CompareRange {
+            since: since.to_string(),
+            until: until.to_string(),
+        };
+        let baseline = report(&[("gpt-5", 10.0, 1000), ("o3", 4.0, 400)]);
+        let current = report(&[("gpt-5", 15.0, 1200), ("gpt-5-mini", 1.0, 500)]);
+        let compared = compare_models(
+            &range("2025-09-08", "2025-09-14"),
+            &range("2025-09-01", "2025-09-07"),
+            &current,
+            &baseline,
+        );
+
+        let models: Vec<&str> = compared
+            .models
+            .iter()
+            .map(|row| row.model.as_str())
+            .collect();
+        assert_eq!(models, ["gpt-5", "gpt-5-mini", "o3"]);
+        assert_eq!(compared.models[0].figures.cost_delta_percent, Some(50.0));
+        assert_eq!(compared.models[0].figures.tokens_delta, 200);
+        assert_eq!(compared.models[1].figures.cost_delta_percent, None);
+        assert_eq!(compared.models[2].figures.cost_delta_usd, -4.0);
+        assert_eq!(compared.totals.cost_delta_usd, 2.0);
+        assert_eq!(compared.totals.tokens_delta, 300);
+    }
+}
@@ -0,0 +1,154 @@
+use crate::config::Config;
+use crate::providers::{CursorUsageEvent, fetch_cursor_usage_events};
+use crate::reports::codex::resolve_timezone;
+use crate::reports::priced::{PricedUsage, build_priced_report};
+use crate::reports::types::{CostReportGroupBy, CostReportKind, ProviderReport};
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+pub struct CursorReportOptions<'a> {
+    pub report: CostReportKind,
+    pub since: Option<&'a str>,
+    pub until: Option<&'a str>,
+    pub timezone: Option<&'a str>,
+    pub group_by: Option<CostReportGroupBy>,
+}
+
+// Without --since the report covers the last 30 days, like the dashboard.
+const DEFAULT_DAYS: i64 = 30;
+
+// Cursor keeps no local logs; the report is built from the usage events on
+// the account dashboard, so it needs the configured cookie.
+pub async fn build_report(
+    options: &CursorReportOptions<'_>,
+    config: &Config,
+) -> Result<ProviderReport> {
+    if options.report == CostReportKind::Session {
+        return Err(anyhow!(
+            "cursor does not expose sessions; use the daily, monthly or models report"
+        ));
+    }
+    let timezone = resolve_timezone(options.timezone)?;
+    let today = Utc::now().with_timezone(&timezone).date_naive();
+    let since = match options.since {
+        Some(raw) => parse_date(raw)?,
+        None => today - Duration::days(DEFAULT_DAYS - 1),
+    };
+    let until = match options.until {
+        Some(raw) => parse_date(raw)?,
+        None => today,
+    };
+    let (events, truncated) = fetch_cursor_usage_events(
+        config,
+        start_of_day(since, timezone),
+        start_of_day(until + Duration::days(1), timezone),
+    )
+    .await?;
+
+    let mut warnings = Vec::new();
+    if truncated {
+        warnings.push(format!(
+            "only the latest {} cursor requests were fetched; narrow --since/--until",
+            events.len()
+        ));
+    }
+    let since = since.format("%Y-%m-%d").to_string();
+    let until = until.format("%Y-%m-%d").to_string();
+    let report = build_priced_report(
+        options.report,
+        &usages_from_events(events),
+        Some(&since),
+        Some(&until),
+        timezone,
+        options.group_by == Some(CostReportGroupBy::Agent),
+    );
+    Ok(report.with_warnings(warnings))
+}
+
+// Every event is one request, even when it carried no tokens (errored or
+// included in the plan). Cursor prices the tokens itself, in cents.
+fn usages_from_events(events: Vec<CursorUsageEvent>) -> Vec<PricedUsage> {
+    let mut usages: Vec<PricedUsage> = events
+        .into_iter()
+        .filter_map(|event| {
+            let timestamp = event
+                .timestamp
+                .as_deref()
+                .and_then(|raw| raw.trim().parse::<i64>().ok())
+                .and_then(DateTime::from_timestamp_millis)?;
+            let tokens = event.token_usage.unwrap_or_default();
+            Some(PricedUsage {
+                session_id: String::new(),
+                agent: AGENT.to_string(),
+                timestamp,
+                model: event
+                    .model
+                    .filter(|model| !model.trim().is_empty())
+                    .unwrap_or_else(|| "unknown".to_string()),
+                input_tokens: tokens.input_tokens
+                    + tokens.cache_write_tokens
+                    + tokens.cache_read_tokens,
+                cached_input_tokens: tokens.cache_read_tokens,
+                output_tokens: tokens.output_tokens,
+                reasoning_output_tokens: 0,
+                requests: Some(1),
+                cost_usd: tokens.total_cents / 100.0,
+            })
+        })
+        .collect();
+    usages.sort_by_key(|usage| usage.timestamp);
+    usages
+}
+
+const AGENT: &str = "cursor";
+
+fn parse_date(raw: &str) -> Result<NaiveDate> {
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d").map_err(|_| anyhow!("invalid date: {}", raw))
+}
+
+fn start_of_day(date: NaiveDate, timezone: Tz) -> DateTime<Utc> {
+    let midnight = date.and_time(NaiveTime::MIN);
+    timezone
+        .from_local_datetime(&midnight)
+        .earliest()
+        .map(|at| at.with_timezone(&Utc))
+        .unwrap_or_else(|| midnight.and_utc())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_requests_and_cursor_priced_tokens_per_day() {
+        let events: Vec<CursorUsageEvent> = serde_json::from_str(
+            r#"[
+                {"timestamp":"1757615140000","model":"claude-4-sonnet","kind":"USAGE_EVENT_KIND_USAGE_BASED",
+                 "tokenUsage":{"inputTokens":1000,"outputTokens":500,"cacheWriteTokens":2000,"cacheReadTokens":10000,"totalCents":2.5}},
+                {"timestamp":"1757615200000","model":"auto","kind":"USAGE_EVENT_KIND_INCLUDED_IN_PRO"},
+                {"timestamp":"1757667600000","model":"gpt-5","tokenUsage":{"inputTokens":100,"outputTokens":50,"totalCents":0.5}},
+                {"model":"gpt-5"}
+            ]"#,
+        )
+        .unwrap();
+        let usages = usages_from_events(events);
+        assert_eq!(usages.len(), 3);
+
+        let utc: Tz = "UTC".parse().unwrap();
+        let ProviderReport::Daily(daily) =
+            build_priced_report(CostReportKind::Daily, &usages, None, None, utc, false)
+        else {
+            panic!("expected daily report");
+        };
+        assert_eq!(daily.daily.len(), 2);
+        let first = &daily.daily[0];
+        assert_eq!(first.date, "2025-09-11");
+        assert_eq!(first.requests, Some(2));
+        assert_eq!(first.input_tokens, 13000);
+        assert_eq!(first.cached_input_tokens, 10000);
+        assert!((first.cost_usd - 0.025).abs() < 1e-9);
+        assert_eq!(daily.totals.requests, Some(3));
+        assert!((daily.totals.cost_usd - 0.03).abs() < 1e-9);
+    }
+}
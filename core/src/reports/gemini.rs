@@ -0,0 +1,295 @@
+use crate::config::ModelPrice;
+use crate::reports::codex::resolve_timezone;
+use crate::reports::configured_price;
+use crate::reports::priced::{PricedUsage, build_priced_report};
+use crate::reports::types::{CostReportGroupBy, CostReportKind, ProviderReport};
+use anyhow::{Result, anyhow};
+use chrono::{DateTime, Utc};
+use directories::BaseDirs;
+use globwalk::GlobWalkerBuilder;
+use serde::Deserialize;
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs::File;
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+
+pub struct GeminiReportOptions<'a> {
+    pub report: CostReportKind,
+    pub since: Option<&'a str>,
+    pub until: Option<&'a str>,
+    pub timezone: Option<&'a str>,
+    pub group_by: Option<CostReportGroupBy>,
+    pub pricing: Option<&'a BTreeMap<String, ModelPrice>>,
+}
+
+// Gemini CLI keeps each conversation as one JSON document under
+// `~/.gemini/tmp/<project hash>/chats/`, rewritten as the session goes on.
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ChatSession {
+    #[serde(default)]
+    session_id: String,
+    #[serde(default)]
+    messages: Vec<ChatMessage>,
+}
+
+#[derive(Deserialize)]
+struct ChatMessage {
+    #[serde(default)]
+    id: String,
+    timestamp: Option<DateTime<Utc>>,
+    model: Option<String>,
+    tokens: Option<ChatTokens>,
+}
+
+// `input` includes `cached`; `thoughts` are billed as output.
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct ChatTokens {
+    input: u64,
+    output: u64,
+    cached: u64,
+    thoughts: u64,
+    tool: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ModelPricing {
+    input_cost_per_m_token: f64,
+    cached_input_cost_per_m_token: f64,
+    output_cost_per_m_token: f64,
+}
+
+pub fn build_report(options: &GeminiReportOptions<'_>) -> Result<ProviderReport> {
+    build_report_from(&sessions_dir()?, options)
+}
+
+fn build_report_from(dir: &Path, options: &GeminiReportOptions<'_>) -> Result<ProviderReport> {
+    let timezone = resolve_timezone(options.timezone)?;
+    let (usages, warnings) = load_usages(dir, options.pricing)?;
+    let report = build_priced_report(
+        options.report,
+        &usages,
+        options.since,
+        options.until,
+        timezone,
+        options.group_by == Some(CostReportGroupBy::Agent),
+    );
+    Ok(report.with_warnings(warnings))
+}
+
+pub fn sessions_dir() -> Result<PathBuf> {
+    BaseDirs::new()
+        .map(|dirs| dirs.home_dir().join(".gemini").join("tmp"))
+        .ok_or_else(|| anyhow!("unable to resolve the home directory"))
+}
+
+fn load_usages(
+    dir: &Path,
+    pricing: Option<&BTreeMap<String, ModelPrice>>,
+) -> Result<(Vec<PricedUsage>, Vec<String>)> {
+    if !dir.exists() {
+        return Ok((Vec::new(), Vec::new()));
+    }
+    let walker = GlobWalkerBuilder::from_patterns(dir, &["*/chats/*.json"])
+        .build()
+        .map_err(|err| anyhow!("failed to scan gemini sessions: {}", err))?;
+    let mut paths: Vec<PathBuf> = walker
+        .flatten()
+        .map(|entry| entry.path().to_path_buf())
+        .collect();
+    paths.sort();
+
+    let mut usages = Vec::new();
+    let mut warnings = Vec::new();
+    let mut seen = HashSet::new();
+    let mut unpriced = BTreeSet::new();
+    for path in paths {
+        // The CLI may be halfway through rewriting a session; skip it this time.
+        let Ok(session) = read_session(&path) else {
+            let relative = path.strip_prefix(dir).unwrap_or(&path);
+            warnings.push(format!("skipped unreadable session {}", relative.display()));
+            continue;
+        };
+        let project = path
+            .parent()
+            .and_then(Path::parent)
+            .and_then(Path::file_name)
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let stem = path
+            .file_stem()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        let session_id = format!("{}/{}", project, stem);
+
+        for message in session.messages {
+            let (Some(timestamp), Some(model), Some(tokens)) =
+                (message.timestamp, message.model, message.tokens)
+            else {
+                continue;
+            };
+            let input = tokens.input + tokens.tool;
+            let output = tokens.output + tokens.thoughts;
+            if input + output == 0
+                || (!message.id.is_empty()
+                    && !seen.insert(format!("{}:{}", session.session_id, message.id)))
+            {
+                continue;
+            }
+            let cached = tokens.cached.min(input);
+            let cost_usd = match model_pricing(&model, input, pricing) {
+                Some(pricing) => {
+                    let per_m = |count: u64, rate: f64| count as f64 / 1_000_000.0 * rate;
+                    per_m(input - cached, pricing.input_cost_per_m_token)
+                        + per_m(cached, pricing.cached_input_cost_per_m_token)
+                        + per_m(output, pricing.output_cost_per_m_token)
+                }
+                None => {
+                    unpriced.insert(model.clone());
+                    0.0
+                }
+            };
+            usages.push(PricedUsage {
+                session_id: session_id.clone(),
+                agent: UNKNOWN_AGENT.to_string(),
+                timestamp,
+                model,
+                input_tokens: input,
+                cached_input_tokens: cached,
+                output_tokens: output,
+                reasoning_output_tokens: tokens.thoughts,
+                requests: None,
+                cost_usd,
+            });
+        }
+    }
+    usages.sort_by_key(|usage| usage.timestamp);
+    warnings.extend(unpriced.into_iter().map(|model| {
+        format!(
+            "no pricing for model {}; its tokens are counted at $0",
+            model
+        )
+    }));
+    Ok((usages, warnings))
+}
+
+fn read_session(path: &Path) -> Result<ChatSession> {
+    let file = File::open(path)?;
+    Ok(serde_json::from_reader(BufReader::new(file))?)
+}
+
+// Gemini sessions do not record which front end wrote them.
+const UNKNOWN_AGENT: &str = "unknown";
+
+const LONG_CONTEXT_TOKENS: u64 = 200_000;
+
+// Google list prices in USD per million tokens unless the config sets one.
+// Pro models charge more for prompts over 200K tokens, so the tier is picked
+// per request; configured prices have a single tier.
+fn model_pricing(
+    model: &str,
+    input_tokens: u64,
+    configured: Option<&BTreeMap<String, ModelPrice>>,
+) -> Option<ModelPricing> {
+    if let Some(price) = configured_price(configured, model) {
+        return Some(ModelPricing {
+            input_cost_per_m_token: price.input,
+            cached_input_cost_per_m_token: price.cached_input.unwrap_or(price.input),
+            output_cost_per_m_token: price.output,
+        });
+    }
+    let model = model.trim().to_ascii_lowercase();
+    let model = model.rsplit('/').next().unwrap_or_default();
+    let long = input_tokens > LONG_CONTEXT_TOKENS;
+    let (input, cached, output) = if model.starts_with("gemini-3-pro") {
+        if long {
+            (4.0, 0.4, 18.0)
+        } else {
+            (2.0, 0.2, 12.0)
+        }
+    } else if model.starts_with("gemini-2.5-pro") {
+        if long {
+            (2.5, 0.25, 15.0)
+        } else {
+            (1.25, 0.125, 10.0)
+        }
+    } else if model.starts_with("gemini-2.5-flash-lite") {
+        (0.1, 0.01, 0.4)
+    } else if model.starts_with("gemini-2.5-flash") {
+        (0.3, 0.03, 2.5)
+    } else if model.starts_with("gemini-2.0-flash-lite") {
+        (0.075, 0.075, 0.3)
+    } else if model.starts_with("gemini-2.0-flash") {
+        (0.1, 0.025, 0.4)
+    } else {
+        return None;
+    };
+    Some(ModelPricing {
+        input_cost_per_m_token: input,
+        cached_input_cost_per_m_token: cached,
+        output_cost_per_m_token: output,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn prices_gemini_messages_per_session() {
+        let dir =
+            std::env::temp_dir().join(format!("fuelcheck-gemini-report-{}", uuid::Uuid::new_v4()));
+        let chats = dir.join("0f3a").join("chats");
+        fs::create_dir_all(&chats).unwrap();
+        fs::write(
+            chats.join("session-2025-09-11T18-25-a1b2.json"),
+            r#"{"sessionId":"a1b2","projectHash":"0f3a","messages":[
+                {"id":"u1","timestamp":"2025-09-11T18:25:30.000Z","type":"user","content":"hi"},
+                {"id":"g1","timestamp":"2025-09-11T18:25:40.000Z","type":"gemini","content":"hello","model":"gemini-2.5-pro",
+                 "tokens":{"input":10000,"output":400,"cached":4000,"thoughts":600,"tool":0,"total":11000}},
+                {"id":"g2","timestamp":"2025-09-12T08:00:00.000Z","type":"gemini","content":"","model":"gemini-2.5-flash",
+                 "tokens":{"input":1000,"output":100,"cached":0,"thoughts":0,"tool":0,"total":1100}}
+            ]}"#,
+        )
+        .unwrap();
+        fs::write(chats.join("session-broken.json"), "{\"messages\":[").unwrap();
+
+        let options = |report| GeminiReportOptions {
+            report,
+            since: None,
+            until: None,
+            timezone: Some("UTC"),
+            group_by: None,
+            pricing: None,
+        };
+        let ProviderReport::Daily(daily) =
+            build_report_from(&dir, &options(CostReportKind::Daily)).unwrap()
+        else {
+            panic!("expected daily report");
+        };
+        assert_eq!(daily.daily.len(), 2);
+        let first = &daily.daily[0];
+        assert_eq!(first.input_tokens, 10000);
+        assert_eq!(first.cached_input_tokens, 4000);
+        assert_eq!(first.output_tokens, 1000);
+        assert_eq!(first.reasoning_output_tokens, 600);
+        // 6000 * 1.25 + 4000 * 0.125 + 1000 * 10, per million.
+        assert!((first.cost_usd - 0.018).abs() < 1e-9);
+        assert_eq!(daily.warnings.len(), 1);
+
+        let ProviderReport::Session(sessions) =
+            build_report_from(&dir, &options(CostReportKind::Session)).unwrap()
+        else {
+            panic!("expected session report");
+        };
+        assert_eq!(sessions.sessions.len(), 1);
+        assert_eq!(sessions.sessions[0].directory, "0f3a");
+        assert_eq!(
+            sessions.sessions[0].session_file,
+            "session-2025-09-11T18-25-a1b2"
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -1,15 +1,30 @@
+pub mod aider;
+pub mod claude;
+pub mod cline;
 pub mod codex;
+pub mod compare;
+pub mod cursor;
+pub mod gemini;
+mod priced;
+pub mod reconcile;
 pub mod types;
 
+use crate::config::{Config, ModelPrice};
 use crate::model::{ErrorKind, ProviderErrorPayload};
 use crate::providers::ProviderId;
+use crate::session_tags::SessionTags;
 use anyhow::{Result, anyhow};
 use chrono_tz::Tz;
+use flate2::read::MultiGzDecoder;
 use serde_json::{Map, Value, json};
 use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
 
 pub use types::{
-    CostReportCollection, CostReportKind, ProviderReportOutcome, ProviderReportResult,
+    CostReportCollection, CostReportGroupBy, CostReportKind, ProviderReportOutcome,
+    ProviderReportResult, ReportSort,
 };
 
 pub struct CostReportRequest<'a> {
@@ -18,6 +33,9 @@ pub struct CostReportRequest<'a> {
     pub since: Option<&'a str>,
     pub until: Option<&'a str>,
     pub timezone: Option<&'a str>,
+    pub group_by: Option<CostReportGroupBy>,
+    // For providers whose reports come from their API (Cursor).
+    pub config: &'a Config,
 }
 
 #[derive(Debug, Clone)]
@@ -41,31 +59,36 @@ pub fn validate_report_filters(
         return Err(anyhow!("--since must be less than or equal to --until"));
     }
 
-    let timezone = match timezone {
-        Some(raw) => {
-            let trimmed = raw.trim();
-            if trimmed.is_empty() {
-                return Err(anyhow!("timezone cannot be empty"));
-            }
-            trimmed
-                .parse::<Tz>()
-                .map_err(|_| anyhow!("invalid timezone: {}", trimmed))?;
-            Some(trimmed.to_string())
-        }
-        None => None,
-    };
-
     Ok(ValidatedReportFilters {
         since,
         until,
-        timezone,
+        timezone: timezone.map(validate_timezone).transpose()?,
     })
 }
 
-pub fn build_cost_report_collection(
+// An IANA name such as "Europe/Berlin", trimmed.
+pub fn validate_timezone(raw: &str) -> Result<String> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        return Err(anyhow!("timezone cannot be empty"));
+    }
+    trimmed
+        .parse::<Tz>()
+        .map_err(|_| anyhow!("invalid timezone: {}", trimmed))?;
+    Ok(trimmed.to_string())
+}
+
+pub async fn build_cost_report_collection(
     request: CostReportRequest<'_>,
 ) -> Result<CostReportCollection> {
     let filters = validate_report_filters(request.since, request.until, request.timezone)?;
+    if request.group_by == Some(CostReportGroupBy::Directory)
+        && request.report != CostReportKind::Session
+    {
+        return Err(anyhow!(
+            "--group-by directory only applies to --report session"
+        ));
+    }
 
     let mut providers = Vec::new();
     for provider_id in request.providers {
@@ -76,14 +99,108 @@ pub fn build_cost_report_collection(
                     since: filters.since.as_deref(),
                     until: filters.until.as_deref(),
                     timezone: filters.timezone.as_deref(),
+                    group_by: request.group_by,
+                    pricing: request.config.pricing.as_ref(),
                 };
                 match codex::build_report(&options) {
+                    Ok(mut report) => {
+                        name_sessions(&mut report);
+                        ProviderReportOutcome::Report(report)
+                    }
+                    Err(err) => {
+                        ProviderReportOutcome::Error(provider_error_payload_from_error(&err))
+                    }
+                }
+            }
+            ProviderId::Claude => {
+                let options = claude::ClaudeReportOptions {
+                    report: request.report,
+                    since: filters.since.as_deref(),
+                    until: filters.until.as_deref(),
+                    timezone: filters.timezone.as_deref(),
+                    group_by: request.group_by,
+                    pricing: request.config.pricing.as_ref(),
+                };
+                match claude::build_report(&options) {
+                    Ok(mut report) => {
+                        name_sessions(&mut report);
+                        ProviderReportOutcome::Report(report)
+                    }
+                    Err(err) => {
+                        ProviderReportOutcome::Error(provider_error_payload_from_error(&err))
+                    }
+                }
+            }
+            ProviderId::Gemini => {
+                let options = gemini::GeminiReportOptions {
+                    report: request.report,
+                    since: filters.since.as_deref(),
+                    until: filters.until.as_deref(),
+                    timezone: filters.timezone.as_deref(),
+                    group_by: request.group_by,
+                    pricing: request.config.pricing.as_ref(),
+                };
+                match gemini::build_report(&options) {
+                    Ok(mut report) => {
+                        name_sessions(&mut report);
+                        ProviderReportOutcome::Report(report)
+                    }
+                    Err(err) => {
+                        ProviderReportOutcome::Error(provider_error_payload_from_error(&err))
+                    }
+                }
+            }
+            ProviderId::Cursor => {
+                let options = cursor::CursorReportOptions {
+                    report: request.report,
+                    since: filters.since.as_deref(),
+                    until: filters.until.as_deref(),
+                    timezone: filters.timezone.as_deref(),
+                    group_by: request.group_by,
+                };
+                match cursor::build_report(&options, request.config).await {
                     Ok(report) => ProviderReportOutcome::Report(report),
                     Err(err) => {
                         ProviderReportOutcome::Error(provider_error_payload_from_error(&err))
                     }
                 }
             }
+            ProviderId::Aider => {
+                let options = aider::AiderReportOptions {
+                    report: request.report,
+                    since: filters.since.as_deref(),
+                    until: filters.until.as_deref(),
+                    timezone: filters.timezone.as_deref(),
+                    group_by: request.group_by,
+                };
+                match aider::build_report(&options) {
+                    Ok(mut report) => {
+                        name_sessions(&mut report);
+                        ProviderReportOutcome::Report(report)
+                    }
+                    Err(err) => {
+                        ProviderReportOutcome::Error(provider_error_payload_from_error(&err))
+                    }
+                }
+            }
+            ProviderId::Cline => {
+                let options = cline::ClineReportOptions {
+                    report: request.report,
+                    since: filters.since.as_deref(),
+                    until: filters.until.as_deref(),
+                    timezone: filters.timezone.as_deref(),
+                    group_by: request.group_by,
+                };
+                match cline::build_report(&options) {
+                    Ok(mut report) => {
+                        name_sessions(&mut report);
+                        ProviderReportOutcome::Report(report)
+                    }
+                    Err(err) => {
+                        ProviderReportOutcome::Error(provider_error_payload_from_error(&err))
+                    }
+                }
+            }
             _ => ProviderReportOutcome::Error(ProviderErrorPayload {
                 code: 1,
                 message: format!("provider {} report not implemented yet", provider_id),
@@ -91,6 +208,16 @@ pub fn build_cost_report_collection(
             }),
         };
 
+        let outcome = match outcome {
+            ProviderReportOutcome::Report(types::ProviderReport::Session(data))
+                if request.group_by == Some(CostReportGroupBy::Directory) =>
+            {
+                ProviderReportOutcome::Report(types::ProviderReport::Session(roll_up_directories(
+                    data,
+                )))
+            }
+            other => other,
+        };
         providers.push(ProviderReportResult {
             provider: provider_id.to_string(),
             outcome,
@@ -103,6 +230,182 @@ pub fn build_cost_report_collection(
     })
 }
 
+// Directories whose logs feed these providers' reports, for `--follow`.
+pub fn report_log_dirs(providers: &[ProviderId]) -> Result<Vec<PathBuf>> {
+    let mut dirs = Vec::new();
+    for provider_id in providers {
+        match provider_id {
+            ProviderId::Codex => dirs.push(codex::sessions_dir()?),
+            // Only one of Claude's default locations usually exists.
+            ProviderId::Claude => dirs.extend(
+                claude::projects_dirs()
+                    .into_iter()
+                    .filter(|dir| dir.exists()),
+            ),
+            ProviderId::Gemini => {
+                dirs.extend(Some(gemini::sessions_dir()?).filter(|dir| dir.exists()))
+            }
+            _ => {}
+        }
+    }
+    Ok(dirs)
+}
+
+pub fn is_log_path(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or_default();
+    // Gemini sessions are whole JSON documents.
+    [".jsonl", ".jsonl.gz", ".jsonl.zst", ".json"]
+        .iter()
+        .any(|suffix| name.ends_with(suffix))
+}
+
+// One row per project directory: the session's working directory when the log
+// records it, else the directory part of its id (Claude's project dir). Rows
+// are ordered by cost, highest first.
+fn roll_up_directories(data: types::SessionReportResponse) -> types::SessionReportResponse {
+    let mut rows: BTreeMap<String, types::SessionReportRow> = BTreeMap::new();
+    for session in data.sessions {
+        let directory = session
+            .project
+            .clone()
+            .unwrap_or_else(|| session.directory.clone());
+        let row = rows
+            .entry(directory.clone())
+            .or_insert_with(|| types::SessionReportRow {
+                session_id: directory.clone(),
+                last_activity: String::new(),
+                session_file: String::new(),
+                directory: directory.clone(),
+                project: None,
+                sessions: Some(0),
+                name: None,
+                agent: None,
+                input_tokens: 0,
+                cached_input_tokens: 0,
+                output_tokens: 0,
+                reasoning_output_tokens: 0,
+                total_tokens: 0,
+                cost_usd: 0.0,
+                efficiency: None,
+                models: BTreeMap::new(),
+            });
+        row.sessions = row.sessions.map(|count| count + 1);
+        // RFC 3339 timestamps in UTC compare as strings.
+        if session.last_activity > row.last_activity {
+            row.last_activity = session.last_activity;
+        }
+        row.input_tokens += session.input_tokens;
+        row.cached_input_tokens += session.cached_input_tokens;
+        row.output_tokens += session.output_tokens;
+        row.reasoning_output_tokens += session.reasoning_output_tokens;
+        row.total_tokens += session.total_tokens;
+        row.cost_usd += session.cost_usd;
+        for (model, usage) in session.models {
+            let total = row.models.entry(model).or_default();
+            total.input_tokens += usage.input_tokens;
+            total.cached_input_tokens += usage.cached_input_tokens;
+            total.output_tokens += usage.output_tokens;
+            total.reasoning_output_tokens += usage.reasoning_output_tokens;
+            total.total_tokens += usage.total_tokens;
+            if usage.is_fallback == Some(true) {
+                total.is_fallback = Some(true);
+            }
+        }
+    }
+    let mut sessions: Vec<_> = rows.into_values().collect();
+    sessions.sort_by(|a, b| b.cost_usd.total_cmp(&a.cost_usd));
+    types::SessionReportResponse { sessions, ..data }
+}
+
+// A broken tags file only costs the names, not the report.
+fn name_sessions(report: &mut types::ProviderReport) {
+    if let types::ProviderReport::Session(data) = report
+        && let Ok(tags) = SessionTags::load_default()
+    {
+        tags.apply(&mut data.sessions);
+    }
+}
+
+// Local usage logs may be rotated into gzip or zstd archives to save disk;
+// both read back as plain JSONL.
+pub(crate) const LOG_PATTERNS: [&str; 3] = ["**/*.jsonl", "**/*.jsonl.gz", "**/*.jsonl.zst"];
+
+pub(crate) fn open_log(path: &Path) -> Result<Box<dyn BufRead>> {
+    let file = File::open(path).map_err(|err| anyhow!("read {}: {}", path.display(), err))?;
+    let reader: Box<dyn BufRead> = match path.extension().and_then(|ext| ext.to_str()) {
+        Some("gz") => Box::new(BufReader::new(MultiGzDecoder::new(file))),
+        Some("zst") => Box::new(BufReader::new(
+            zstd::Decoder::new(file).map_err(|err| anyhow!("read {}: {}", path.display(), err))?,
+        )),
+        _ => Box::new(BufReader::new(file)),
+    };
+    Ok(reader)
+}
+
+// Token and context lines are a few KB at most; anything past this is an
+// embedded image or tool output and is never worth parsing.
+pub(crate) const MAX_LOG_LINE_BYTES: usize = 1 << 20;
+
+pub(crate) enum LogLine {
+    Line,
+    // Longer than the limit; consumed without being buffered.
+    TooLong,
+}
+
+// Reads the next line into `buf` (newline included) without ever holding more
+// than `limit` bytes of it, so peak memory stays flat however large a log or
+// a single line is. `None` at end of input.
+pub(crate) fn read_log_line(
+    reader: &mut dyn BufRead,
+    buf: &mut Vec<u8>,
+    limit: usize,
+) -> io::Result<Option<LogLine>> {
+    buf.clear();
+    let mut read_any = false;
+    let mut too_long = false;
+    loop {
+        let available = reader.fill_buf()?;
+        if available.is_empty() {
+            break;
+        }
+        read_any = true;
+        let (chunk, done) = match available.iter().position(|byte| *byte == b'\n') {
+            Some(index) => (&available[..=index], true),
+            None => (available, false),
+        };
+        let used = chunk.len();
+        if !too_long {
+            if buf.len() + used > limit {
+                too_long = true;
+                buf.clear();
+            } else {
+                buf.extend_from_slice(chunk);
+            }
+        }
+        reader.consume(used);
+        if done {
+            break;
+        }
+    }
+    Ok(match (read_any, too_long) {
+        (false, _) => None,
+        (true, true) => Some(LogLine::TooLong),
+        (true, false) => Some(LogLine::Line),
+    })
+}
+
+// "a/b.jsonl.gz" -> "a/b", so a log and its archived copy share an id.
+pub(crate) fn strip_log_extension(name: &str) -> &str {
+    let name = name
+        .strip_suffix(".gz")
+        .or_else(|| name.strip_suffix(".zst"))
+        .unwrap_or(name);
+    name.strip_suffix(".jsonl").unwrap_or(name)
+}
+
 pub fn collection_to_json_value(collection: &CostReportCollection) -> Result<Value> {
     if collection.providers.len() == 1 {
         let single = collection
@@ -183,6 +486,19 @@ pub fn normalize_model_name(model: &str) -> String {
     trimmed.to_string()
 }
 
+// The price set for `model` in the config's `pricing` section. Names match
+// without case and without router prefixes such as `openai/`.
+pub(crate) fn configured_price(
+    pricing: Option<&BTreeMap<String, ModelPrice>>,
+    model: &str,
+) -> Option<ModelPrice> {
+    let model = normalize_model_name(model);
+    pricing?
+        .iter()
+        .find(|(name, _)| normalize_model_name(name).eq_ignore_ascii_case(&model))
+        .map(|(_, price)| *price)
+}
+
 pub fn annotate_models_with_fallback(models: &BTreeMap<String, types::ModelUsage>) -> Vec<String> {
     models
         .iter()
@@ -195,3 +511,102 @@ pub fn annotate_models_with_fallback(models: &BTreeMap<String, types::ModelUsage
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::reports::types::{
+        DailyReportResponse, DailyReportRow, ProviderReport, ReportSort, ReportTotals,
+        SessionReportResponse, SessionReportRow,
+    };
+
+    #[test]
+    fn sorts_report_rows_by_cost_tokens_or_date() {
+        let day = |date: &str, total_tokens: u64, cost_usd: f64| DailyReportRow {
+            date: date.to_string(),
+            agent: None,
+            input_tokens: total_tokens,
+            cached_input_tokens: 0,
+            output_tokens: 0,
+            reasoning_output_tokens: 0,
+            total_tokens,
+            requests: None,
+            cost_usd,
+            efficiency: None,
+            models: BTreeMap::new(),
+        };
+        let mut report = ProviderReport::Daily(DailyReportResponse {
+            daily: vec![
+                day("2025-09-01", 300, 1.0),
+                day("2025-09-02", 100, 5.0),
+                day("2025-09-03", 200, 2.0),
+            ],
+            totals: ReportTotals::default(),
+            warnings: Vec::new(),
+        });
+        let dates = |report: &ProviderReport| match report {
+            ProviderReport::Daily(data) => data
+                .daily
+                .iter()
+                .map(|row| row.date[8..].to_string())
+                .collect::<Vec<_>>(),
+            _ => unreachable!(),
+        };
+
+        report.sort_rows(ReportSort::Cost, true);
+        assert_eq!(dates(&report), ["02", "03", "01"]);
+        report.sort_rows(ReportSort::Tokens, false);
+        assert_eq!(dates(&report), ["02", "03", "01"]);
+        report.sort_rows(ReportSort::Date, true);
+        assert_eq!(dates(&report), ["03", "02", "01"]);
+    }
+
+    #[test]
+    fn rolls_sessions_up_by_project_directory() {
+        let session =
+            |id: &str, directory: &str, project: Option<&str>, cost_usd: f64| SessionReportRow {
+                session_id: format!("{}/{}", directory, id),
+                last_activity: format!("2025-09-0{}T10:00:00.000Z", id.len()),
+                session_file: id.to_string(),
+                directory: directory.to_string(),
+                project: project.map(str::to_string),
+                sessions: None,
+                name: None,
+                agent: None,
+                input_tokens: 100,
+                cached_input_tokens: 0,
+                output_tokens: 10,
+                reasoning_output_tokens: 0,
+                total_tokens: 110,
+                cost_usd,
+                efficiency: None,
+                models: BTreeMap::new(),
+            };
+        let rolled = roll_up_directories(SessionReportResponse {
+            sessions: vec![
+                session("a", "2025/09/01", Some("/src/api"), 1.0),
+                session("bb", "2025/09/02", Some("/src/web"), 3.0),
+                session("ccc", "2025/09/03", Some("/src/api"), 1.5),
+                session("d", "-home-me-cli", None, 0.5),
+            ],
+            totals: ReportTotals::default(),
+            warnings: Vec::new(),
+        });
+
+        let rows: Vec<(&str, Option<u64>, f64)> = rolled
+            .sessions
+            .iter()
+            .map(|row| (row.directory.as_str(), row.sessions, row.cost_usd))
+            .collect();
+        assert_eq!(
+            rows,
+            [
+                ("/src/web", Some(1), 3.0),
+                ("/src/api", Some(2), 2.5),
+                ("-home-me-cli", Some(1), 0.5)
+            ]
+        );
+        assert_eq!(rolled.sessions[1].last_activity, "2025-09-03T10:00:00.000Z");
+        assert_eq!(rolled.sessions[1].total_tokens, 220);
+    }
+}
@@ -0,0 +1,258 @@
+use crate::reports::codex::{is_within_range, split_session_path, to_date_key, to_month_key};
+use crate::reports::types::{
+    CostReportKind, DailyReportResponse, DailyReportRow, ModelReportResponse, ModelReportRow,
+    ModelUsage, MonthlyReportResponse, MonthlyReportRow, ProviderReport, ReportTotals,
+    SessionReportResponse, SessionReportRow,
+};
+use chrono::{DateTime, NaiveTime, SecondsFormat, TimeZone, Utc};
+use chrono_tz::Tz;
+use std::collections::BTreeMap;
+
+// One model call from a local log that was priced when it was read, for
+// providers whose cost depends on more than the per-model token totals
+// (cache writes, long-context tiers). Token fields follow the Codex report:
+// `cached_input_tokens` is part of `input_tokens`, reasoning part of output.
+// `requests` is only set by providers that bill per request.
+#[derive(Debug, Clone)]
+pub(crate) struct PricedUsage {
+    // "<directory>/<session file>", split apart for the session report.
+    pub session_id: String,
+    pub agent: String,
+    pub timestamp: DateTime<Utc>,
+    pub model: String,
+    pub input_tokens: u64,
+    pub cached_input_tokens: u64,
+    pub output_tokens: u64,
+    pub reasoning_output_tokens: u64,
+    pub requests: Option<u64>,
+    pub cost_usd: f64,
+}
+
+#[derive(Debug, Clone, Default)]
+struct UsageSummary {
+    input_tokens: u64,
+    cached_input_tokens: u64,
+    output_tokens: u64,
+    reasoning_output_tokens: u64,
+    requests: Option<u64>,
+    cost_usd: f64,
+    models: BTreeMap<String, ModelUsage>,
+    last_activity: Option<DateTime<Utc>>,
+    agent: String,
+}
+
+impl UsageSummary {
+    fn total_tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+}
+
+pub(crate) fn build_priced_report(
+    kind: CostReportKind,
+    usages: &[PricedUsage],
+    since: Option<&str>,
+    until: Option<&str>,
+    timezone: Tz,
+    by_agent: bool,
+) -> ProviderReport {
+    let in_range = usages
+        .iter()
+        .filter(|usage| is_within_range(&to_date_key(usage.timestamp, timezone), since, until));
+    let mut totals = ReportTotals::default();
+    match kind {
+        CostReportKind::Daily => {
+            let summaries = summarize(in_range, |usage| {
+                (
+                    to_date_key(usage.timestamp, timezone),
+                    by_agent.then(|| usage.agent.clone()),
+                )
+            });
+            let daily = summaries
+                .into_iter()
+                .map(|((date, agent), summary)| {
+                    add_to_totals(&mut totals, &summary);
+                    DailyReportRow {
+                        date,
+                        agent,
+                        input_tokens: summary.input_tokens,
+                        cached_input_tokens: summary.cached_input_tokens,
+                        output_tokens: summary.output_tokens,
+                        reasoning_output_tokens: summary.reasoning_output_tokens,
+                        total_tokens: summary.total_tokens(),
+                        requests: summary.requests,
+                        cost_usd: summary.cost_usd,
+                        efficiency: None,
+                        models: summary.models,
+                    }
+                })
+                .collect();
+            ProviderReport::Daily(DailyReportResponse {
+                daily,
+                totals,
+                warnings: Vec::new(),
+            })
+        }
+        CostReportKind::Monthly => {
+            let summaries = summarize(in_range, |usage| {
+                (
+                    to_month_key(usage.timestamp, timezone),
+                    by_agent.then(|| usage.agent.clone()),
+                )
+            });
+            let monthly = summaries
+                .into_iter()
+                .map(|((month, agent), summary)| {
+                    add_to_totals(&mut totals, &summary);
+                    MonthlyReportRow {
+                        month,
+                        agent,
+                        input_tokens: summary.input_tokens,
+                        cached_input_tokens: summary.cached_input_tokens,
+                        output_tokens: summary.output_tokens,
+                        reasoning_output_tokens: summary.reasoning_output_tokens,
+                        total_tokens: summary.total_tokens(),
+                        requests: summary.requests,
+                        cost_usd: summary.cost_usd,
+                        efficiency: None,
+                        models: summary.models,
+                    }
+                })
+                .collect();
+            ProviderReport::Monthly(MonthlyReportResponse {
+                monthly,
+                totals,
+                warnings: Vec::new(),
+            })
+        }
+        CostReportKind::Models => {
+            let summaries = summarize(in_range, |usage| {
+                (usage.model.clone(), by_agent.then(|| usage.agent.clone()))
+            });
+            let mut models: Vec<_> = summaries
+                .into_iter()
+                .map(|((model, agent), summary)| {
+                    add_to_totals(&mut totals, &summary);
+                    ModelReportRow {
+                        model,
+                        agent,
+                        input_tokens: summary.input_tokens,
+                        cached_input_tokens: summary.cached_input_tokens,
+                        output_tokens: summary.output_tokens,
+                        reasoning_output_tokens: summary.reasoning_output_tokens,
+                        total_tokens: summary.total_tokens(),
+                        requests: summary.requests,
+                        cost_usd: summary.cost_usd,
+                        efficiency: None,
+                        is_fallback: None,
+                    }
+                })
+                .collect();
+            // Stable, so equal costs keep the model name order.
+            models.sort_by(|a, b| b.cost_usd.total_cmp(&a.cost_usd));
+            ProviderReport::Models(ModelReportResponse {
+                models,
+                totals,
+                warnings: Vec::new(),
+            })
+        }
+        CostReportKind::Session => {
+            let mut summaries: Vec<_> = summarize(in_range, |usage| usage.session_id.clone())
+                .into_iter()
+                .collect();
+            summaries.sort_by_key(|(_, summary)| summary.last_activity);
+            let sessions = summaries
+                .into_iter()
+                .map(|(session_id, summary)| {
+                    add_to_totals(&mut totals, &summary);
+                    let (directory, session_file) = split_session_path(&session_id);
+                    SessionReportRow {
+                        last_activity: summary
+                            .last_activity
+                            .unwrap_or_default()
+                            .to_rfc3339_opts(SecondsFormat::Millis, true),
+                        session_id,
+                        session_file,
+                        directory,
+                        project: None,
+                        sessions: None,
+                        name: None,
+                        agent: by_agent.then(|| summary.agent.clone()),
+                        input_tokens: summary.input_tokens,
+                        cached_input_tokens: summary.cached_input_tokens,
+                        output_tokens: summary.output_tokens,
+                        reasoning_output_tokens: summary.reasoning_output_tokens,
+                        total_tokens: summary.total_tokens(),
+                        cost_usd: summary.cost_usd,
+                        efficiency: None,
+                        models: summary.models,
+                    }
+                })
+                .collect();
+            ProviderReport::Session(SessionReportResponse {
+                sessions,
+                totals,
+                warnings: Vec::new(),
+            })
+        }
+    }
+}
+
+// Spend on the current day in `timezone`, and when that day ends; for
+// local-only providers whose `usage` is today's cost.
+pub(crate) fn today_cost(usages: &[PricedUsage], timezone: Tz) -> (f64, Option<DateTime<Utc>>) {
+    let today = Utc::now().with_timezone(&timezone).date_naive();
+    let spent = usages
+        .iter()
+        .filter(|usage| usage.timestamp.with_timezone(&timezone).date_naive() == today)
+        .fold(0.0, |total, usage| total + usage.cost_usd);
+    let day_ends = today
+        .succ_opt()
+        .and_then(|tomorrow| {
+            timezone
+                .from_local_datetime(&tomorrow.and_time(NaiveTime::MIN))
+                .earliest()
+        })
+        .map(|at| at.with_timezone(&Utc));
+    (spent, day_ends)
+}
+
+fn summarize<'a, K: Ord>(
+    usages: impl Iterator<Item = &'a PricedUsage>,
+    key: impl Fn(&PricedUsage) -> K,
+) -> BTreeMap<K, UsageSummary> {
+    let mut summaries: BTreeMap<K, UsageSummary> = BTreeMap::new();
+    for usage in usages {
+        let summary = summaries.entry(key(usage)).or_default();
+        summary.input_tokens += usage.input_tokens;
+        summary.cached_input_tokens += usage.cached_input_tokens;
+        summary.output_tokens += usage.output_tokens;
+        summary.reasoning_output_tokens += usage.reasoning_output_tokens;
+        if let Some(requests) = usage.requests {
+            *summary.requests.get_or_insert(0) += requests;
+        }
+        summary.cost_usd += usage.cost_usd;
+        let model = summary.models.entry(usage.model.clone()).or_default();
+        model.input_tokens += usage.input_tokens;
+        model.cached_input_tokens += usage.cached_input_tokens;
+        model.output_tokens += usage.output_tokens;
+        model.reasoning_output_tokens += usage.reasoning_output_tokens;
+        model.total_tokens += usage.input_tokens + usage.output_tokens;
+        if summary.last_activity < Some(usage.timestamp) {
+            summary.last_activity = Some(usage.timestamp);
+            summary.agent = usage.agent.clone();
+        }
+    }
+    summaries
+}
+
+fn add_to_totals(totals: &mut ReportTotals, summary: &UsageSummary) {
+    totals.input_tokens += summary.input_tokens;
+    totals.cached_input_tokens += summary.cached_input_tokens;
+    totals.output_tokens += summary.output_tokens;
+    totals.reasoning_output_tokens += summary.reasoning_output_tokens;
+    totals.total_tokens += summary.total_tokens();
+    if let Some(requests) = summary.requests {
+        *totals.requests.get_or_insert(0) += requests;
+    }
+    totals.cost_usd += summary.cost_usd;
+}
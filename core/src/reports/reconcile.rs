@@ -0,0 +1,226 @@
+use crate::history::HistoryEvent;
+use crate::reports::types::DailyReportResponse;
+use anyhow::{Result, anyhow};
+use chrono::{Months, NaiveDate};
+use chrono_tz::Tz;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconcileRow {
+    pub date: String,
+    pub local_cost_usd: f64,
+    // Credits the provider balance dropped by; `None` without samples that day.
+    pub reported_credits: Option<f64>,
+    pub expected_cost_usd: Option<f64>,
+    pub diff_percent: Option<f64>,
+    pub flagged: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReconcileReport {
+    pub provider: String,
+    pub month: String,
+    // USD per credit: `--credit-usd` when given ("fixed"), otherwise the
+    // month's local total over reported total ("implied").
+    pub credit_usd: Option<f64>,
+    pub rate_source: &'static str,
+    pub tolerance_percent: f64,
+    pub local_cost_usd: f64,
+    pub reported_credits: f64,
+    pub flagged_days: usize,
+    pub rows: Vec<ReconcileRow>,
+}
+
+// "2025-09" -> first and last day of that month.
+pub fn month_bounds(month: &str) -> Result<(NaiveDate, NaiveDate)> {
+    let first = NaiveDate::parse_from_str(&format!("{}-01", month.trim()), "%Y-%m-%d")
+        .map_err(|_| anyhow!("invalid month: {} (expected YYYY-MM)", month))?;
+    let last = first
+        .checked_add_months(Months::new(1))
+        .and_then(|next| next.pred_opt())
+        .ok_or_else(|| anyhow!("invalid month: {}", month))?;
+    Ok((first, last))
+}
+
+// Credits consumed per local day, from drops in the sampled balance between
+// consecutive samples. A drop is booked on the day of the later sample, and
+// increases (top-ups, grants) are ignored.
+pub fn consumed_credits_by_day(samples: &[HistoryEvent], timezone: Tz) -> BTreeMap<NaiveDate, f64> {
+    let mut days = BTreeMap::new();
+    let mut previous: Option<f64> = None;
+    for sample in samples {
+        let Some(balance) = sample.credits() else {
+            continue;
+        };
+        let day = sample.at.with_timezone(&timezone).date_naive();
+        let consumed = previous.map_or(0.0, |before| (before - balance).max(0.0));
+        *days.entry(day).or_insert(0.0) += consumed;
+        previous = Some(balance);
+    }
+    days
+}
+
+pub fn reconcile(
+    provider: &str,
+    month: &str,
+    local: &DailyReportResponse,
+    samples: &[HistoryEvent],
+    timezone: Tz,
+    credit_usd: Option<f64>,
+    tolerance_percent: f64,
+) -> Result<ReconcileReport> {
+    let (first, last) = month_bounds(month)?;
+    let reported = consumed_credits_by_day(samples, timezone);
+    let mut local_by_day = BTreeMap::new();
+    for row in &local.daily {
+        if let Ok(day) = NaiveDate::parse_from_str(&row.date, "%Y-%m-%d") {
+            *local_by_day.entry(day).or_insert(0.0) += row.cost_usd;
+        }
+    }
+
+    let in_month = |day: &NaiveDate| (first..=last).contains(day);
+    let local_total: f64 = local_by_day
+        .iter()
+        .filter(|(day, _)| in_month(day))
+        .map(|(_, cost)| cost)
+        .sum();
+    let reported_total: f64 = reported
+        .iter()
+        .filter(|(day, _)| in_month(day))
+        .map(|(_, credits)| credits)
+        .sum();
+    let (rate, rate_source) = match credit_usd {
+        Some(rate) => (Some(rate), "fixed"),
+        None if reported_total > 0.0 => (Some(local_total / reported_total), "implied"),
+        None => (None, "implied"),
+    };
+
+    let mut rows = Vec::new();
+    for day in first.iter_days().take_while(|day| *day <= last) {
+        let local_cost = local_by_day.get(&day).copied();
+        let reported_credits = reported.get(&day).copied();
+        if local_cost.is_some() || reported_credits.is_some() {
+            let local_cost = local_cost.unwrap_or(0.0);
+            let expected = reported_credits
+                .zip(rate)
+                .map(|(credits, rate)| credits * rate);
+            let diff_percent = expected
+                .filter(|expected| *expected > 0.0)
+                .map(|expected| (local_cost - expected) / expected * 100.0);
+            let flagged = match (expected, diff_percent) {
+                (_, Some(diff)) => diff.abs() > tolerance_percent,
+                (Some(_), None) => local_cost >= 0.01,
+                (None, None) => false,
+            };
+            rows.push(ReconcileRow {
+                date: day.format("%Y-%m-%d").to_string(),
+                local_cost_usd: local_cost,
+                reported_credits,
+                expected_cost_usd: expected,
+                diff_percent,
+                flagged,
+            });
+        }
+    }
+
+    Ok(ReconcileReport {
+        provider: provider.to_string(),
+        month: first.format("%Y-%m").to_string(),
+        credit_usd: rate,
+        rate_source,
+        tolerance_percent,
+        local_cost_usd: local_total,
+        reported_credits: reported_total,
+        flagged_days: rows.iter().filter(|row| row.flagged).count(),
+        rows,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::history::USAGE_SAMPLE;
+    use crate::reports::types::{DailyReportRow, ReportTotals};
+    use chrono::{DateTime, Utc};
+
+    fn sample(at: &str, credits: f64) -> HistoryEvent {
+        let mut event = HistoryEvent::new(
+            "codex",
+            USAGE_SAMPLE,
+            serde_json::json!({ "credits": credits }),
+        );
+        event.at = DateTime::parse_from_rfc3339(at)
+            .unwrap()
+            .with_timezone(&Utc);
+        event
+    }
+
+    fn day(date: &str, cost_usd: f64) -> DailyReportRow {
+        DailyReportRow {
+            date: date.to_string(),
+            agent: None,
+            input_tokens: 0,
+            cached_input_tokens: 0,
+            output_tokens: 0,
+            reasoning_output_tokens: 0,
+            total_tokens: 0,
+            requests: None,
+            cost_usd,
+            efficiency: None,
+            models: BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn flags_days_that_drift_from_the_implied_rate() {
+        let samples = [
+            sample("2025-09-01T08:00:00Z", 100.0),
+            sample("2025-09-01T20:00:00Z", 90.0),
+            sample("2025-09-02T20:00:00Z", 80.0),
+            sample("2025-09-03T09:00:00Z", 150.0),
+            sample("2025-09-03T20:00:00Z", 140.0),
+        ];
+        let local = DailyReportResponse {
+            daily: vec![
+                day("2025-09-01", 6.0),
+                day("2025-09-02", 6.0),
+                day("2025-09-03", 8.0),
+            ],
+            totals: ReportTotals {
+                input_tokens: 0,
+                cached_input_tokens: 0,
+                output_tokens: 0,
+                reasoning_output_tokens: 0,
+                total_tokens: 0,
+                requests: None,
+                cost_usd: 20.0,
+                efficiency: None,
+            },
+            warnings: Vec::new(),
+        };
+        let report = reconcile(
+            "codex",
+            "2025-09",
+            &local,
+            &samples,
+            chrono_tz::UTC,
+            None,
+            15.0,
+        )
+        .unwrap();
+        assert_eq!(report.reported_credits, 30.0);
+        assert_eq!(report.rate_source, "implied");
+        assert_eq!(report.rows.len(), 3);
+        assert!(!report.rows[0].flagged);
+        assert!(report.rows[2].flagged);
+        assert_eq!(report.flagged_days, 1);
+        assert!(month_bounds("2025-13").is_err());
+        assert_eq!(
+            month_bounds("2024-02").unwrap().1,
+            NaiveDate::from_ymd_opt(2024, 2, 29).unwrap()
+        );
+    }
+}
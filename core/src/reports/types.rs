@@ -1,14 +1,17 @@
 use crate::model::ProviderErrorPayload;
+use schemars::JsonSchema;
 use serde::Serialize;
 use std::collections::BTreeMap;
 use std::fmt;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
 #[serde(rename_all = "lowercase")]
 pub enum CostReportKind {
     Daily,
     Monthly,
     Session,
+    // One row per model over the whole range.
+    Models,
 }
 
 impl fmt::Display for CostReportKind {
@@ -17,12 +20,33 @@ impl fmt::Display for CostReportKind {
             Self::Daily => "daily",
             Self::Monthly => "monthly",
             Self::Session => "session",
+            Self::Models => "models",
         };
         write!(f, "{}", value)
     }
 }
 
-#[derive(Debug, Clone, Default, Serialize)]
+// Extra dimension the report rows are split by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CostReportGroupBy {
+    // The Codex front end that wrote the session: "cli", "ide", "exec", ...
+    Agent,
+    // Session reports only: one row per project directory instead of per
+    // session.
+    Directory,
+}
+
+// Row order for `--sort`; ties keep the report's own order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportSort {
+    Cost,
+    Tokens,
+    // The day, month, or last activity; models have no date and keep their
+    // order.
+    Date,
+}
+
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ModelUsage {
     pub input_tokens: u64,
@@ -34,7 +58,35 @@ pub struct ModelUsage {
     pub is_fallback: Option<bool>,
 }
 
-#[derive(Debug, Clone, Default, Serialize)]
+// Derived from a row's token counts; only attached with `--show-efficiency`.
+// Ratios are `None` when their denominator is zero.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct Efficiency {
+    // Share of input tokens served from the prompt cache.
+    pub cache_hit_ratio: Option<f64>,
+    pub output_input_ratio: Option<f64>,
+    #[serde(rename = "costPer1kOutputUSD")]
+    pub cost_per_1k_output_usd: Option<f64>,
+}
+
+impl Efficiency {
+    pub fn from_usage(
+        input_tokens: u64,
+        cached_input_tokens: u64,
+        output_tokens: u64,
+        cost_usd: f64,
+    ) -> Self {
+        let ratio = |part: f64, whole: u64| (whole > 0).then(|| part / whole as f64);
+        Self {
+            cache_hit_ratio: ratio(cached_input_tokens.min(input_tokens) as f64, input_tokens),
+            output_input_ratio: ratio(output_tokens as f64, input_tokens),
+            cost_per_1k_output_usd: ratio(cost_usd * 1000.0, output_tokens),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct ReportTotals {
     pub input_tokens: u64,
@@ -42,45 +94,76 @@ pub struct ReportTotals {
     pub output_tokens: u64,
     pub reasoning_output_tokens: u64,
     pub total_tokens: u64,
+    // Only for providers that bill per request (Cursor).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requests: Option<u64>,
     #[serde(rename = "costUSD")]
     pub cost_usd: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub efficiency: Option<Efficiency>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct DailyReportRow {
     pub date: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
     pub input_tokens: u64,
     pub cached_input_tokens: u64,
     pub output_tokens: u64,
     pub reasoning_output_tokens: u64,
     pub total_tokens: u64,
+    // Only for providers that bill per request (Cursor).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requests: Option<u64>,
     #[serde(rename = "costUSD")]
     pub cost_usd: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub efficiency: Option<Efficiency>,
     pub models: BTreeMap<String, ModelUsage>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct MonthlyReportRow {
     pub month: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
     pub input_tokens: u64,
     pub cached_input_tokens: u64,
     pub output_tokens: u64,
     pub reasoning_output_tokens: u64,
     pub total_tokens: u64,
+    // Only for providers that bill per request (Cursor).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requests: Option<u64>,
     #[serde(rename = "costUSD")]
     pub cost_usd: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub efficiency: Option<Efficiency>,
     pub models: BTreeMap<String, ModelUsage>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 #[serde(rename_all = "camelCase")]
 pub struct SessionReportRow {
     pub session_id: String,
     pub last_activity: String,
     pub session_file: String,
     pub directory: String,
+    // The working directory the session ran in, when its log records one
+    // (Codex).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project: Option<String>,
+    // How many sessions a `--group-by directory` row rolls up.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sessions: Option<u64>,
+    // Set from `sessions tag`; absent for untagged sessions.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
     pub input_tokens: u64,
     pub cached_input_tokens: u64,
     pub output_tokens: u64,
@@ -88,25 +171,63 @@ pub struct SessionReportRow {
     pub total_tokens: u64,
     #[serde(rename = "costUSD")]
     pub cost_usd: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub efficiency: Option<Efficiency>,
     pub models: BTreeMap<String, ModelUsage>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ModelReportRow {
+    pub model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+    pub input_tokens: u64,
+    pub cached_input_tokens: u64,
+    pub output_tokens: u64,
+    pub reasoning_output_tokens: u64,
+    pub total_tokens: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requests: Option<u64>,
+    #[serde(rename = "costUSD")]
+    pub cost_usd: f64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub efficiency: Option<Efficiency>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_fallback: Option<bool>,
+}
+
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct DailyReportResponse {
     pub daily: Vec<DailyReportRow>,
     pub totals: ReportTotals,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct MonthlyReportResponse {
     pub monthly: Vec<MonthlyReportRow>,
     pub totals: ReportTotals,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct SessionReportResponse {
     pub sessions: Vec<SessionReportRow>,
     pub totals: ReportTotals,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
+}
+
+// Rows are ordered by cost, most expensive first.
+#[derive(Debug, Clone, Serialize, JsonSchema)]
+pub struct ModelReportResponse {
+    pub models: Vec<ModelReportRow>,
+    pub totals: ReportTotals,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -114,6 +235,7 @@ pub enum ProviderReport {
     Daily(DailyReportResponse),
     Monthly(MonthlyReportResponse),
     Session(SessionReportResponse),
+    Models(ModelReportResponse),
 }
 
 impl ProviderReport {
@@ -122,10 +244,130 @@ impl ProviderReport {
             Self::Daily(_) => CostReportKind::Daily,
             Self::Monthly(_) => CostReportKind::Monthly,
             Self::Session(_) => CostReportKind::Session,
+            Self::Models(_) => CostReportKind::Models,
+        }
+    }
+
+    pub fn warnings(&self) -> &[String] {
+        match self {
+            Self::Daily(data) => &data.warnings,
+            Self::Monthly(data) => &data.warnings,
+            Self::Session(data) => &data.warnings,
+            Self::Models(data) => &data.warnings,
+        }
+    }
+
+    pub fn with_warnings(mut self, warnings: Vec<String>) -> Self {
+        match &mut self {
+            Self::Daily(data) => data.warnings = warnings,
+            Self::Monthly(data) => data.warnings = warnings,
+            Self::Session(data) => data.warnings = warnings,
+            Self::Models(data) => data.warnings = warnings,
+        }
+        self
+    }
+
+    pub fn attach_efficiency(&mut self) {
+        let totals = match self {
+            Self::Daily(data) => {
+                for row in &mut data.daily {
+                    row.efficiency = Some(Efficiency::from_usage(
+                        row.input_tokens,
+                        row.cached_input_tokens,
+                        row.output_tokens,
+                        row.cost_usd,
+                    ));
+                }
+                &mut data.totals
+            }
+            Self::Monthly(data) => {
+                for row in &mut data.monthly {
+                    row.efficiency = Some(Efficiency::from_usage(
+                        row.input_tokens,
+                        row.cached_input_tokens,
+                        row.output_tokens,
+                        row.cost_usd,
+                    ));
+                }
+                &mut data.totals
+            }
+            Self::Session(data) => {
+                for row in &mut data.sessions {
+                    row.efficiency = Some(Efficiency::from_usage(
+                        row.input_tokens,
+                        row.cached_input_tokens,
+                        row.output_tokens,
+                        row.cost_usd,
+                    ));
+                }
+                &mut data.totals
+            }
+            Self::Models(data) => {
+                for row in &mut data.models {
+                    row.efficiency = Some(Efficiency::from_usage(
+                        row.input_tokens,
+                        row.cached_input_tokens,
+                        row.output_tokens,
+                        row.cost_usd,
+                    ));
+                }
+                &mut data.totals
+            }
+        };
+        totals.efficiency = Some(Efficiency::from_usage(
+            totals.input_tokens,
+            totals.cached_input_tokens,
+            totals.output_tokens,
+            totals.cost_usd,
+        ));
+    }
+}
+
+impl ProviderReport {
+    pub fn sort_rows(&mut self, sort: ReportSort, descending: bool) {
+        match self {
+            Self::Daily(data) => sort_rows(&mut data.daily, sort, descending, |row| {
+                (row.cost_usd, row.total_tokens, Some(row.date.as_str()))
+            }),
+            Self::Monthly(data) => sort_rows(&mut data.monthly, sort, descending, |row| {
+                (row.cost_usd, row.total_tokens, Some(row.month.as_str()))
+            }),
+            Self::Session(data) => sort_rows(&mut data.sessions, sort, descending, |row| {
+                (
+                    row.cost_usd,
+                    row.total_tokens,
+                    Some(row.last_activity.as_str()),
+                )
+            }),
+            Self::Models(data) => sort_rows(&mut data.models, sort, descending, |row| {
+                (row.cost_usd, row.total_tokens, None)
+            }),
         }
     }
 }
 
+// `key` gives a row's cost, total tokens and date; dates are ISO strings, so
+// they order as text.
+fn sort_rows<T>(
+    rows: &mut [T],
+    sort: ReportSort,
+    descending: bool,
+    key: impl Fn(&T) -> (f64, u64, Option<&str>),
+) {
+    if sort == ReportSort::Date && rows.first().is_some_and(|row| key(row).2.is_none()) {
+        return;
+    }
+    rows.sort_by(|a, b| {
+        let (a, b) = (key(a), key(b));
+        let order = match sort {
+            ReportSort::Cost => a.0.total_cmp(&b.0),
+            ReportSort::Tokens => a.1.cmp(&b.1),
+            ReportSort::Date => a.2.cmp(&b.2),
+        };
+        if descending { order.reverse() } else { order }
+    });
+}
+
 impl Serialize for ProviderReport {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -135,6 +377,7 @@ impl Serialize for ProviderReport {
             Self::Daily(data) => data.serialize(serializer),
             Self::Monthly(data) => data.serialize(serializer),
             Self::Session(data) => data.serialize(serializer),
+            Self::Models(data) => data.serialize(serializer),
         }
     }
 }
@@ -157,6 +400,24 @@ pub struct CostReportCollection {
     pub providers: Vec<ProviderReportResult>,
 }
 
+impl CostReportCollection {
+    pub fn sort_rows(&mut self, sort: ReportSort, descending: bool) {
+        for provider in &mut self.providers {
+            if let ProviderReportOutcome::Report(report) = &mut provider.outcome {
+                report.sort_rows(sort, descending);
+            }
+        }
+    }
+
+    pub fn attach_efficiency(&mut self) {
+        for provider in &mut self.providers {
+            if let ProviderReportOutcome::Report(report) = &mut provider.outcome {
+                report.attach_efficiency();
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct SplitUsageTokens {
     pub input_tokens: u64,
@@ -0,0 +1,84 @@
+use crate::config::Config;
+use crate::model::{ProviderPayload, UsageSnapshot};
+use crate::reports::types::{
+    DailyReportResponse, ModelReportResponse, MonthlyReportResponse, SessionReportResponse,
+};
+use schemars::generate::SchemaSettings;
+use schemars::{JsonSchema, Schema};
+use serde_json::Value;
+
+// The JSON documents the CLI reads and writes, for consumers that validate or
+// generate code against them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SchemaTarget {
+    // What `usage --json` and `cost --json` print: one payload per provider.
+    Payload,
+    UsageSnapshot,
+    // One provider's `cost --report` output; several providers are keyed by id
+    // under `providers`.
+    Report,
+    Config,
+}
+
+impl SchemaTarget {
+    pub fn name(self) -> &'static str {
+        match self {
+            SchemaTarget::Payload => "payload",
+            SchemaTarget::UsageSnapshot => "usage-snapshot",
+            SchemaTarget::Report => "report",
+            SchemaTarget::Config => "config",
+        }
+    }
+}
+
+// Report responses serialize without a tag; the row key tells them apart.
+#[allow(dead_code)]
+#[derive(JsonSchema)]
+#[serde(untagged)]
+enum ReportResponse {
+    Daily(DailyReportResponse),
+    Monthly(MonthlyReportResponse),
+    Session(SessionReportResponse),
+    Models(ModelReportResponse),
+}
+
+pub fn json_schema(target: SchemaTarget) -> Value {
+    // Output documents are described as written (fields skipped when empty are
+    // optional), the config as read.
+    let settings = match target {
+        SchemaTarget::Config => SchemaSettings::default(),
+        _ => SchemaSettings::default().for_serialize(),
+    };
+    let generator = settings.into_generator();
+    let mut schema: Schema = match target {
+        SchemaTarget::Payload => generator.into_root_schema_for::<Vec<ProviderPayload>>(),
+        SchemaTarget::UsageSnapshot => generator.into_root_schema_for::<UsageSnapshot>(),
+        SchemaTarget::Report => generator.into_root_schema_for::<ReportResponse>(),
+        SchemaTarget::Config => generator.into_root_schema_for::<Config>(),
+    };
+    schema.insert(
+        "title".to_string(),
+        Value::String(format!("fuelcheck {}", target.name())),
+    );
+    schema.to_value()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn payload_schema_uses_the_serialized_field_names() {
+        let schema = json_schema(SchemaTarget::Payload);
+        assert_eq!(schema["type"], "array");
+        let payload = &schema["$defs"]["ProviderPayload"]["properties"];
+        assert!(payload.get("provider").is_some());
+        let usage = &schema["$defs"]["UsageSnapshot"]["properties"];
+        assert!(usage.get("updatedAt").is_some());
+
+        let report = json_schema(SchemaTarget::Report);
+        assert_eq!(report["anyOf"].as_array().map(Vec::len), Some(4));
+        let required = &report["$defs"]["DailyReportResponse"]["required"];
+        assert!(!required.as_array().unwrap().contains(&"warnings".into()));
+    }
+}
@@ -1,11 +1,42 @@
-use crate::config::{Config, DetectResult, ProviderConfig};
-use crate::errors::CliError;
-use crate::model::{ErrorKind, ProviderErrorPayload, ProviderPayload};
+use crate::accounts::{account_label, find_account_index};
+use crate::alerts::apply_alerts;
+use crate::budget::{
+    Budget, BudgetCheck, BudgetPeriod, BudgetSkip, BudgetSpend, MonthlyBudgetReport,
+    MonthlyBudgetStatus, month_end,
+};
+use crate::clock;
+use crate::config::{
+    CONFIG_VERSION, Config, DetectResult, NetworkSettings, ProviderConfig, migrate_config_value,
+};
+use crate::errors::{CliError, classify_error};
+use crate::forecast::{CostForecast, ForecastReport, ProviderForecast, forecast_windows};
+use crate::history::HistoryEvent;
+use crate::http::{RequestPolicy, with_request_policy};
+use crate::model::{ErrorKind, IdentityPayload, ProviderErrorPayload, ProviderPayload};
+use crate::progress;
 use crate::providers::{
-    ProviderId, ProviderRegistry, ProviderSelector, SourcePreference, expand_provider_selectors,
+    Provider, ProviderCapabilities, ProviderId, ProviderRegistry, ProviderSelector,
+    SourcePreference, expand_provider_selectors, fetch_status_summary,
+};
+use crate::reports::compare::{self, CompareRange, ProviderCompareResult};
+use crate::reports::reconcile::{self, ReconcileReport};
+use crate::reports::types::{ProviderReport, ProviderReportOutcome};
+use crate::reports::{
+    self, CostReportCollection, CostReportGroupBy, CostReportKind, CostReportRequest,
 };
-use crate::reports::{self, CostReportCollection, CostReportKind, CostReportRequest};
+use crate::shared_cache::SharedCache;
+use crate::snapshots::{COST_SNAPSHOT, SnapshotDb, USAGE_SNAPSHOT};
+use crate::validation::{PROVIDER_KEYS, credential_requirement};
+use crate::warnings::collect_warnings;
 use anyhow::{Context, Result, anyhow};
+use chrono::{NaiveDate, Utc};
+use futures_util::stream::{self, StreamExt};
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
 
 #[derive(Debug, Clone)]
 pub struct UsageRequest {
@@ -22,17 +53,120 @@ pub struct UsageRequest {
     pub antigravity_plan_debug: bool,
 }
 
+impl UsageRequest {
+    // The default source and account, without status pages or credits; what
+    // budget and forecast lookups need.
+    pub fn for_providers(providers: Vec<ProviderSelector>) -> Self {
+        Self {
+            providers,
+            source: SourcePreference::Auto,
+            status: false,
+            no_credits: true,
+            refresh: false,
+            web_debug_dump_html: false,
+            web_timeout: 20,
+            account: None,
+            account_index: None,
+            all_accounts: false,
+            antigravity_plan_debug: false,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct CostRequest {
     pub providers: Vec<ProviderSelector>,
 }
 
+#[derive(Debug, Clone)]
+pub struct BudgetRequest {
+    pub providers: Vec<ProviderSelector>,
+    pub budget: Budget,
+    pub period: BudgetPeriod,
+    pub timezone: Option<String>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct MonthlyBudgetRequest {
+    pub providers: Vec<ProviderSelector>,
+    pub timezone: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ForecastRequest {
+    pub providers: Vec<ProviderSelector>,
+    // How many days, ending today, the burn rate averages.
+    pub days: u32,
+    pub timezone: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CompareRequest {
+    pub providers: Vec<ProviderSelector>,
+    pub since: String,
+    pub until: String,
+    pub baseline_since: String,
+    pub baseline_until: String,
+    pub timezone: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReconcileRequest {
+    pub provider: ProviderId,
+    // "YYYY-MM"; the current month when unset.
+    pub month: Option<String>,
+    pub timezone: Option<String>,
+    pub credit_usd: Option<f64>,
+    pub tolerance_percent: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct WhoamiRequest {
+    pub providers: Vec<ProviderSelector>,
+    pub source: SourcePreference,
+}
+
+#[derive(Debug, Clone)]
+pub struct StatusRequest {
+    pub providers: Vec<ProviderSelector>,
+    pub web_timeout: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LogoutRequest {
+    pub providers: Vec<ProviderSelector>,
+    pub account: Option<String>,
+    pub all: bool,
+    // Also deletes the credentials the providers' own CLIs keep (e.g.
+    // `~/.codex/auth.json`), which logs the user out of those tools too.
+    pub cli_credentials: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct LogoutOutcome {
+    pub provider: ProviderId,
+    pub removed: Vec<String>,
+    pub disabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProviderListing {
+    #[serde(flatten)]
+    pub capabilities: ProviderCapabilities,
+    pub enabled: bool,
+    // Something to authenticate with was found in the config, the environment
+    // or the provider's own local login; it is not checked upstream.
+    pub credentials: bool,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct SetupRequest {
     pub enable_all: bool,
     pub claude_cookie: Option<String>,
     pub cursor_cookie: Option<String>,
     pub factory_cookie: Option<String>,
+    pub api_key: Option<String>,
 }
 
 pub async fn collect_usage_outputs(
@@ -43,15 +177,40 @@ pub async fn collect_usage_outputs(
     let provider_ids = if request.providers.is_empty() {
         config.enabled_providers_or_default()
     } else {
-        expand_provider_selectors(&request.providers)
+        registry.expand(&request.providers, config)
     };
 
-    let wants_account_override =
-        request.account.is_some() || request.account_index.is_some() || request.all_accounts;
+    if provider_ids.is_empty() {
+        return Err(anyhow!(
+            "no providers enabled; run `fuelcheck-cli setup` or pass --provider"
+        ));
+    }
+
+    let wants_account_override = request.account.is_some() || request.account_index.is_some();
     if wants_account_override && provider_ids.len() != 1 {
         return Err(anyhow!("account selection requires a single provider"));
     }
-    if wants_account_override {
+    // Across several providers `--all-accounts` covers the ones with token
+    // accounts and skips the rest.
+    let provider_ids = if request.all_accounts && provider_ids.len() > 1 {
+        let supported: Vec<_> = provider_ids
+            .into_iter()
+            .filter(|id| {
+                registry
+                    .get(id)
+                    .is_none_or(|provider| provider.supports_token_accounts())
+            })
+            .collect();
+        if supported.is_empty() {
+            return Err(anyhow!(
+                "none of the selected providers support token accounts"
+            ));
+        }
+        supported
+    } else {
+        provider_ids
+    };
+    if wants_account_override || (request.all_accounts && provider_ids.len() == 1) {
         let provider_id = provider_ids
             .first()
             .ok_or_else(|| anyhow!("no provider selected"))?;
@@ -66,32 +225,237 @@ pub async fn collect_usage_outputs(
         }
     }
 
-    let mut outputs: Vec<ProviderPayload> = Vec::new();
-    for provider_id in provider_ids {
-        let provider = registry
-            .get(&provider_id)
-            .ok_or_else(|| CliError::UnknownProvider(provider_id.to_string()))?;
-        match provider
-            .fetch_usage_all(request, config, request.source)
-            .await
-            .with_context(|| format!("provider {}", provider_id))
-        {
-            Ok(mut output_set) => outputs.append(&mut output_set),
-            Err(err) => outputs.push(ProviderPayload::error(
+    let providers = resolve_providers(registry, &provider_ids)?;
+    let output_sets = fetch_concurrently(providers, |provider| async move {
+        let provider_id = provider.id();
+        let (result, warnings) = progress::track(
+            provider_id,
+            collect_warnings(fetch_usage_with_settings(provider, request, config)),
+            |(result, _)| match result {
+                Ok(outputs) => outputs
+                    .iter()
+                    .find_map(|output| output.error.as_ref().map(|err| err.message.clone())),
+                Err(err) => Some(format!("{:#}", err)),
+            },
+        )
+        .await;
+        let mut output_set = match result.with_context(|| format!("provider {}", provider_id)) {
+            Ok(output_set) => output_set,
+            Err(err) => vec![ProviderPayload::error(
                 provider_id.to_string(),
                 request.source.to_string(),
-                ProviderErrorPayload {
-                    code: 1,
-                    message: format_error_chain(&err),
-                    kind: Some(ErrorKind::Provider),
-                },
-            )),
+                provider_error_payload(&err),
+            )],
+        };
+        attach_warnings(&mut output_set, &warnings);
+        let threshold = config
+            .provider_config(provider_id)
+            .and_then(|cfg| cfg.alert_at_percent);
+        for output in &mut output_set {
+            apply_alerts(output, threshold);
+        }
+        progress::emit_outputs(&output_set);
+        output_set
+    })
+    .await;
+
+    Ok(output_sets.into_iter().flatten().collect())
+}
+
+fn resolve_providers<'a>(
+    registry: &'a ProviderRegistry,
+    provider_ids: &[ProviderId],
+) -> Result<Vec<&'a dyn Provider>> {
+    provider_ids
+        .iter()
+        .map(|id| {
+            registry
+                .get(id)
+                .ok_or_else(|| CliError::UnknownProvider(id.to_string()).into())
+        })
+        .collect()
+}
+
+// Runs `fetch` for up to `fetch_concurrency()` providers at a time. Results
+// keep the providers' order; streamed payloads go out as each one finishes.
+async fn fetch_concurrently<'a, T, F, Fut>(providers: Vec<&'a dyn Provider>, fetch: F) -> Vec<T>
+where
+    F: FnMut(&'a dyn Provider) -> Fut,
+    Fut: Future<Output = T>,
+{
+    stream::iter(providers)
+        .map(fetch)
+        .buffered(fetch_concurrency())
+        .collect()
+        .await
+}
+
+pub const DEFAULT_FETCH_CONCURRENCY: usize = 4;
+
+static FETCH_CONCURRENCY: OnceLock<usize> = OnceLock::new();
+
+// How many providers `usage`, `cost`, `whoami` and `status` fetch at once.
+pub fn set_fetch_concurrency(limit: usize) {
+    let _ = FETCH_CONCURRENCY.set(limit.max(1));
+}
+
+pub fn fetch_concurrency() -> usize {
+    FETCH_CONCURRENCY
+        .get()
+        .copied()
+        .unwrap_or(DEFAULT_FETCH_CONCURRENCY)
+}
+
+fn attach_warnings(outputs: &mut [ProviderPayload], warnings: &[String]) {
+    for output in outputs {
+        for warning in warnings {
+            if !output.warnings.contains(warning) {
+                output.warnings.push(warning.clone());
+            }
         }
     }
+}
+
+fn payload_failure<T>(
+    result: &Result<T>,
+    error: impl Fn(&T) -> Option<&ProviderErrorPayload>,
+) -> Option<String> {
+    match result {
+        Ok(output) => error(output).map(|err| err.message.clone()),
+        Err(err) => Some(format!("{:#}", err)),
+    }
+}
+
+type UsageCache = HashMap<ProviderId, (Instant, Vec<ProviderPayload>)>;
 
+fn usage_cache() -> &'static Mutex<UsageCache> {
+    static CACHE: OnceLock<Mutex<UsageCache>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+async fn fetch_usage_with_settings(
+    provider: &dyn Provider,
+    request: &UsageRequest,
+    config: &Config,
+) -> Result<Vec<ProviderPayload>> {
+    let provider_id = provider.id();
+    let settings = config.network_settings(provider_id);
+
+    // Providers with a min interval reuse their last good result (e.g. in watch mode)
+    // instead of hitting the upstream again too soon.
+    if let Some(min_interval) = settings.min_interval
+        && let Ok(cache) = usage_cache().lock()
+        && let Some((fetched_at, outputs)) = cache.get(&provider_id)
+        && fetched_at.elapsed() < min_interval
+    {
+        return Ok(outputs.clone());
+    }
+
+    // Other invocations may be fetching the same provider right now; wait for
+    // them and take their result. Debug runs always go upstream.
+    let started = Utc::now();
+    let shared = SharedCache::open_default()
+        .filter(|_| !request.web_debug_dump_html && !request.antigravity_plan_debug);
+    let key = shared_cache_key(request);
+    let _lock = match shared.clone() {
+        Some(cache) => tokio::task::spawn_blocking(move || cache.lock(provider_id))
+            .await
+            .ok()
+            .and_then(Result::ok),
+        None => None,
+    };
+    if let Some(cache) = &shared {
+        let since = match settings.min_interval {
+            Some(min_interval) if !request.refresh => {
+                started - chrono::Duration::from_std(min_interval).unwrap_or_default()
+            }
+            _ => started,
+        };
+        if let Some(outputs) = cache.read(provider_id, &key, since) {
+            return Ok(outputs);
+        }
+    }
+
+    let mut request = request.clone();
+    if let Some(timeout) = settings.timeout {
+        request.web_timeout = timeout.as_secs();
+    }
+    let outputs = run_with_settings(provider_id, settings, || {
+        provider.fetch_usage_all(&request, config, request.source)
+    })
+    .await?;
+
+    record_snapshots(config, USAGE_SNAPSHOT, &outputs).await;
+    // A cache that cannot be written only costs coalescing.
+    if let Some(cache) = &shared
+        && outputs.iter().all(|output| output.error.is_none())
+    {
+        let _ = cache.write(provider_id, &key, &outputs);
+    }
+    if settings.min_interval.is_some()
+        && outputs.iter().all(|output| output.error.is_none())
+        && let Ok(mut cache) = usage_cache().lock()
+    {
+        cache.insert(provider_id, (Instant::now(), outputs.clone()));
+    }
     Ok(outputs)
 }
 
+// History is best effort: a missing or locked DB never fails a fetch. SQLite
+// blocks, so the write runs off the async workers.
+async fn record_snapshots(config: &Config, kind: &'static str, outputs: &[ProviderPayload]) {
+    if config.history.as_ref().and_then(|history| history.enabled) == Some(false) {
+        return;
+    }
+    let outputs = outputs.to_vec();
+    let _ = tokio::task::spawn_blocking(move || {
+        if let Ok(db) = SnapshotDb::open_default() {
+            let _ = db.record(kind, &outputs);
+        }
+    })
+    .await;
+}
+
+// Only results fetched with the same options can stand in for each other.
+fn shared_cache_key(request: &UsageRequest) -> String {
+    format!(
+        "{}|status={}|credits={}|account={:?}|index={:?}|all={}",
+        request.source,
+        request.status,
+        !request.no_credits,
+        request.account,
+        request.account_index,
+        request.all_accounts
+    )
+}
+
+async fn run_with_settings<T, F, Fut>(
+    provider: ProviderId,
+    settings: NetworkSettings,
+    fetch: F,
+) -> Result<T>
+where
+    F: FnOnce() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let policy = RequestPolicy {
+        provider: Some(provider),
+        retries: settings.retries,
+        backoff: settings.backoff,
+        timeout: settings.timeout,
+    };
+    match settings.timeout {
+        Some(timeout) => {
+            match tokio::time::timeout(timeout, with_request_policy(policy, fetch())).await {
+                Ok(result) => result,
+                Err(elapsed) => Err(anyhow::Error::new(elapsed)
+                    .context(format!("timed out after {}s", timeout.as_secs()))),
+            }
+        }
+        None => with_request_policy(policy, fetch()).await,
+    }
+}
+
 pub async fn collect_cost_outputs(
     request: &CostRequest,
     config: &Config,
@@ -100,31 +464,123 @@ pub async fn collect_cost_outputs(
     let provider_ids = if request.providers.is_empty() {
         config.enabled_providers_or_default()
     } else {
-        expand_provider_selectors(&request.providers)
+        registry.expand(&request.providers, config)
     };
 
-    let mut outputs: Vec<ProviderPayload> = Vec::new();
-    for provider_id in provider_ids {
-        let provider = registry
-            .get(&provider_id)
-            .ok_or_else(|| CliError::UnknownProvider(provider_id.to_string()))?;
-        match provider
-            .fetch_cost(request, config)
-            .await
-            .with_context(|| format!("provider {}", provider_id))
-        {
-            Ok(output) => outputs.push(output),
-            Err(err) => outputs.push(ProviderPayload::error(
+    let providers = resolve_providers(registry, &provider_ids)?;
+    let outputs = fetch_concurrently(providers, |provider| async move {
+        let provider_id = provider.id();
+        let settings = config.network_settings(provider_id);
+        let fetch = run_with_settings(provider_id, settings, || {
+            provider.fetch_cost(request, config)
+        });
+        let (result, warnings) =
+            progress::track(provider_id, collect_warnings(fetch), |(result, _)| {
+                payload_failure(result, |output| output.error.as_ref())
+            })
+            .await;
+        let mut output = match result.with_context(|| format!("provider {}", provider_id)) {
+            Ok(output) => output,
+            Err(err) => ProviderPayload::error(
                 provider_id.to_string(),
                 "local".to_string(),
-                ProviderErrorPayload {
-                    code: 1,
-                    message: format_error_chain(&err),
-                    kind: Some(ErrorKind::Provider),
-                },
-            )),
-        }
-    }
+                provider_error_payload(&err),
+            ),
+        };
+        attach_warnings(std::slice::from_mut(&mut output), &warnings);
+        record_snapshots(config, COST_SNAPSHOT, std::slice::from_ref(&output)).await;
+        progress::emit_outputs(std::slice::from_ref(&output));
+        output
+    })
+    .await;
+
+    Ok(outputs)
+}
+
+pub async fn collect_identity_outputs(
+    request: &WhoamiRequest,
+    config: &Config,
+    registry: &ProviderRegistry,
+) -> Result<Vec<IdentityPayload>> {
+    let provider_ids = if request.providers.is_empty() {
+        config.enabled_providers_or_default()
+    } else {
+        registry.expand(&request.providers, config)
+    };
+
+    let providers = resolve_providers(registry, &provider_ids)?;
+    let outputs = fetch_concurrently(providers, |provider| async move {
+        let provider_id = provider.id();
+        let settings = config.network_settings(provider_id);
+        let fetch = run_with_settings(provider_id, settings, || {
+            provider.fetch_identity(config, request.source)
+        });
+        let output = match progress::track(provider_id, fetch, |result| {
+            payload_failure(result, |output| output.error.as_ref())
+        })
+        .await
+        .with_context(|| format!("provider {}", provider_id))
+        {
+            Ok(output) => output,
+            Err(err) => IdentityPayload::error(
+                provider_id.to_string(),
+                request.source.to_string(),
+                provider_error_payload(&err),
+            ),
+        };
+        progress::emit_outputs(std::slice::from_ref(&output));
+        output
+    })
+    .await;
+
+    Ok(outputs)
+}
+
+// Only hits public status pages, so it works without any provider credentials.
+pub async fn collect_status_outputs(
+    request: &StatusRequest,
+    config: &Config,
+    registry: &ProviderRegistry,
+) -> Result<Vec<ProviderPayload>> {
+    let provider_ids = if request.providers.is_empty() {
+        config
+            .enabled_providers_or_default()
+            .into_iter()
+            .filter(|id| registry.get(id).is_some_and(|p| p.status_page().is_some()))
+            .collect()
+    } else {
+        registry.expand(&request.providers, config)
+    };
+
+    let providers = resolve_providers(registry, &provider_ids)?;
+    let outputs = fetch_concurrently(providers, |provider| async move {
+        let provider_id = provider.id();
+        let result = match provider.status_page() {
+            Some(url) => {
+                let fetch = fetch_status_summary(url, request.web_timeout);
+                progress::track(provider_id, fetch, |result| {
+                    result.as_ref().err().map(|err| format!("{:#}", err))
+                })
+                .await
+            }
+            None => Err(anyhow!("provider {} has no status page", provider_id)),
+        };
+        let output = match result.with_context(|| format!("provider {}", provider_id)) {
+            Ok(status) => {
+                let mut payload = provider.ok_output("status", None);
+                payload.status = Some(status);
+                payload
+            }
+            Err(err) => ProviderPayload::error(
+                provider_id.to_string(),
+                "status".to_string(),
+                provider_error_payload(&err),
+            ),
+        };
+        progress::emit_outputs(std::slice::from_ref(&output));
+        output
+    })
+    .await;
 
     Ok(outputs)
 }
@@ -133,15 +589,17 @@ pub fn collect_report_provider_ids(selectors: &[ProviderSelector]) -> Vec<Provid
     if selectors.is_empty() {
         return vec![ProviderId::Codex];
     }
-    expand_provider_selectors(selectors)
+    expand_provider_selectors(selectors, &ProviderId::ordered())
 }
 
-pub fn build_cost_report_collection<'a>(
+pub async fn build_cost_report_collection<'a>(
     report: CostReportKind,
     providers: Vec<ProviderId>,
     since: Option<&'a str>,
     until: Option<&'a str>,
     timezone: Option<&'a str>,
+    group_by: Option<CostReportGroupBy>,
+    config: &'a Config,
 ) -> Result<CostReportCollection> {
     reports::build_cost_report_collection(CostReportRequest {
         report,
@@ -149,9 +607,524 @@ pub fn build_cost_report_collection<'a>(
         since,
         until,
         timezone,
+        group_by,
+        config,
+    })
+    .await
+}
+
+// Builds the model report for both ranges and pairs them up per provider.
+pub async fn collect_comparisons(
+    request: &CompareRequest,
+    config: &Config,
+) -> Result<Vec<ProviderCompareResult>> {
+    let timezone = request.timezone.as_deref();
+    let current =
+        reports::validate_report_filters(Some(&request.since), Some(&request.until), timezone)?;
+    let baseline = reports::validate_report_filters(
+        Some(&request.baseline_since),
+        Some(&request.baseline_until),
+        timezone,
+    )
+    .context("baseline range")?;
+    let range = |filters: &reports::ValidatedReportFilters| CompareRange {
+        since: filters.since.clone().unwrap_or_default(),
+        until: filters.until.clone().unwrap_or_default(),
+    };
+    let (current_range, baseline_range) = (range(&current), range(&baseline));
+
+    let providers = collect_report_provider_ids(&request.providers);
+    let current = build_cost_report_collection(
+        CostReportKind::Models,
+        providers.clone(),
+        current.since.as_deref(),
+        current.until.as_deref(),
+        timezone,
+        None,
+        config,
+    )
+    .await?;
+    let baseline = build_cost_report_collection(
+        CostReportKind::Models,
+        providers,
+        baseline.since.as_deref(),
+        baseline.until.as_deref(),
+        timezone,
+        None,
+        config,
+    )
+    .await?;
+    compare::compare_collections(&current_range, &baseline_range, current, baseline)
+}
+
+// Spend since the start of the budget period. Providers with cost reports are
+// priced from their daily report; the rest count the spend they report
+// themselves, which is always a monthly figure.
+pub async fn collect_budget_check(
+    request: &BudgetRequest,
+    config: &Config,
+    registry: &ProviderRegistry,
+) -> Result<BudgetCheck> {
+    let timezone = reports::codex::resolve_timezone(request.timezone.as_deref())?;
+    let since = request
+        .period
+        .start(clock::now().with_timezone(&timezone).date_naive());
+    let provider_ids = if request.providers.is_empty() {
+        config.enabled_providers_or_default()
+    } else {
+        registry.expand(&request.providers, config)
+    };
+
+    let mut spends = Vec::new();
+    let mut skipped = Vec::new();
+    let mut remote = Vec::new();
+    let skip = |provider: &str, reason: String| BudgetSkip {
+        provider: provider.to_string(),
+        reason,
+    };
+    let mut local = Vec::new();
+    for provider_id in provider_ids {
+        if !registry
+            .get(&provider_id)
+            .is_some_and(|provider| provider.supports_cost_reports())
+        {
+            remote.push(ProviderSelector::from(provider_id));
+        } else if request.budget.currency != "USD" {
+            skipped.push(skip(
+                &provider_id.to_string(),
+                "local reports are priced in USD".to_string(),
+            ));
+        } else {
+            local.push(provider_id);
+        }
+    }
+
+    if !local.is_empty() {
+        let since = since.format("%Y-%m-%d").to_string();
+        let collection = build_cost_report_collection(
+            CostReportKind::Daily,
+            local,
+            Some(&since),
+            None,
+            request.timezone.as_deref(),
+            None,
+            config,
+        )
+        .await?;
+        for result in collection.providers {
+            match result.outcome {
+                ProviderReportOutcome::Report(ProviderReport::Daily(daily)) => {
+                    spends.push(BudgetSpend {
+                        provider: result.provider,
+                        source: "report".to_string(),
+                        amount: daily.totals.cost_usd,
+                    })
+                }
+                ProviderReportOutcome::Report(report) => skipped.push(skip(
+                    &result.provider,
+                    format!("unexpected {} report", report.kind()),
+                )),
+                ProviderReportOutcome::Error(error) => {
+                    skipped.push(skip(&result.provider, error.message))
+                }
+            }
+        }
+    }
+
+    if !remote.is_empty() {
+        let usage = UsageRequest::for_providers(remote);
+        for output in collect_usage_outputs(&usage, config, registry).await? {
+            if let Some(error) = &output.error {
+                skipped.push(skip(&output.provider, error.message.clone()));
+                continue;
+            }
+            let Some(cost) = output
+                .usage
+                .as_ref()
+                .and_then(|usage| usage.provider_cost.as_ref())
+            else {
+                skipped.push(skip(&output.provider, "no spend reported".to_string()));
+                continue;
+            };
+            if request.period != BudgetPeriod::Month {
+                skipped.push(skip(
+                    &output.provider,
+                    "provider only reports monthly spend".to_string(),
+                ));
+            } else if !cost
+                .currency_code
+                .eq_ignore_ascii_case(&request.budget.currency)
+            {
+                skipped.push(skip(
+                    &output.provider,
+                    format!("spend reported in {}", cost.currency_code),
+                ));
+            } else {
+                spends.push(BudgetSpend {
+                    provider: output.provider.clone(),
+                    source: "snapshot".to_string(),
+                    amount: cost.used,
+                });
+            }
+        }
+    }
+
+    Ok(BudgetCheck::new(
+        &request.budget,
+        request.period,
+        since,
+        spends,
+        skipped,
+    ))
+}
+
+// Month-to-date spend of each provider with a `monthly_budget`. Providers with
+// cost reports are priced from their daily report; the rest count the spend
+// they report themselves.
+pub async fn collect_monthly_budgets(
+    request: &MonthlyBudgetRequest,
+    config: &Config,
+    registry: &ProviderRegistry,
+) -> Result<MonthlyBudgetReport> {
+    let timezone = reports::codex::resolve_timezone(request.timezone.as_deref())?;
+    let today = clock::now().with_timezone(&timezone).date_naive();
+    let since = BudgetPeriod::Month.start(today);
+    let provider_ids = if request.providers.is_empty() {
+        config
+            .providers
+            .iter()
+            .flatten()
+            .filter(|cfg| cfg.monthly_budget.is_some())
+            .map(|cfg| cfg.id)
+            .collect()
+    } else {
+        registry.expand(&request.providers, config)
+    };
+    if provider_ids.is_empty() {
+        return Err(anyhow!(
+            "no provider has a monthly_budget; set one in the config, e.g. \"monthly_budget\": \"50USD\""
+        ));
+    }
+
+    let mut statuses = Vec::new();
+    let mut skipped = Vec::new();
+    let mut remote = Vec::new();
+    let skip = |provider: &str, reason: String| BudgetSkip {
+        provider: provider.to_string(),
+        reason,
+    };
+    let budget_for = |id: ProviderId| -> Result<Option<Budget>> {
+        let Some(raw) = config
+            .provider_config(id)
+            .and_then(|cfg| cfg.monthly_budget)
+        else {
+            return Ok(None);
+        };
+        raw.parse()
+            .map(Some)
+            .with_context(|| format!("{} monthly_budget", id))
+    };
+    for provider_id in provider_ids {
+        let provider = provider_id.to_string();
+        let Some(budget) = budget_for(provider_id)? else {
+            skipped.push(skip(&provider, "no monthly_budget configured".to_string()));
+            continue;
+        };
+        if !registry
+            .get(&provider_id)
+            .is_some_and(|provider| provider.supports_cost_reports())
+        {
+            remote.push((provider_id, budget));
+            continue;
+        }
+        if budget.currency != "USD" {
+            skipped.push(skip(
+                &provider,
+                "local reports are priced in USD".to_string(),
+            ));
+            continue;
+        }
+        let since = since.format("%Y-%m-%d").to_string();
+        let collection = build_cost_report_collection(
+            CostReportKind::Daily,
+            vec![provider_id],
+            Some(&since),
+            None,
+            request.timezone.as_deref(),
+            None,
+            config,
+        )
+        .await?;
+        for result in collection.providers {
+            match result.outcome {
+                ProviderReportOutcome::Report(ProviderReport::Daily(daily)) => {
+                    let mut days: Vec<(NaiveDate, f64)> = daily
+                        .daily
+                        .iter()
+                        .filter_map(|row| {
+                            let date = NaiveDate::parse_from_str(&row.date, "%Y-%m-%d").ok()?;
+                            Some((date, row.cost_usd))
+                        })
+                        .collect();
+                    days.sort_by_key(|(date, _)| *date);
+                    statuses.push(MonthlyBudgetStatus::new(
+                        result.provider,
+                        "report",
+                        &budget,
+                        daily.totals.cost_usd,
+                        today,
+                        &days,
+                    ));
+                }
+                ProviderReportOutcome::Report(report) => skipped.push(skip(
+                    &result.provider,
+                    format!("unexpected {} report", report.kind()),
+                )),
+                ProviderReportOutcome::Error(error) => {
+                    skipped.push(skip(&result.provider, error.message))
+                }
+            }
+        }
+    }
+
+    if !remote.is_empty() {
+        let usage = UsageRequest::for_providers(
+            remote
+                .iter()
+                .map(|(id, _)| ProviderSelector::from(*id))
+                .collect(),
+        );
+        let outputs = collect_usage_outputs(&usage, config, registry).await?;
+        for output in outputs {
+            let Some((_, budget)) = remote
+                .iter()
+                .find(|(id, _)| id.to_string() == output.provider)
+            else {
+                continue;
+            };
+            if let Some(error) = &output.error {
+                skipped.push(skip(&output.provider, error.message.clone()));
+                continue;
+            }
+            let Some(cost) = output
+                .usage
+                .as_ref()
+                .and_then(|usage| usage.provider_cost.as_ref())
+            else {
+                skipped.push(skip(&output.provider, "no spend reported".to_string()));
+                continue;
+            };
+            if !cost.currency_code.eq_ignore_ascii_case(&budget.currency) {
+                skipped.push(skip(
+                    &output.provider,
+                    format!("spend reported in {}", cost.currency_code),
+                ));
+                continue;
+            }
+            statuses.push(MonthlyBudgetStatus::new(
+                output.provider.clone(),
+                "snapshot",
+                budget,
+                cost.used,
+                today,
+                &[],
+            ));
+        }
+    }
+
+    Ok(MonthlyBudgetReport {
+        since,
+        today,
+        exceeded: statuses.iter().any(|status| status.exceeded),
+        providers: statuses,
+        skipped,
+    })
+}
+
+// Projects month-end spend from the daily report's recent burn rate, and when
+// each rate window runs out from its usage so far. Providers default to the
+// report ones, as with `cost --report`.
+pub async fn collect_forecast(
+    request: &ForecastRequest,
+    config: &Config,
+    registry: &ProviderRegistry,
+) -> Result<ForecastReport> {
+    let timezone = reports::codex::resolve_timezone(request.timezone.as_deref())?;
+    let now = clock::now();
+    let today = now.with_timezone(&timezone).date_naive();
+    let days = request.days.max(1);
+    let fit_start = today - chrono::Duration::days(days as i64 - 1);
+    let report_since = fit_start.min(BudgetPeriod::Month.start(today));
+    let provider_ids = collect_report_provider_ids(&request.providers);
+
+    let mut forecasts: Vec<ProviderForecast> = provider_ids
+        .iter()
+        .map(|id| ProviderForecast {
+            provider: id.to_string(),
+            cost: None,
+            windows: Vec::new(),
+        })
+        .collect();
+    let mut reasons: Vec<(String, String)> = Vec::new();
+
+    let reported: Vec<ProviderId> = provider_ids
+        .iter()
+        .copied()
+        .filter(|id| {
+            registry
+                .get(id)
+                .is_some_and(|provider| provider.supports_cost_reports())
+        })
+        .collect();
+    if !reported.is_empty() {
+        let since = report_since.format("%Y-%m-%d").to_string();
+        let collection = build_cost_report_collection(
+            CostReportKind::Daily,
+            reported,
+            Some(&since),
+            None,
+            request.timezone.as_deref(),
+            None,
+            config,
+        )
+        .await?;
+        for result in collection.providers {
+            match result.outcome {
+                ProviderReportOutcome::Report(ProviderReport::Daily(daily)) => {
+                    let rows: Vec<(NaiveDate, f64)> = daily
+                        .daily
+                        .iter()
+                        .filter_map(|row| {
+                            let date = NaiveDate::parse_from_str(&row.date, "%Y-%m-%d").ok()?;
+                            Some((date, row.cost_usd))
+                        })
+                        .collect();
+                    if let Some(forecast) = forecasts
+                        .iter_mut()
+                        .find(|forecast| forecast.provider == result.provider)
+                    {
+                        forecast.cost = Some(CostForecast::new(&rows, today, days));
+                    }
+                }
+                ProviderReportOutcome::Report(report) => reasons.push((
+                    result.provider,
+                    format!("unexpected {} report", report.kind()),
+                )),
+                ProviderReportOutcome::Error(error) => {
+                    reasons.push((result.provider, error.message))
+                }
+            }
+        }
+    }
+
+    let usage = UsageRequest::for_providers(provider_ids.iter().copied().map(Into::into).collect());
+    for output in collect_usage_outputs(&usage, config, registry).await? {
+        if let Some(error) = &output.error {
+            reasons.push((output.provider.clone(), error.message.clone()));
+            continue;
+        }
+        if let (Some(usage), Some(forecast)) = (
+            &output.usage,
+            forecasts
+                .iter_mut()
+                .find(|forecast| forecast.provider == output.provider),
+        ) {
+            forecast.windows = forecast_windows(usage, now);
+        }
+    }
+
+    // A provider is only left out when neither half produced anything.
+    let (providers, empty): (Vec<_>, Vec<_>) = forecasts
+        .into_iter()
+        .partition(|forecast| forecast.cost.is_some() || !forecast.windows.is_empty());
+    let skipped = empty
+        .into_iter()
+        .map(|forecast| {
+            let reason = reasons
+                .iter()
+                .filter(|(provider, _)| *provider == forecast.provider)
+                .map(|(_, reason)| reason.as_str())
+                .collect::<Vec<_>>()
+                .join("; ");
+            BudgetSkip {
+                reason: if reason.is_empty() {
+                    "no cost report or timed usage windows".to_string()
+                } else {
+                    reason
+                },
+                provider: forecast.provider,
+            }
+        })
+        .collect();
+
+    Ok(ForecastReport {
+        since: fit_start,
+        today,
+        month_end: month_end(today),
+        days,
+        providers,
+        skipped,
     })
 }
 
+// Compares the month's locally estimated cost against the credits the
+// provider's sampled balance says were consumed. Only providers with local
+// usage logs (Codex) can be reconciled.
+pub fn build_reconcile_report(
+    request: &ReconcileRequest,
+    samples: &[HistoryEvent],
+    config: &Config,
+) -> Result<ReconcileReport> {
+    validate_reconcile_request(request)?;
+    let timezone = reports::codex::resolve_timezone(request.timezone.as_deref())?;
+    let month = match &request.month {
+        Some(month) => month.clone(),
+        None => clock::now()
+            .with_timezone(&timezone)
+            .format("%Y-%m")
+            .to_string(),
+    };
+    let (first, last) = reconcile::month_bounds(&month)?;
+    let since = first.format("%Y-%m-%d").to_string();
+    let until = last.format("%Y-%m-%d").to_string();
+    let options = reports::codex::CodexReportOptions {
+        report: CostReportKind::Daily,
+        since: Some(&since),
+        until: Some(&until),
+        timezone: request.timezone.as_deref(),
+        group_by: None,
+        pricing: config.pricing.as_ref(),
+    };
+    let local = match reports::codex::build_report(&options)? {
+        ProviderReport::Daily(daily) => daily,
+        report => return Err(anyhow!("unexpected {} report", report.kind())),
+    };
+    reconcile::reconcile(
+        &request.provider.to_string(),
+        &month,
+        &local,
+        samples,
+        timezone,
+        request.credit_usd,
+        request.tolerance_percent,
+    )
+}
+
+// Cheap checks to run before sampling the provider.
+pub fn validate_reconcile_request(request: &ReconcileRequest) -> Result<()> {
+    if request.provider != ProviderId::Codex {
+        return Err(anyhow!(
+            "provider {} has no local usage logs to reconcile",
+            request.provider
+        ));
+    }
+    if let Some(month) = &request.month {
+        reconcile::month_bounds(month)?;
+    }
+    reports::codex::resolve_timezone(request.timezone.as_deref())?;
+    Ok(())
+}
+
 pub fn build_setup_config(request: &SetupRequest, detected: &DetectResult) -> Config {
     let mut providers = Vec::new();
 
@@ -232,8 +1205,292 @@ pub fn build_setup_config(request: &SetupRequest, detected: &DetectResult) -> Co
     );
 
     Config {
-        version: Some(1),
+        version: Some(CONFIG_VERSION),
         providers: Some(providers),
+        defaults: None,
+        groups: None,
+        logging: None,
+        pricing: None,
+        history: None,
+    }
+}
+
+pub fn apply_provider_setup(
+    config: &mut Config,
+    providers: &[ProviderId],
+    request: &SetupRequest,
+    detected: &DetectResult,
+) {
+    let fresh = build_setup_config(request, detected);
+    for id in providers {
+        let built = fresh
+            .provider_config(*id)
+            .unwrap_or_else(|| ProviderConfig::default_provider(*id));
+        let entry = config.provider_config_mut(*id);
+        entry.enabled = Some(true);
+        if request.api_key.is_some() {
+            entry.api_key = request.api_key.clone();
+        }
+        if built.cookie_header.is_some() {
+            entry.cookie_header = built.cookie_header;
+            entry.source = built.source;
+        } else if entry.source.is_none() {
+            entry.source = built.source;
+        }
+    }
+    if config.version.is_none() {
+        config.version = Some(CONFIG_VERSION);
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CodexBarImport {
+    pub config: Config,
+    pub skipped: Vec<String>,
+}
+
+pub fn import_codexbar_config(contents: &str) -> Result<CodexBarImport> {
+    let mut value: Value = serde_json::from_str(contents).context("parse CodexBar config")?;
+    migrate_config_value(&mut value)?;
+
+    let mut providers: Vec<ProviderConfig> = Vec::new();
+    let mut skipped = Vec::new();
+    let entries = value
+        .get("providers")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    for entry in entries {
+        let raw_id = entry
+            .get("id")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string();
+        let Some(id) = ProviderId::from_name(&raw_id) else {
+            skipped.push(format!("{} (not supported)", raw_id));
+            continue;
+        };
+        if providers.iter().any(|cfg| cfg.id == id) {
+            skipped.push(format!("{} (duplicate)", raw_id));
+            continue;
+        }
+        let mut fields = entry.as_object().cloned().unwrap_or_default();
+        // cookie_source only makes sense inside the CodexBar app.
+        fields.retain(|key, _| key != "cookie_source" && PROVIDER_KEYS.contains(&key.as_str()));
+        fields.insert("id".to_string(), serde_json::to_value(id)?);
+        match serde_json::from_value::<ProviderConfig>(Value::Object(fields)) {
+            Ok(cfg) => providers.push(cfg),
+            Err(err) => skipped.push(format!("{} ({})", raw_id, err)),
+        }
+    }
+
+    Ok(CodexBarImport {
+        config: Config {
+            version: Some(CONFIG_VERSION),
+            providers: Some(providers),
+            defaults: None,
+            groups: None,
+            logging: None,
+            pricing: None,
+            history: None,
+        },
+        skipped,
+    })
+}
+
+pub fn logout_providers(
+    request: &LogoutRequest,
+    config: &mut Config,
+    registry: &ProviderRegistry,
+) -> Result<Vec<LogoutOutcome>> {
+    if request.all && !request.providers.is_empty() {
+        return Err(anyhow!("use --provider or --all, not both"));
+    }
+    let provider_ids = if request.all {
+        registry.ids(config)
+    } else {
+        registry.expand(&request.providers, config)
+    };
+    if provider_ids.is_empty() {
+        return Err(anyhow!("no provider selected; pass --provider or --all"));
+    }
+
+    if let Some(name) = request.account.as_deref() {
+        let [provider_id] = provider_ids.as_slice() else {
+            return Err(anyhow!("account selection requires a single provider"));
+        };
+        return logout_account(*provider_id, name, config).map(|outcome| vec![outcome]);
+    }
+
+    let mut outcomes = Vec::new();
+    for provider_id in provider_ids {
+        let provider = registry
+            .get(&provider_id)
+            .ok_or_else(|| CliError::UnknownProvider(provider_id.to_string()))?;
+        let mut removed = Vec::new();
+        if request.cli_credentials {
+            removed.extend(
+                provider
+                    .clear_local_credentials()
+                    .with_context(|| format!("provider {}", provider_id))?,
+            );
+        }
+
+        // `--all` only touches the entries the config has; a provider named
+        // with `--provider` gets one so it can be disabled.
+        if request.all && config.provider_config(provider_id).is_none() {
+            if !removed.is_empty() {
+                outcomes.push(LogoutOutcome {
+                    provider: provider_id,
+                    removed,
+                    disabled: false,
+                });
+            }
+            continue;
+        }
+        let cfg = config.provider_config_mut(provider_id);
+        if cfg.cookie_header.take().is_some() {
+            removed.push("config: cookie_header".to_string());
+        }
+        if cfg.api_key.take().is_some() {
+            removed.push("config: api_key".to_string());
+        }
+        if let Some(accounts) = cfg.token_accounts.take() {
+            let count = accounts.accounts.map(|list| list.len()).unwrap_or(0);
+            removed.push(format!("config: token_accounts ({})", count));
+        }
+        if let Some(accounts) = cfg.cookie_accounts.take() {
+            removed.push(format!("config: cookie_accounts ({})", accounts.len()));
+        }
+        cfg.enabled = Some(false);
+
+        outcomes.push(LogoutOutcome {
+            provider: provider_id,
+            removed,
+            disabled: true,
+        });
+    }
+
+    Ok(outcomes)
+}
+
+fn logout_account(
+    provider_id: ProviderId,
+    name: &str,
+    config: &mut Config,
+) -> Result<LogoutOutcome> {
+    let cfg = config.provider_config_mut(provider_id);
+    if let Some(removed) = remove_cookie_account(cfg, name) {
+        return Ok(LogoutOutcome {
+            provider: provider_id,
+            removed: vec![format!("config: cookie account {}", removed)],
+            disabled: false,
+        });
+    }
+    let accounts = cfg
+        .token_accounts
+        .as_mut()
+        .and_then(|token_accounts| token_accounts.accounts.as_mut())
+        .ok_or_else(|| anyhow!("no accounts configured"))?;
+    let index = find_account_index(accounts, name)
+        .ok_or_else(|| anyhow!("account '{}' not found", name))?;
+    let account = accounts.remove(index);
+    let remaining = accounts.len();
+
+    if let Some(token_accounts) = cfg.token_accounts.as_mut() {
+        token_accounts.active_index = match token_accounts.active_index {
+            Some(active) if active == index => None,
+            Some(active) if active > index => Some(active - 1),
+            other => other,
+        };
+    }
+    if remaining == 0 {
+        cfg.token_accounts = None;
+    }
+
+    Ok(LogoutOutcome {
+        provider: provider_id,
+        removed: vec![format!(
+            "config: token account {}",
+            account_label(&account, index)
+        )],
+        disabled: false,
+    })
+}
+
+fn remove_cookie_account(cfg: &mut ProviderConfig, name: &str) -> Option<String> {
+    let accounts = cfg.cookie_accounts.as_mut()?;
+    let index = accounts.iter().position(|account| {
+        account
+            .label
+            .as_deref()
+            .is_some_and(|label| label.eq_ignore_ascii_case(name.trim()))
+    })?;
+    let account = accounts.remove(index);
+    if accounts.is_empty() {
+        cfg.cookie_accounts = None;
+    }
+    Some(
+        account
+            .label
+            .unwrap_or_else(|| format!("Account {}", index + 1)),
+    )
+}
+
+pub fn list_providers(config: &Config, registry: &ProviderRegistry) -> Vec<ProviderListing> {
+    let enabled = config.enabled_providers_or_default();
+    let detected = DetectResult::detect();
+    registry
+        .ids(config)
+        .iter()
+        .filter_map(|id| registry.get(id))
+        .map(|provider| ProviderListing {
+            capabilities: provider.capabilities(),
+            enabled: enabled.contains(&provider.id()),
+            credentials: has_credentials(provider, config, &detected),
+        })
+        .collect()
+}
+
+fn has_credentials(provider: &dyn Provider, config: &Config, detected: &DetectResult) -> bool {
+    let id = provider.id();
+    let local_login = match id {
+        ProviderId::Codex => detected.codex_auth,
+        ProviderId::Claude => detected.claude_oauth,
+        ProviderId::Gemini => detected.gemini_oauth,
+        _ => false,
+    };
+    let configured = config.provider_config(id).is_some_and(|cfg| {
+        [&cfg.api_key, &cfg.cookie_header]
+            .into_iter()
+            .flatten()
+            .any(|value| !value.trim().is_empty())
+            || cfg
+                .token_accounts
+                .as_ref()
+                .and_then(|accounts| accounts.accounts.as_ref())
+                .is_some_and(|accounts| !accounts.is_empty())
+            || cfg
+                .cookie_accounts
+                .as_ref()
+                .is_some_and(|accounts| !accounts.is_empty())
+    });
+    let from_env = provider.supported_sources().iter().any(|source| {
+        credential_requirement(id, *source).is_some_and(|(_, env_vars)| {
+            env_vars
+                .iter()
+                .any(|name| std::env::var(name).is_ok_and(|value| !value.trim().is_empty()))
+        })
+    });
+    local_login || configured || from_env
+}
+
+pub fn provider_error_payload(err: &anyhow::Error) -> ProviderErrorPayload {
+    let kind = classify_error(err).unwrap_or(ErrorKind::Provider);
+    ProviderErrorPayload {
+        code: kind.exit_code(),
+        message: format_error_chain(err),
+        kind: Some(kind),
     }
 }
 
@@ -245,3 +1502,126 @@ pub fn format_error_chain(err: &anyhow::Error) -> String {
     parts.dedup();
     parts.join(": ")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{HistoryConfig, ProviderConfig};
+    use crate::providers::Provider;
+    use crate::test_env::{self, EnvVarGuard};
+    use async_trait::async_trait;
+    use std::fs;
+    use std::time::Duration;
+
+    struct SlowCost;
+
+    #[async_trait]
+    impl Provider for SlowCost {
+        fn id(&self) -> ProviderId {
+            ProviderId::Codex
+        }
+
+        fn version(&self) -> &'static str {
+            "test"
+        }
+
+        fn supported_sources(&self) -> &'static [SourcePreference] {
+            &[SourcePreference::Local]
+        }
+
+        async fn fetch_usage(
+            &self,
+            _args: &UsageRequest,
+            _config: &Config,
+            _source: SourcePreference,
+        ) -> Result<ProviderPayload> {
+            unreachable!("only cost is fetched")
+        }
+
+        async fn fetch_cost(
+            &self,
+            _args: &CostRequest,
+            _config: &Config,
+        ) -> Result<ProviderPayload> {
+            tokio::time::sleep(Duration::from_secs(30)).await;
+            unreachable!("the provider timeout ends the fetch first")
+        }
+    }
+
+    #[tokio::test]
+    async fn cost_fetches_use_the_provider_timeout() {
+        let mut registry = ProviderRegistry::new();
+        registry.register(Box::new(SlowCost)).unwrap();
+        let mut codex = ProviderConfig::default_provider(ProviderId::Codex);
+        codex.timeout_secs = Some(1);
+        let config = Config {
+            providers: Some(vec![codex]),
+            history: Some(HistoryConfig {
+                enabled: Some(false),
+            }),
+            ..Config::default()
+        };
+        let request = CostRequest {
+            providers: vec![ProviderId::Codex.into()],
+        };
+
+        let started = Instant::now();
+        let outputs = collect_cost_outputs(&request, &config, &registry)
+            .await
+            .unwrap();
+        assert!(started.elapsed() < Duration::from_secs(10));
+        let error = outputs[0].error.as_ref().expect("timeout error");
+        assert!(
+            error.message.contains("timed out after 1s"),
+            "{}",
+            error.message
+        );
+    }
+
+    // Not a tokio test: the env lock is held for the whole check, so the
+    // runtime is driven from inside it.
+    #[test]
+    fn budget_check_prices_claude_from_its_logs() {
+        let dir =
+            std::env::temp_dir().join(format!("fuelcheck-budget-check-{}", uuid::Uuid::new_v4()));
+        let project = dir.join("projects").join("-home-me-app");
+        fs::create_dir_all(&project).unwrap();
+        let timestamp = Utc::now().format("%Y-%m-%dT%H:%M:%S%.3fZ");
+        // 1M input tokens and 100k output tokens of Sonnet: $3 + $1.50.
+        fs::write(
+            project.join("a1.jsonl"),
+            format!(
+                r#"{{"type":"assistant","timestamp":"{}","requestId":"req_1","message":{{"id":"msg_1","model":"claude-sonnet-4-5-20250929","usage":{{"input_tokens":1000000,"output_tokens":100000}}}}}}"#,
+                timestamp
+            ),
+        )
+        .unwrap();
+        let _lock = test_env::lock();
+        let _guard = EnvVarGuard::set("CLAUDE_CONFIG_DIR", &dir.display().to_string());
+
+        let request = BudgetRequest {
+            providers: vec![ProviderId::Claude.into()],
+            budget: "4USD".parse().unwrap(),
+            period: BudgetPeriod::Day,
+            timezone: Some("UTC".to_string()),
+        };
+        let registry = ProviderRegistry::new();
+        let check = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap()
+            .block_on(collect_budget_check(
+                &request,
+                &Config::default(),
+                &registry,
+            ))
+            .unwrap();
+
+        fs::remove_dir_all(dir).unwrap();
+        assert!(check.skipped.is_empty(), "{:?}", check.skipped);
+        assert_eq!(check.providers.len(), 1);
+        assert_eq!(check.providers[0].source, "report");
+        assert!((check.spent - 4.5).abs() < 1e-9);
+        assert!(!check.passed);
+    }
+}
@@ -0,0 +1,123 @@
+use crate::paths::default_session_tags_path;
+use crate::reports::types::SessionReportRow;
+use anyhow::{Context, Result, anyhow};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+// User-chosen names for Codex sessions, kept as a JSON object in the data dir.
+// Keys are whatever the user tagged with: the session UUID, the rollout file
+// name, or the report's session id.
+pub struct SessionTags {
+    path: PathBuf,
+    tags: BTreeMap<String, String>,
+}
+
+impl SessionTags {
+    pub fn load_default() -> Result<Self> {
+        let path =
+            default_session_tags_path().ok_or_else(|| anyhow!("session tags path unavailable"))?;
+        Self::load(path)
+    }
+
+    pub fn load(path: impl Into<PathBuf>) -> Result<Self> {
+        let path = path.into();
+        let tags = match fs::read_to_string(&path) {
+            Ok(raw) => serde_json::from_str(&raw)
+                .with_context(|| format!("parse session tags {}", path.display()))?,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(err) => return Err(err.into()),
+        };
+        Ok(Self { path, tags })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn tags(&self) -> &BTreeMap<String, String> {
+        &self.tags
+    }
+
+    pub fn set(&mut self, session: &str, name: &str) {
+        self.tags
+            .insert(session.trim().to_string(), name.trim().to_string());
+    }
+
+    pub fn remove(&mut self, session: &str) -> bool {
+        self.tags.remove(session.trim()).is_some()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("create data dir {}", parent.display()))?;
+        }
+        fs::write(&self.path, serde_json::to_string_pretty(&self.tags)?)
+            .with_context(|| format!("write session tags {}", self.path.display()))
+    }
+
+    // Rollout files are named "rollout-<timestamp>-<uuid>", so a bare UUID
+    // matches on the file name suffix.
+    pub fn name_for(&self, row: &SessionReportRow) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(key, _)| {
+                *key == &row.session_id
+                    || *key == &row.session_file
+                    || row.session_file.ends_with(&format!("-{}", key))
+            })
+            .map(|(_, name)| name.as_str())
+    }
+
+    pub fn apply(&self, rows: &mut [SessionReportRow]) {
+        for row in rows {
+            row.name = self.name_for(row).map(str::to_string);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_sessions_by_uuid_or_file() {
+        let dir = std::env::temp_dir().join(format!("fuelcheck-tags-{}", uuid::Uuid::new_v4()));
+        let mut tags = SessionTags::load(dir.join("session-tags.json")).unwrap();
+        tags.set("0199a213-81c0-7800-8aa1-bbab2a035a53", "auth refactor");
+        tags.save().unwrap();
+
+        let tags = SessionTags::load(dir.join("session-tags.json")).unwrap();
+        let mut rows = vec![SessionReportRow {
+            session_id:
+                "2025/09/01/rollout-2025-09-01T10-00-00-0199a213-81c0-7800-8aa1-bbab2a035a53"
+                    .to_string(),
+            last_activity: String::new(),
+            session_file: "rollout-2025-09-01T10-00-00-0199a213-81c0-7800-8aa1-bbab2a035a53"
+                .to_string(),
+            directory: "2025/09/01".to_string(),
+            project: None,
+            sessions: None,
+            name: None,
+            agent: None,
+            input_tokens: 0,
+            cached_input_tokens: 0,
+            output_tokens: 0,
+            reasoning_output_tokens: 0,
+            total_tokens: 0,
+            cost_usd: 0.0,
+            efficiency: None,
+            models: BTreeMap::new(),
+        }];
+        rows.push(SessionReportRow {
+            session_id: "other".to_string(),
+            session_file: "other".to_string(),
+            ..rows[0].clone()
+        });
+        tags.apply(&mut rows);
+        assert_eq!(rows[0].name.as_deref(), Some("auth refactor"));
+        assert_eq!(rows[1].name, None);
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
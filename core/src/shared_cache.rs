@@ -0,0 +1,132 @@
+use crate::model::ProviderPayload;
+use crate::paths::cache_dir;
+use crate::providers::ProviderId;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::path::PathBuf;
+
+// Usage results shared between invocations that run at the same time (a
+// prompt integration, a watch session, a cron poll). A per-provider lock file
+// serializes fetches, so whoever waited on it finds the fresh result instead
+// of hitting the upstream again.
+#[derive(Debug, Clone)]
+pub struct SharedCache {
+    dir: PathBuf,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CacheEntry {
+    fetched_at: DateTime<Utc>,
+    key: String,
+    outputs: Vec<ProviderPayload>,
+}
+
+impl SharedCache {
+    pub fn open_default() -> Option<Self> {
+        cache_dir().map(|dir| Self::at(dir.join("usage")))
+    }
+
+    pub fn at(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    // Blocks until no other invocation holds the lock for `provider`. The lock
+    // is released when the returned file is dropped, or when the process dies.
+    pub fn lock(&self, provider: ProviderId) -> Result<File> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("create cache dir {}", self.dir.display()))?;
+        let path = self.dir.join(format!("{}.lock", provider));
+        let file = OpenOptions::new()
+            .create(true)
+            .truncate(false)
+            .write(true)
+            .open(&path)
+            .with_context(|| format!("open {}", path.display()))?;
+        file.lock()
+            .with_context(|| format!("lock {}", path.display()))?;
+        Ok(file)
+    }
+
+    // The cached outputs for `key` if they were fetched at or after `since`.
+    pub fn read(
+        &self,
+        provider: ProviderId,
+        key: &str,
+        since: DateTime<Utc>,
+    ) -> Option<Vec<ProviderPayload>> {
+        let raw = fs::read_to_string(self.entry_path(provider)).ok()?;
+        let entry: CacheEntry = serde_json::from_str(&raw).ok()?;
+        (entry.key == key && entry.fetched_at >= since).then_some(entry.outputs)
+    }
+
+    pub fn write(
+        &self,
+        provider: ProviderId,
+        key: &str,
+        outputs: &[ProviderPayload],
+    ) -> Result<()> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("create cache dir {}", self.dir.display()))?;
+        let entry = CacheEntry {
+            fetched_at: Utc::now(),
+            key: key.to_string(),
+            outputs: outputs.to_vec(),
+        };
+        // Write then rename, so readers that skip the lock never see half a file.
+        let path = self.entry_path(provider);
+        let tmp = path.with_extension(format!("json.{}", std::process::id()));
+        fs::write(&tmp, serde_json::to_vec(&entry)?)
+            .with_context(|| format!("write {}", tmp.display()))?;
+        fs::rename(&tmp, &path).with_context(|| format!("write {}", path.display()))?;
+        Ok(())
+    }
+
+    fn entry_path(&self, provider: ProviderId) -> PathBuf {
+        self.dir.join(format!("{}.json", provider))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_back_entries_newer_than_since_with_the_same_key() {
+        let dir = std::env::temp_dir().join(format!("fuelcheck-cache-{}", uuid::Uuid::new_v4()));
+        let cache = SharedCache::at(&dir);
+        let before = Utc::now();
+        assert!(cache.read(ProviderId::Codex, "auto", before).is_none());
+
+        let lock = cache.lock(ProviderId::Codex).unwrap();
+        let output = ProviderPayload {
+            schema_version: crate::model::PAYLOAD_SCHEMA_VERSION,
+            provider: "codex".to_string(),
+            account: None,
+            version: None,
+            source: "oauth".to_string(),
+            status: None,
+            usage: None,
+            credits: None,
+            antigravity_plan_info: None,
+            openai_dashboard: None,
+            error: None,
+            warnings: vec!["refreshed token".to_string()],
+            alerting: false,
+            alerts: Vec::new(),
+            loaded_models: Vec::new(),
+        };
+        cache.write(ProviderId::Codex, "auto", &[output]).unwrap();
+        drop(lock);
+
+        let cached = cache.read(ProviderId::Codex, "auto", before).unwrap();
+        assert_eq!(cached[0].source, "oauth");
+        assert_eq!(cached[0].warnings, ["refreshed token"]);
+        assert!(cache.read(ProviderId::Codex, "web", before).is_none());
+        let later = Utc::now() + chrono::Duration::seconds(1);
+        assert!(cache.read(ProviderId::Codex, "auto", later).is_none());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
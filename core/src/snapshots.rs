@@ -0,0 +1,243 @@
+use crate::model::ProviderPayload;
+use crate::paths::default_snapshots_path;
+use anyhow::{Context, Result, anyhow};
+use chrono::{DateTime, Duration, NaiveDate, SecondsFormat, Utc};
+use rusqlite::{Connection, params, params_from_iter};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+
+pub const USAGE_SNAPSHOT: &str = "usage";
+pub const COST_SNAPSHOT: &str = "cost";
+
+// Every usage and cost snapshot fetched upstream, kept in a SQLite DB in the
+// data dir. Rows hold the whole payload as JSON, so trends and diffs can pick
+// out whatever they need later.
+pub struct SnapshotDb {
+    conn: Connection,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SnapshotRecord {
+    pub recorded_at: DateTime<Utc>,
+    pub kind: String,
+    pub payload: ProviderPayload,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SnapshotQuery {
+    pub providers: Vec<String>,
+    pub since: Option<DateTime<Utc>>,
+    // Exclusive.
+    pub until: Option<DateTime<Utc>>,
+    // The most recent `limit` rows, still returned oldest first.
+    pub limit: Option<usize>,
+}
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS snapshots (
+    id INTEGER PRIMARY KEY,
+    recorded_at TEXT NOT NULL,
+    provider TEXT NOT NULL,
+    account TEXT,
+    kind TEXT NOT NULL,
+    payload TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS snapshots_provider_time ON snapshots (provider, recorded_at);
+";
+
+impl SnapshotDb {
+    pub fn open_default() -> Result<Self> {
+        let path = default_snapshots_path().ok_or_else(|| anyhow!("data dir unavailable"))?;
+        Self::open(&path)
+    }
+
+    pub fn open(path: &Path) -> Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("create data dir {}", parent.display()))?;
+        }
+        let conn = Connection::open(path).with_context(|| format!("open {}", path.display()))?;
+        // Concurrent invocations write to the same file.
+        conn.busy_timeout(std::time::Duration::from_secs(5))?;
+        conn.execute_batch(SCHEMA)
+            .with_context(|| format!("prepare {}", path.display()))?;
+        Ok(Self { conn })
+    }
+
+    // Failed fetches carry nothing worth keeping and are skipped.
+    pub fn record(&self, kind: &str, outputs: &[ProviderPayload]) -> Result<usize> {
+        let recorded_at = Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true);
+        let mut recorded = 0;
+        for output in outputs {
+            if output.error.is_some() || (output.usage.is_none() && output.credits.is_none()) {
+                continue;
+            }
+            self.conn.execute(
+                "INSERT INTO snapshots (recorded_at, provider, account, kind, payload)
+                 VALUES (?1, ?2, ?3, ?4, ?5)",
+                params![
+                    recorded_at,
+                    output.provider,
+                    output.account,
+                    kind,
+                    serde_json::to_string(output)?
+                ],
+            )?;
+            recorded += 1;
+        }
+        Ok(recorded)
+    }
+
+    pub fn query(&self, query: &SnapshotQuery) -> Result<Vec<SnapshotRecord>> {
+        let mut filters = Vec::new();
+        let mut values = Vec::new();
+        if !query.providers.is_empty() {
+            filters.push(format!(
+                "provider IN ({})",
+                vec!["?"; query.providers.len()].join(", ")
+            ));
+            values.extend(query.providers.iter().cloned());
+        }
+        if let Some(since) = query.since {
+            filters.push("recorded_at >= ?".to_string());
+            values.push(since.to_rfc3339_opts(SecondsFormat::Millis, true));
+        }
+        if let Some(until) = query.until {
+            filters.push("recorded_at < ?".to_string());
+            values.push(until.to_rfc3339_opts(SecondsFormat::Millis, true));
+        }
+        let mut sql = "SELECT recorded_at, kind, payload FROM snapshots".to_string();
+        if !filters.is_empty() {
+            sql.push_str(" WHERE ");
+            sql.push_str(&filters.join(" AND "));
+        }
+        sql.push_str(" ORDER BY recorded_at DESC, id DESC");
+        if let Some(limit) = query.limit {
+            sql.push_str(&format!(" LIMIT {}", limit));
+        }
+
+        let mut statement = self.conn.prepare(&sql)?;
+        let rows = statement.query_map(params_from_iter(values), |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+        let mut records = Vec::new();
+        for row in rows {
+            let (recorded_at, kind, payload) = row?;
+            // Rows written by a newer build may not parse; they are skipped.
+            let (Ok(recorded_at), Ok(payload)) = (
+                DateTime::parse_from_rfc3339(&recorded_at),
+                serde_json::from_str::<ProviderPayload>(&payload),
+            ) else {
+                continue;
+            };
+            records.push(SnapshotRecord {
+                recorded_at: recorded_at.with_timezone(&Utc),
+                kind,
+                payload,
+            });
+        }
+        records.reverse();
+        Ok(records)
+    }
+}
+
+// RFC 3339, or a UTC date (YYYY-MM-DD / YYYYMMDD). With `end_of_day` a date
+// means the start of the following day, so it works as an exclusive bound.
+pub fn parse_time_bound(raw: &str, end_of_day: bool) -> Result<DateTime<Utc>> {
+    let trimmed = raw.trim();
+    if let Ok(at) = DateTime::parse_from_rfc3339(trimmed) {
+        return Ok(at.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(trimmed, "%Y-%m-%d")
+        .or_else(|_| NaiveDate::parse_from_str(trimmed, "%Y%m%d"))
+        .map_err(|_| {
+            anyhow!(
+                "invalid time: {} (expected YYYY-MM-DD or RFC 3339)",
+                trimmed
+            )
+        })?;
+    let start = date.and_time(chrono::NaiveTime::MIN).and_utc();
+    Ok(if end_of_day {
+        start + Duration::days(1)
+    } else {
+        start
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{CreditsSnapshot, ProviderErrorPayload};
+
+    fn payload(provider: &str, remaining: f64) -> ProviderPayload {
+        let mut payload = ProviderPayload::error(
+            provider.to_string(),
+            "oauth".to_string(),
+            ProviderErrorPayload {
+                code: 1,
+                message: String::new(),
+                kind: None,
+            },
+        );
+        payload.error = None;
+        payload.credits = Some(CreditsSnapshot {
+            remaining,
+            events: Vec::new(),
+            updated_at: Utc::now(),
+        });
+        payload
+    }
+
+    #[test]
+    fn records_and_queries_snapshots() {
+        let dir =
+            std::env::temp_dir().join(format!("fuelcheck-snapshots-{}", uuid::Uuid::new_v4()));
+        let db = SnapshotDb::open(&dir.join("snapshots.db")).unwrap();
+        let failed = ProviderPayload::error(
+            "claude".to_string(),
+            "oauth".to_string(),
+            ProviderErrorPayload {
+                code: 6,
+                message: "expired".to_string(),
+                kind: None,
+            },
+        );
+        let recorded = db
+            .record(
+                USAGE_SNAPSHOT,
+                &[payload("codex", 40.0), failed, payload("codex", 30.0)],
+            )
+            .unwrap();
+        assert_eq!(recorded, 2);
+
+        let all = db.query(&SnapshotQuery::default()).unwrap();
+        assert_eq!(all.len(), 2);
+        let latest = db
+            .query(&SnapshotQuery {
+                providers: vec!["codex".to_string()],
+                limit: Some(1),
+                ..SnapshotQuery::default()
+            })
+            .unwrap();
+        assert_eq!(latest[0].payload.credits.as_ref().unwrap().remaining, 30.0);
+        let none = db
+            .query(&SnapshotQuery {
+                until: Some(parse_time_bound("2020-01-01", true).unwrap()),
+                ..SnapshotQuery::default()
+            })
+            .unwrap();
+        assert!(none.is_empty());
+        assert_eq!(
+            parse_time_bound("20250301", true).unwrap().to_rfc3339(),
+            "2025-03-02T00:00:00+00:00"
+        );
+        assert!(parse_time_bound("yesterday", false).is_err());
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
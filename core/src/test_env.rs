@@ -0,0 +1,47 @@
+use std::sync::{Mutex, MutexGuard};
+
+// Every test that changes the environment holds this while it does, so two of
+// them never set and read variables at the same time.
+static ENV_LOCK: Mutex<()> = Mutex::new(());
+
+pub(crate) fn lock() -> MutexGuard<'static, ()> {
+    ENV_LOCK
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+}
+
+// Sets a variable for the rest of the test and restores it on Drop. Only use
+// it while holding `lock()`.
+pub(crate) struct EnvVarGuard {
+    key: String,
+    prev: Option<String>,
+}
+
+impl EnvVarGuard {
+    pub(crate) fn set(key: &str, value: &str) -> Self {
+        let prev = std::env::var(key).ok();
+        // SAFETY: the caller holds `lock()`, and the key is restored on Drop.
+        unsafe {
+            std::env::set_var(key, value);
+        }
+        Self {
+            key: key.to_string(),
+            prev,
+        }
+    }
+}
+
+impl Drop for EnvVarGuard {
+    fn drop(&mut self) {
+        match &self.prev {
+            // SAFETY: still under the caller's `lock()`.
+            Some(value) => unsafe {
+                std::env::set_var(&self.key, value);
+            },
+            // SAFETY: still under the caller's `lock()`.
+            None => unsafe {
+                std::env::remove_var(&self.key);
+            },
+        }
+    }
+}
@@ -0,0 +1,1230 @@
+use crate::budget::Budget;
+use crate::config::{CONFIG_VERSION, Config, migrate_config_value};
+use crate::providers::{ProviderId, ProviderRegistry, SourcePreference};
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+const CONFIG_KEYS: &[&str] = &[
+    "version",
+    "providers",
+    "defaults",
+    "groups",
+    "logging",
+    "pricing",
+    "history",
+];
+const HISTORY_KEYS: &[&str] = &["enabled"];
+const LOGGING_KEYS: &[&str] = &["path", "level", "max_bytes", "max_files"];
+const LOG_LEVELS: &[&str] = &[
+    "trace", "verbose", "debug", "info", "warning", "error", "critical",
+];
+const DEFAULTS_KEYS: &[&str] = &[
+    "format",
+    "pretty",
+    "status",
+    "no_credits",
+    "interval",
+    "alert_below",
+    "timezone",
+    "timeout",
+    "concurrency",
+    "proxy",
+    "extra_ca_bundle",
+];
+pub(crate) const PROVIDER_KEYS: &[&str] = &[
+    "id",
+    "enabled",
+    "source",
+    "cookie_source",
+    "cookie_header",
+    "api_key",
+    "region",
+    "workspace_id",
+    "token_accounts",
+    "cookie_accounts",
+    "timeout_secs",
+    "retries",
+    "backoff_ms",
+    "min_interval_secs",
+    "display_name",
+    "aliases",
+    "alert_at_percent",
+    "monthly_budget",
+    "command",
+    "args",
+    "custom",
+];
+const CUSTOM_KEYS: &[&str] = &[
+    "url",
+    "method",
+    "headers",
+    "body",
+    "primary",
+    "secondary",
+    "tertiary",
+    "cost",
+    "plan",
+    "account",
+];
+const CUSTOM_WINDOW_KEYS: &[&str] = &[
+    "used_percent",
+    "used",
+    "remaining",
+    "limit",
+    "resets_at",
+    "window_minutes",
+];
+const CUSTOM_COST_KEYS: &[&str] = &["used", "limit", "resets_at", "currency", "period"];
+const CUSTOM_EXAMPLE: &str = "use `\"custom\": { \"url\": \"https://gateway.example/usage\", \"primary\": { \"used_percent\": \"/quota/percent\" } }`";
+const PRICE_KEYS: &[&str] = &["input", "cached_input", "output"];
+const TOKEN_ACCOUNTS_KEYS: &[&str] = &["version", "active_index", "accounts"];
+const TOKEN_ACCOUNT_KEYS: &[&str] = &["id", "label", "token", "added_at", "last_used"];
+const COOKIE_ACCOUNT_KEYS: &[&str] = &["label", "cookie_header"];
+const COOKIE_ACCOUNT_PROVIDERS: &[ProviderId] =
+    &[ProviderId::Cursor, ProviderId::Factory, ProviderId::Claude];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticSeverity {
+    Warning,
+    Error,
+}
+
+impl fmt::Display for DiagnosticSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            DiagnosticSeverity::Warning => "warning",
+            DiagnosticSeverity::Error => "error",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigDiagnostic {
+    pub path: String,
+    pub severity: DiagnosticSeverity,
+    pub message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<String>,
+}
+
+impl ConfigDiagnostic {
+    fn error(path: impl Into<String>, message: impl Into<String>, fix: Option<String>) -> Self {
+        Self {
+            path: path.into(),
+            severity: DiagnosticSeverity::Error,
+            message: message.into(),
+            fix,
+        }
+    }
+
+    fn warning(path: impl Into<String>, message: impl Into<String>, fix: Option<String>) -> Self {
+        Self {
+            path: path.into(),
+            severity: DiagnosticSeverity::Warning,
+            message: message.into(),
+            fix,
+        }
+    }
+}
+
+pub fn validate_config_json(contents: &str, registry: &ProviderRegistry) -> Vec<ConfigDiagnostic> {
+    let mut value: Value = match serde_json::from_str(contents) {
+        Ok(value) => value,
+        Err(err) => {
+            return vec![ConfigDiagnostic::error(
+                "$",
+                format!("invalid JSON: {}", err),
+                Some(format!(
+                    "fix the syntax near line {}, column {}",
+                    err.line(),
+                    err.column()
+                )),
+            )];
+        }
+    };
+
+    let mut diagnostics = Vec::new();
+    if !value.is_object() {
+        diagnostics.push(ConfigDiagnostic::error(
+            "$",
+            "config must be a JSON object",
+            Some("wrap the config in `{ ... }`".to_string()),
+        ));
+        return diagnostics;
+    }
+
+    if let Some(version) = value.get("version")
+        && !version.is_null()
+        && !version.is_u64()
+    {
+        diagnostics.push(ConfigDiagnostic::error(
+            "version",
+            "version must be a non-negative integer",
+            Some(format!("set `\"version\": {}`", CONFIG_VERSION)),
+        ));
+        return diagnostics;
+    }
+
+    match migrate_config_value(&mut value) {
+        Ok(report) if !report.is_empty() => diagnostics.push(ConfigDiagnostic::warning(
+            "version",
+            format!(
+                "config is version {} (current is {}); it is upgraded in memory on load",
+                report.from, report.to
+            ),
+            Some("run `fuelcheck-cli config migrate` to rewrite it".to_string()),
+        )),
+        Ok(_) => {}
+        Err(err) => {
+            diagnostics.push(ConfigDiagnostic::error("version", err.to_string(), None));
+            return diagnostics;
+        }
+    }
+
+    let Some(root) = value.as_object() else {
+        return diagnostics;
+    };
+    check_unknown_keys("", root, CONFIG_KEYS, &mut diagnostics);
+    match root.get("defaults") {
+        None | Some(Value::Null) => {}
+        Some(Value::Object(defaults)) => {
+            check_unknown_keys("defaults", defaults, DEFAULTS_KEYS, &mut diagnostics);
+            if let Some(timezone) = defaults.get("timezone").and_then(Value::as_str)
+                && timezone.parse::<chrono_tz::Tz>().is_err()
+            {
+                diagnostics.push(ConfigDiagnostic::error(
+                    "defaults.timezone",
+                    format!("unknown timezone \"{}\"", timezone),
+                    Some("use an IANA name such as \"America/New_York\"".to_string()),
+                ));
+            }
+            if let Some(raw) = defaults.get("alert_below").filter(|v| !v.is_null())
+                && !raw
+                    .as_f64()
+                    .is_some_and(|value| (0.0..=100.0).contains(&value))
+            {
+                diagnostics.push(ConfigDiagnostic::error(
+                    "defaults.alert_below",
+                    "alert_below must be a number from 0 to 100",
+                    None,
+                ));
+            }
+            if let Some(raw) = defaults.get("concurrency").filter(|v| !v.is_null())
+                && raw.as_u64().is_none_or(|value| value == 0)
+            {
+                diagnostics.push(ConfigDiagnostic::error(
+                    "defaults.concurrency",
+                    "concurrency must be a whole number of 1 or more",
+                    None,
+                ));
+            }
+        }
+        Some(_) => diagnostics.push(ConfigDiagnostic::error(
+            "defaults",
+            "defaults must be an object",
+            None,
+        )),
+    }
+
+    match root.get("providers") {
+        None | Some(Value::Null) => {}
+        Some(Value::Array(entries)) => {
+            let mut seen = HashSet::new();
+            let mut any_enabled = false;
+            for (index, entry) in entries.iter().enumerate() {
+                let path = format!("providers[{}]", index);
+                any_enabled |=
+                    validate_provider(&path, entry, registry, &mut seen, &mut diagnostics);
+            }
+            validate_aliases(entries, &mut diagnostics);
+            if !entries.is_empty() && !any_enabled {
+                diagnostics.push(ConfigDiagnostic::warning(
+                    "providers",
+                    "every configured provider is disabled",
+                    Some("enable at least one provider or pass --provider".to_string()),
+                ));
+            }
+        }
+        Some(_) => diagnostics.push(ConfigDiagnostic::error(
+            "providers",
+            "providers must be an array",
+            Some("use `\"providers\": [{ \"id\": \"codex\" }]`".to_string()),
+        )),
+    }
+
+    match root.get("logging") {
+        None | Some(Value::Null) => {}
+        Some(Value::Object(logging)) => {
+            check_unknown_keys("logging", logging, LOGGING_KEYS, &mut diagnostics);
+            if let Some(level) = logging.get("level").and_then(Value::as_str)
+                && !LOG_LEVELS.contains(&level.to_lowercase().as_str())
+            {
+                diagnostics.push(ConfigDiagnostic::error(
+                    "logging.level",
+                    format!("unknown log level \"{}\"", level),
+                    Some(format!("use one of {}", LOG_LEVELS.join(", "))),
+                ));
+            }
+        }
+        Some(_) => diagnostics.push(ConfigDiagnostic::error(
+            "logging",
+            "logging must be an object",
+            Some("use `\"logging\": { \"level\": \"info\" }`".to_string()),
+        )),
+    }
+
+    match root.get("history") {
+        None | Some(Value::Null) => {}
+        Some(Value::Object(history)) => {
+            check_unknown_keys("history", history, HISTORY_KEYS, &mut diagnostics);
+        }
+        Some(_) => diagnostics.push(ConfigDiagnostic::error(
+            "history",
+            "history must be an object",
+            Some("use `\"history\": { \"enabled\": false }`".to_string()),
+        )),
+    }
+
+    match root.get("groups") {
+        None | Some(Value::Null) => {}
+        Some(Value::Object(groups)) => {
+            let entries = root
+                .get("providers")
+                .and_then(Value::as_array)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            validate_groups(groups, entries, registry, &mut diagnostics);
+        }
+        Some(_) => diagnostics.push(ConfigDiagnostic::error(
+            "groups",
+            "groups must be an object",
+            Some("use `\"groups\": { \"work\": [\"codex\", \"copilot\"] }`".to_string()),
+        )),
+    }
+
+    match root.get("pricing") {
+        None | Some(Value::Null) => {}
+        Some(Value::Object(pricing)) => validate_pricing(pricing, &mut diagnostics),
+        Some(_) => diagnostics.push(ConfigDiagnostic::error(
+            "pricing",
+            "pricing must be an object",
+            Some(PRICE_EXAMPLE.to_string()),
+        )),
+    }
+
+    if !diagnostics
+        .iter()
+        .any(|d| d.severity == DiagnosticSeverity::Error)
+        && let Err(err) = serde_json::from_value::<Config>(value)
+    {
+        diagnostics.push(ConfigDiagnostic::error("$", err.to_string(), None));
+    }
+
+    diagnostics
+}
+
+fn validate_plugin_fields(
+    path: &str,
+    obj: &Map<String, Value>,
+    diagnostics: &mut Vec<ConfigDiagnostic>,
+) {
+    let command = obj.get("command").filter(|v| !v.is_null());
+    if command.is_some() && obj.get("custom").is_some_and(|v| !v.is_null()) {
+        diagnostics.push(ConfigDiagnostic::error(
+            path,
+            "provider entry sets both command and custom",
+            Some("set either command or custom".to_string()),
+        ));
+    }
+    if command.is_some_and(|command| command.as_str().is_none_or(|raw| raw.trim().is_empty())) {
+        diagnostics.push(ConfigDiagnostic::error(
+            format!("{}.command", path),
+            "command must be the plugin executable",
+            Some("use `\"command\": \"fuelcheck-provider-foo\"`".to_string()),
+        ));
+    }
+    match obj.get("args").filter(|v| !v.is_null()) {
+        None => {}
+        Some(_) if command.is_none() => diagnostics.push(ConfigDiagnostic::warning(
+            format!("{}.args", path),
+            "args is only used with command",
+            Some("remove args or set command".to_string()),
+        )),
+        Some(Value::Array(args)) if args.iter().all(Value::is_string) => {}
+        Some(_) => diagnostics.push(ConfigDiagnostic::error(
+            format!("{}.args", path),
+            "args must be a list of strings",
+            None,
+        )),
+    }
+}
+
+fn validate_custom(path: &str, custom: &Value, diagnostics: &mut Vec<ConfigDiagnostic>) {
+    let Some(custom) = custom.as_object() else {
+        diagnostics.push(ConfigDiagnostic::error(
+            path,
+            "custom must be an object",
+            Some(CUSTOM_EXAMPLE.to_string()),
+        ));
+        return;
+    };
+    check_unknown_keys(path, custom, CUSTOM_KEYS, diagnostics);
+    let url = custom
+        .get("url")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if !url.starts_with("http://") && !url.starts_with("https://") {
+        diagnostics.push(ConfigDiagnostic::error(
+            format!("{}.url", path),
+            "url must be an http or https URL",
+            Some(CUSTOM_EXAMPLE.to_string()),
+        ));
+    }
+    for key in ["plan", "account"] {
+        check_pointer(path, custom, key, diagnostics);
+    }
+
+    let mut mapped = false;
+    for key in ["primary", "secondary", "tertiary", "cost"] {
+        let Some(mapping) = custom.get(key).filter(|v| !v.is_null()) else {
+            continue;
+        };
+        mapped = true;
+        let mapping_path = format!("{}.{}", path, key);
+        let Some(mapping) = mapping.as_object() else {
+            diagnostics.push(ConfigDiagnostic::error(
+                &mapping_path,
+                format!("{} must be an object of JSON pointers", key),
+                Some(CUSTOM_EXAMPLE.to_string()),
+            ));
+            continue;
+        };
+        let (keys, complete) = if key == "cost" {
+            (CUSTOM_COST_KEYS, mapping.contains_key("used"))
+        } else {
+            (
+                CUSTOM_WINDOW_KEYS,
+                mapping.contains_key("used_percent")
+                    || (mapping.contains_key("limit")
+                        && (mapping.contains_key("used") || mapping.contains_key("remaining"))),
+            )
+        };
+        check_unknown_keys(&mapping_path, mapping, keys, diagnostics);
+        for field in keys {
+            if !matches!(*field, "window_minutes" | "currency" | "period") {
+                check_pointer(&mapping_path, mapping, field, diagnostics);
+            }
+        }
+        if !complete {
+            let fix = if key == "cost" {
+                "set used"
+            } else {
+                "set used_percent, or limit with used or remaining"
+            };
+            diagnostics.push(ConfigDiagnostic::error(
+                &mapping_path,
+                format!("{} maps no usage", key),
+                Some(fix.to_string()),
+            ));
+        }
+    }
+    if !mapped {
+        diagnostics.push(ConfigDiagnostic::error(
+            path,
+            "custom maps no usage fields",
+            Some(CUSTOM_EXAMPLE.to_string()),
+        ));
+    }
+}
+
+// JSON pointers start with `/` (or are empty for the whole document).
+fn check_pointer(
+    path: &str,
+    obj: &Map<String, Value>,
+    key: &str,
+    diagnostics: &mut Vec<ConfigDiagnostic>,
+) {
+    match obj.get(key) {
+        None | Some(Value::Null) => {}
+        Some(Value::String(pointer)) if pointer.is_empty() || pointer.starts_with('/') => {}
+        Some(_) => diagnostics.push(ConfigDiagnostic::error(
+            format!("{}.{}", path, key),
+            format!("{} must be a JSON pointer such as \"/data/{}\"", key, key),
+            None,
+        )),
+    }
+}
+
+// Returns whether the provider entry is enabled.
+fn validate_provider(
+    path: &str,
+    entry: &Value,
+    registry: &ProviderRegistry,
+    seen: &mut HashSet<ProviderId>,
+    diagnostics: &mut Vec<ConfigDiagnostic>,
+) -> bool {
+    let Some(obj) = entry.as_object() else {
+        diagnostics.push(ConfigDiagnostic::error(
+            path,
+            "provider entry must be an object",
+            Some("use `{ \"id\": \"<provider>\" }`".to_string()),
+        ));
+        return false;
+    };
+
+    check_unknown_keys(path, obj, PROVIDER_KEYS, diagnostics);
+    validate_plugin_fields(path, obj, diagnostics);
+    if let Some(custom) = obj.get("custom").filter(|v| !v.is_null()) {
+        validate_custom(&format!("{}.custom", path), custom, diagnostics);
+    }
+
+    if obj.get("cookie_source").is_some_and(|v| !v.is_null()) {
+        diagnostics.push(ConfigDiagnostic::warning(
+            format!("{}.cookie_source", path),
+            "cookie_source is deprecated and ignored; browser cookie import is not supported",
+            Some("remove cookie_source and set cookie_header instead".to_string()),
+        ));
+    }
+
+    let enabled = match obj.get("enabled") {
+        None | Some(Value::Null) => true,
+        Some(Value::Bool(value)) => *value,
+        Some(_) => {
+            diagnostics.push(ConfigDiagnostic::error(
+                format!("{}.enabled", path),
+                "enabled must be true or false",
+                None,
+            ));
+            true
+        }
+    };
+
+    let id = match obj.get("id") {
+        None | Some(Value::Null) => {
+            diagnostics.push(ConfigDiagnostic::error(
+                format!("{}.id", path),
+                "provider id is missing",
+                Some(format!("set id to one of: {}", provider_id_list())),
+            ));
+            return enabled;
+        }
+        Some(raw) => match serde_json::from_value::<ProviderId>(raw.clone()) {
+            Ok(id) => id,
+            Err(_) => {
+                diagnostics.push(unknown_provider_id(path, raw, entry));
+                return enabled;
+            }
+        },
+    };
+    if matches!(id, ProviderId::External(_))
+        && !registry.is_registered(&id)
+        && !defines_provider(entry)
+    {
+        diagnostics.push(unknown_provider_id(path, &obj["id"], entry));
+        return enabled;
+    }
+
+    if !seen.insert(id) {
+        diagnostics.push(ConfigDiagnostic::warning(
+            format!("{}.id", path),
+            format!(
+                "duplicate entry for provider {}; only the first is used",
+                id
+            ),
+            Some("merge the entries into one".to_string()),
+        ));
+    }
+
+    let Some(provider) = registry.get(&id) else {
+        diagnostics.push(ConfigDiagnostic::error(
+            format!("{}.id", path),
+            format!("provider {} is not available in this build", id),
+            Some("remove the entry or disable it".to_string()),
+        ));
+        return enabled;
+    };
+
+    let source = match obj.get("source") {
+        None | Some(Value::Null) => None,
+        Some(raw) => match serde_json::from_value::<SourcePreference>(raw.clone()) {
+            Ok(source) => Some(source),
+            Err(_) => {
+                diagnostics.push(ConfigDiagnostic::error(
+                    format!("{}.source", path),
+                    format!("unknown source {}", raw),
+                    Some("use one of: auto, oauth, web, cli, api, local".to_string()),
+                ));
+                None
+            }
+        },
+    };
+
+    if let Some(source) = source
+        && source != SourcePreference::Auto
+    {
+        let supported = provider.supported_sources();
+        if !supported.contains(&source) {
+            diagnostics.push(ConfigDiagnostic::error(
+                format!("{}.source", path),
+                format!("provider {} cannot be reached via source {}", id, source),
+                Some(format!("use one of: auto, {}", join_sources(supported))),
+            ));
+        } else if enabled {
+            check_credentials(path, id, source, obj, diagnostics);
+        }
+    }
+
+    for (field, readers) in [
+        ("region", &[ProviderId::Zai, ProviderId::MiniMax][..]),
+        ("workspace_id", &[ProviderId::OpenCode][..]),
+    ] {
+        if obj.get(field).is_some_and(|v| !v.is_null()) && !readers.contains(&id) {
+            diagnostics.push(ConfigDiagnostic::warning(
+                format!("{}.{}", path, field),
+                format!("{} is ignored by provider {}", field, id),
+                Some(format!("remove {}", field)),
+            ));
+        }
+    }
+
+    if let Some(accounts) = obj.get("token_accounts").filter(|v| !v.is_null()) {
+        let accounts_path = format!("{}.token_accounts", path);
+        if !provider.supports_token_accounts() {
+            diagnostics.push(ConfigDiagnostic::warning(
+                &accounts_path,
+                format!("provider {} does not support token accounts", id),
+                Some("remove token_accounts".to_string()),
+            ));
+        }
+        validate_token_accounts(&accounts_path, accounts, diagnostics);
+    }
+
+    if let Some(accounts) = obj.get("cookie_accounts").filter(|v| !v.is_null()) {
+        let accounts_path = format!("{}.cookie_accounts", path);
+        if !COOKIE_ACCOUNT_PROVIDERS.contains(&id) {
+            diagnostics.push(ConfigDiagnostic::warning(
+                &accounts_path,
+                format!("provider {} does not support cookie accounts", id),
+                Some("remove cookie_accounts".to_string()),
+            ));
+        }
+        validate_cookie_accounts(&accounts_path, accounts, diagnostics);
+    }
+
+    if let Some(raw) = obj.get("alert_at_percent").filter(|v| !v.is_null())
+        && !raw
+            .as_f64()
+            .is_some_and(|value| (0.0..=100.0).contains(&value))
+    {
+        diagnostics.push(ConfigDiagnostic::error(
+            format!("{}.alert_at_percent", path),
+            "alert_at_percent must be a number from 0 to 100",
+            None,
+        ));
+    }
+
+    if let Some(raw) = obj.get("monthly_budget").filter(|v| !v.is_null())
+        && raw
+            .as_str()
+            .is_none_or(|value| value.parse::<Budget>().is_err())
+    {
+        diagnostics.push(ConfigDiagnostic::error(
+            format!("{}.monthly_budget", path),
+            "monthly_budget must be an amount such as \"50USD\"",
+            None,
+        ));
+    }
+
+    enabled
+}
+
+const PRICE_EXAMPLE: &str = "use `\"pricing\": { \"gpt-4.1\": { \"input\": 2.0, \"cached_input\": 0.5, \"output\": 8.0 } }`";
+
+// Model prices in USD per million tokens; `cached_input` may be left out.
+fn validate_pricing(pricing: &Map<String, Value>, diagnostics: &mut Vec<ConfigDiagnostic>) {
+    for (model, price) in pricing {
+        let path = format!("pricing.{}", model);
+        let Some(price) = price.as_object() else {
+            diagnostics.push(ConfigDiagnostic::error(
+                &path,
+                "a model price must be an object",
+                Some(PRICE_EXAMPLE.to_string()),
+            ));
+            continue;
+        };
+        check_unknown_keys(&path, price, PRICE_KEYS, diagnostics);
+        for key in PRICE_KEYS {
+            let value = price.get(*key).filter(|v| !v.is_null());
+            if value.is_none() && *key == "cached_input" {
+                continue;
+            }
+            if !value
+                .and_then(Value::as_f64)
+                .is_some_and(|value| value >= 0.0)
+            {
+                diagnostics.push(ConfigDiagnostic::error(
+                    format!("{}.{}", path, key),
+                    format!("{} must be a non-negative price per million tokens", key),
+                    None,
+                ));
+            }
+        }
+    }
+}
+
+fn builtin_selector_names() -> HashSet<String> {
+    ProviderId::ordered()
+        .into_iter()
+        .map(|id| id.to_string())
+        .chain(["all", "both", "droid", "kimi-k2"].map(str::to_string))
+        .collect()
+}
+
+// Registered external providers and the ones the config defines itself.
+fn external_provider_names(entries: &[Value], registry: &ProviderRegistry) -> HashSet<String> {
+    let defined = entries
+        .iter()
+        .filter(|entry| defines_provider(entry))
+        .filter_map(|entry| entry.get("id").and_then(Value::as_str))
+        .map(str::to_string);
+    registry
+        .external()
+        .iter()
+        .map(ToString::to_string)
+        .chain(defined)
+        .collect()
+}
+
+// A `command` (plugin) or a `custom` HTTP endpoint.
+fn defines_provider(entry: &Value) -> bool {
+    ["command", "custom"]
+        .iter()
+        .any(|key| entry.get(*key).is_some_and(|value| !value.is_null()))
+}
+
+fn configured_aliases(entries: &[Value]) -> HashSet<String> {
+    entries
+        .iter()
+        .filter_map(|entry| entry.get("aliases").and_then(Value::as_array))
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(|alias| alias.trim().to_lowercase())
+        .collect()
+}
+
+fn validate_aliases(entries: &[Value], diagnostics: &mut Vec<ConfigDiagnostic>) {
+    let builtin = builtin_selector_names();
+    let mut claimed: HashMap<String, usize> = HashMap::new();
+
+    for (index, entry) in entries.iter().enumerate() {
+        let path = format!("providers[{}].aliases", index);
+        let Some(raw) = entry.get("aliases").filter(|v| !v.is_null()) else {
+            continue;
+        };
+        let Some(aliases) = raw.as_array() else {
+            diagnostics.push(ConfigDiagnostic::error(
+                path,
+                "aliases must be an array of strings",
+                Some("use `\"aliases\": [\"work\"]`".to_string()),
+            ));
+            continue;
+        };
+        for (alias_index, alias) in aliases.iter().enumerate() {
+            let alias_path = format!("{}[{}]", path, alias_index);
+            let Some(alias) = alias.as_str().map(str::trim).filter(|a| !a.is_empty()) else {
+                diagnostics.push(ConfigDiagnostic::error(
+                    alias_path,
+                    "alias must be a non-empty string",
+                    None,
+                ));
+                continue;
+            };
+            let key = alias.to_lowercase();
+            if builtin.contains(&key) {
+                diagnostics.push(ConfigDiagnostic::warning(
+                    alias_path,
+                    format!(
+                        "alias \"{}\" shadows a built-in provider name and is ignored",
+                        alias
+                    ),
+                    Some("pick a different alias".to_string()),
+                ));
+            } else if let Some(owner) = claimed.get(&key) {
+                if *owner != index {
+                    diagnostics.push(ConfigDiagnostic::warning(
+                        alias_path,
+                        format!(
+                            "alias \"{}\" is already used by providers[{}]; only the first is used",
+                            alias, owner
+                        ),
+                        Some("remove one of the duplicate aliases".to_string()),
+                    ));
+                }
+            } else {
+                claimed.insert(key, index);
+            }
+        }
+    }
+}
+
+fn validate_groups(
+    groups: &Map<String, Value>,
+    entries: &[Value],
+    registry: &ProviderRegistry,
+    diagnostics: &mut Vec<ConfigDiagnostic>,
+) {
+    let builtin = builtin_selector_names();
+    let aliases = configured_aliases(entries);
+    let external = external_provider_names(entries, registry);
+
+    for (name, members) in groups {
+        let path = format!("groups.{}", name);
+        let key = name.trim().to_lowercase();
+        if builtin.contains(&key) || aliases.contains(&key) {
+            diagnostics.push(ConfigDiagnostic::warning(
+                path.clone(),
+                format!(
+                    "group \"{}\" shares its name with a provider or alias and is ignored",
+                    name
+                ),
+                Some("rename the group".to_string()),
+            ));
+        }
+        let Some(members) = members.as_array() else {
+            diagnostics.push(ConfigDiagnostic::error(
+                path,
+                "group must be an array of provider names",
+                Some("use `[\"codex\", \"copilot\"]`".to_string()),
+            ));
+            continue;
+        };
+        if members.is_empty() {
+            diagnostics.push(ConfigDiagnostic::warning(
+                path.clone(),
+                "group is empty",
+                Some("add providers or remove the group".to_string()),
+            ));
+        }
+        for (index, member) in members.iter().enumerate() {
+            let member_path = format!("{}[{}]", path, index);
+            let Some(member) = member.as_str().map(str::trim).filter(|m| !m.is_empty()) else {
+                diagnostics.push(ConfigDiagnostic::error(
+                    member_path,
+                    "group member must be a non-empty string",
+                    None,
+                ));
+                continue;
+            };
+            let key = member.to_lowercase();
+            let known = ProviderId::from_name(member).is_some()
+                || builtin.contains(&key)
+                || aliases.contains(&key)
+                || external.contains(&key);
+            if !known {
+                diagnostics.push(ConfigDiagnostic::error(
+                    member_path,
+                    format!("unknown provider \"{}\"", member),
+                    Some(
+                        suggest_provider_id(member)
+                            .map(|id| format!("did you mean \"{}\"?", id))
+                            .unwrap_or_else(|| {
+                                format!("use a provider id or alias ({})", provider_id_list())
+                            }),
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+fn validate_token_accounts(path: &str, value: &Value, diagnostics: &mut Vec<ConfigDiagnostic>) {
+    let Some(obj) = value.as_object() else {
+        diagnostics.push(ConfigDiagnostic::error(
+            path,
+            "token_accounts must be an object",
+            None,
+        ));
+        return;
+    };
+    check_unknown_keys(path, obj, TOKEN_ACCOUNTS_KEYS, diagnostics);
+
+    let accounts = obj
+        .get("accounts")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default();
+    for (index, account) in accounts.iter().enumerate() {
+        let account_path = format!("{}.accounts[{}]", path, index);
+        let Some(account) = account.as_object() else {
+            diagnostics.push(ConfigDiagnostic::error(
+                account_path,
+                "token account must be an object",
+                None,
+            ));
+            continue;
+        };
+        check_unknown_keys(&account_path, account, TOKEN_ACCOUNT_KEYS, diagnostics);
+        if account
+            .get("token")
+            .and_then(Value::as_str)
+            .is_none_or(|token| token.trim().is_empty())
+        {
+            diagnostics.push(ConfigDiagnostic::warning(
+                format!("{}.token", account_path),
+                "token account has no token and will be skipped",
+                Some("set token or remove the account".to_string()),
+            ));
+        }
+    }
+
+    if let Some(index) = obj.get("active_index").and_then(Value::as_u64)
+        && index as usize >= accounts.len()
+    {
+        diagnostics.push(ConfigDiagnostic::warning(
+            format!("{}.active_index", path),
+            format!(
+                "active_index {} is out of range ({} accounts)",
+                index,
+                accounts.len()
+            ),
+            Some("set active_index to 0 or remove it".to_string()),
+        ));
+    }
+}
+
+fn validate_cookie_accounts(path: &str, value: &Value, diagnostics: &mut Vec<ConfigDiagnostic>) {
+    let Some(accounts) = value.as_array() else {
+        diagnostics.push(ConfigDiagnostic::error(
+            path,
+            "cookie_accounts must be an array",
+            Some(
+                "use `\"cookie_accounts\": [{\"label\": \"work\", \"cookie_header\": \"...\"}]`"
+                    .to_string(),
+            ),
+        ));
+        return;
+    };
+    for (index, account) in accounts.iter().enumerate() {
+        let account_path = format!("{}[{}]", path, index);
+        let Some(account) = account.as_object() else {
+            diagnostics.push(ConfigDiagnostic::error(
+                account_path,
+                "cookie account must be an object",
+                None,
+            ));
+            continue;
+        };
+        check_unknown_keys(&account_path, account, COOKIE_ACCOUNT_KEYS, diagnostics);
+        if account
+            .get("cookie_header")
+            .and_then(Value::as_str)
+            .is_none_or(|header| header.trim().is_empty())
+        {
+            diagnostics.push(ConfigDiagnostic::warning(
+                format!("{}.cookie_header", account_path),
+                "cookie account has no cookie_header and will fail to fetch",
+                Some("set cookie_header or remove the account".to_string()),
+            ));
+        }
+    }
+}
+
+fn check_credentials(
+    path: &str,
+    id: ProviderId,
+    source: SourcePreference,
+    obj: &Map<String, Value>,
+    diagnostics: &mut Vec<ConfigDiagnostic>,
+) {
+    let Some((field, env_vars)) = credential_requirement(id, source) else {
+        return;
+    };
+    let configured = obj
+        .get(field)
+        .and_then(Value::as_str)
+        .is_some_and(|v| !v.trim().is_empty());
+    let has_accounts = obj
+        .get("token_accounts")
+        .and_then(|v| v.get("accounts"))
+        .and_then(Value::as_array)
+        .is_some_and(|accounts| !accounts.is_empty())
+        || obj
+            .get("cookie_accounts")
+            .and_then(Value::as_array)
+            .is_some_and(|accounts| !accounts.is_empty());
+    let from_env = env_vars.iter().any(|name| {
+        std::env::var(name)
+            .ok()
+            .is_some_and(|v| !v.trim().is_empty())
+    });
+    if configured || has_accounts || from_env {
+        return;
+    }
+
+    let fix = if env_vars.is_empty() {
+        format!("set {}", field)
+    } else {
+        format!("set {} or export {}", field, env_vars.join(" / "))
+    };
+    diagnostics.push(ConfigDiagnostic::warning(
+        format!("{}.source", path),
+        format!("source is {} but no {} is configured", source, field),
+        Some(fix),
+    ));
+}
+
+pub(crate) fn credential_requirement(
+    id: ProviderId,
+    source: SourcePreference,
+) -> Option<(&'static str, &'static [&'static str])> {
+    match (id, source) {
+        (ProviderId::AnthropicApi, SourcePreference::Api) => {
+            Some(("api_key", &["ANTHROPIC_ADMIN_KEY"]))
+        }
+        (ProviderId::OpenAIPlatform, SourcePreference::Api) => {
+            Some(("api_key", &["OPENAI_ADMIN_KEY"]))
+        }
+        (ProviderId::OpenAIPlatform, SourcePreference::Web) => {
+            Some(("cookie_header", &["OPENAI_SESSION_KEY"]))
+        }
+        (ProviderId::Amp, SourcePreference::Web) => {
+            Some(("cookie_header", &["AMP_COOKIE", "AMP_COOKIE_HEADER"]))
+        }
+        (ProviderId::Claude, SourcePreference::Web) => Some(("cookie_header", &["CLAUDE_COOKIE"])),
+        (ProviderId::Cursor, SourcePreference::Web | SourcePreference::Api) => {
+            Some(("cookie_header", &["CURSOR_COOKIE"]))
+        }
+        (ProviderId::Factory, SourcePreference::Web) => {
+            Some(("cookie_header", &["FACTORY_COOKIE", "DROID_COOKIE"]))
+        }
+        (ProviderId::Factory, SourcePreference::Api) => {
+            Some(("api_key", &["FACTORY_BEARER_TOKEN"]))
+        }
+        (ProviderId::MiniMax, SourcePreference::Web) => Some((
+            "cookie_header",
+            &["MINIMAX_COOKIE", "MINIMAX_COOKIE_HEADER"],
+        )),
+        (ProviderId::MiniMax, SourcePreference::Api) => Some(("api_key", &["MINIMAX_API_KEY"])),
+        (ProviderId::Mistral, SourcePreference::Web) => {
+            Some(("cookie_header", &["MISTRAL_COOKIE", "LECHAT_COOKIE"]))
+        }
+        (ProviderId::Mistral, SourcePreference::Api) => Some(("api_key", &["MISTRAL_API_KEY"])),
+        (ProviderId::OpenCode, SourcePreference::Web) => Some((
+            "cookie_header",
+            &["OPENCODE_COOKIE", "OPENCODE_COOKIE_HEADER"],
+        )),
+        (ProviderId::Perplexity, SourcePreference::Web) => {
+            Some(("cookie_header", &["PERPLEXITY_COOKIE"]))
+        }
+        (ProviderId::Perplexity, SourcePreference::Api) => {
+            Some(("api_key", &["PERPLEXITY_API_KEY", "PPLX_API_KEY"]))
+        }
+        (ProviderId::Copilot, SourcePreference::Api) => {
+            Some(("api_key", &["COPILOT_API_TOKEN", "GITHUB_TOKEN"]))
+        }
+        (ProviderId::Kimi, SourcePreference::Api) => Some(("api_key", &["KIMI_AUTH_TOKEN"])),
+        (ProviderId::KimiK2, SourcePreference::Api) => {
+            Some(("api_key", &["KIMI_K2_API_KEY", "KIMI_API_KEY", "KIMI_KEY"]))
+        }
+        (ProviderId::Warp, SourcePreference::Api) => {
+            Some(("api_key", &["WARP_API_KEY", "WARP_TOKEN"]))
+        }
+        (ProviderId::Zai, SourcePreference::Api) => Some(("api_key", &["Z_AI_API_KEY"])),
+        _ => None,
+    }
+}
+
+fn check_unknown_keys(
+    path: &str,
+    obj: &Map<String, Value>,
+    known: &[&str],
+    diagnostics: &mut Vec<ConfigDiagnostic>,
+) {
+    for key in obj.keys() {
+        if known.contains(&key.as_str()) {
+            continue;
+        }
+        let normalized = normalize_key(key);
+        let fix = known
+            .iter()
+            .find(|candidate| normalize_key(candidate) == normalized)
+            .map(|candidate| format!("rename to \"{}\"", candidate))
+            .or_else(|| Some(format!("remove the key (known keys: {})", known.join(", "))));
+        let full_path = if path.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", path, key)
+        };
+        diagnostics.push(ConfigDiagnostic::warning(
+            full_path,
+            format!("unknown key \"{}\"", key),
+            fix,
+        ));
+    }
+}
+
+fn normalize_key(key: &str) -> String {
+    key.chars()
+        .filter(|c| *c != '_' && *c != '-')
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn provider_id_name(id: ProviderId) -> String {
+    serde_json::to_value(id)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_else(|| id.to_string())
+}
+
+fn provider_id_list() -> String {
+    ProviderId::ordered()
+        .into_iter()
+        .map(provider_id_name)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn unknown_provider_id(path: &str, raw: &Value, entry: &Value) -> ConfigDiagnostic {
+    let fix = raw
+        .as_str()
+        .and_then(suggest_provider_id)
+        .map(|name| format!("did you mean \"{}\"?", name))
+        .unwrap_or_else(|| {
+            if defines_provider(entry) {
+                "give plugin and custom providers a lowercase id of letters and digits".to_string()
+            } else {
+                format!("use one of: {}", provider_id_list())
+            }
+        });
+    ConfigDiagnostic::error(
+        format!("{}.id", path),
+        format!("unknown provider id {}", raw),
+        Some(fix),
+    )
+}
+
+fn suggest_provider_id(raw: &str) -> Option<String> {
+    ProviderId::from_name(raw).map(provider_id_name)
+}
+
+fn join_sources(sources: &[SourcePreference]) -> String {
+    sources
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn diagnostics_for(json: &str) -> Vec<ConfigDiagnostic> {
+        validate_config_json(json, &ProviderRegistry::new())
+    }
+
+    #[test]
+    fn clean_config_has_no_diagnostics() {
+        let diagnostics = diagnostics_for(
+            r#"{"version":1,"providers":[{"id":"codex","enabled":true,"source":"oauth"}]}"#,
+        );
+        assert!(diagnostics.is_empty(), "{:?}", diagnostics);
+    }
+
+    #[test]
+    fn unknown_keys_suggest_rename() {
+        let diagnostics =
+            diagnostics_for(r#"{"version":1,"providers":[{"id":"cursor","cookieHeader":"a=b"}]}"#);
+        let diag = diagnostics
+            .iter()
+            .find(|d| d.path == "providers[0].cookieHeader")
+            .expect("unknown key diagnostic");
+        assert_eq!(diag.severity, DiagnosticSeverity::Warning);
+        assert_eq!(diag.fix.as_deref(), Some("rename to \"cookie_header\""));
+    }
+
+    #[test]
+    fn unsupported_source_is_an_error() {
+        let diagnostics = diagnostics_for(r#"{"providers":[{"id":"codex","source":"web"}]}"#);
+        assert!(diagnostics.iter().any(|d| d.path == "providers[0].source"
+            && d.severity == DiagnosticSeverity::Error));
+    }
+
+    #[test]
+    fn deprecated_and_duplicate_entries_warn() {
+        let diagnostics = diagnostics_for(
+            r#"{"providers":[{"id":"claude","cookie_source":"chrome"},{"id":"claude"}]}"#,
+        );
+        assert!(
+            diagnostics
+                .iter()
+                .any(|d| d.path == "providers[0].cookie_source")
+        );
+        assert!(diagnostics.iter().any(|d| d.path == "providers[1].id"));
+        assert!(
+            diagnostics
+                .iter()
+                .all(|d| d.severity == DiagnosticSeverity::Warning)
+        );
+    }
+
+    #[test]
+    fn cookie_accounts_count_as_credentials() {
+        let diagnostics = diagnostics_for(
+            r#"{"version":1,"providers":[{"id":"cursor","source":"web","cookie_accounts":[{"label":"work","cookie_header":"a=b"},{"label":"home","cookie":"c=d"}]}]}"#,
+        );
+        assert!(diagnostics.iter().all(|d| d.path != "providers[0].source"));
+        assert!(
+            diagnostics
+                .iter()
+                .any(|d| d.path == "providers[0].cookie_accounts[1].cookie")
+        );
+    }
+
+    #[test]
+    fn unknown_provider_suggests_known_id() {
+        let diagnostics = diagnostics_for(r#"{"version":1,"providers":[{"id":"Codex"}]}"#);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(
+            diagnostics[0].fix.as_deref(),
+            Some("did you mean \"codex\"?")
+        );
+    }
+
+    #[test]
+    fn groups_accept_ids_and_aliases() {
+        let diagnostics = diagnostics_for(
+            r#"{"version":1,"providers":[{"id":"codex","aliases":["work-codex"]}],
+                "groups":{"work":["work-codex","copilot"],"codex":["claude"],"bad":["nope"]}}"#,
+        );
+        let paths: Vec<_> = diagnostics.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(paths, ["groups.bad[0]", "groups.codex"]);
+        assert_eq!(diagnostics[0].severity, DiagnosticSeverity::Error);
+        assert_eq!(diagnostics[1].severity, DiagnosticSeverity::Warning);
+    }
+
+    #[test]
+    fn custom_mappings_need_pointers_and_usage() {
+        let custom = serde_json::json!({
+            "url": "https://gateway.example/usage",
+            "primary": { "used": "/quota/used", "limit": "/quota/limit" },
+            "secondary": { "used": "/month/used" },
+            "cost": { "used": "spend", "currency": "EUR" },
+        });
+        let mut diagnostics = Vec::new();
+        validate_custom("providers[0].custom", &custom, &mut diagnostics);
+        let paths: Vec<_> = diagnostics.iter().map(|d| d.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "providers[0].custom.secondary",
+                "providers[0].custom.cost.used"
+            ]
+        );
+    }
+}
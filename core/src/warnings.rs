@@ -0,0 +1,46 @@
+use std::cell::RefCell;
+use std::future::Future;
+
+tokio::task_local! {
+    static WARNINGS: RefCell<Vec<String>>;
+}
+
+// Records a non-fatal issue against the provider fetch currently running. Outside of
+// `collect_warnings` this is a no-op.
+pub fn push_warning(message: impl Into<String>) {
+    let message = message.into();
+    let _ = WARNINGS.try_with(|warnings| {
+        let mut warnings = warnings.borrow_mut();
+        if !warnings.contains(&message) {
+            warnings.push(message);
+        }
+    });
+}
+
+pub async fn collect_warnings<F: Future>(fut: F) -> (F::Output, Vec<String>) {
+    WARNINGS
+        .scope(RefCell::new(Vec::new()), async {
+            let output = fut.await;
+            let warnings = WARNINGS.with(|warnings| warnings.take());
+            (output, warnings)
+        })
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn collects_warnings_within_scope_only() {
+        push_warning("dropped");
+        let (value, warnings) = collect_warnings(async {
+            push_warning("token refreshed");
+            push_warning("token refreshed");
+            7
+        })
+        .await;
+        assert_eq!(value, 7);
+        assert_eq!(warnings, vec!["token refreshed".to_string()]);
+    }
+}
@@ -0,0 +1,284 @@
+use fuelcheck_core::errors::CliError;
+use std::fmt;
+use std::sync::OnceLock;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    #[default]
+    En,
+    Zh,
+}
+
+impl Locale {
+    // Accepts plain tags ("zh", "en") as well as POSIX locales ("zh_CN.UTF-8").
+    pub fn parse(raw: &str) -> Option<Self> {
+        let raw = raw.trim().to_ascii_lowercase();
+        let lang = raw.split(['_', '-', '.', '@']).next().unwrap_or_default();
+        match lang {
+            "en" | "c" | "posix" => Some(Locale::En),
+            "zh" => Some(Locale::Zh),
+            _ => None,
+        }
+    }
+
+    // FUELCHECK_LANG wins, then the first non-empty POSIX locale variable.
+    pub fn from_env() -> Self {
+        ["FUELCHECK_LANG", "LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find(|value| !value.trim().is_empty())
+            .and_then(|value| Locale::parse(&value))
+            .unwrap_or_default()
+    }
+}
+
+static LOCALE: OnceLock<Locale> = OnceLock::new();
+
+pub fn set_locale(locale: Locale) {
+    let _ = LOCALE.set(locale);
+}
+
+pub fn locale() -> Locale {
+    *LOCALE.get_or_init(Locale::from_env)
+}
+
+// Messages are keyed by their English text; untranslated keys fall back to it.
+pub fn t(text: &'static str) -> &'static str {
+    let catalog = match locale() {
+        Locale::En => return text,
+        Locale::Zh => ZH,
+    };
+    catalog
+        .iter()
+        .find(|(key, _)| *key == text)
+        .map(|(_, translated)| *translated)
+        .unwrap_or(text)
+}
+
+// Like `t`, then fills `{name}` placeholders.
+pub fn tf(text: &'static str, args: &[(&str, &dyn fmt::Display)]) -> String {
+    args.iter()
+        .fold(t(text).to_string(), |message, (name, value)| {
+            message.replace(&format!("{{{}}}", name), &value.to_string())
+        })
+}
+
+// Text-mode error line for the CLI; JSON output keeps the English message.
+pub fn error_message(err: &anyhow::Error) -> String {
+    if locale() == Locale::En {
+        return err.to_string();
+    }
+    match err.downcast_ref::<CliError>() {
+        Some(CliError::UnknownProvider(name)) => tf("unknown provider: {name}", &[("name", name)]),
+        Some(CliError::ProviderNotConfigured(id)) => {
+            tf("provider {id} not configured", &[("id", id)])
+        }
+        Some(CliError::UnsupportedSource(id, source)) => tf(
+            "provider {id} does not support source {source}",
+            &[("id", id), ("source", source)],
+        ),
+        Some(CliError::IdentityNotSupported(id)) => tf(
+            "provider {id} does not support identity lookup",
+            &[("id", id)],
+        ),
+        Some(CliError::NetworkDisabled(target)) => tf(
+            "network access disabled by --no-network (needed {target})",
+            &[("target", target)],
+        ),
+        Some(CliError::QuotaLow {
+            min_remaining,
+            windows,
+        }) => tf(
+            "quota below {min}% remaining: {windows}",
+            &[("min", min_remaining), ("windows", windows)],
+        ),
+        Some(CliError::BudgetExceeded { spent, budget, .. }) => tf(
+            "spend {spent} exceeds budget {budget}",
+            &[("spent", spent), ("budget", budget)],
+        ),
+        Some(
+            CliError::PartialFailure { failed, total }
+            | CliError::ProvidersFailed { failed, total, .. },
+        ) => tf(
+            "{failed} of {total} provider(s) failed",
+            &[("failed", failed), ("total", total)],
+        ),
+        _ => err.to_string(),
+    }
+}
+
+const ZH: &[(&str, &str)] = &[
+    ("error", "错误"),
+    ("Error", "错误"),
+    ("Warning", "警告"),
+    ("Alert", "警报"),
+    (
+        "{percent}% used (alert at {threshold}%)",
+        "已用 {percent}%（警报阈值 {threshold}%）",
+    ),
+    ("Session", "会话"),
+    ("Weekly", "每周"),
+    ("Monthly", "每月"),
+    ("Tertiary", "其他"),
+    ("Features", "功能"),
+    ("Pro searches", "Pro 搜索"),
+    ("Loaded", "已加载"),
+    ("{percent}% GPU", "GPU {percent}%"),
+    ("Unloads {when}", "{when}卸载"),
+    ("Pace", "节奏"),
+    ("Credits", "额度"),
+    ("Account", "账户"),
+    ("Account ID", "账户 ID"),
+    ("Organization", "组织"),
+    ("Plan", "套餐"),
+    ("Incident", "事件"),
+    ("Cost", "费用"),
+    ("Status", "状态"),
+    ("No identity details available", "暂无身份信息"),
+    ("{percent}% left", "剩余 {percent}%"),
+    ("{value} left", "剩余 {value}"),
+    ("Resets {when}", "{when}重置"),
+    ("resets {when}", "{when}后重置"),
+    ("now", "现在"),
+    ("in {duration}", "{duration}后"),
+    ("{n}d", "{n}天"),
+    ("{n}h", "{n}小时"),
+    ("{n}m", "{n}分钟"),
+    ("Expected {percent}% used", "预计已用 {percent}%"),
+    ("On pace", "进度正常"),
+    ("{percent}% in deficit", "超支 {percent}%"),
+    ("{percent}% in reserve", "结余 {percent}%"),
+    ("Lasts until reset", "可用至重置"),
+    ("Runs out now", "即将用尽"),
+    ("Runs out in {duration}", "{duration}后用尽"),
+    ("Operational", "运行正常"),
+    ("Partial outage", "部分中断"),
+    ("Major outage", "重大中断"),
+    ("Critical issue", "严重问题"),
+    ("Maintenance", "维护中"),
+    ("Status unknown", "状态未知"),
+    ("{impact} impact", "影响: {impact}"),
+    ("Fuelcheck Live", "Fuelcheck 实时"),
+    (" - usage + cost", " - 用量 + 费用"),
+    ("Providers: {providers}", "服务商: {providers}"),
+    ("auto", "自动"),
+    ("Refresh: {seconds}s", "刷新: {seconds}秒"),
+    ("Source: {source}", "来源: {source}"),
+    ("Last update: {time}", "上次更新: {time}"),
+    (
+        "Last update: waiting for first refresh",
+        "上次更新: 等待首次刷新",
+    ),
+    ("Tabs: ←/→ or Tab", "切换: ←/→ 或 Tab"),
+    ("Ctrl+C to exit", "Ctrl+C 退出"),
+    ("Refresh count: {count}", "刷新次数: {count}"),
+    ("Accounts", "账户"),
+    ("All", "全部"),
+    ("Usage", "用量"),
+    ("Waiting for data...", "等待数据..."),
+    ("No data for this account yet.", "该账户暂无数据。"),
+    ("c: cost view", "c: 费用视图"),
+    ("c: usage view", "c: 用量视图"),
+    (
+        "1-9: toggle providers, p: show all",
+        "1-9: 切换服务商, p: 全部显示",
+    ),
+    ("s: save snapshot", "s: 保存快照"),
+    ("o: sort by remaining", "o: 按剩余排序"),
+    ("o: fetch order", "o: 按获取顺序"),
+    (
+        "{provider} {window}: {remaining}% left",
+        "{provider} {window}: 剩余 {remaining}%",
+    ),
+    (
+        "↑/↓ PgUp/PgDn: lines {first}-{last} of {total}",
+        "↑/↓ PgUp/PgDn: 第 {first}-{last} 行，共 {total} 行",
+    ),
+    ("saved {path}", "已保存 {path}"),
+    ("snapshot failed: {error}", "快照保存失败: {error}"),
+    (
+        "All providers are hidden. Press p to show them.",
+        "所有服务商都已隐藏。按 p 重新显示。",
+    ),
+    ("Cost (last {days} days)", "费用（近 {days} 天）"),
+    ("Loading cost report...", "正在加载费用报告..."),
+    (" - cost today", " - 今日费用"),
+    ("Today: ${cost}", "今日: ${cost}"),
+    ("+${cost} since start", "启动以来 +${cost}"),
+    (
+        "No cost report for the selected providers.",
+        "所选服务商没有费用报告。",
+    ),
+    ("primary", "主要"),
+    ("secondary", "次要"),
+    ("tertiary", "其他"),
+    ("window {minutes}m", "窗口 {minutes}分钟"),
+    ("cost: n/a", "费用: 无"),
+    ("cost", "费用"),
+    ("credits: {value}", "额度: {value}"),
+    ("updated: {time}", "更新: {time}"),
+    ("resets {time}", "{time} 重置"),
+    ("no usage data", "无用量数据"),
+    ("fetching {provider}…", "正在获取 {provider}…"),
+    ("ok", "成功"),
+    ("failed", "失败"),
+    ("unknown provider: {name}", "未知服务商: {name}"),
+    ("provider {id} not configured", "服务商 {id} 未配置"),
+    (
+        "provider {id} does not support source {source}",
+        "服务商 {id} 不支持来源 {source}",
+    ),
+    (
+        "provider {id} does not support identity lookup",
+        "服务商 {id} 不支持身份查询",
+    ),
+    (
+        "network access disabled by --no-network (needed {target})",
+        "已通过 --no-network 禁用网络访问（需要 {target}）",
+    ),
+    (
+        "quota below {min}% remaining: {windows}",
+        "剩余额度低于 {min}%: {windows}",
+    ),
+    (
+        "spend {spent} exceeds budget {budget}",
+        "本期支出 {spent} 超出预算 {budget}",
+    ),
+    (
+        "{failed} of {total} provider(s) failed",
+        "{total} 个服务商中有 {failed} 个失败",
+    ),
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    fn placeholders(text: &str) -> Vec<&str> {
+        let mut names: Vec<&str> = text
+            .split('{')
+            .skip(1)
+            .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+            .collect();
+        names.sort_unstable();
+        names
+    }
+
+    #[test]
+    fn catalog_keys_are_unique_and_keep_placeholders() {
+        let mut seen = HashSet::new();
+        for (key, translated) in ZH {
+            assert!(seen.insert(*key), "duplicate key {:?}", key);
+            assert_eq!(placeholders(key), placeholders(translated), "{:?}", key);
+        }
+    }
+
+    #[test]
+    fn parses_tags_and_posix_locales() {
+        assert_eq!(Locale::parse("zh_CN.UTF-8"), Some(Locale::Zh));
+        assert_eq!(Locale::parse("zh-Hant"), Some(Locale::Zh));
+        assert_eq!(Locale::parse("C"), Some(Locale::En));
+        assert_eq!(Locale::parse("fr_FR"), None);
+    }
+}
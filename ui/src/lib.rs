@@ -1,3 +1,5 @@
+pub mod i18n;
+pub mod metrics;
 pub mod reports;
 pub mod text;
 pub mod tui;
@@ -0,0 +1,184 @@
+use chrono::{DateTime, Utc};
+use fuelcheck_core::model::ProviderPayload;
+use std::fmt::Write;
+
+// Prometheus text exposition of the same data `usage --json` prints. Series
+// carry `provider` and `account` labels; `account` is empty for the default
+// login so every series of a metric has the same label set.
+pub fn render_metrics(outputs: &[ProviderPayload], refreshed_at: DateTime<Utc>) -> String {
+    let mut up = Vec::new();
+    let mut used = Vec::new();
+    let mut resets = Vec::new();
+    let mut credits = Vec::new();
+    let mut cost = Vec::new();
+    let mut cost_limit = Vec::new();
+
+    for output in outputs {
+        let labels = format!(
+            "provider=\"{}\",account=\"{}\"",
+            escape_label(&output.provider),
+            escape_label(output.account.as_deref().unwrap_or_default())
+        );
+        up.push((
+            labels.clone(),
+            if output.error.is_none() { 1.0 } else { 0.0 },
+        ));
+        if let Some(usage) = &output.usage {
+            let windows = [
+                ("primary", usage.primary.as_ref()),
+                ("secondary", usage.secondary.as_ref()),
+                ("tertiary", usage.tertiary.as_ref()),
+            ];
+            for (name, window) in windows {
+                let Some(window) = window else {
+                    continue;
+                };
+                let labels = format!("{},window=\"{}\"", labels, name);
+                used.push((labels.clone(), window.used_percent));
+                if let Some(resets_at) = window.resets_at {
+                    resets.push((labels, resets_at.timestamp() as f64));
+                }
+            }
+            // Only USD costs are exported, so the series can be summed.
+            if let Some(snapshot) = &usage.provider_cost
+                && snapshot.currency_code.eq_ignore_ascii_case("USD")
+            {
+                cost.push((labels.clone(), snapshot.used));
+                cost_limit.push((labels.clone(), snapshot.limit));
+            }
+        }
+        let remaining = output
+            .credits
+            .as_ref()
+            .map(|snapshot| snapshot.remaining)
+            .or_else(|| {
+                output
+                    .openai_dashboard
+                    .as_ref()
+                    .and_then(|dashboard| dashboard.credits_remaining)
+            });
+        if let Some(remaining) = remaining {
+            credits.push((labels, remaining));
+        }
+    }
+
+    let mut text = String::new();
+    let families = [
+        ("fuelcheck_up", "1 if the last fetch succeeded.", up),
+        (
+            "fuelcheck_used_percent",
+            "Percent of the rate-limit window used.",
+            used,
+        ),
+        (
+            "fuelcheck_window_resets_at_seconds",
+            "Unix time the rate-limit window resets.",
+            resets,
+        ),
+        (
+            "fuelcheck_credits_remaining",
+            "Credits left on the account.",
+            credits,
+        ),
+        (
+            "fuelcheck_cost_usd",
+            "Spend reported by the provider for the current period.",
+            cost,
+        ),
+        (
+            "fuelcheck_cost_limit_usd",
+            "Spend limit reported by the provider for the current period.",
+            cost_limit,
+        ),
+    ];
+    for (name, help, samples) in families {
+        if samples.is_empty() {
+            continue;
+        }
+        let _ = writeln!(text, "# HELP {} {}", name, help);
+        let _ = writeln!(text, "# TYPE {} gauge", name);
+        for (labels, value) in samples {
+            let _ = writeln!(text, "{}{{{}}} {}", name, labels, value);
+        }
+    }
+    let _ = writeln!(
+        text,
+        "# HELP fuelcheck_last_refresh_timestamp_seconds Unix time of the last refresh."
+    );
+    let _ = writeln!(
+        text,
+        "# TYPE fuelcheck_last_refresh_timestamp_seconds gauge"
+    );
+    let _ = writeln!(
+        text,
+        "fuelcheck_last_refresh_timestamp_seconds {}",
+        refreshed_at.timestamp()
+    );
+    text
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuelcheck_core::model::{ProviderErrorPayload, RateWindow, UsageSnapshot};
+
+    #[test]
+    fn renders_usage_as_gauges() {
+        let usage = UsageSnapshot {
+            primary: Some(RateWindow {
+                used_percent: 42.5,
+                window_minutes: Some(300),
+                resets_at: None,
+                reset_description: None,
+            }),
+            secondary: None,
+            tertiary: None,
+            provider_cost: None,
+            updated_at: Utc::now(),
+            identity: None,
+            account_email: None,
+            account_organization: None,
+            login_method: None,
+        };
+        let mut codex = ProviderPayload::error(
+            "codex".to_string(),
+            "oauth".to_string(),
+            ProviderErrorPayload {
+                code: 1,
+                message: String::new(),
+                kind: None,
+            },
+        );
+        codex.error = None;
+        codex.account = Some("Work \"main\"".to_string());
+        codex.usage = Some(usage);
+        let claude = ProviderPayload::error(
+            "claude".to_string(),
+            "oauth".to_string(),
+            ProviderErrorPayload {
+                code: 6,
+                message: "expired".to_string(),
+                kind: None,
+            },
+        );
+
+        let text = render_metrics(
+            &[codex, claude],
+            DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+        );
+        assert!(text.contains("# TYPE fuelcheck_used_percent gauge\n"));
+        assert!(text.contains(
+            "fuelcheck_used_percent{provider=\"codex\",account=\"Work \\\"main\\\"\",window=\"primary\"} 42.5\n"
+        ));
+        assert!(text.contains("fuelcheck_up{provider=\"claude\",account=\"\"} 0\n"));
+        assert!(!text.contains("fuelcheck_credits_remaining"));
+        assert!(text.ends_with("fuelcheck_last_refresh_timestamp_seconds 1700000000\n"));
+    }
+}
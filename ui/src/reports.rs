@@ -2,16 +2,21 @@ use chrono::{DateTime, Utc};
 use chrono_tz::Tz;
 use crossterm::terminal;
 use fuelcheck_core::reports::annotate_models_with_fallback;
+use fuelcheck_core::reports::compare::{CompareFigures, CompareOutcome, ProviderCompareResult};
+use fuelcheck_core::reports::reconcile::ReconcileReport;
 use fuelcheck_core::reports::types::{
-    DailyReportResponse, MonthlyReportResponse, ProviderReport, SessionReportResponse,
-    split_usage_tokens,
+    DailyReportResponse, Efficiency, ModelReportResponse, MonthlyReportResponse, ProviderReport,
+    SessionReportResponse, SessionReportRow, split_usage_tokens,
 };
-use fuelcheck_core::reports::{CostReportCollection, ProviderReportOutcome};
+use fuelcheck_core::reports::{CostReportCollection, CostReportKind, ProviderReportOutcome};
+use fuelcheck_core::snapshots::SnapshotRecord;
 
 pub struct RenderOptions<'a> {
     pub force_compact: bool,
     pub timezone: Option<&'a str>,
     pub compact_override: Option<bool>,
+    // GitHub-flavored Markdown tables and headings instead of plain text.
+    pub markdown: bool,
 }
 
 pub fn render_collection_text(
@@ -19,22 +24,54 @@ pub fn render_collection_text(
     force_compact: bool,
     timezone: Option<&str>,
 ) -> String {
-    let render_options = RenderOptions {
-        force_compact,
-        timezone,
-        compact_override: None,
-    };
+    render_collection(
+        collection,
+        &RenderOptions {
+            force_compact,
+            timezone,
+            compact_override: None,
+            markdown: false,
+        },
+    )
+}
+
+// For pasting into issues and wikis. The terminal width is ignored, so the
+// full tables are used unless `force_compact` is set.
+pub fn render_collection_markdown(
+    collection: &CostReportCollection,
+    force_compact: bool,
+    timezone: Option<&str>,
+) -> String {
+    render_collection(
+        collection,
+        &RenderOptions {
+            force_compact,
+            timezone,
+            compact_override: Some(force_compact),
+            markdown: true,
+        },
+    )
+}
 
+fn render_collection(
+    collection: &CostReportCollection,
+    render_options: &RenderOptions<'_>,
+) -> String {
     let mut sections = Vec::new();
     for provider in &collection.providers {
         let section = match &provider.outcome {
             ProviderReportOutcome::Report(report) => {
-                render_provider_report(&provider.provider, report, &render_options)
+                render_provider_report(&provider.provider, report, render_options)
             }
             ProviderReportOutcome::Error(error) => {
                 format!(
-                    "== {} report ({}) ==\nerror: {}",
-                    provider.provider, collection.report, error.message
+                    "{}\nerror: {}",
+                    report_heading(
+                        &provider.provider,
+                        collection.report,
+                        render_options.markdown
+                    ),
+                    error.message
                 )
             }
         };
@@ -54,15 +91,29 @@ pub fn render_provider_report(
         .compact_override
         .unwrap_or_else(|| options.force_compact || is_compact_terminal());
 
-    let mut out = String::new();
-    out.push_str(&format!("== {} report ({}) ==\n", provider, report.kind()));
+    let markdown = options.markdown;
+    let mut out = report_heading(provider, report.kind(), markdown);
+    out.push('\n');
 
     let table = match report {
-        ProviderReport::Daily(data) => render_daily(data, compact),
-        ProviderReport::Monthly(data) => render_monthly(data, compact),
-        ProviderReport::Session(data) => render_sessions(data, compact, timezone),
+        ProviderReport::Daily(data) => render_daily(data, compact, markdown),
+        ProviderReport::Monthly(data) => render_monthly(data, compact, markdown),
+        ProviderReport::Session(data) => render_sessions(data, compact, timezone, markdown),
+        ProviderReport::Models(data) => render_models(data, compact, markdown),
     };
     out.push_str(&table);
+    if markdown {
+        if !report.warnings().is_empty() {
+            out.push('\n');
+        }
+        for warning in report.warnings() {
+            out.push_str(&format!("\n> Warning: {}", warning));
+        }
+        return out;
+    }
+    for warning in report.warnings() {
+        out.push_str(&format!("\nWarning: {}", warning));
+    }
 
     if compact {
         out.push_str("\n\nRunning in Compact Mode");
@@ -71,7 +122,192 @@ pub fn render_provider_report(
     out
 }
 
-fn render_daily(data: &DailyReportResponse, compact: bool) -> String {
+fn report_heading(provider: &str, kind: CostReportKind, markdown: bool) -> String {
+    if markdown {
+        format!("### {} report ({})\n", provider, kind)
+    } else {
+        format!("== {} report ({}) ==", provider, kind)
+    }
+}
+
+pub fn render_reconcile_text(report: &ReconcileReport) -> String {
+    let mut out = format!("== {} reconcile ({}) ==\n", report.provider, report.month);
+    let headers = [
+        "Date",
+        "Local (USD)",
+        "Credits Used",
+        "Expected (USD)",
+        "Diff",
+        "",
+    ];
+    let mut rows = Vec::new();
+    for row in &report.rows {
+        rows.push(vec![
+            row.date.clone(),
+            format_currency(row.local_cost_usd),
+            row.reported_credits
+                .map(|credits| format!("{:.2}", credits))
+                .unwrap_or_else(|| "-".to_string()),
+            row.expected_cost_usd
+                .map(format_currency)
+                .unwrap_or_else(|| "-".to_string()),
+            row.diff_percent
+                .map(|diff| format!("{:+.1}%", diff))
+                .unwrap_or_else(|| "-".to_string()),
+            if row.flagged { "!" } else { "" }.to_string(),
+        ]);
+    }
+    rows.push(vec![
+        "Total".to_string(),
+        format_currency(report.local_cost_usd),
+        format!("{:.2}", report.reported_credits),
+        String::new(),
+        String::new(),
+        String::new(),
+    ]);
+    out.push_str(&render_table(&headers, &rows));
+
+    out.push_str("\n\n");
+    match report.credit_usd {
+        Some(rate) => out.push_str(&format!(
+            "Credit rate: {:.4} USD ({}); tolerance ±{}%",
+            rate, report.rate_source, report.tolerance_percent
+        )),
+        None => out.push_str("No credit usage sampled this month; run hooks or guard to record it"),
+    }
+    if report.flagged_days > 0 {
+        out.push_str(&format!(
+            "\n{} day(s) differ by more than the tolerance (marked !)",
+            report.flagged_days
+        ));
+    }
+    out
+}
+
+pub fn render_comparisons_text(results: &[ProviderCompareResult]) -> String {
+    let mut sections = Vec::new();
+    for result in results {
+        let report = match &result.outcome {
+            CompareOutcome::Report(report) => report,
+            CompareOutcome::Error(error) => {
+                sections.push(format!(
+                    "== {} compare ==\nerror: {}",
+                    result.provider, error.message
+                ));
+                continue;
+            }
+        };
+        let mut out = format!(
+            "== {} compare ({} to {} vs {} to {}) ==\n",
+            result.provider,
+            report.current.since,
+            report.current.until,
+            report.baseline.since,
+            report.baseline.until
+        );
+        let headers = [
+            "Model",
+            "Baseline (USD)",
+            "Current (USD)",
+            "Cost Diff",
+            "Cost Diff %",
+            "Baseline Tokens",
+            "Current Tokens",
+            "Token Diff %",
+        ];
+        let row = |label: &str, figures: &CompareFigures| {
+            let percent = |value: Option<f64>| {
+                value
+                    .map(|value| format!("{:+.1}%", value))
+                    .unwrap_or_else(|| "-".to_string())
+            };
+            vec![
+                label.to_string(),
+                format_currency(figures.baseline_cost_usd),
+                format_currency(figures.current_cost_usd),
+                format!("{:+.4}", figures.cost_delta_usd),
+                percent(figures.cost_delta_percent),
+                format_number(figures.baseline_tokens),
+                format_number(figures.current_tokens),
+                percent(figures.tokens_delta_percent),
+            ]
+        };
+        let mut rows: Vec<Vec<String>> = report
+            .models
+            .iter()
+            .map(|model| row(&model.model, &model.figures))
+            .collect();
+        rows.push(row("Total", &report.totals));
+        out.push_str(&render_table(&headers, &rows));
+        sections.push(out);
+    }
+    sections.join("\n\n")
+}
+
+// One row per stored snapshot, oldest first, with times in the local zone.
+pub fn render_history_text(records: &[SnapshotRecord]) -> String {
+    if records.is_empty() {
+        return "No snapshots recorded for this range".to_string();
+    }
+    let headers = [
+        "Time",
+        "Provider",
+        "Kind",
+        "Primary",
+        "Secondary",
+        "Cost",
+        "Credits",
+    ];
+    let used = |window: Option<&fuelcheck_core::model::RateWindow>| {
+        window
+            .map(|window| format!("{:.0}%", window.used_percent))
+            .unwrap_or_else(|| "-".to_string())
+    };
+    let mut rows = Vec::new();
+    for record in records {
+        let payload = &record.payload;
+        let usage = payload.usage.as_ref();
+        rows.push(vec![
+            record
+                .recorded_at
+                .with_timezone(&chrono::Local)
+                .format("%Y-%m-%d %H:%M")
+                .to_string(),
+            match &payload.account {
+                Some(account) => format!("{} ({})", payload.provider, account),
+                None => payload.provider.clone(),
+            },
+            record.kind.clone(),
+            used(usage.and_then(|usage| usage.primary.as_ref())),
+            used(usage.and_then(|usage| usage.secondary.as_ref())),
+            usage
+                .and_then(|usage| usage.provider_cost.as_ref())
+                .map(|cost| format!("{:.2}/{:.2} {}", cost.used, cost.limit, cost.currency_code))
+                .unwrap_or_else(|| "-".to_string()),
+            payload
+                .credits
+                .as_ref()
+                .map(|credits| format!("{:.2}", credits.remaining))
+                .unwrap_or_else(|| "-".to_string()),
+        ]);
+    }
+    render_table(&headers, &rows)
+}
+
+fn render_daily(data: &DailyReportResponse, compact: bool, markdown: bool) -> String {
+    let agents: Vec<_> = data.daily.iter().map(|row| row.agent.as_deref()).collect();
+    let requests: Vec<_> = data
+        .daily
+        .iter()
+        .map(|row| row.requests)
+        .chain([data.totals.requests])
+        .collect();
+    let efficiency: Vec<_> = data
+        .daily
+        .iter()
+        .map(|row| row.efficiency.as_ref())
+        .chain([data.totals.efficiency.as_ref()])
+        .collect();
     if compact {
         let headers = ["Date", "Models", "Input", "Output", "Cost (USD)"];
         let mut rows = Vec::new();
@@ -104,7 +340,7 @@ fn render_daily(data: &DailyReportResponse, compact: bool) -> String {
             format_number(totals.output_tokens),
             format_currency(data.totals.cost_usd),
         ]);
-        return render_table(&headers, &rows);
+        return render_report_table(&headers, rows, &agents, &requests, &efficiency, markdown);
     }
 
     let headers = [
@@ -155,10 +391,27 @@ fn render_daily(data: &DailyReportResponse, compact: bool) -> String {
         format_currency(data.totals.cost_usd),
     ]);
 
-    render_table(&headers, &rows)
+    render_report_table(&headers, rows, &agents, &requests, &efficiency, markdown)
 }
 
-fn render_monthly(data: &MonthlyReportResponse, compact: bool) -> String {
+fn render_monthly(data: &MonthlyReportResponse, compact: bool, markdown: bool) -> String {
+    let agents: Vec<_> = data
+        .monthly
+        .iter()
+        .map(|row| row.agent.as_deref())
+        .collect();
+    let requests: Vec<_> = data
+        .monthly
+        .iter()
+        .map(|row| row.requests)
+        .chain([data.totals.requests])
+        .collect();
+    let efficiency: Vec<_> = data
+        .monthly
+        .iter()
+        .map(|row| row.efficiency.as_ref())
+        .chain([data.totals.efficiency.as_ref()])
+        .collect();
     if compact {
         let headers = ["Month", "Models", "Input", "Output", "Cost (USD)"];
         let mut rows = Vec::new();
@@ -191,7 +444,7 @@ fn render_monthly(data: &MonthlyReportResponse, compact: bool) -> String {
             format_number(totals.output_tokens),
             format_currency(data.totals.cost_usd),
         ]);
-        return render_table(&headers, &rows);
+        return render_report_table(&headers, rows, &agents, &requests, &efficiency, markdown);
     }
 
     let headers = [
@@ -242,10 +495,120 @@ fn render_monthly(data: &MonthlyReportResponse, compact: bool) -> String {
         format_currency(data.totals.cost_usd),
     ]);
 
-    render_table(&headers, &rows)
+    render_report_table(&headers, rows, &agents, &requests, &efficiency, markdown)
 }
 
-fn render_sessions(data: &SessionReportResponse, compact: bool, timezone: Tz) -> String {
+fn render_models(data: &ModelReportResponse, compact: bool, markdown: bool) -> String {
+    let agents: Vec<_> = data.models.iter().map(|row| row.agent.as_deref()).collect();
+    let requests: Vec<_> = data
+        .models
+        .iter()
+        .map(|row| row.requests)
+        .chain([data.totals.requests])
+        .collect();
+    let efficiency: Vec<_> = data
+        .models
+        .iter()
+        .map(|row| row.efficiency.as_ref())
+        .chain([data.totals.efficiency.as_ref()])
+        .collect();
+    let model_label = |model: &str, is_fallback: Option<bool>| {
+        if is_fallback == Some(true) {
+            format!("{} (fallback)", model)
+        } else {
+            model.to_string()
+        }
+    };
+    let totals = split_usage_tokens(
+        data.totals.input_tokens,
+        data.totals.cached_input_tokens,
+        data.totals.output_tokens,
+        data.totals.reasoning_output_tokens,
+    );
+
+    if compact {
+        let headers = ["Model", "Input", "Output", "Cost (USD)"];
+        let mut rows = Vec::new();
+        for row in &data.models {
+            let split = split_usage_tokens(
+                row.input_tokens,
+                row.cached_input_tokens,
+                row.output_tokens,
+                row.reasoning_output_tokens,
+            );
+            rows.push(vec![
+                model_label(&row.model, row.is_fallback),
+                format_number(split.input_tokens),
+                format_number(split.output_tokens),
+                format_currency(row.cost_usd),
+            ]);
+        }
+        rows.push(vec![
+            "Total".to_string(),
+            format_number(totals.input_tokens),
+            format_number(totals.output_tokens),
+            format_currency(data.totals.cost_usd),
+        ]);
+        return render_report_table(&headers, rows, &agents, &requests, &efficiency, markdown);
+    }
+
+    let headers = [
+        "Model",
+        "Input",
+        "Output",
+        "Reasoning",
+        "Cache Read",
+        "Total Tokens",
+        "Cost (USD)",
+    ];
+    let mut rows = Vec::new();
+    for row in &data.models {
+        let split = split_usage_tokens(
+            row.input_tokens,
+            row.cached_input_tokens,
+            row.output_tokens,
+            row.reasoning_output_tokens,
+        );
+        rows.push(vec![
+            model_label(&row.model, row.is_fallback),
+            format_number(split.input_tokens),
+            format_number(split.output_tokens),
+            format_number(split.reasoning_tokens),
+            format_number(split.cache_read_tokens),
+            format_number(row.total_tokens),
+            format_currency(row.cost_usd),
+        ]);
+    }
+    rows.push(vec![
+        "Total".to_string(),
+        format_number(totals.input_tokens),
+        format_number(totals.output_tokens),
+        format_number(totals.reasoning_tokens),
+        format_number(totals.cache_read_tokens),
+        format_number(data.totals.total_tokens),
+        format_currency(data.totals.cost_usd),
+    ]);
+
+    render_report_table(&headers, rows, &agents, &requests, &efficiency, markdown)
+}
+
+fn render_sessions(
+    data: &SessionReportResponse,
+    compact: bool,
+    timezone: Tz,
+    markdown: bool,
+) -> String {
+    let agents: Vec<_> = data
+        .sessions
+        .iter()
+        .map(|row| row.agent.as_deref())
+        .collect();
+    let efficiency: Vec<_> = data
+        .sessions
+        .iter()
+        .map(|row| row.efficiency.as_ref())
+        .chain([data.totals.efficiency.as_ref()])
+        .collect();
     if compact {
         let headers = [
             "Date",
@@ -271,7 +634,7 @@ fn render_sessions(data: &SessionReportResponse, compact: bool, timezone: Tz) ->
                 } else {
                     row.directory.clone()
                 },
-                shorten_session(&row.session_file),
+                session_label(row),
                 format_number(split.input_tokens),
                 format_number(split.output_tokens),
                 format_currency(row.cost_usd),
@@ -293,7 +656,7 @@ fn render_sessions(data: &SessionReportResponse, compact: bool, timezone: Tz) ->
             format_currency(data.totals.cost_usd),
         ]);
 
-        return render_table(&headers, &rows);
+        return render_report_table(&headers, rows, &agents, &[], &efficiency, markdown);
     }
 
     let headers = [
@@ -325,7 +688,7 @@ fn render_sessions(data: &SessionReportResponse, compact: bool, timezone: Tz) ->
             } else {
                 row.directory.clone()
             },
-            shorten_session(&row.session_file),
+            session_label(row),
             annotate_models_with_fallback(&row.models).join(", "),
             format_number(split.input_tokens),
             format_number(split.output_tokens),
@@ -357,7 +720,68 @@ fn render_sessions(data: &SessionReportResponse, compact: bool, timezone: Tz) ->
         String::new(),
     ]);
 
-    render_table(&headers, &rows)
+    render_report_table(&headers, rows, &agents, &[], &efficiency, markdown)
+}
+
+// Adds a Requests column before the cost for providers that count requests
+// and an Agent column after the first one for `--group-by agent` (one entry
+// per data row), and appends the efficiency columns when the report carries
+// them. Requests and efficiency have one entry per row, totals last.
+fn render_report_table(
+    headers: &[&str],
+    mut rows: Vec<Vec<String>>,
+    agents: &[Option<&str>],
+    requests: &[Option<u64>],
+    efficiency: &[Option<&Efficiency>],
+    markdown: bool,
+) -> String {
+    let render = if markdown {
+        render_markdown_table
+    } else {
+        render_table
+    };
+    let mut headers = headers.to_vec();
+    if requests.iter().any(Option::is_some)
+        && let Some(cost) = headers.iter().position(|header| *header == "Cost (USD)")
+    {
+        headers.insert(cost, "Requests");
+        for (row, requests) in rows.iter_mut().zip(requests) {
+            row.insert(cost, requests.map(format_number).unwrap_or_default());
+        }
+    }
+    if agents.iter().any(Option::is_some) {
+        headers.insert(1, "Agent");
+        for (idx, row) in rows.iter_mut().enumerate() {
+            let agent = agents.get(idx).copied().flatten().unwrap_or_default();
+            row.insert(1, agent.to_string());
+        }
+    }
+    if efficiency.iter().all(Option::is_none) {
+        return render(&headers, &rows);
+    }
+    headers.extend(["Cache Hit", "Out/In", "$/1K Out"]);
+    for (row, efficiency) in rows.iter_mut().zip(efficiency) {
+        let metrics = efficiency.copied().unwrap_or_default();
+        row.push(
+            metrics
+                .cache_hit_ratio
+                .map(|ratio| format!("{:.1}%", ratio * 100.0))
+                .unwrap_or_else(|| "-".to_string()),
+        );
+        row.push(
+            metrics
+                .output_input_ratio
+                .map(|ratio| format!("{:.3}", ratio))
+                .unwrap_or_else(|| "-".to_string()),
+        );
+        row.push(
+            metrics
+                .cost_per_1k_output_usd
+                .map(format_currency)
+                .unwrap_or_else(|| "-".to_string()),
+        );
+    }
+    render(&headers, &rows)
 }
 
 fn render_table(headers: &[&str], rows: &[Vec<String>]) -> String {
@@ -389,6 +813,44 @@ fn render_table(headers: &[&str], rows: &[Vec<String>]) -> String {
     output
 }
 
+// Number columns (every filled cell a figure such as `1,200`, `0.1234` or
+// `12.5%`) are right-aligned; pipes in cells are escaped.
+fn render_markdown_table(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let escape = |value: &str| value.replace('|', "\\|");
+    let numeric: Vec<bool> = (0..headers.len())
+        .map(|idx| {
+            let mut cells = rows
+                .iter()
+                .filter_map(|row| row.get(idx))
+                .filter(|value| !value.is_empty() && value.as_str() != "-")
+                .peekable();
+            cells.peek().is_some()
+                && cells.all(|value| {
+                    value
+                        .chars()
+                        .all(|ch| ch.is_ascii_digit() || ",.%".contains(ch))
+                })
+        })
+        .collect();
+    let line = |cells: Vec<String>| format!("| {} |", cells.join(" | "));
+
+    let mut lines = vec![line(headers.iter().map(|h| escape(h)).collect())];
+    lines.push(line(
+        numeric
+            .iter()
+            .map(|right| if *right { "---:" } else { "---" }.to_string())
+            .collect(),
+    ));
+    for row in rows {
+        lines.push(line(
+            (0..headers.len())
+                .map(|idx| escape(row.get(idx).map(String::as_str).unwrap_or_default()))
+                .collect(),
+        ));
+    }
+    lines.join("\n")
+}
+
 fn render_row(row: &[String], widths: &[usize]) -> String {
     row.iter()
         .enumerate()
@@ -460,6 +922,14 @@ fn parse_timestamp(value: &str) -> Option<DateTime<Utc>> {
         .map(|date| date.with_timezone(&Utc))
 }
 
+fn session_label(row: &SessionReportRow) -> String {
+    match (&row.name, row.sessions) {
+        (Some(name), _) => name.clone(),
+        (None, Some(sessions)) => format!("{} session(s)", sessions),
+        (None, None) => shorten_session(&row.session_file),
+    }
+}
+
 fn shorten_session(value: &str) -> String {
     if value.len() <= 8 {
         value.to_string()
@@ -492,15 +962,18 @@ mod tests {
             },
         );
 
-        let report = ProviderReport::Daily(DailyReportResponse {
+        let mut report = ProviderReport::Daily(DailyReportResponse {
             daily: vec![DailyReportRow {
                 date: "2025-09-11".to_string(),
+                agent: None,
                 input_tokens: 1200,
                 cached_input_tokens: 200,
                 output_tokens: 500,
                 reasoning_output_tokens: 10,
                 total_tokens: 1700,
+                requests: None,
                 cost_usd: 0.1234,
+                efficiency: None,
                 models,
             }],
             totals: ReportTotals {
@@ -509,8 +982,11 @@ mod tests {
                 output_tokens: 500,
                 reasoning_output_tokens: 10,
                 total_tokens: 1700,
+                requests: None,
                 cost_usd: 0.1234,
+                efficiency: None,
             },
+            warnings: Vec::new(),
         });
 
         let text = render_provider_report(
@@ -520,12 +996,51 @@ mod tests {
                 force_compact: false,
                 timezone: Some("UTC"),
                 compact_override: Some(false),
+                markdown: false,
             },
         );
 
         assert!(text.contains("Reasoning"));
         assert!(text.contains("Cache Read"));
         assert!(text.contains("Total Tokens"));
+        assert!(!text.contains("Cache Hit"));
+
+        let markdown = render_provider_report(
+            "codex",
+            &report,
+            &RenderOptions {
+                force_compact: false,
+                timezone: Some("UTC"),
+                compact_override: Some(false),
+                markdown: true,
+            },
+        );
+        let lines: Vec<_> = markdown.lines().collect();
+        assert_eq!(lines[0], "### codex report (daily)");
+        assert_eq!(
+            lines[2],
+            "| Date | Models | Input | Output | Reasoning | Cache Read | Total Tokens | Cost (USD) |"
+        );
+        assert_eq!(
+            lines[3],
+            "| --- | --- | ---: | ---: | ---: | ---: | ---: | ---: |"
+        );
+        assert_eq!(lines.len(), 6);
+
+        report.attach_efficiency();
+        let text = render_provider_report(
+            "codex",
+            &report,
+            &RenderOptions {
+                force_compact: false,
+                timezone: Some("UTC"),
+                compact_override: Some(true),
+                markdown: false,
+            },
+        );
+        assert!(text.contains("Cache Hit"));
+        assert!(text.contains("16.7%"));
+        assert!(text.contains("0.2468"));
     }
 
     #[test]
@@ -533,6 +1048,7 @@ mod tests {
         let report = ProviderReport::Daily(DailyReportResponse {
             daily: vec![],
             totals: ReportTotals::default(),
+            warnings: Vec::new(),
         });
 
         let text = render_provider_report(
@@ -542,6 +1058,7 @@ mod tests {
                 force_compact: false,
                 timezone: Some("UTC"),
                 compact_override: Some(true),
+                markdown: false,
             },
         );
 
@@ -572,12 +1089,17 @@ mod tests {
                 last_activity: "2025-09-11T18:25:40Z".to_string(),
                 session_file: "a-session".to_string(),
                 directory: "proj".to_string(),
+                project: None,
+                sessions: None,
+                name: None,
+                agent: None,
                 input_tokens: 100,
                 cached_input_tokens: 10,
                 output_tokens: 20,
                 reasoning_output_tokens: 3,
                 total_tokens: 120,
                 cost_usd: 0.001,
+                efficiency: None,
                 models,
             }],
             totals: ReportTotals {
@@ -586,8 +1108,11 @@ mod tests {
                 output_tokens: 20,
                 reasoning_output_tokens: 3,
                 total_tokens: 120,
+                requests: None,
                 cost_usd: 0.001,
+                efficiency: None,
             },
+            warnings: Vec::new(),
         });
 
         let text = render_provider_report(
@@ -597,6 +1122,7 @@ mod tests {
                 force_compact: false,
                 timezone: Some("UTC"),
                 compact_override: Some(false),
+                markdown: false,
             },
         );
 
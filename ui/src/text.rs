@@ -1,31 +1,37 @@
+use crate::i18n::{t, tf};
 use anyhow::Result;
+use chrono::{DateTime, Duration, Utc};
+use chrono_tz::Tz;
+use fuelcheck_core::clock;
+use fuelcheck_core::config::ProviderLabels;
 use fuelcheck_core::model::{
-    OutputFormat, ProviderCostSnapshot, ProviderPayload, ProviderStatusIndicator,
-    ProviderStatusPayload, RateWindow,
+    IdentityPayload, LoadedModel, OutputFormat, OutputSchema, ProviderCostSnapshot,
+    ProviderPayload, ProviderStatusIndicator, ProviderStatusPayload, RateWindow, StatusIncident,
 };
 
 #[derive(Debug, Clone, Copy)]
-pub struct RenderOptions {
+pub struct RenderOptions<'a> {
     pub format: OutputFormat,
     pub pretty: bool,
     pub json_only: bool,
     pub use_color: bool,
+    pub labels: &'a ProviderLabels,
+    // Adds the local wall-clock time to reset countdowns in text output.
+    pub timezone: Option<&'a str>,
+    // Payload version for JSON output.
+    pub schema: OutputSchema,
 }
 
 pub fn render_outputs(
     outputs: &[ProviderPayload],
-    options: &RenderOptions,
+    options: &RenderOptions<'_>,
 ) -> Result<Option<String>> {
     match options.format {
         OutputFormat::Json => {
-            let json = if options.pretty {
-                serde_json::to_string_pretty(outputs)?
-            } else {
-                serde_json::to_string(outputs)?
-            };
+            let json = options.schema.payloads_to_string(outputs, options.pretty)?;
             Ok(Some(json))
         }
-        OutputFormat::Text => {
+        OutputFormat::Text | OutputFormat::Markdown => {
             if options.json_only {
                 return Ok(None);
             }
@@ -36,19 +42,70 @@ pub fn render_outputs(
                 .join("\n");
             Ok(Some(text))
         }
+        OutputFormat::Raycast => {
+            if options.json_only {
+                return Ok(None);
+            }
+            let text = outputs
+                .iter()
+                .map(|output| format_payload_raycast(output, options.labels))
+                .collect::<Vec<_>>()
+                .join("\n");
+            Ok(Some(text))
+        }
+        OutputFormat::Alfred => {
+            let items = outputs
+                .iter()
+                .flat_map(|output| alfred_items(output, options.labels))
+                .collect();
+            alfred_document(items, options.pretty).map(Some)
+        }
+        OutputFormat::Share => {
+            if options.json_only {
+                return Ok(None);
+            }
+            let line = outputs
+                .iter()
+                .filter_map(|output| format_payload_share(output, options.labels))
+                .collect::<Vec<_>>()
+                .join(" · ");
+            Ok(Some(line))
+        }
+        OutputFormat::Statusbar => {
+            if options.json_only {
+                return Ok(None);
+            }
+            Ok(Some(statusbar_line(outputs, options.labels)))
+        }
+        OutputFormat::Waybar => waybar_document(outputs, options.labels).map(Some),
+        OutputFormat::Ndjson => {
+            let lines = outputs
+                .iter()
+                .map(|output| options.schema.payload_to_line(output))
+                .collect::<serde_json::Result<Vec<_>>>()?;
+            Ok(Some(lines.join("\n")))
+        }
     }
 }
 
-pub fn format_payload_text(payload: &ProviderPayload, options: &RenderOptions) -> String {
+pub fn format_payload_text(payload: &ProviderPayload, options: &RenderOptions<'_>) -> String {
     if let Some(error) = &payload.error {
-        return format!("{}: error: {}", payload.provider, error.message);
+        let mut lines = vec![format!(
+            "{}: {}: {}",
+            payload.provider,
+            t("error"),
+            error.message
+        )];
+        lines.extend(warning_lines(&payload.warnings, options.use_color));
+        return lines.join("\n");
     }
 
+    let timezone = options.timezone.and_then(|raw| raw.parse::<Tz>().ok());
     let mut lines = Vec::new();
     let header = format!(
         "== {} ==",
         format_header_title(
-            provider_display_name(&payload.provider),
+            options.labels.display_name(&payload.provider),
             payload.version.as_deref(),
             &payload.source
         )
@@ -57,46 +114,60 @@ pub fn format_payload_text(payload: &ProviderPayload, options: &RenderOptions) -
 
     if let Some(usage) = &payload.usage {
         if let Some(primary) = &usage.primary {
-            lines.push(rate_line("Session", primary, options.use_color));
-            if let Some(reset) = reset_line(primary) {
+            lines.push(rate_line(
+                primary_label(&payload.provider),
+                primary,
+                options.use_color,
+            ));
+            if let Some(reset) = reset_line(primary, timezone) {
                 lines.push(subtle_line(&reset, options.use_color));
             }
         }
         if let Some(secondary) = &usage.secondary {
-            lines.push(rate_line("Weekly", secondary, options.use_color));
+            lines.push(rate_line(
+                secondary_label(&payload.provider),
+                secondary,
+                options.use_color,
+            ));
             if let Some(pace) = pace_line(&payload.provider, secondary) {
-                lines.push(label_line("Pace", &pace, options.use_color));
+                lines.push(label_line(t("Pace"), &pace, options.use_color));
             }
-            if let Some(reset) = reset_line(secondary) {
+            if let Some(reset) = reset_line(secondary, timezone) {
                 lines.push(subtle_line(&reset, options.use_color));
             }
         }
         if let Some(tertiary) = &usage.tertiary {
             let label = tertiary_label(&payload.provider);
             lines.push(rate_line(label, tertiary, options.use_color));
-            if let Some(reset) = reset_line(tertiary) {
+            if let Some(reset) = reset_line(tertiary, timezone) {
                 lines.push(subtle_line(&reset, options.use_color));
             }
         }
         if let Some(cost) = &usage.provider_cost {
-            lines.push(cost_line(cost));
-        }
-        if payload.provider == "codex" {
-            if let Some(credits) = &payload.credits {
-                lines.push(label_line(
-                    "Credits",
-                    &format_credits(credits.remaining),
-                    options.use_color,
-                ));
-            } else if let Some(dashboard) = &payload.openai_dashboard
-                && let Some(credits) = dashboard.credits_remaining
-            {
-                lines.push(label_line(
-                    "Credits",
-                    &format_credits(credits),
-                    options.use_color,
-                ));
-            }
+            lines.push(cost_line(cost, timezone));
+        }
+        if let Some(credits) = &payload.credits {
+            lines.push(label_line(
+                t("Credits"),
+                &format_credits(credits.remaining),
+                options.use_color,
+            ));
+        } else if payload.provider == "codex"
+            && let Some(dashboard) = &payload.openai_dashboard
+            && let Some(credits) = dashboard.credits_remaining
+        {
+            lines.push(label_line(
+                t("Credits"),
+                &format_credits(credits),
+                options.use_color,
+            ));
+        }
+        for model in &payload.loaded_models {
+            lines.push(label_line(
+                t("Loaded"),
+                &loaded_model_line(model, timezone),
+                options.use_color,
+            ));
         }
         if let Some(account) = usage.account_email.clone().or_else(|| {
             usage
@@ -104,7 +175,7 @@ pub fn format_payload_text(payload: &ProviderPayload, options: &RenderOptions) -
                 .as_ref()
                 .and_then(|i| i.account_email.clone())
         }) {
-            lines.push(label_line("Account", &account, options.use_color));
+            lines.push(label_line(t("Account"), &account, options.use_color));
         }
         if let Some(plan) = usage
             .login_method
@@ -112,7 +183,7 @@ pub fn format_payload_text(payload: &ProviderPayload, options: &RenderOptions) -
             .or_else(|| usage.identity.as_ref().and_then(|i| i.login_method.clone()))
             && !plan.is_empty()
         {
-            lines.push(label_line("Plan", &plan, options.use_color));
+            lines.push(label_line(t("Plan"), &plan, options.use_color));
         }
     }
 
@@ -123,6 +194,342 @@ pub fn format_payload_text(payload: &ProviderPayload, options: &RenderOptions) -
             status.indicator.clone(),
             options.use_color,
         ));
+        for incident in &status.incidents {
+            lines.push(label_line(
+                t("Incident"),
+                &incident_line(incident),
+                options.use_color,
+            ));
+        }
+    }
+
+    lines.extend(alert_lines(payload, options.use_color));
+    lines.extend(warning_lines(&payload.warnings, options.use_color));
+
+    lines.join("\n")
+}
+
+// Raycast script commands show plain output lines (only the first one in
+// inline mode), so each provider gets a single line: a severity emoji, the
+// provider as title, then its windows as the subtitle.
+pub fn format_payload_raycast(payload: &ProviderPayload, labels: &ProviderLabels) -> String {
+    let title = labels.display_name(&payload.provider);
+    if let Some(error) = &payload.error {
+        return format!("⚠️ {}  {}: {}", title, t("error"), error.message);
+    }
+
+    let mut parts = Vec::new();
+    let mut lowest: Option<f64> = None;
+    if let Some(usage) = &payload.usage {
+        let windows = [
+            (primary_label(&payload.provider), usage.primary.as_ref()),
+            (secondary_label(&payload.provider), usage.secondary.as_ref()),
+            (tertiary_label(&payload.provider), usage.tertiary.as_ref()),
+        ];
+        for (label, window) in windows {
+            let Some(window) = window else {
+                continue;
+            };
+            let remaining = remaining_percent(window.used_percent);
+            lowest = Some(lowest.map_or(remaining, |value| value.min(remaining)));
+            let mut part = format!("{} {}", label, usage_line(remaining));
+            if let Some(reset) = reset_line(window, None) {
+                part.push_str(&format!(" ({})", reset));
+            }
+            parts.push(part);
+        }
+        if let Some(cost) = &usage.provider_cost {
+            parts.push(cost_line(cost, None));
+        }
+    }
+    if let Some(status) = &payload.status {
+        parts.push(status_line(status));
+    }
+
+    let icon = match (lowest, &payload.status) {
+        (Some(remaining), _) if remaining < 10.0 => "🔴",
+        (Some(remaining), _) if remaining < 25.0 => "🟡",
+        (Some(_), _) => "🟢",
+        (None, Some(status)) => match status.indicator {
+            ProviderStatusIndicator::None => "🟢",
+            ProviderStatusIndicator::Minor | ProviderStatusIndicator::Maintenance => "🟡",
+            ProviderStatusIndicator::Major | ProviderStatusIndicator::Critical => "🔴",
+            ProviderStatusIndicator::Unknown => "⚪",
+        },
+        (None, None) => "⚪",
+    };
+    format!("{} {}  {}", icon, title, parts.join(" · "))
+}
+
+// One chat-friendly segment per provider for pasting into team channels, e.g.
+// `Claude ▓▓░░░░░ 31% (resets 3d)`. Only the tightest window is shown.
+pub fn format_payload_share(payload: &ProviderPayload, labels: &ProviderLabels) -> Option<String> {
+    let name = labels.display_name(&payload.provider);
+    if payload.error.is_some() {
+        return Some(format!("{} {}", name, t("error")));
+    }
+    let window = tightest_window(payload)?;
+    let remaining = remaining_percent(window.used_percent);
+    let filled = ((remaining / 100.0) * 7.0).round() as usize;
+    let mut segment = format!(
+        "{} {}{} {:.0}%",
+        name,
+        "▓".repeat(filled),
+        "░".repeat(7 - filled),
+        remaining
+    );
+    if let Some(resets_at) = window.resets_at {
+        let seconds = resets_at.signed_duration_since(clock::now()).num_seconds();
+        if seconds > 0 {
+            segment.push_str(&format!(
+                " ({})",
+                tf("resets {when}", &[("when", &short_duration_text(seconds))])
+            ));
+        }
+    }
+    Some(segment)
+}
+
+pub(crate) fn tightest_window(payload: &ProviderPayload) -> Option<&RateWindow> {
+    let usage = payload.usage.as_ref()?;
+    [&usage.primary, &usage.secondary, &usage.tertiary]
+        .into_iter()
+        .flatten()
+        .max_by(|a, b| a.used_percent.total_cmp(&b.used_percent))
+}
+
+// For polybar, i3blocks or tmux: `Codex 68% · Claude 31%`, the remaining
+// percent of each provider's most-used window.
+pub fn statusbar_line(outputs: &[ProviderPayload], labels: &ProviderLabels) -> String {
+    outputs
+        .iter()
+        .filter_map(|output| {
+            let name = labels.display_name(&output.provider);
+            if output.error.is_some() {
+                return Some(format!("{} {}", name, t("error")));
+            }
+            let window = tightest_window(output)?;
+            Some(format!(
+                "{} {:.0}%",
+                name,
+                remaining_percent(window.used_percent)
+            ))
+        })
+        .collect::<Vec<_>>()
+        .join(" · ")
+}
+
+// A waybar custom module with `return-type: json` reads one object per line.
+// `class` follows the Raycast thresholds so the bar's CSS can color it, and
+// `percentage` (the lowest remaining) drives `format-icons`.
+pub fn waybar_document(outputs: &[ProviderPayload], labels: &ProviderLabels) -> Result<String> {
+    let lowest = outputs
+        .iter()
+        .filter(|output| output.error.is_none())
+        .filter_map(tightest_window)
+        .map(|window| remaining_percent(window.used_percent))
+        .min_by(f64::total_cmp);
+    let class = match lowest {
+        Some(remaining) if remaining < 10.0 => "critical",
+        Some(remaining) if remaining < 25.0 => "warning",
+        _ if outputs.iter().any(|output| output.error.is_some()) => "error",
+        _ => "ok",
+    };
+    let tooltip = outputs
+        .iter()
+        .map(|output| format_payload_raycast(output, labels))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut document = serde_json::json!({
+        "text": statusbar_line(outputs, labels),
+        "tooltip": tooltip,
+        "class": class,
+    });
+    if let Some(remaining) = lowest {
+        document["percentage"] = serde_json::json!(remaining.round() as u64);
+    }
+    Ok(serde_json::to_string(&document)?)
+}
+
+pub fn waybar_error(message: &str) -> Result<String> {
+    let document = serde_json::json!({
+        "text": format!("fuelcheck {}", t("error")),
+        "tooltip": message,
+        "class": "error",
+    });
+    Ok(serde_json::to_string(&document)?)
+}
+
+// Alfred reuses a script filter's last output for this long and reloads it in
+// the background, so typing in the workflow never waits on provider requests.
+const ALFRED_CACHE_SECONDS: u64 = 300;
+
+// Alfred script-filter items, one per provider window.
+pub fn alfred_items(payload: &ProviderPayload, labels: &ProviderLabels) -> Vec<serde_json::Value> {
+    let name = labels.display_name(&payload.provider);
+    if let Some(error) = &payload.error {
+        return vec![serde_json::json!({
+            "uid": format!("{}.error", payload.provider),
+            "title": format!("{}: {}", name, t("error")),
+            "subtitle": error.message,
+            "valid": false,
+        })];
+    }
+
+    let mut items = Vec::new();
+    let mut push = |key: &str, title: String, subtitle: String| {
+        items.push(serde_json::json!({
+            "uid": format!("{}.{}", payload.provider, key),
+            "title": title,
+            "subtitle": subtitle,
+            "arg": title,
+        }));
+    };
+    if let Some(usage) = &payload.usage {
+        let windows = [
+            (
+                "primary",
+                primary_label(&payload.provider),
+                usage.primary.as_ref(),
+            ),
+            (
+                "secondary",
+                secondary_label(&payload.provider),
+                usage.secondary.as_ref(),
+            ),
+            (
+                "tertiary",
+                tertiary_label(&payload.provider),
+                usage.tertiary.as_ref(),
+            ),
+        ];
+        for (key, label, window) in windows {
+            let Some(window) = window else {
+                continue;
+            };
+            let remaining = remaining_percent(window.used_percent);
+            push(
+                key,
+                format!("{} {}: {}", name, label, usage_line(remaining)),
+                reset_line(window, None).unwrap_or_default(),
+            );
+        }
+        if let Some(cost) = &usage.provider_cost {
+            push(
+                "cost",
+                format!("{} {}", name, cost_line(cost, None)),
+                String::new(),
+            );
+        }
+    }
+    if let Some(status) = &payload.status {
+        let description = status.description.clone().unwrap_or_default();
+        push(
+            "status",
+            format!("{} {}", name, status_line(status)),
+            description,
+        );
+    }
+    items
+}
+
+pub fn alfred_error(message: &str) -> Result<String> {
+    let item = serde_json::json!({
+        "title": format!("fuelcheck: {}", t("error")),
+        "subtitle": message,
+        "valid": false,
+    });
+    alfred_document(vec![item], false)
+}
+
+fn alfred_document(items: Vec<serde_json::Value>, pretty: bool) -> Result<String> {
+    let document = serde_json::json!({
+        "cache": { "seconds": ALFRED_CACHE_SECONDS, "loosereload": true },
+        "items": items,
+    });
+    Ok(if pretty {
+        serde_json::to_string_pretty(&document)?
+    } else {
+        serde_json::to_string(&document)?
+    })
+}
+
+pub fn render_identities(
+    outputs: &[IdentityPayload],
+    options: &RenderOptions<'_>,
+) -> Result<Option<String>> {
+    match options.format {
+        OutputFormat::Json => {
+            let json = if options.pretty {
+                serde_json::to_string_pretty(outputs)?
+            } else {
+                serde_json::to_string(outputs)?
+            };
+            Ok(Some(json))
+        }
+        OutputFormat::Ndjson => {
+            let lines = outputs
+                .iter()
+                .map(serde_json::to_string)
+                .collect::<serde_json::Result<Vec<_>>>()?;
+            Ok(Some(lines.join("\n")))
+        }
+        OutputFormat::Text
+        | OutputFormat::Raycast
+        | OutputFormat::Alfred
+        | OutputFormat::Share
+        | OutputFormat::Markdown
+        | OutputFormat::Statusbar
+        | OutputFormat::Waybar => {
+            if options.json_only {
+                return Ok(None);
+            }
+            let text = outputs
+                .iter()
+                .map(|output| format_identity_text(output, options))
+                .collect::<Vec<_>>()
+                .join("\n");
+            Ok(Some(text))
+        }
+    }
+}
+
+pub fn format_identity_text(payload: &IdentityPayload, options: &RenderOptions<'_>) -> String {
+    if let Some(error) = &payload.error {
+        return format!("{}: {}: {}", payload.provider, t("error"), error.message);
+    }
+
+    let mut lines = Vec::new();
+    let header = format!(
+        "== {} ==",
+        format_header_title(
+            options.labels.display_name(&payload.provider),
+            None,
+            &payload.source
+        )
+    );
+    lines.push(colorize_header(&header, options.use_color));
+
+    let identity = payload.identity.as_ref();
+    let fields = [
+        (t("Account"), identity.and_then(|i| i.account_email.clone())),
+        (
+            t("Organization"),
+            identity.and_then(|i| i.account_organization.clone()),
+        ),
+        (t("Plan"), identity.and_then(|i| i.login_method.clone())),
+        (t("Account ID"), payload.account_id.clone()),
+    ];
+    for (label, value) in fields {
+        if let Some(value) = value.filter(|v| !v.trim().is_empty()) {
+            lines.push(label_line(label, &value, options.use_color));
+        }
+    }
+    if lines.len() == 1 {
+        lines.push(subtle_line(
+            t("No identity details available"),
+            options.use_color,
+        ));
     }
 
     lines.join("\n")
@@ -135,45 +542,42 @@ fn format_header_title(provider: String, version: Option<&str>, source: &str) ->
     }
 }
 
-fn provider_display_name(raw: &str) -> String {
-    match raw {
-        "codex" => "Codex".to_string(),
-        "claude" => "Claude".to_string(),
-        "gemini" => "Gemini".to_string(),
-        "cursor" => "Cursor".to_string(),
-        "factory" => "Factory".to_string(),
-        other => {
-            let mut chars = other.chars();
-            match chars.next() {
-                Some(first) => first.to_uppercase().collect::<String>() + chars.as_str(),
-                None => other.to_string(),
-            }
-        }
+fn primary_label(provider: &str) -> &'static str {
+    match provider {
+        "perplexity" => t("Pro searches"),
+        _ => t("Session"),
+    }
+}
+
+fn secondary_label(provider: &str) -> &'static str {
+    match provider {
+        "mistral" => t("Monthly"),
+        _ => t("Weekly"),
     }
 }
 
 fn tertiary_label(provider: &str) -> &'static str {
     match provider {
         "claude" => "Sonnet",
-        _ => "Tertiary",
+        "mistral" => t("Features"),
+        _ => t("Tertiary"),
     }
 }
 
 fn rate_line(label: &str, window: &RateWindow, use_color: bool) -> String {
     let remaining = remaining_percent(window.used_percent);
-    let usage_text = usage_line(remaining, window.used_percent);
+    let usage_text = usage_line(remaining);
     let colored_usage = colorize_usage(&usage_text, remaining, use_color);
     let bar = usage_bar(remaining, use_color);
     format!("{}: {} {}", label, colored_usage, bar)
 }
 
-fn usage_line(remaining: f64, used: f64) -> String {
+fn usage_line(remaining: f64) -> String {
     let percent = remaining.clamp(0.0, 100.0);
-    if used.is_nan() {
-        format!("{:.0}% left", percent)
-    } else {
-        format!("{:.0}% left", percent)
-    }
+    tf(
+        "{percent}% left",
+        &[("percent", &format!("{:.0}", percent))],
+    )
 }
 
 fn remaining_percent(used_percent: f64) -> f64 {
@@ -189,9 +593,9 @@ fn usage_bar(remaining: f64, use_color: bool) -> String {
     if use_color { ansi("95", &bar) } else { bar }
 }
 
-fn reset_line(window: &RateWindow) -> Option<String> {
+fn reset_line(window: &RateWindow, timezone: Option<Tz>) -> Option<String> {
     if let Some(resets_at) = window.resets_at {
-        return Some(format!("Resets {}", reset_countdown_description(resets_at)));
+        return Some(resets_text(&reset_when(resets_at, timezone)));
     }
     if let Some(desc) = &window.reset_description {
         let trimmed = desc.trim();
@@ -201,35 +605,44 @@ fn reset_line(window: &RateWindow) -> Option<String> {
         if trimmed.to_lowercase().starts_with("resets") {
             return Some(trimmed.to_string());
         }
-        return Some(format!("Resets {}", trimmed));
+        return Some(resets_text(trimmed));
     }
     None
 }
 
-fn reset_countdown_description(resets_at: chrono::DateTime<chrono::Utc>) -> String {
-    let now = chrono::Utc::now();
+fn reset_countdown_description(resets_at: DateTime<Utc>) -> String {
+    let now = clock::now();
     let delta = resets_at.signed_duration_since(now);
     if delta.num_seconds() < 1 {
-        return "now".to_string();
+        return t("now").to_string();
     }
-    let total_minutes = (delta.num_seconds() as f64 / 60.0).ceil() as i64;
-    let total_minutes = total_minutes.max(1);
-    let days = total_minutes / (24 * 60);
-    let hours = (total_minutes / 60) % 24;
-    let minutes = total_minutes % 60;
-    if days > 0 {
-        if hours > 0 {
-            return format!("in {}d {}h", days, hours);
-        }
-        return format!("in {}d", days);
+    tf(
+        "in {duration}",
+        &[("duration", &pace_duration_text(delta.num_seconds()))],
+    )
+}
+
+// "in 3h 12m (09:00 Tue CET)" with a timezone, the bare countdown without.
+fn reset_when(resets_at: DateTime<Utc>, timezone: Option<Tz>) -> String {
+    let countdown = reset_countdown_description(resets_at);
+    match timezone {
+        Some(timezone) => format!("{} ({})", countdown, reset_wall_clock(resets_at, timezone)),
+        None => countdown,
     }
-    if hours > 0 {
-        if minutes > 0 {
-            return format!("in {}h {}m", hours, minutes);
-        }
-        return format!("in {}h", hours);
+}
+
+// The weekday is enough within a week; further out the date is spelled out.
+pub fn reset_wall_clock(resets_at: DateTime<Utc>, timezone: Tz) -> String {
+    let local = resets_at.with_timezone(&timezone);
+    if resets_at.signed_duration_since(clock::now()) < Duration::days(6) {
+        local.format("%H:%M %a %Z").to_string()
+    } else {
+        local.format("%b %-d %H:%M %Z").to_string()
     }
-    format!("in {}m", minutes)
+}
+
+fn resets_text(when: &str) -> String {
+    tf("Resets {when}", &[("when", &when)])
 }
 
 fn pace_line(provider: &str, window: &RateWindow) -> Option<String> {
@@ -246,7 +659,7 @@ fn pace_line(provider: &str, window: &RateWindow) -> Option<String> {
     let expected = pace.expected_used_percent.round() as i64;
     let mut parts = Vec::new();
     parts.push(pace_left_label(&pace));
-    parts.push(format!("Expected {}% used", expected));
+    parts.push(tf("Expected {percent}% used", &[("percent", &expected)]));
     if let Some(right) = pace_right_label(&pace) {
         parts.push(right);
     }
@@ -257,7 +670,6 @@ struct UsagePaceSummary {
     stage: UsagePaceStage,
     delta_percent: f64,
     expected_used_percent: f64,
-    actual_used_percent: f64,
     eta_seconds: Option<i64>,
     will_last_to_reset: bool,
 }
@@ -278,7 +690,7 @@ fn usage_pace_weekly(window: &RateWindow) -> Option<UsagePaceSummary> {
     if minutes <= 0 {
         return None;
     }
-    let now = chrono::Utc::now();
+    let now = clock::now();
     let duration_secs = minutes * 60;
     let time_until_reset = (resets_at - now).num_seconds();
     if time_until_reset <= 0 || time_until_reset > duration_secs {
@@ -314,7 +726,6 @@ fn usage_pace_weekly(window: &RateWindow) -> Option<UsagePaceSummary> {
         stage,
         delta_percent: delta,
         expected_used_percent: expected,
-        actual_used_percent: actual,
         eta_seconds,
         will_last_to_reset,
     })
@@ -346,64 +757,104 @@ fn usage_pace_stage(delta: f64) -> UsagePaceStage {
 fn pace_left_label(pace: &UsagePaceSummary) -> String {
     let delta = pace.delta_percent.abs().round() as i64;
     match pace.stage {
-        UsagePaceStage::OnTrack => "On pace".to_string(),
+        UsagePaceStage::OnTrack => t("On pace").to_string(),
         UsagePaceStage::SlightlyAhead | UsagePaceStage::Ahead | UsagePaceStage::FarAhead => {
-            format!("{}% in deficit", delta)
+            tf("{percent}% in deficit", &[("percent", &delta)])
         }
         UsagePaceStage::SlightlyBehind | UsagePaceStage::Behind | UsagePaceStage::FarBehind => {
-            format!("{}% in reserve", delta)
+            tf("{percent}% in reserve", &[("percent", &delta)])
         }
     }
 }
 
 fn pace_right_label(pace: &UsagePaceSummary) -> Option<String> {
     if pace.will_last_to_reset {
-        return Some("Lasts until reset".to_string());
+        return Some(t("Lasts until reset").to_string());
     }
     let eta = pace.eta_seconds?;
-    let text = pace_duration_text(eta);
-    if text == "now" {
-        Some("Runs out now".to_string())
+    if eta < 1 {
+        Some(t("Runs out now").to_string())
     } else {
-        Some(format!("Runs out in {}", text))
+        Some(tf(
+            "Runs out in {duration}",
+            &[("duration", &pace_duration_text(eta))],
+        ))
     }
 }
 
 fn pace_duration_text(seconds: i64) -> String {
     let seconds = seconds.max(0);
     if seconds < 1 {
-        return "now".to_string();
+        return t("now").to_string();
     }
     let minutes = ((seconds as f64) / 60.0).ceil() as i64;
     let minutes = minutes.max(1);
     let days = minutes / (24 * 60);
     let hours = (minutes / 60) % 24;
     let mins = minutes % 60;
-    if days > 0 {
-        if hours > 0 {
-            return format!("{}d {}h", days, hours);
-        }
-        return format!("{}d", days);
+    let (first, second) = if days > 0 {
+        (
+            tf("{n}d", &[("n", &days)]),
+            (hours > 0).then_some(("{n}h", hours)),
+        )
+    } else if hours > 0 {
+        (
+            tf("{n}h", &[("n", &hours)]),
+            (mins > 0).then_some(("{n}m", mins)),
+        )
+    } else {
+        (tf("{n}m", &[("n", &mins)]), None)
+    };
+    match second {
+        Some((unit, n)) => format!("{} {}", first, tf(unit, &[("n", &n)])),
+        None => first,
     }
-    if hours > 0 {
-        if mins > 0 {
-            return format!("{}h {}m", hours, mins);
-        }
-        return format!("{}h", hours);
+}
+
+// Largest unit only ("3d", "5h", "12m") to keep shared lines short.
+fn short_duration_text(seconds: i64) -> String {
+    let minutes = ((seconds.max(0) as f64) / 60.0).ceil().max(1.0) as i64;
+    if minutes >= 24 * 60 {
+        tf("{n}d", &[("n", &(minutes / (24 * 60)))])
+    } else if minutes >= 60 {
+        tf("{n}h", &[("n", &(minutes / 60))])
+    } else {
+        tf("{n}m", &[("n", &minutes)])
     }
-    format!("{}m", mins)
 }
 
-fn cost_line(cost: &ProviderCostSnapshot) -> String {
+fn cost_line(cost: &ProviderCostSnapshot, timezone: Option<Tz>) -> String {
     let mut parts = vec![format!(
-        "Cost: {:.1} / {:.1} {}",
-        cost.used, cost.limit, cost.currency_code
+        "{}: {:.1} / {:.1} {}",
+        t("Cost"),
+        cost.used,
+        cost.limit,
+        cost.currency_code
     )];
     if let Some(period) = &cost.period {
         parts.push(period.clone());
     }
     if let Some(resets_at) = cost.resets_at {
-        parts.push(format!("Resets {}", reset_countdown_description(resets_at)));
+        parts.push(resets_text(&reset_when(resets_at, timezone)));
+    }
+    parts.join(" | ")
+}
+
+// "llama3.2:3b | 2.0 GB | 100% GPU | Unloads in 4m"
+fn loaded_model_line(model: &LoadedModel, timezone: Option<Tz>) -> String {
+    let mut parts = vec![
+        model.name.clone(),
+        format!("{:.1} GB", model.size_bytes as f64 / 1e9),
+    ];
+    if model.size_bytes > 0 {
+        let gpu = (model.vram_bytes as f64 / model.size_bytes as f64 * 100.0).clamp(0.0, 100.0);
+        parts.push(tf("{percent}% GPU", &[("percent", &format!("{:.0}", gpu))]));
+    }
+    if let Some(expires_at) = model.expires_at {
+        parts.push(tf(
+            "Unloads {when}",
+            &[("when", &reset_when(expires_at, timezone))],
+        ));
     }
     parts.join(" | ")
 }
@@ -417,6 +868,36 @@ fn label_line(label: &str, value: &str, use_color: bool) -> String {
     format!("{}: {}", label_text, value)
 }
 
+fn alert_lines(payload: &ProviderPayload, use_color: bool) -> impl Iterator<Item = String> + '_ {
+    payload.alerts.iter().map(move |alert| {
+        let window = match alert.window.as_str() {
+            "primary" => primary_label(&payload.provider),
+            "secondary" => secondary_label(&payload.provider),
+            "tertiary" => tertiary_label(&payload.provider),
+            _ => t("Cost"),
+        };
+        let text = format!(
+            "{}: {} {}",
+            t("Alert"),
+            window,
+            tf(
+                "{percent}% used (alert at {threshold}%)",
+                &[
+                    ("percent", &format!("{:.0}", alert.used_percent)),
+                    ("threshold", &alert.threshold),
+                ],
+            )
+        );
+        if use_color { ansi("31", &text) } else { text }
+    })
+}
+
+fn warning_lines(warnings: &[String], use_color: bool) -> impl Iterator<Item = String> + '_ {
+    warnings
+        .iter()
+        .map(move |warning| subtle_line(&format!("{}: {}", t("Warning"), warning), use_color))
+}
+
 fn subtle_line(text: &str, use_color: bool) -> String {
     if use_color {
         ansi("90", text)
@@ -461,16 +942,20 @@ fn colorize_status(text: &str, indicator: ProviderStatusIndicator, use_color: bo
     ansi(code, text)
 }
 
+pub(crate) fn status_label(indicator: &ProviderStatusIndicator) -> &'static str {
+    match indicator {
+        ProviderStatusIndicator::None => t("Operational"),
+        ProviderStatusIndicator::Minor => t("Partial outage"),
+        ProviderStatusIndicator::Major => t("Major outage"),
+        ProviderStatusIndicator::Critical => t("Critical issue"),
+        ProviderStatusIndicator::Maintenance => t("Maintenance"),
+        ProviderStatusIndicator::Unknown => t("Status unknown"),
+    }
+}
+
 fn status_line(status: &ProviderStatusPayload) -> String {
-    let label = match status.indicator.clone() {
-        ProviderStatusIndicator::None => "Operational",
-        ProviderStatusIndicator::Minor => "Partial outage",
-        ProviderStatusIndicator::Major => "Major outage",
-        ProviderStatusIndicator::Critical => "Critical issue",
-        ProviderStatusIndicator::Maintenance => "Maintenance",
-        ProviderStatusIndicator::Unknown => "Status unknown",
-    };
-    let mut text = format!("Status: {}", label);
+    let label = status_label(&status.indicator);
+    let mut text = format!("{}: {}", t("Status"), label);
     if let Some(desc) = &status.description
         && !desc.trim().is_empty()
     {
@@ -479,9 +964,27 @@ fn status_line(status: &ProviderStatusPayload) -> String {
     text
 }
 
+pub(crate) fn incident_line(incident: &StatusIncident) -> String {
+    let mut text = format!("{} ({}", incident.name, incident.status);
+    if let Some(impact) = &incident.impact {
+        text.push_str(&format!(
+            ", {}",
+            tf("{impact} impact", &[("impact", impact)])
+        ));
+    }
+    text.push(')');
+    if let Some(url) = &incident.url {
+        text.push_str(&format!(" {}", url));
+    }
+    text
+}
+
 fn format_credits(value: f64) -> String {
     let formatted = format!("{:.2}", value);
-    format!("{} left", add_thousand_separators(&formatted))
+    tf(
+        "{value} left",
+        &[("value", &add_thousand_separators(&formatted))],
+    )
 }
 
 fn add_thousand_separators(value: &str) -> String {
@@ -510,3 +1013,68 @@ fn add_thousand_separators(value: &str) -> String {
 fn ansi(code: &str, text: &str) -> String {
     format!("\u{001B}[{}m{}\u{001B}[0m", code, text)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reset_wall_clock_uses_the_given_timezone() {
+        let soon = clock::now() + Duration::hours(30);
+        assert!(reset_wall_clock(soon, chrono_tz::UTC).ends_with(" UTC"));
+        let later = DateTime::parse_from_rfc3339("2099-01-06T08:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            reset_wall_clock(later, chrono_tz::Europe::Berlin),
+            "Jan 6 09:00 CET"
+        );
+    }
+
+    #[test]
+    fn waybar_summarizes_the_tightest_window_per_provider() {
+        use fuelcheck_core::model::{ProviderErrorPayload, UsageSnapshot};
+        let window = |used_percent| RateWindow {
+            used_percent,
+            window_minutes: None,
+            resets_at: None,
+            reset_description: None,
+        };
+        let failed = |provider: &str| {
+            ProviderPayload::error(
+                provider.to_string(),
+                "auto".to_string(),
+                ProviderErrorPayload {
+                    code: 1,
+                    message: "boom".to_string(),
+                    kind: None,
+                },
+            )
+        };
+        let mut codex = failed("codex");
+        codex.error = None;
+        codex.usage = Some(UsageSnapshot {
+            primary: Some(window(40.0)),
+            secondary: Some(window(85.0)),
+            tertiary: None,
+            provider_cost: None,
+            updated_at: Utc::now(),
+            identity: None,
+            account_email: None,
+            account_organization: None,
+            login_method: None,
+        });
+        let outputs = [codex, failed("claude")];
+        let labels = ProviderLabels::default();
+
+        assert_eq!(
+            statusbar_line(&outputs, &labels),
+            "Codex 15% · Claude error"
+        );
+        let document: serde_json::Value =
+            serde_json::from_str(&waybar_document(&outputs, &labels).unwrap()).unwrap();
+        assert_eq!(document["class"], "warning");
+        assert_eq!(document["percentage"], 15);
+        assert_eq!(document["tooltip"].as_str().unwrap().lines().count(), 2);
+    }
+}
@@ -1,5 +1,8 @@
+use crate::i18n::{t, tf};
+use crate::text::{incident_line, reset_wall_clock, status_label, tightest_window};
 use anyhow::Result;
-use chrono::{DateTime, Local, Utc};
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use chrono_tz::Tz;
 use crossterm::cursor::{Hide, Show};
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
 use crossterm::execute;
@@ -9,17 +12,25 @@ use crossterm::terminal::{
 use ratatui::backend::CrosstermBackend;
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 use ratatui::style::{Color, Modifier, Style};
+use ratatui::symbols;
 use ratatui::text::{Line, Span};
 use ratatui::widgets::{Block, Borders, Paragraph, Tabs, Wrap};
 use ratatui::{Frame, Terminal};
-use std::collections::HashSet;
-use std::io;
-use std::time::Duration;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
 
-use fuelcheck_core::config::Config;
-use fuelcheck_core::model::{ProviderCostSnapshot, ProviderPayload, RateWindow};
-use fuelcheck_core::providers::{ProviderRegistry, ProviderSelector, SourcePreference};
-use fuelcheck_core::service::{UsageRequest, collect_usage_outputs};
+use fuelcheck_core::config::{Config, ProviderLabels};
+use fuelcheck_core::errors::CliError;
+use fuelcheck_core::model::{
+    ProviderCostSnapshot, ProviderPayload, ProviderStatusIndicator, RateWindow, UsageSnapshot,
+};
+use fuelcheck_core::paths;
+use fuelcheck_core::providers::{ProviderId, ProviderRegistry, ProviderSelector, SourcePreference};
+use fuelcheck_core::reports::types::ProviderReport;
+use fuelcheck_core::reports::{CostReportCollection, CostReportKind, ProviderReportOutcome};
+use fuelcheck_core::service::{UsageRequest, build_cost_report_collection, collect_usage_outputs};
 
 #[derive(Debug, Clone)]
 pub struct UsageArgs {
@@ -35,6 +46,11 @@ pub struct UsageArgs {
     pub all_accounts: bool,
     pub antigravity_plan_debug: bool,
     pub interval: u64,
+    pub timezone: Option<String>,
+    // Remaining percent under which a window alerts, and how.
+    pub alert_below: Option<f64>,
+    pub bell: bool,
+    pub notify: bool,
 }
 
 impl UsageArgs {
@@ -55,6 +71,14 @@ impl UsageArgs {
     }
 }
 
+#[derive(Debug, Clone)]
+pub struct CostWatchArgs {
+    pub providers: Vec<ProviderId>,
+    pub interval: u64,
+    pub timezone: Option<String>,
+    pub compact: bool,
+}
+
 #[derive(Clone, Copy)]
 struct TuiTheme {
     accent: Color,
@@ -110,7 +134,10 @@ pub async fn run_usage_watch(
         args.refresh = true;
     }
 
-    let mut state = LiveState::default();
+    let mut state = LiveState {
+        labels: config.provider_labels(),
+        ..LiveState::default()
+    };
     let mut ticker = tokio::time::interval(Duration::from_secs(args.interval));
     let mut ui_tick = tokio::time::interval(Duration::from_millis(100));
     let ctrl_c = tokio::signal::ctrl_c();
@@ -129,20 +156,37 @@ pub async fn run_usage_watch(
                         state.outputs = outputs;
                         state.last_error = None;
                         state.last_updated = Some(Utc::now());
+                        record_history(&mut state);
+                        if let Some(threshold) = args.alert_below {
+                            let alerts = check_alerts(&mut state, threshold);
+                            if !alerts.is_empty() {
+                                if args.bell {
+                                    ring_bell();
+                                }
+                                if args.notify {
+                                    send_notification(&alerts.join("\n"));
+                                }
+                            }
+                        }
                     }
                     Err(err) => {
                         state.last_error = Some(err.to_string());
                     }
                 }
+                state.cost_stale = true;
                 needs_redraw = true;
             }
             _ = ui_tick.tick() => {
+                if !state.flashing.is_empty() {
+                    state.flashing.retain(|_, since| since.elapsed() < FLASH_FOR);
+                    needs_redraw = true;
+                }
                 if event::poll(Duration::from_millis(0))?
                     && let Event::Key(key) = event::read()? {
                         if is_ctrl_c(key) {
                             should_quit = true;
                         } else {
-                            let tabs = build_account_tabs(&state.outputs);
+                            let tabs = build_account_tabs(&state);
                             if handle_key_event(key, &mut state, &tabs) {
                                 needs_redraw = true;
                             }
@@ -155,10 +199,17 @@ pub async fn run_usage_watch(
             break;
         }
 
+        if state.view == View::Cost && state.cost_stale {
+            let tabs = build_account_tabs(&state);
+            sync_active_tab(&mut state, &tabs);
+            refresh_cost(&mut state, &tabs, &args, registry, &config).await;
+            needs_redraw = true;
+        }
+
         if needs_redraw {
-            let tabs = build_account_tabs(&state.outputs);
+            let tabs = build_account_tabs(&state);
             sync_active_tab(&mut state, &tabs);
-            terminal.draw(|frame| draw(frame, &args, &state, &tabs))?;
+            terminal.draw(|frame| draw(frame, &args, &mut state, &tabs))?;
             needs_redraw = false;
         }
     }
@@ -174,8 +225,46 @@ struct LiveState {
     refresh_count: u64,
     active_tab: usize,
     active_tab_key: Option<String>,
+    labels: ProviderLabels,
+    view: View,
+    // The daily report for the providers of the selected tab; rebuilt on the
+    // usage interval, and right away when the view or tab changes.
+    cost: Option<Result<CostReportCollection, String>>,
+    cost_stale: bool,
+    // Used percent per "<tab key>/<window>", oldest first, for the sparklines.
+    history: HashMap<String, VecDeque<f64>>,
+    // Providers switched off with the number keys. They are still fetched,
+    // so switching one back on shows it right away.
+    hidden_providers: HashSet<String>,
+    // Where `s` last saved the outputs, shown in the header.
+    last_capture: Option<Result<PathBuf, String>>,
+    // `o`: tabs and payloads by least remaining quota instead of fetch order.
+    sort_by_remaining: bool,
+    // First body line shown. The draw clamps it and records how far it can
+    // go and how many lines a page is, for the keys.
+    scroll: u16,
+    max_scroll: u16,
+    page_height: u16,
+    // History keys of the windows under --alert-below, and when the ones
+    // that dropped below during the session did so, for the flashing.
+    alerted: HashSet<String>,
+    flashing: HashMap<String, Instant>,
+}
+
+const FLASH_FOR: Duration = Duration::from_secs(6);
+
+// At the default 10s interval, the last ten minutes.
+const HISTORY_SAMPLES: usize = 60;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum View {
+    #[default]
+    Usage,
+    Cost,
 }
 
+const COST_DAYS: i64 = 7;
+
 #[derive(Debug, Clone)]
 struct AccountTab {
     key: String,
@@ -199,7 +288,7 @@ impl Drop for TuiGuard {
     }
 }
 
-fn draw(frame: &mut Frame<'_>, args: &UsageArgs, state: &LiveState, tabs: &[AccountTab]) {
+fn draw(frame: &mut Frame<'_>, args: &UsageArgs, state: &mut LiveState, tabs: &[AccountTab]) {
     let theme = TuiTheme::default();
     let area = frame.size();
     let layout = Layout::default()
@@ -212,8 +301,11 @@ fn draw(frame: &mut Frame<'_>, args: &UsageArgs, state: &LiveState, tabs: &[Acco
         .split(area);
 
     draw_header(frame, layout[0], args, state, theme);
-    draw_tabs(frame, layout[1], tabs, state.active_tab, theme);
-    draw_body(frame, layout[2], args, state, tabs, theme);
+    draw_tabs(frame, layout[1], state, tabs, theme);
+    match state.view {
+        View::Usage => draw_body(frame, layout[2], args, state, tabs, theme),
+        View::Cost => draw_cost(frame, layout[2], args, state, theme),
+    }
 }
 
 fn draw_header(
@@ -227,7 +319,7 @@ fn draw_header(
     let dim_style = theme.dim_style();
 
     let provider_label = if args.providers.is_empty() {
-        "auto".to_string()
+        t("auto").to_string()
     } else {
         args.providers
             .iter()
@@ -235,38 +327,79 @@ fn draw_header(
             .collect::<Vec<_>>()
             .join(", ")
     };
-    let refresh_label = format!("Refresh: {}s", args.interval);
-    let source_label = format!("Source: {}", args.source);
+    let refresh_label = tf("Refresh: {seconds}s", &[("seconds", &args.interval)]);
+    let source_label = tf("Source: {source}", &[("source", &args.source)]);
     let update_label = match state.last_updated {
-        Some(dt) => format!("Last update: {}", format_timestamp(dt)),
-        None => "Last update: waiting for first refresh".to_string(),
+        Some(dt) => tf("Last update: {time}", &[("time", &format_timestamp(dt))]),
+        None => t("Last update: waiting for first refresh").to_string(),
     };
 
+    let mut title = vec![
+        Span::styled(t("Fuelcheck Live"), title_style),
+        Span::styled(t(" - usage + cost"), dim_style),
+    ];
+    match &state.last_capture {
+        Some(Ok(path)) => title.push(Span::styled(
+            format!(" | {}", tf("saved {path}", &[("path", &path.display())])),
+            theme.accent_style(),
+        )),
+        Some(Err(err)) => title.push(Span::styled(
+            format!(" | {}", tf("snapshot failed: {error}", &[("error", err)])),
+            theme.alert_style(),
+        )),
+        None => {}
+    }
+
     let lines = vec![
+        Line::from(title),
         Line::from(vec![
-            Span::styled("Fuelcheck Live", title_style),
-            Span::styled(" - usage + cost", dim_style),
-        ]),
-        Line::from(vec![
-            Span::styled(format!("Providers: {}", provider_label), dim_style),
+            Span::styled(
+                tf("Providers: {providers}", &[("providers", &provider_label)]),
+                dim_style,
+            ),
             Span::styled(" | ", dim_style),
             Span::styled(refresh_label, dim_style),
             Span::styled(" | ", dim_style),
             Span::styled(source_label, dim_style),
             Span::styled(" | ", dim_style),
-            Span::styled("Tabs: ←/→ or Tab", dim_style),
+            Span::styled(t("Tabs: ←/→ or Tab"), dim_style),
             Span::styled(" | ", dim_style),
-            Span::styled("Ctrl+C to exit", dim_style),
+            Span::styled(
+                match state.view {
+                    View::Usage => t("c: cost view"),
+                    View::Cost => t("c: usage view"),
+                },
+                dim_style,
+            ),
+            Span::styled(" | ", dim_style),
+            Span::styled(t("1-9: toggle providers, p: show all"), dim_style),
+            Span::styled(" | ", dim_style),
+            Span::styled(t("s: save snapshot"), dim_style),
+            Span::styled(" | ", dim_style),
+            Span::styled(
+                if state.sort_by_remaining {
+                    t("o: fetch order")
+                } else {
+                    t("o: sort by remaining")
+                },
+                dim_style,
+            ),
+            Span::styled(" | ", dim_style),
+            Span::styled(t("Ctrl+C to exit"), dim_style),
         ]),
         Line::from(vec![Span::styled(update_label, dim_style)]),
         Line::from(vec![Span::styled(
-            format!("Refresh count: {}", state.refresh_count),
+            tf("Refresh count: {count}", &[("count", &state.refresh_count)]),
             dim_style,
         )]),
     ];
 
+    let mut block = Block::default().borders(Borders::ALL).title(t("Status"));
+    if args.status {
+        block = block.title_bottom(status_strip(state, theme));
+    }
     let header = Paragraph::new(lines)
-        .block(Block::default().borders(Borders::ALL).title("Status"))
+        .block(block)
         .wrap(Wrap { trim: false });
     frame.render_widget(header, area);
 }
@@ -274,17 +407,36 @@ fn draw_header(
 fn draw_tabs(
     frame: &mut Frame<'_>,
     area: Rect,
+    state: &LiveState,
     tabs: &[AccountTab],
-    active_tab: usize,
     theme: TuiTheme,
 ) {
     let titles: Vec<Line<'static>> = tabs
         .iter()
         .map(|tab| Line::from(Span::raw(tab.label.clone())))
         .collect();
+    // The number keys, on the bottom border: "1 Codex  2 Claude", with the
+    // hidden providers crossed out.
+    let mut toggles = Vec::new();
+    for (index, provider) in provider_order(&state.outputs).iter().enumerate().take(9) {
+        let style = if state.hidden_providers.contains(provider) {
+            theme.dim_style().add_modifier(Modifier::CROSSED_OUT)
+        } else {
+            theme.accent_style()
+        };
+        toggles.push(Span::styled(
+            format!(" {} {} ", index + 1, state.labels.display_name(provider)),
+            style,
+        ));
+    }
     let tab_bar = Tabs::new(titles)
-        .block(Block::default().borders(Borders::ALL).title("Accounts"))
-        .select(active_tab)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(t("Accounts"))
+                .title_bottom(Line::from(toggles)),
+        )
+        .select(state.active_tab)
         .style(theme.dim_style())
         .highlight_style(theme.accent_bold())
         .divider(Span::raw(" | "));
@@ -295,14 +447,14 @@ fn draw_body(
     frame: &mut Frame<'_>,
     area: Rect,
     args: &UsageArgs,
-    state: &LiveState,
+    state: &mut LiveState,
     tabs: &[AccountTab],
     theme: TuiTheme,
 ) {
     let mut lines = Vec::new();
     if let Some(err) = &state.last_error {
         lines.push(Line::from(Span::styled(
-            format!("error: {}", err),
+            format!("{}: {}", t("error"), err),
             theme.alert_style(),
         )));
     }
@@ -315,10 +467,10 @@ fn draw_body(
 
     if state.outputs.is_empty() {
         if lines.is_empty() {
-            lines.push(Line::from("Waiting for data..."));
+            lines.push(Line::from(t("Waiting for data...")));
         }
     } else {
-        for payload in &state.outputs {
+        for payload in visible_outputs(state) {
             if let Some(key) = selected_tab
                 && key != "all"
                 && tab_key_for_payload(payload) != key
@@ -328,79 +480,408 @@ fn draw_body(
             if !lines.is_empty() {
                 lines.push(Line::from(""));
             }
-            lines.extend(render_payload(payload, args, theme));
+            lines.extend(render_payload(payload, args, state, theme));
             rendered_payloads += 1;
         }
     }
 
     if rendered_payloads == 0 && state.last_error.is_none() {
-        lines.push(Line::from("No data for this account yet."));
+        if !state.outputs.is_empty()
+            && state
+                .outputs
+                .iter()
+                .all(|payload| state.hidden_providers.contains(&payload.provider))
+        {
+            lines.push(Line::from(t(
+                "All providers are hidden. Press p to show them.",
+            )));
+        } else {
+            lines.push(Line::from(t("No data for this account yet.")));
+        }
+    }
+
+    let body = Paragraph::new(lines).wrap(Wrap { trim: false });
+    let total = body.line_count(area.width.saturating_sub(2));
+    let visible = area.height.saturating_sub(2);
+    state.page_height = visible;
+    state.max_scroll = u16::try_from(total)
+        .unwrap_or(u16::MAX)
+        .saturating_sub(visible);
+    state.scroll = state.scroll.min(state.max_scroll);
+
+    let mut block = Block::default().borders(Borders::ALL).title(t("Usage"));
+    if state.max_scroll > 0 {
+        let first = state.scroll + 1;
+        let last = (state.scroll + visible).min(total as u16);
+        block = block.title_bottom(
+            Line::from(Span::styled(
+                format!(
+                    " {} ",
+                    tf(
+                        "↑/↓ PgUp/PgDn: lines {first}-{last} of {total}",
+                        &[("first", &first), ("last", &last), ("total", &total)],
+                    )
+                ),
+                theme.dim_style(),
+            ))
+            .right_aligned(),
+        );
+    }
+    frame.render_widget(body.block(block).scroll((state.scroll, 0)), area);
+}
+
+fn draw_cost(
+    frame: &mut Frame<'_>,
+    area: Rect,
+    args: &UsageArgs,
+    state: &LiveState,
+    theme: TuiTheme,
+) {
+    let lines: Vec<Line<'static>> = match &state.cost {
+        None => vec![Line::from(t("Loading cost report..."))],
+        Some(Err(err)) => vec![Line::from(Span::styled(
+            format!("{}: {}", t("error"), err),
+            theme.alert_style(),
+        ))],
+        Some(Ok(collection)) if collection.providers.is_empty() => {
+            vec![Line::from(t("No cost report for the selected providers."))]
+        }
+        Some(Ok(collection)) => {
+            // The borders take two columns; narrower bodies get the compact table.
+            let compact = area.width < 102;
+            crate::reports::render_collection_text(collection, compact, args.timezone.as_deref())
+                .lines()
+                .map(|line| Line::from(line.to_string()))
+                .collect()
+        }
+    };
+    let title = tf("Cost (last {days} days)", &[("days", &COST_DAYS)]);
+    let body = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title));
+    frame.render_widget(body, area);
+}
+
+async fn refresh_cost(
+    state: &mut LiveState,
+    tabs: &[AccountTab],
+    args: &UsageArgs,
+    registry: &ProviderRegistry,
+    config: &Config,
+) {
+    state.cost_stale = false;
+    let selected = tabs
+        .get(state.active_tab)
+        .map(|tab| tab.key.as_str())
+        .unwrap_or("all");
+    let mut providers = Vec::new();
+    for payload in &state.outputs {
+        if state.hidden_providers.contains(&payload.provider)
+            || (selected != "all" && tab_key_for_payload(payload) != selected)
+        {
+            continue;
+        }
+        if let Some(id) = ProviderId::from_name(&payload.provider)
+            && registry
+                .get(&id)
+                .is_some_and(|provider| provider.supports_cost_reports())
+            && !providers.contains(&id)
+        {
+            providers.push(id);
+        }
     }
+    if providers.is_empty() {
+        state.cost = Some(Ok(CostReportCollection {
+            report: CostReportKind::Daily,
+            providers: Vec::new(),
+        }));
+        return;
+    }
+
+    let timezone = args.timezone.as_deref();
+    let since = (today(timezone) - chrono::Duration::days(COST_DAYS - 1))
+        .format("%Y-%m-%d")
+        .to_string();
+    let result = build_cost_report_collection(
+        CostReportKind::Daily,
+        providers,
+        Some(&since),
+        None,
+        timezone,
+        None,
+        config,
+    )
+    .await;
+    state.cost = Some(result.map_err(|err| err.to_string()));
+}
+
+fn today(timezone: Option<&str>) -> NaiveDate {
+    match timezone.and_then(|raw| raw.parse::<Tz>().ok()) {
+        Some(tz) => Utc::now().with_timezone(&tz).date_naive(),
+        None => Local::now().date_naive(),
+    }
+}
+
+#[derive(Default)]
+struct CostWatchState {
+    cost: Option<Result<CostReportCollection, String>>,
+    last_updated: Option<DateTime<Utc>>,
+    // Today's total when the watch started (or the day rolled over), for the
+    // "since start" figure.
+    baseline: Option<(NaiveDate, f64)>,
+}
+
+// `cost --watch`: today's per-model report, rebuilt from the session logs on
+// the interval.
+pub async fn run_cost_watch(mut args: CostWatchArgs, config: Config) -> Result<()> {
+    let _guard = TuiGuard::enter()?;
+    let backend = CrosstermBackend::new(io::stdout());
+    let mut terminal = Terminal::new(backend)?;
+    terminal.clear()?;
+
+    if args.interval == 0 {
+        args.interval = 10;
+    }
+    let mut state = CostWatchState::default();
+    let mut ticker = tokio::time::interval(Duration::from_secs(args.interval));
+    let mut ui_tick = tokio::time::interval(Duration::from_millis(100));
+    let ctrl_c = tokio::signal::ctrl_c();
+    tokio::pin!(ctrl_c);
+
+    loop {
+        tokio::select! {
+            _ = &mut ctrl_c => break,
+            _ = ticker.tick() => {
+                let timezone = args.timezone.as_deref();
+                let day = today(timezone).format("%Y-%m-%d").to_string();
+                let result = build_cost_report_collection(
+                    CostReportKind::Models,
+                    args.providers.clone(),
+                    Some(&day),
+                    Some(&day),
+                    timezone,
+                    None,
+                    &config,
+                )
+                .await;
+                if let Ok(collection) = &result {
+                    let date = today(timezone);
+                    let total = collection_cost(collection);
+                    if state.baseline.is_none_or(|(start, _)| start != date) {
+                        state.baseline = Some((date, total));
+                    }
+                    state.last_updated = Some(Utc::now());
+                }
+                state.cost = Some(result.map_err(|err| err.to_string()));
+                terminal.draw(|frame| draw_cost_watch(frame, &args, &state))?;
+            }
+            _ = ui_tick.tick() => {
+                if event::poll(Duration::from_millis(0))?
+                    && let Event::Key(key) = event::read()?
+                    && is_ctrl_c(key)
+                {
+                    break;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn collection_cost(collection: &CostReportCollection) -> f64 {
+    collection
+        .providers
+        .iter()
+        .filter_map(|provider| match &provider.outcome {
+            ProviderReportOutcome::Report(ProviderReport::Models(data)) => {
+                Some(data.totals.cost_usd)
+            }
+            _ => None,
+        })
+        .sum()
+}
 
-    let body = Paragraph::new(lines)
-        .block(Block::default().borders(Borders::ALL).title("Usage"))
+fn draw_cost_watch(frame: &mut Frame<'_>, args: &CostWatchArgs, state: &CostWatchState) {
+    let theme = TuiTheme::default();
+    let dim_style = theme.dim_style();
+    let layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(5), Constraint::Min(0)])
+        .split(frame.size());
+
+    let providers = args
+        .providers
+        .iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+    let update_label = match state.last_updated {
+        Some(dt) => tf("Last update: {time}", &[("time", &format_timestamp(dt))]),
+        None => t("Last update: waiting for first refresh").to_string(),
+    };
+    let mut spend = Vec::new();
+    if let Some(Ok(collection)) = &state.cost {
+        let total = collection_cost(collection);
+        spend.push(Span::styled(
+            tf("Today: ${cost}", &[("cost", &format!("{:.2}", total))]),
+            theme.accent_bold(),
+        ));
+        if let Some((_, start)) = state.baseline {
+            spend.push(Span::styled(" | ", dim_style));
+            spend.push(Span::styled(
+                tf(
+                    "+${cost} since start",
+                    &[("cost", &format!("{:.2}", (total - start).max(0.0)))],
+                ),
+                theme.accent_style(),
+            ));
+        }
+    }
+    let mut lines = vec![Line::from(vec![
+        Span::styled(t("Fuelcheck Live"), theme.accent_bold()),
+        Span::styled(t(" - cost today"), dim_style),
+    ])];
+    if !spend.is_empty() {
+        lines.push(Line::from(spend));
+    }
+    lines.push(Line::from(vec![
+        Span::styled(
+            tf("Providers: {providers}", &[("providers", &providers)]),
+            dim_style,
+        ),
+        Span::styled(" | ", dim_style),
+        Span::styled(
+            tf("Refresh: {seconds}s", &[("seconds", &args.interval)]),
+            dim_style,
+        ),
+        Span::styled(" | ", dim_style),
+        Span::styled(update_label, dim_style),
+        Span::styled(" | ", dim_style),
+        Span::styled(t("Ctrl+C to exit"), dim_style),
+    ]));
+    let header = Paragraph::new(lines)
+        .block(Block::default().borders(Borders::ALL).title(t("Status")))
         .wrap(Wrap { trim: false });
+    frame.render_widget(header, layout[0]);
+
+    let area = layout[1];
+    let body: Vec<Line<'static>> = match &state.cost {
+        None => vec![Line::from(t("Loading cost report..."))],
+        Some(Err(err)) => vec![Line::from(Span::styled(
+            format!("{}: {}", t("error"), err),
+            theme.alert_style(),
+        ))],
+        Some(Ok(collection)) => {
+            let compact = args.compact || area.width < 102;
+            crate::reports::render_collection_text(collection, compact, args.timezone.as_deref())
+                .lines()
+                .map(|line| Line::from(line.to_string()))
+                .collect()
+        }
+    };
+    let body = Paragraph::new(body).block(Block::default().borders(Borders::ALL).title(t("Cost")));
     frame.render_widget(body, area);
 }
 
 fn render_payload(
     payload: &ProviderPayload,
     args: &UsageArgs,
+    state: &LiveState,
     theme: TuiTheme,
 ) -> Vec<Line<'static>> {
     let dim_style = theme.dim_style();
     let mut lines = Vec::new();
 
-    let header = provider_header(payload, theme);
+    let header = provider_header(payload, &state.labels, theme);
     lines.push(header);
 
+    if let Some(status) = &payload.status {
+        let mut spans = vec![status_badge(&status.indicator)];
+        if let Some(description) = &status.description
+            && !description.trim().is_empty()
+        {
+            spans.push(Span::styled(format!(" {}", description), dim_style));
+        }
+        lines.push(Line::from(spans));
+        for incident in &status.incidents {
+            lines.push(Line::from(Span::styled(
+                format!("{}: {}", t("Incident"), incident_line(incident)),
+                dim_style,
+            )));
+        }
+    }
+
     if let Some(error) = &payload.error {
         lines.push(Line::from(Span::styled(
-            format!("error: {}", error.message),
+            format!("{}: {}", t("error"), error.message),
             theme.alert_style(),
         )));
         return lines;
     }
 
+    let timezone = args
+        .timezone
+        .as_deref()
+        .and_then(|raw| raw.parse::<Tz>().ok());
     if let Some(usage) = &payload.usage {
-        if let Some(primary) = usage.primary.as_ref() {
-            lines.push(rate_window_line("primary", primary, theme));
-        }
-        if let Some(secondary) = usage.secondary.as_ref() {
-            lines.push(rate_window_line("secondary", secondary, theme));
-        }
-        if let Some(tertiary) = usage.tertiary.as_ref() {
-            lines.push(rate_window_line("tertiary", tertiary, theme));
+        let tab_key = tab_key_for_payload(payload);
+        let windows = [
+            ("primary", t("primary"), usage.primary.as_ref()),
+            ("secondary", t("secondary"), usage.secondary.as_ref()),
+            ("tertiary", t("tertiary"), usage.tertiary.as_ref()),
+        ];
+        for (key, label, window) in windows {
+            let Some(window) = window else {
+                continue;
+            };
+            let key = format!("{}/{}", tab_key, key);
+            let mut line =
+                rate_window_line(label, window, timezone, state.alerted.contains(&key), theme);
+            // Blinks a few times after the window drops below --alert-below.
+            if let Some(since) = state.flashing.get(&key)
+                && (since.elapsed().as_millis() / 400) % 2 == 0
+            {
+                line = line.patch_style(Modifier::REVERSED);
+            }
+            lines.push(line);
+            if let Some(samples) = state.history.get(&key)
+                && samples.len() > 1
+            {
+                lines.push(sparkline_line(samples, theme));
+            }
         }
         if let Some(cost) = usage.provider_cost.as_ref() {
-            lines.push(cost_line(cost));
+            lines.push(cost_line(cost, timezone));
         } else {
-            lines.push(Line::from("cost: n/a"));
+            lines.push(Line::from(t("cost: n/a")));
         }
         if !args.no_credits {
             if let Some(credits) = payload.credits.as_ref() {
-                lines.push(Line::from(format!("credits: {:.2}", credits.remaining)));
+                lines.push(Line::from(credits_text(credits.remaining)));
             } else if let Some(dashboard) = payload.openai_dashboard.as_ref()
                 && let Some(credits) = dashboard.credits_remaining
             {
-                lines.push(Line::from(format!("credits: {:.2}", credits)));
+                lines.push(Line::from(credits_text(credits)));
             }
         }
-        lines.push(Line::from(format!(
-            "updated: {}",
-            format_timestamp(usage.updated_at)
+        lines.push(Line::from(tf(
+            "updated: {time}",
+            &[("time", &format_timestamp(usage.updated_at))],
         )));
     } else {
-        lines.push(Line::from(Span::styled("no usage data", dim_style)));
+        lines.push(Line::from(Span::styled(t("no usage data"), dim_style)));
     }
 
     lines
 }
 
-fn provider_header(payload: &ProviderPayload, theme: TuiTheme) -> Line<'static> {
+fn provider_header(
+    payload: &ProviderPayload,
+    labels: &ProviderLabels,
+    theme: TuiTheme,
+) -> Line<'static> {
     let header_style = theme.accent_bold();
     let dim_style = theme.dim_style();
-    let mut label = payload.provider.clone();
+    let mut label = labels.display_name(&payload.provider);
     if let Some(version) = &payload.version {
         label.push(' ');
         label.push_str(version);
@@ -423,6 +904,45 @@ fn provider_header(payload: &ProviderPayload, theme: TuiTheme) -> Line<'static>
     Line::from(spans)
 }
 
+// The header's bottom border: each shown provider's status, once per
+// provider however many accounts it has.
+fn status_strip(state: &LiveState, theme: TuiTheme) -> Line<'static> {
+    let mut seen = HashSet::new();
+    let mut spans = Vec::new();
+    for payload in visible_outputs(state) {
+        let Some(status) = &payload.status else {
+            continue;
+        };
+        if !seen.insert(payload.provider.as_str()) {
+            continue;
+        }
+        spans.push(Span::styled(
+            format!(" {} ", state.labels.display_name(&payload.provider)),
+            theme.dim_style(),
+        ));
+        spans.push(status_badge(&status.indicator));
+    }
+    Line::from(spans)
+}
+
+// Same colors as the text output's status line.
+fn status_badge(indicator: &ProviderStatusIndicator) -> Span<'static> {
+    let color = match indicator {
+        ProviderStatusIndicator::None => Color::Green,
+        ProviderStatusIndicator::Minor => Color::Yellow,
+        ProviderStatusIndicator::Major | ProviderStatusIndicator::Critical => Color::Red,
+        ProviderStatusIndicator::Maintenance => Color::Blue,
+        ProviderStatusIndicator::Unknown => Color::DarkGray,
+    };
+    Span::styled(
+        format!(" {} ", status_label(indicator)),
+        Style::default()
+            .fg(Color::Black)
+            .bg(color)
+            .add_modifier(Modifier::BOLD),
+    )
+}
+
 fn resolve_account(payload: &ProviderPayload) -> Option<String> {
     payload
         .account
@@ -436,20 +956,20 @@ fn resolve_account(payload: &ProviderPayload) -> Option<String> {
         })
 }
 
-fn build_account_tabs(outputs: &[ProviderPayload]) -> Vec<AccountTab> {
+fn build_account_tabs(state: &LiveState) -> Vec<AccountTab> {
     let mut tabs = Vec::new();
     tabs.push(AccountTab {
         key: "all".to_string(),
-        label: "All".to_string(),
+        label: t("All").to_string(),
     });
 
     let mut seen = HashSet::new();
-    for payload in outputs {
+    for payload in visible_outputs(state) {
         let key = tab_key_for_payload(payload);
         if seen.insert(key.clone()) {
             tabs.push(AccountTab {
                 key,
-                label: tab_label_for_payload(payload),
+                label: tab_label_for_payload(payload, &state.labels),
             });
         }
     }
@@ -457,6 +977,45 @@ fn build_account_tabs(outputs: &[ProviderPayload]) -> Vec<AccountTab> {
     tabs
 }
 
+// The payloads of the providers that are not hidden, in display order. When
+// sorting, the provider with the least left in its tightest window comes
+// first; errors and payloads without windows keep their order at the end.
+fn visible_outputs(state: &LiveState) -> Vec<&ProviderPayload> {
+    let mut outputs: Vec<&ProviderPayload> = state
+        .outputs
+        .iter()
+        .filter(|payload| !state.hidden_providers.contains(&payload.provider))
+        .collect();
+    if state.sort_by_remaining {
+        let used = |payload: &ProviderPayload| {
+            payload
+                .error
+                .is_none()
+                .then(|| tightest_window(payload))
+                .flatten()
+                .map(|window| window.used_percent)
+        };
+        outputs.sort_by(|a, b| match (used(a), used(b)) {
+            (Some(a), Some(b)) => b.total_cmp(&a),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+    }
+    outputs
+}
+
+// Providers in the order they were fetched; the number keys follow it.
+fn provider_order(outputs: &[ProviderPayload]) -> Vec<String> {
+    let mut providers: Vec<String> = Vec::new();
+    for payload in outputs {
+        if !providers.contains(&payload.provider) {
+            providers.push(payload.provider.clone());
+        }
+    }
+    providers
+}
+
 fn sync_active_tab(state: &mut LiveState, tabs: &[AccountTab]) {
     if let Some(active_key) = state.active_tab_key.as_ref()
         && let Some(index) = tabs.iter().position(|tab| tab.key == *active_key)
@@ -481,6 +1040,57 @@ fn handle_key_event(key: KeyEvent, state: &mut LiveState, tabs: &[AccountTab]) -
     if key.kind != KeyEventKind::Press || tabs.is_empty() {
         return false;
     }
+    if key.code == KeyCode::Char('c') {
+        state.view = match state.view {
+            View::Usage => View::Cost,
+            View::Cost => View::Usage,
+        };
+        state.cost_stale = true;
+        return true;
+    }
+    if let KeyCode::Char(digit @ '1'..='9') = key.code {
+        let providers = provider_order(&state.outputs);
+        let index = digit as usize - '1' as usize;
+        let Some(provider) = providers.get(index) else {
+            return false;
+        };
+        if !state.hidden_providers.remove(provider) {
+            state.hidden_providers.insert(provider.clone());
+        }
+        state.cost_stale = true;
+        return true;
+    }
+    if state.view == View::Usage {
+        let scroll = match key.code {
+            KeyCode::Up => Some(state.scroll.saturating_sub(1)),
+            KeyCode::Down => Some(state.scroll.saturating_add(1)),
+            KeyCode::PageUp => Some(state.scroll.saturating_sub(state.page_height.max(1))),
+            KeyCode::PageDown => Some(state.scroll.saturating_add(state.page_height.max(1))),
+            _ => None,
+        };
+        if let Some(scroll) = scroll {
+            let scroll = scroll.min(state.max_scroll);
+            let changed = scroll != state.scroll;
+            state.scroll = scroll;
+            return changed;
+        }
+    }
+    if key.code == KeyCode::Char('o') {
+        state.sort_by_remaining = !state.sort_by_remaining;
+        return true;
+    }
+    if key.code == KeyCode::Char('s') {
+        state.last_capture = Some(save_capture(&state.outputs).map_err(|err| err.to_string()));
+        return true;
+    }
+    if key.code == KeyCode::Char('p') {
+        if state.hidden_providers.is_empty() {
+            return false;
+        }
+        state.hidden_providers.clear();
+        state.cost_stale = true;
+        return true;
+    }
 
     let last_index = tabs.len().saturating_sub(1);
     let mut next_index = None;
@@ -507,12 +1117,26 @@ fn handle_key_event(key: KeyEvent, state: &mut LiveState, tabs: &[AccountTab]) -
     if let Some(index) = next_index {
         state.active_tab = index;
         state.active_tab_key = tabs.get(index).map(|tab| tab.key.clone());
+        state.cost_stale = true;
+        state.scroll = 0;
         return true;
     }
 
     false
 }
 
+// The same JSON as `usage --json`, one file per capture in the data dir.
+fn save_capture(outputs: &[ProviderPayload]) -> Result<PathBuf> {
+    let dir = paths::default_watch_captures_dir().ok_or(CliError::ConfigPathUnavailable)?;
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!(
+        "usage-{}.json",
+        Utc::now().format("%Y%m%dT%H%M%S%.3fZ")
+    ));
+    std::fs::write(&path, serde_json::to_string_pretty(outputs)?)?;
+    Ok(path)
+}
+
 fn is_ctrl_c(key: KeyEvent) -> bool {
     key.kind == KeyEventKind::Press
         && key.code == KeyCode::Char('c')
@@ -524,12 +1148,18 @@ fn tab_key_for_payload(payload: &ProviderPayload) -> String {
     format!("{}::{}", payload.provider, account)
 }
 
-fn tab_label_for_payload(payload: &ProviderPayload) -> String {
+fn tab_label_for_payload(payload: &ProviderPayload, labels: &ProviderLabels) -> String {
     let account = resolve_account(payload).unwrap_or_else(|| "default".to_string());
-    format!("{}: {}", payload.provider, account)
+    format!("{}: {}", labels.display_name(&payload.provider), account)
 }
 
-fn rate_window_line(label: &str, window: &RateWindow, theme: TuiTheme) -> Line<'static> {
+fn rate_window_line(
+    label: &str,
+    window: &RateWindow,
+    timezone: Option<Tz>,
+    alerting: bool,
+    theme: TuiTheme,
+) -> Line<'static> {
     let bar = percent_bar(window.used_percent, 18);
     let mut parts = vec![format!(
         "{}: {:>5.1}% [{}]",
@@ -538,28 +1168,186 @@ fn rate_window_line(label: &str, window: &RateWindow, theme: TuiTheme) -> Line<'
     if let Some(desc) = &window.reset_description {
         parts.push(desc.clone());
     }
+    if let (Some(resets_at), Some(timezone)) = (window.resets_at, timezone) {
+        parts.push(tf(
+            "resets {time}",
+            &[("time", &reset_wall_clock(resets_at, timezone))],
+        ));
+    }
     if let Some(minutes) = window.window_minutes {
-        parts.push(format!("window {}m", minutes));
+        parts.push(tf("window {minutes}m", &[("minutes", &minutes)]));
     }
 
-    let style = usage_style(window.used_percent, theme);
+    let style = if alerting {
+        theme.alert_style()
+    } else {
+        usage_style(window.used_percent, theme)
+    };
     Line::from(Span::styled(parts.join(" | "), style))
 }
 
-fn cost_line(cost: &ProviderCostSnapshot) -> Line<'static> {
+fn record_history(state: &mut LiveState) {
+    for payload in state
+        .outputs
+        .iter()
+        .filter(|payload| payload.error.is_none())
+    {
+        let Some(usage) = &payload.usage else {
+            continue;
+        };
+        let tab_key = tab_key_for_payload(payload);
+        for (key, window) in rate_windows(usage) {
+            let Some(window) = window else {
+                continue;
+            };
+            let samples = state
+                .history
+                .entry(format!("{}/{}", tab_key, key))
+                .or_default();
+            samples.push_back(window.used_percent);
+            if samples.len() > HISTORY_SAMPLES {
+                samples.pop_front();
+            }
+        }
+    }
+}
+
+fn rate_windows(usage: &UsageSnapshot) -> [(&'static str, Option<&RateWindow>); 3] {
+    [
+        ("primary", usage.primary.as_ref()),
+        ("secondary", usage.secondary.as_ref()),
+        ("tertiary", usage.tertiary.as_ref()),
+    ]
+}
+
+// Updates which windows are under the threshold and returns a message for
+// each one that dropped below it since the last refresh. A window already
+// below on its first sample is marked but not announced.
+fn check_alerts(state: &mut LiveState, threshold: f64) -> Vec<String> {
+    let mut messages = Vec::new();
+    for payload in state
+        .outputs
+        .iter()
+        .filter(|payload| payload.error.is_none())
+    {
+        let Some(usage) = &payload.usage else {
+            continue;
+        };
+        let tab_key = tab_key_for_payload(payload);
+        for (name, window) in rate_windows(usage) {
+            let Some(window) = window else {
+                continue;
+            };
+            let key = format!("{}/{}", tab_key, name);
+            let remaining = (100.0 - window.used_percent).max(0.0);
+            if remaining >= threshold {
+                state.alerted.remove(&key);
+                continue;
+            }
+            if !state.alerted.insert(key.clone()) {
+                continue;
+            }
+            if state
+                .history
+                .get(&key)
+                .is_some_and(|samples| samples.len() > 1)
+            {
+                messages.push(tf(
+                    "{provider} {window}: {remaining}% left",
+                    &[
+                        ("provider", &state.labels.display_name(&payload.provider)),
+                        ("window", &t(name)),
+                        ("remaining", &format!("{:.0}", remaining)),
+                    ],
+                ));
+                state.flashing.insert(key, Instant::now());
+            }
+        }
+    }
+    messages
+}
+
+fn ring_bell() {
+    let mut stdout = io::stdout();
+    let _ = stdout.write_all(b"\x07").and_then(|()| stdout.flush());
+}
+
+// Best effort through the desktop's own tool, off the UI thread; there is
+// no notifier on Windows.
+fn send_notification(body: &str) {
+    let mut command = if cfg!(target_os = "macos") {
+        let mut command = std::process::Command::new("osascript");
+        command.arg("-e").arg(format!(
+            "display notification {:?} with title \"fuelcheck\"",
+            body
+        ));
+        command
+    } else if cfg!(windows) {
+        return;
+    } else {
+        let mut command = std::process::Command::new("notify-send");
+        command.arg("fuelcheck").arg(body);
+        command
+    };
+    command
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null());
+    std::thread::spawn(move || {
+        let _ = command.status();
+    });
+}
+
+// The bar glyphs of ratatui's Sparkline, inline so it flows with the
+// paragraph. Always scaled to 0-100% so sessions compare at a glance.
+fn sparkline_line(samples: &VecDeque<f64>, theme: TuiTheme) -> Line<'static> {
+    let set = symbols::bar::NINE_LEVELS;
+    let levels = [
+        set.empty,
+        set.one_eighth,
+        set.one_quarter,
+        set.three_eighths,
+        set.half,
+        set.five_eighths,
+        set.three_quarters,
+        set.seven_eighths,
+        set.full,
+    ];
+    let bars: String = samples
+        .iter()
+        .map(|percent| levels[((percent.clamp(0.0, 100.0) / 100.0) * 8.0).round() as usize])
+        .collect();
+    let latest = samples.back().copied().unwrap_or_default();
+    Line::from(vec![
+        Span::styled("  ", theme.dim_style()),
+        Span::styled(bars, usage_style(latest, theme)),
+    ])
+}
+
+fn cost_line(cost: &ProviderCostSnapshot, timezone: Option<Tz>) -> Line<'static> {
     let mut parts = vec![format!(
-        "cost: {:.2}/{:.2} {}",
-        cost.used, cost.limit, cost.currency_code
+        "{}: {:.2}/{:.2} {}",
+        t("cost"),
+        cost.used,
+        cost.limit,
+        cost.currency_code
     )];
     if let Some(period) = &cost.period {
         parts.push(period.clone());
     }
     if let Some(resets_at) = cost.resets_at {
-        parts.push(format!("resets {}", format_timestamp(resets_at)));
+        let time = match timezone {
+            Some(timezone) => reset_wall_clock(resets_at, timezone),
+            None => format_timestamp(resets_at),
+        };
+        parts.push(tf("resets {time}", &[("time", &time)]));
     }
     Line::from(parts.join(" | "))
 }
 
+fn credits_text(credits: f64) -> String {
+    tf("credits: {value}", &[("value", &format!("{:.2}", credits))])
+}
+
 fn usage_style(percent: f64, theme: TuiTheme) -> Style {
     if percent >= 90.0 {
         theme.alert_style()
@@ -592,3 +1380,195 @@ fn format_timestamp(dt: DateTime<Utc>) -> String {
         .format("%Y-%m-%d %H:%M:%S")
         .to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuelcheck_core::model::ProviderErrorPayload;
+
+    fn payload(provider: &str, used_percent: Option<f64>) -> ProviderPayload {
+        let mut payload = ProviderPayload::error(
+            provider.to_string(),
+            "auto".to_string(),
+            ProviderErrorPayload {
+                code: 1,
+                message: "boom".to_string(),
+                kind: None,
+            },
+        );
+        if let Some(used_percent) = used_percent {
+            payload.error = None;
+            payload.usage = Some(UsageSnapshot {
+                primary: Some(RateWindow {
+                    used_percent,
+                    window_minutes: None,
+                    resets_at: None,
+                    reset_description: None,
+                }),
+                secondary: None,
+                tertiary: None,
+                provider_cost: None,
+                updated_at: Utc::now(),
+                identity: None,
+                account_email: None,
+                account_organization: None,
+                login_method: None,
+            });
+        }
+        payload
+    }
+
+    fn press(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn providers(outputs: &[&ProviderPayload]) -> Vec<String> {
+        outputs
+            .iter()
+            .map(|payload| payload.provider.clone())
+            .collect()
+    }
+
+    #[test]
+    fn sorting_puts_the_least_remaining_first_and_errors_last() {
+        let mut state = LiveState {
+            outputs: vec![
+                payload("claude", None),
+                payload("codex", Some(40.0)),
+                payload("cursor", Some(90.0)),
+            ],
+            ..LiveState::default()
+        };
+        assert_eq!(
+            providers(&visible_outputs(&state)),
+            ["claude", "codex", "cursor"]
+        );
+        let tabs = build_account_tabs(&state);
+        assert!(handle_key_event(
+            press(KeyCode::Char('o')),
+            &mut state,
+            &tabs
+        ));
+        assert_eq!(
+            providers(&visible_outputs(&state)),
+            ["cursor", "codex", "claude"]
+        );
+    }
+
+    #[test]
+    fn number_keys_toggle_providers_in_fetch_order() {
+        let mut state = LiveState {
+            outputs: vec![
+                payload("codex", Some(10.0)),
+                payload("claude", Some(20.0)),
+                payload("codex", Some(30.0)),
+            ],
+            sort_by_remaining: true,
+            ..LiveState::default()
+        };
+        assert_eq!(provider_order(&state.outputs), ["codex", "claude"]);
+        let tabs = build_account_tabs(&state);
+
+        assert!(handle_key_event(
+            press(KeyCode::Char('1')),
+            &mut state,
+            &tabs
+        ));
+        assert_eq!(providers(&visible_outputs(&state)), ["claude"]);
+        assert!(!handle_key_event(
+            press(KeyCode::Char('3')),
+            &mut state,
+            &tabs
+        ));
+        assert!(handle_key_event(
+            press(KeyCode::Char('1')),
+            &mut state,
+            &tabs
+        ));
+        assert_eq!(visible_outputs(&state).len(), 3);
+
+        state.hidden_providers.insert("claude".to_string());
+        assert!(handle_key_event(
+            press(KeyCode::Char('p')),
+            &mut state,
+            &tabs
+        ));
+        assert!(state.hidden_providers.is_empty());
+        assert!(!handle_key_event(
+            press(KeyCode::Char('p')),
+            &mut state,
+            &tabs
+        ));
+    }
+
+    #[test]
+    fn keys_clamp_scrolling_and_wrap_tabs() {
+        let mut state = LiveState {
+            outputs: vec![payload("codex", Some(10.0)), payload("claude", Some(20.0))],
+            max_scroll: 5,
+            page_height: 4,
+            ..LiveState::default()
+        };
+        let tabs = build_account_tabs(&state);
+        assert_eq!(tabs.len(), 3);
+
+        assert!(!handle_key_event(press(KeyCode::Up), &mut state, &tabs));
+        assert!(handle_key_event(
+            press(KeyCode::PageDown),
+            &mut state,
+            &tabs
+        ));
+        assert_eq!(state.scroll, 4);
+        assert!(handle_key_event(
+            press(KeyCode::PageDown),
+            &mut state,
+            &tabs
+        ));
+        assert_eq!(state.scroll, 5);
+        assert!(!handle_key_event(press(KeyCode::Down), &mut state, &tabs));
+
+        assert!(handle_key_event(press(KeyCode::Left), &mut state, &tabs));
+        assert_eq!(state.active_tab, 2);
+        assert_eq!(state.active_tab_key.as_deref(), Some("claude::default"));
+        assert_eq!(state.scroll, 0);
+        assert!(handle_key_event(press(KeyCode::Tab), &mut state, &tabs));
+        assert_eq!(state.active_tab, 0);
+
+        assert!(handle_key_event(
+            press(KeyCode::Char('c')),
+            &mut state,
+            &tabs
+        ));
+        assert_eq!(state.view, View::Cost);
+        state.max_scroll = 5;
+        assert!(!handle_key_event(press(KeyCode::Down), &mut state, &tabs));
+        assert_eq!(state.scroll, 0);
+    }
+
+    #[test]
+    fn alerts_fire_once_when_a_window_drops_below() {
+        let mut state = LiveState {
+            outputs: vec![payload("codex", Some(95.0))],
+            ..LiveState::default()
+        };
+        // Already below on the first sample: marked, not announced.
+        record_history(&mut state);
+        assert!(check_alerts(&mut state, 10.0).is_empty());
+        assert!(state.alerted.contains("codex::default/primary"));
+
+        state.outputs = vec![payload("codex", Some(50.0))];
+        record_history(&mut state);
+        assert!(check_alerts(&mut state, 10.0).is_empty());
+        assert!(state.alerted.is_empty());
+
+        state.outputs = vec![payload("codex", Some(96.0))];
+        record_history(&mut state);
+        let alerts = check_alerts(&mut state, 10.0);
+        assert_eq!(alerts.len(), 1);
+        assert!(alerts[0].contains("4%"));
+        assert!(state.flashing.contains_key("codex::default/primary"));
+
+        record_history(&mut state);
+        assert!(check_alerts(&mut state, 10.0).is_empty());
+    }
+}